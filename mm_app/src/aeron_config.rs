@@ -46,6 +46,37 @@ pub const POSITION_CHANNEL: &str = "aeron:ipc";
 /// Aeron stream ID for position updates
 pub const POSITION_STREAM_ID: i32 = 17;
 
+/// Aeron IPC channel for order entry requests (mm_strategy publishes here)
+pub const ORDER_ENTRY_CHANNEL: &str = "aeron:ipc";
+
+/// Aeron stream ID for order entry requests (new, cancel, replace), enveloped
+pub const ORDER_ENTRY_STREAM_ID: i32 = 18;
+
+/// Aeron IPC channel for order responses (mm_simulator or a gateway publishes here)
+pub const ORDER_RESPONSE_CHANNEL: &str = "aeron:ipc";
+
+/// Aeron stream ID for order responses (acks, rejects), enveloped
+pub const ORDER_RESPONSE_STREAM_ID: i32 = 19;
+
+/// Aeron IPC channel for derivatives data (collector publishes here when futures streams are enabled)
+pub const DERIVATIVES_DATA_CHANNEL: &str = "aeron:ipc";
+
+/// Aeron stream ID for derivatives data (mark price, funding, open interest, liquidations),
+/// enveloped so subscribers tell them apart with `decode_any`
+pub const DERIVATIVES_DATA_STREAM_ID: i32 = 20;
+
+/// Aeron IPC channel for control commands (operators publish here)
+pub const CONTROL_CHANNEL: &str = "aeron:ipc";
+
+/// Aeron stream ID for the control plane (kill switch, resume, parameter updates)
+pub const CONTROL_STREAM_ID: i32 = 21;
+
+/// Aeron IPC channel for risk events (mm_strategy publishes here)
+pub const RISK_EVENT_CHANNEL: &str = "aeron:ipc";
+
+/// Aeron stream ID for risk events (kills, resumes, applied and rejected parameter updates)
+pub const RISK_EVENT_STREAM_ID: i32 = 22;
+
 /// Default channel capacity for bounded channels (can be overridden via env var)
 pub fn default_channel_capacity() -> usize {
     std::env::var("CHANNEL_CAPACITY").ok().and_then(|s| s.parse().ok()).unwrap_or(10_000)
@@ -110,4 +141,13 @@ pub mod udp {
 
     /// Position updates UDP channel
     pub const POSITION_CHANNEL: &str = "aeron:udp?endpoint=localhost:40130";
+
+    /// Order entry UDP channel
+    pub const ORDER_ENTRY_CHANNEL: &str = "aeron:udp?endpoint=localhost:40131";
+
+    /// Order response UDP channel
+    pub const ORDER_RESPONSE_CHANNEL: &str = "aeron:udp?endpoint=localhost:40132";
+
+    /// Control plane UDP channel
+    pub const CONTROL_CHANNEL: &str = "aeron:udp?endpoint=localhost:40133";
 }
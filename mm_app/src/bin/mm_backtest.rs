@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
@@ -7,24 +8,41 @@ use std::time::Duration;
 use mm_aeron::Publisher;
 use mm_aeron::Subscriber;
 use mm_app::aeron_config;
+use mm_app::config_loader;
 use mm_app::shutdown_handler;
 use mm_backtest::BacktestConfig;
+use mm_backtest::BacktestError;
 use mm_backtest::HistoricalDataStream;
 use mm_backtest::loader;
 use mm_backtest::metrics::PerformanceTracker;
 use mm_backtest::replay::DataReplayEngine;
+use mm_backtest::replay::JournalReplayEngine;
+use mm_binary::CompressedString;
+use mm_binary::Exchange;
 use mm_binary::OrderBookBatchMessage;
-use mm_binary::from_fixed_point;
+use mm_binary::journal::JournalReader;
+use mm_binary::latency::split_trailer;
 use mm_binary::messages::OrderFillMessage;
 use mm_binary::messages::PositionMessage;
 use mm_orderbook::OrderBook;
-use mm_strategy::FixedPoint;
+use mm_strategy::FundingFeed;
+use mm_strategy::Notional;
 use mm_strategy::Position;
+use mm_strategy::Price;
+use mm_strategy::Qty;
 use time::OffsetDateTime;
 use time::macros::datetime;
 use tracing::error;
 use tracing::info;
 
+/// Where replayed market data comes from
+enum ReplaySource {
+    /// Events rebuilt from CSV files
+    Csv(HistoricalDataStream),
+    /// Frames captured by `mm_recorder`, replayed byte-for-byte
+    Journal(JournalReader),
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
     let _guard = mm_app::tracing_setup::init_with_stdout("mm_backtest", "./logs", tracing::Level::INFO);
@@ -40,6 +58,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         initial_capital: 10000.0,
         data_dir: "./data".to_string(),
     };
+    let fees = config_loader::load_fee_schedule_or_default("config/fees.toml");
 
     info!("Configuration:");
     info!("  Symbol: {}", config.symbol);
@@ -48,22 +67,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("  Initial capital: ${:.2}", config.initial_capital);
     info!("  Data directory: {}", config.data_dir);
 
-    // Load historical data
-    info!("Loading historical data...");
-    let events = loader::load_historical_data(&config.data_dir, &config.symbol, config.start_time, config.end_time)?;
-
-    if events.is_empty() {
-        error!("No data loaded. Ensure CSV files exist in {}/", config.data_dir);
-        error!("Required files:");
-        error!("  - {}_orderbook.csv", config.symbol.to_lowercase());
-        error!("  - {}_trades.csv", config.symbol.to_lowercase());
-        return Err("No historical data available".into());
-    }
+    let (start_ns, end_ns) = (config.start_time.unix_timestamp_nanos() as u64, config.end_time.unix_timestamp_nanos() as u64);
+    let replay_speed = config.replay_speed;
+
+    // A recorded journal takes precedence over CSV files
+    let journal_dir = Path::new(&config.data_dir).join("journal");
+    let replay_source = if journal_dir.is_dir() {
+        info!("Replaying journal from {}", journal_dir.display());
+        ReplaySource::Journal(JournalReader::open_from(&journal_dir, start_ns)?)
+    } else {
+        // Load historical data
+        info!("Loading historical data...");
+        let events = loader::load_historical_data(&config.data_dir, &config.symbol, config.start_time, config.end_time)?;
+
+        if events.is_empty() {
+            error!("No data loaded. Ensure CSV files exist in {}/", config.data_dir);
+            error!("Required files:");
+            error!("  - {}_orderbook.csv", config.symbol.to_lowercase());
+            error!("  - {}_trades.csv", config.symbol.to_lowercase());
+            return Err("No historical data available".into());
+        }
 
-    info!("Loaded {} events", events.len());
+        info!("Loaded {} events", events.len());
 
-    // Create data stream (will be moved to replay thread)
-    let data_stream = HistoricalDataStream::new(events, config.replay_speed);
+        // Create data stream (will be moved to replay thread)
+        ReplaySource::Csv(HistoricalDataStream::new(events, config.replay_speed))
+    };
 
     // Set up subscribers to collect results
     let mut fill_subscriber = Subscriber::new();
@@ -74,11 +103,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize performance tracker
     let mut performance = PerformanceTracker::new(config.initial_capital);
-    let mut position = Position { quantity: FixedPoint::ZERO, avg_entry_price: FixedPoint::ZERO, realized_pnl: FixedPoint::ZERO };
+    let instrument = config_loader::load_instrument_spec_or_default("config/instruments.toml", Exchange::Binance, &config.symbol);
+    let contract = instrument.contract;
+    let mut position = Position::new().with_contract(contract);
+    let mut funding = FundingFeed::new(CompressedString::from_str(&config.symbol)?.0, contract);
 
     // Initialize orderbook for mark price tracking
     let mut orderbook = OrderBook::new(&config.symbol);
-    let mut last_mark_price = FixedPoint::from_f64(50000.0); // Default
+    let mut last_mark_price = Price::from_f64(50000.0); // Default
 
     // Set up shutdown handler
     let running = Arc::new(AtomicBool::new(true));
@@ -101,17 +133,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         // Create replay engine
-        let mut replay_engine = DataReplayEngine::new(data_stream, orderbook_publisher, trade_publisher);
-
-        while replay_running.load(Ordering::Relaxed) && !replay_engine.is_complete() {
-            match replay_engine.tick() {
-                Ok(_) => {}
-                Err(err) => {
-                    error!("Replay error: {err}");
-                    break;
+        match replay_source {
+            ReplaySource::Csv(data_stream) => {
+                let mut replay_engine = DataReplayEngine::new(data_stream, orderbook_publisher, trade_publisher);
+                drive_replay(&replay_running, || (!replay_engine.is_complete()).then(|| replay_engine.tick()));
+            }
+            ReplaySource::Journal(reader) => {
+                let mut replay_engine = match JournalReplayEngine::new(reader, replay_speed, start_ns, end_ns) {
+                    Ok(engine) => engine,
+                    Err(err) => {
+                        error!("Failed to open journal replay: {err}");
+                        return;
+                    }
+                };
+                replay_engine.add_stream(aeron_config::MARKET_DATA_STREAM_ID, orderbook_publisher);
+                replay_engine.add_stream(aeron_config::TRADE_DATA_STREAM_ID, trade_publisher);
+
+                let mut derivatives_publisher = Publisher::new();
+                match derivatives_publisher
+                    .add_publication(aeron_config::DERIVATIVES_DATA_CHANNEL, aeron_config::DERIVATIVES_DATA_STREAM_ID)
+                {
+                    Ok(()) => replay_engine.add_stream(aeron_config::DERIVATIVES_DATA_STREAM_ID, derivatives_publisher),
+                    Err(err) => error!("Failed to create derivatives publisher, replaying without funding: {err}"),
                 }
+                drive_replay(&replay_running, || (!replay_engine.is_complete()).then(|| replay_engine.tick()));
+                info!("Replayed {} journal frames", replay_engine.frames_published());
             }
-            thread::sleep(Duration::from_millis(10));
         }
         info!("Replay thread completed");
     });
@@ -125,56 +172,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut market_subscriber = Subscriber::new();
     market_subscriber.add_subscription(aeron_config::MARKET_DATA_CHANNEL, aeron_config::MARKET_DATA_STREAM_ID)?;
 
+    // Subscriber for derivatives data (funding settlements, journal replay only)
+    let mut derivatives_subscriber = Subscriber::new();
+    derivatives_subscriber.add_subscription(aeron_config::DERIVATIVES_DATA_CHANNEL, aeron_config::DERIVATIVES_DATA_STREAM_ID)?;
+
     while running.load(Ordering::Relaxed) {
         // Update orderbook
         if let Ok(data) = market_subscriber.receive() {
-            if let Ok(batch) = OrderBookBatchMessage::from_bytes(&data) {
-                for bid in batch.bids() {
-                    let price = bid.price;
-                    let qty = bid.size;
-                    if price > 0 {
-                        orderbook.update_bid(price, qty);
-                    }
-                }
-                for ask in batch.asks() {
-                    let price = ask.price;
-                    let qty = ask.size;
-                    if price > 0 {
-                        orderbook.update_ask(price, qty);
-                    }
-                }
+            let (payload, _trace) = split_trailer(&data);
+            if let Ok(batch) = OrderBookBatchMessage::from_bytes(payload) {
+                orderbook.apply_batch(&batch);
 
                 // Update mark price
                 if let Some(mid) = orderbook.mid_price() {
-                    last_mark_price = FixedPoint(mid);
+                    last_mark_price = mid;
                 }
             }
         }
 
+        // Settle funding on a perpetual at the venue mark price (the book mid before one arrives)
+        if let Ok(Some(data)) = derivatives_subscriber.try_receive() {
+            if let Ok(Some(settlement)) = funding.observe_frame(&data) {
+                let (rate, mark_price) = (settlement.rate, settlement.mark_price.unwrap_or(last_mark_price));
+                let payment = position.apply_funding(rate, mark_price);
+                info!("Funding settled at rate {rate}: ${:.2}", contract.to_quote(payment, mark_price).to_f64());
+            }
+        }
+
         // Collect fills
         if let Ok(data) = fill_subscriber.receive() {
             if let Ok(fill_msg) = OrderFillMessage::from_bytes(&data) {
-                let side = if fill_msg.side == 0 { mm_strategy::OrderSide::Bid } else { mm_strategy::OrderSide::Ask };
+                let side = fill_msg.order_side();
+                let price = Price(fill_msg.fill_price);
+                let quantity = Qty(fill_msg.fill_quantity);
 
-                let price = from_fixed_point(fill_msg.fill_price);
-                let quantity = from_fixed_point(fill_msg.fill_quantity);
-
-                // Calculate PnL change
-                let prev_realized = position.realized_pnl.to_f64();
+                // Calculate PnL change, net of the fee or rebate
+                let prev_realized = position.realized_pnl;
 
                 // Update position
-                position.apply_fill(side, FixedPoint::from_f64(price), FixedPoint::from_f64(quantity));
+                let fee = position.apply_fill_message(&fill_msg, &fees);
 
-                let pnl_change = position.realized_pnl.to_f64() - prev_realized;
+                let pnl_change = position.realized_pnl - prev_realized - fee;
 
-                // Record in tracker
-                performance.record_fill(fill_msg.timestamp, side, price, quantity, pnl_change);
+                // Record in tracker, in base units and quote currency
+                performance.record_fill(
+                    fill_msg.timestamp,
+                    side,
+                    price,
+                    contract.base_exposure(quantity, price),
+                    contract.to_quote(pnl_change, price),
+                );
 
                 info!(
                     "Fill: {} {:.4} @ ${:.2} | Position: {:.4} | Realized PnL: ${:.2}",
                     if matches!(side, mm_strategy::OrderSide::Bid) { "BUY" } else { "SELL" },
-                    quantity,
-                    price,
+                    quantity.to_f64(),
+                    price.to_f64(),
                     position.quantity.to_f64(),
                     position.realized_pnl.to_f64()
                 );
@@ -185,30 +238,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         if let Ok(data) = position_subscriber.receive() {
             if let Ok(pos_msg) = PositionMessage::from_bytes(&data) {
                 let timestamp = pos_msg.timestamp;
-                let quantity = from_fixed_point(pos_msg.quantity);
+                let exposure = contract.base_exposure(Qty(pos_msg.quantity), last_mark_price);
 
-                let unrealized_pnl = from_fixed_point(pos_msg.unrealized_pnl);
-                let realized_pnl = from_fixed_point(pos_msg.realized_pnl);
-                let total_pnl = realized_pnl + unrealized_pnl;
-                let equity = config.initial_capital + total_pnl;
+                let net_pnl = contract.to_quote(Notional(pos_msg.net_pnl()), last_mark_price);
+                let equity = config.initial_capital + net_pnl.to_f64();
 
                 performance.update_equity(timestamp, equity);
-                performance.update_position(timestamp, quantity);
+                performance.update_position(timestamp, exposure);
             }
         }
 
         // Log progress periodically
         if last_progress_log.elapsed() > Duration::from_secs(5) {
-            let unrealized = position.unrealized_pnl(last_mark_price).to_f64();
-            let realized = position.realized_pnl.to_f64();
-            let total_pnl = realized + unrealized;
-            let equity = config.initial_capital + total_pnl;
+            let unrealized = contract.to_quote(position.unrealized_pnl(last_mark_price), last_mark_price).to_f64();
+            let realized = contract.to_quote(position.realized_pnl, last_mark_price).to_f64();
+            let breakdown = position.pnl_breakdown(last_mark_price).in_quote(contract, last_mark_price);
+            let equity = config.initial_capital + breakdown.net.to_f64();
 
             info!(
-                "Progress: Position={:.4}, Realized=${:.2}, Unrealized=${:.2}, Equity=${:.2}",
+                "Progress: Position={:.4}, Realized=${:.2}, Unrealized=${:.2}, Net fees=${:.2}, Equity=${:.2}",
                 position.quantity.to_f64(),
                 realized,
                 unrealized,
+                breakdown.net_fees().to_f64(),
                 equity
             );
 
@@ -251,6 +303,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Tick a replay engine until it reports completion (`None`), fails, or shutdown is requested
+fn drive_replay(running: &AtomicBool, mut tick: impl FnMut() -> Option<Result<usize, BacktestError>>) {
+    while running.load(Ordering::Relaxed) {
+        match tick() {
+            Some(Ok(_)) => {}
+            Some(Err(err)) => {
+                error!("Replay error: {err}");
+                break;
+            }
+            None => break,
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
 fn print_metrics(metrics: &mm_backtest::metrics::BacktestMetrics) {
     info!("\n╔═══════════════════════════════════════════╗");
     info!("║        BACKTEST PERFORMANCE SUMMARY        ║");
@@ -266,9 +333,13 @@ fn print_metrics(metrics: &mm_backtest::metrics::BacktestMetrics) {
     info!("║ PnL Metrics:");
     info!("║   Initial Capital: ${:.2}", metrics.initial_capital);
     info!("║   Final Capital: ${:.2}", metrics.final_capital);
-    info!("║   Total PnL: ${:.2} ({:.2}%)", metrics.total_pnl, metrics.total_pnl_pct);
+    info!("║   Net PnL: ${:.2} ({:.2}%)", metrics.total_pnl, metrics.total_pnl_pct);
     info!("║   Realized PnL: ${:.2}", metrics.realized_pnl);
     info!("║   Unrealized PnL: ${:.2}", metrics.unrealized_pnl);
+    info!("║   Gross PnL: ${:.2}", metrics.gross_pnl);
+    info!("║   Fees Paid: ${:.2}", metrics.fees_paid);
+    info!("║   Rebates Earned: ${:.2}", metrics.rebates_earned);
+    info!("║   Funding: ${:.2}", metrics.funding_pnl);
     info!("║");
     info!("║ Performance:");
     info!("║   Sharpe Ratio: {:.2}", metrics.sharpe_ratio);
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicI64;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
+use std::time::Instant;
 
 use bytes::Bytes;
 use crossbeam_channel::Sender;
@@ -10,20 +12,35 @@ use crossbeam_channel::bounded;
 use mm_aeron::Publisher;
 use mm_app::aeron_config;
 use mm_app::cli;
+use mm_app::config_loader;
+use mm_app::latency_tracing;
 use mm_app::publisher_helpers::PublisherConfig;
 use mm_app::publisher_helpers::spawn_channel_publisher;
 use mm_app::shutdown_handler;
 use mm_app::time_utils;
+use mm_binary::AnyMessage;
 use mm_binary::CollectorState;
 use mm_binary::CollectorStateMessage;
 use mm_binary::CompressedString;
 use mm_binary::Exchange;
+use mm_binary::FundingRateMessage;
 use mm_binary::HeartbeatMessage;
+use mm_binary::LatencyTrailer;
+use mm_binary::LiquidationMessage;
+use mm_binary::MarkPriceMessage;
+use mm_binary::OpenInterestMessage;
 use mm_binary::OrderBookBatchMessage;
+use mm_binary::Scale;
+use mm_binary::latency::HopTimestamps;
+use mm_binary::latency::PipelineStage;
 use mm_binary::messages::TradeMessage;
 use mm_binary::messages::TradeSide;
 use mm_binary::messages::UpdateType;
-use mm_binary::to_fixed_point;
+use mm_binary::parse_json_decimal_to_fixed_point;
+use mm_binary::parse_json_decimal_to_fixed_point_scaled;
+use mm_http::binance::BinanceClient;
+use mm_http::binance::OpenInterest;
+use mm_strategy::FixedPoint;
 use mm_ws::AffinityManager;
 use mm_ws::BinanceIngestor;
 use simd_json::prelude::ValueAsArray;
@@ -35,7 +52,17 @@ use tracing::info;
 use tracing::warn;
 
 /// Parse JSON orderbook update and send as a single batch message
-fn parse_and_send_batch(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// Levels are parsed straight from the decimal strings at the instrument's scales.
+/// When `receive_time` is set, a latency trailer with the collector hop is appended.
+fn parse_and_send_batch(
+    json_str: &str,
+    tx: &Sender<Bytes>,
+    instrument_id: u32,
+    price_scale: Scale,
+    qty_scale: Scale,
+    receive_time: Option<u64>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let mut bytes = json_str.as_bytes().to_vec();
     let parsed = simd_json::to_borrowed_value(&mut bytes)?;
 
@@ -75,14 +102,19 @@ fn parse_and_send_batch(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
         final_update_id,
         prev_update_id,
     );
+    batch.set_instrument_id(instrument_id);
+    batch.set_scales(price_scale, qty_scale);
 
     // Add all bids
     for bid in bids {
         if let Some(bid_array) = bid.as_array() {
             if bid_array.len() >= 2 {
-                let price = bid_array[0].as_str().ok_or("Invalid bid price")?.parse::<f64>()?;
-                let size = bid_array[1].as_str().ok_or("Invalid bid size")?.parse::<f64>()?;
-                batch.add_bid(to_fixed_point(price), to_fixed_point(size));
+                let price = bid_array[0].as_str().ok_or("Invalid bid price")?;
+                let size = bid_array[1].as_str().ok_or("Invalid bid size")?;
+                batch.add_bid(
+                    parse_json_decimal_to_fixed_point_scaled(price.as_bytes(), price_scale)?,
+                    parse_json_decimal_to_fixed_point_scaled(size.as_bytes(), qty_scale)?,
+                );
             }
         }
     }
@@ -91,15 +123,25 @@ fn parse_and_send_batch(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
     for ask in asks {
         if let Some(ask_array) = ask.as_array() {
             if ask_array.len() >= 2 {
-                let price = ask_array[0].as_str().ok_or("Invalid ask price")?.parse::<f64>()?;
-                let size = ask_array[1].as_str().ok_or("Invalid ask size")?.parse::<f64>()?;
-                batch.add_ask(to_fixed_point(price), to_fixed_point(size));
+                let price = ask_array[0].as_str().ok_or("Invalid ask price")?;
+                let size = ask_array[1].as_str().ok_or("Invalid ask size")?;
+                batch.add_ask(
+                    parse_json_decimal_to_fixed_point_scaled(price.as_bytes(), price_scale)?,
+                    parse_json_decimal_to_fixed_point_scaled(size.as_bytes(), qty_scale)?,
+                );
             }
         }
     }
 
     // Send single batch message containing all levels
-    let msg_bytes = Bytes::from(batch.to_bytes());
+    let mut frame = batch.to_bytes();
+    if let Some(receive_time) = receive_time {
+        let parse_time = time_utils::unix_timestamp_ns();
+        let mut trailer = LatencyTrailer::new(timestamp * 1_000_000); // Event time is in ms
+        trailer.push_hop(HopTimestamps::new(PipelineStage::Collector, receive_time, parse_time, time_utils::unix_timestamp_ns()));
+        trailer.append_to(&mut frame);
+    }
+    let msg_bytes = Bytes::from(frame);
 
     // Debug: log message size occasionally
     static MSG_COUNT: AtomicU64 = AtomicU64::new(0);
@@ -114,6 +156,8 @@ fn parse_and_send_batch(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
 }
 
 /// Parse JSON trade update and send as TradeMessage
+///
+/// Trade messages carry no scale, so prices and quantities are always at `Scale::DEFAULT`.
 fn parse_and_send_trade(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>> {
     let mut bytes = json_str.as_bytes().to_vec();
     let parsed = simd_json::to_borrowed_value(&mut bytes)?;
@@ -138,8 +182,8 @@ fn parse_and_send_trade(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
     let is_buyer_maker = parsed["m"].as_bool().unwrap_or(false);
 
     // Parse price and quantity
-    let price = price_str.parse::<f64>()?;
-    let quantity = quantity_str.parse::<f64>()?;
+    let price = parse_json_decimal_to_fixed_point(price_str.as_bytes())?;
+    let quantity = parse_json_decimal_to_fixed_point(quantity_str.as_bytes())?;
 
     // Encode symbol
     let (symbol, encoding) = CompressedString::from_str(symbol_str)?;
@@ -162,8 +206,8 @@ fn parse_and_send_trade(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
         encoding,
         timestamp_ns,
         trade_id,
-        to_fixed_point(price),
-        to_fixed_point(quantity),
+        price,
+        quantity,
         side,
         !is_buyer_maker, // is_aggressor: if not maker, then taker (aggressor)
     );
@@ -175,6 +219,203 @@ fn parse_and_send_trade(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dy
     Ok(())
 }
 
+/// Latest futures mark price at the default scale, for valuing open interest
+static LAST_MARK_PRICE: AtomicI64 = AtomicI64::new(0);
+
+/// How often open interest is polled; Binance serves it over REST only
+const OPEN_INTEREST_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Parse a Binance futures markPrice event into mark price and funding rate messages
+///
+/// Derivatives messages carry no scale, so every value is at `Scale::DEFAULT`.
+fn parse_and_send_mark_price(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut bytes = json_str.as_bytes().to_vec();
+    let parsed = simd_json::to_borrowed_value(&mut bytes)?;
+
+    // {
+    //   "e": "markPriceUpdate",
+    //   "E": 1562305380000,    // Event time (ms)
+    //   "s": "BTCUSDT",
+    //   "p": "11794.15000000",  // Mark price
+    //   "i": "11784.62659091",  // Index price
+    //   "r": "0.00038167",      // Funding rate
+    //   "T": 1562306400000,     // Next funding time (ms)
+    // }
+
+    let symbol_str = parsed["s"].as_str().ok_or("Missing symbol")?;
+    let event_time = parsed["E"].as_u64().ok_or("Missing event time")?;
+    let mark_price = parsed["p"].as_str().ok_or("Missing mark price")?;
+    let index_price = parsed["i"].as_str().ok_or("Missing index price")?;
+    let funding_rate = parsed["r"].as_str().ok_or("Missing funding rate")?;
+    let next_funding_time = parsed["T"].as_u64().ok_or("Missing next funding time")?;
+
+    let (symbol, encoding) = CompressedString::from_str(symbol_str)?;
+    let timestamp_ns = event_time * 1_000_000;
+
+    let mark_price = parse_json_decimal_to_fixed_point(mark_price.as_bytes())?;
+    LAST_MARK_PRICE.store(mark_price, Ordering::Relaxed);
+
+    let mark_msg = MarkPriceMessage::new(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        timestamp_ns,
+        mark_price,
+        parse_json_decimal_to_fixed_point(index_price.as_bytes())?,
+    );
+    tx.send(Bytes::from(AnyMessage::from(mark_msg).to_bytes()))?;
+
+    let funding_msg = FundingRateMessage::new(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        timestamp_ns,
+        parse_json_decimal_to_fixed_point(funding_rate.as_bytes())?,
+        next_funding_time * 1_000_000,
+    );
+    tx.send(Bytes::from(AnyMessage::from(funding_msg).to_bytes()))?;
+
+    Ok(())
+}
+
+/// Parse a Binance futures forceOrder event into a LiquidationMessage at `Scale::DEFAULT`
+fn parse_and_send_liquidation(json_str: &str, tx: &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut bytes = json_str.as_bytes().to_vec();
+    let parsed = simd_json::to_borrowed_value(&mut bytes)?;
+
+    // {
+    //   "e": "forceOrder",
+    //   "E": 1568014460893,
+    //   "o": {
+    //     "s": "BTCUSDT",
+    //     "S": "SELL",          // Side of the liquidation order
+    //     "q": "0.014",         // Original quantity
+    //     "p": "9910",          // Price
+    //     "ap": "9910",         // Average price
+    //     "z": "0.014",         // Accumulated filled quantity
+    //     "T": 1568014460893,   // Trade time (ms)
+    //   }
+    // }
+
+    let order = parsed.get("o").ok_or("Missing order")?;
+    let symbol_str = order["s"].as_str().ok_or("Missing symbol")?;
+    let trade_time = order["T"].as_u64().ok_or("Missing trade time")?;
+    let side = if order["S"].as_str().ok_or("Missing side")? == "BUY" { TradeSide::Buy } else { TradeSide::Sell };
+    let price = order["p"].as_str().ok_or("Missing price")?;
+    let avg_price = order["ap"].as_str().ok_or("Missing average price")?;
+    let quantity = order["q"].as_str().ok_or("Missing quantity")?;
+    let filled_quantity = order["z"].as_str().ok_or("Missing filled quantity")?;
+
+    let (symbol, encoding) = CompressedString::from_str(symbol_str)?;
+
+    let liquidation_msg = LiquidationMessage::new(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        trade_time * 1_000_000,
+        side,
+        parse_json_decimal_to_fixed_point(price.as_bytes())?,
+        parse_json_decimal_to_fixed_point(avg_price.as_bytes())?,
+        parse_json_decimal_to_fixed_point(quantity.as_bytes())?,
+        parse_json_decimal_to_fixed_point(filled_quantity.as_bytes())?,
+    );
+    tx.send(Bytes::from(AnyMessage::from(liquidation_msg).to_bytes()))?;
+
+    Ok(())
+}
+
+/// Convert a REST open interest reading into an OpenInterestMessage
+///
+/// The quote value uses the latest mark price at the default scale, and stays 0
+/// until the mark price stream has delivered one.
+fn send_open_interest(open_interest: &OpenInterest, tx: &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>> {
+    let (symbol, encoding) = CompressedString::from_str(&open_interest.symbol)?;
+    let contracts = open_interest.open_interest;
+    let mark_price = FixedPoint(LAST_MARK_PRICE.load(Ordering::Relaxed));
+    let value = mark_price.mul_scaled(FixedPoint(contracts), Scale::DEFAULT).ok_or("Open interest value out of range")?;
+
+    let open_interest_msg =
+        OpenInterestMessage::new(Exchange::Binance, symbol, encoding, open_interest.time * 1_000_000, contracts, value.0);
+    tx.send(Bytes::from(AnyMessage::from(open_interest_msg).to_bytes()))?;
+
+    Ok(())
+}
+
+/// Poll futures open interest every `OPEN_INTEREST_POLL_INTERVAL` on its own thread
+fn spawn_open_interest_poller(
+    symbol: String,
+    tx: Sender<Bytes>,
+) -> Result<(Arc<AtomicBool>, std::thread::JoinHandle<()>), Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let client = BinanceClient::new()?;
+    let running = Arc::new(AtomicBool::new(true));
+    let poller_running = Arc::clone(&running);
+
+    let handle = std::thread::spawn(move || {
+        let mut next_poll = Instant::now();
+        while poller_running.load(Ordering::Relaxed) {
+            if Instant::now() >= next_poll {
+                next_poll += OPEN_INTEREST_POLL_INTERVAL;
+                match runtime.block_on(client.open_interest(&symbol)) {
+                    Ok(open_interest) => {
+                        if let Err(err) = send_open_interest(&open_interest, &tx) {
+                            warn!("Failed to publish open interest: {err}");
+                        }
+                    }
+                    Err(err) => warn!("Failed to fetch open interest: {err}"),
+                }
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        info!("open interest poller exiting");
+    });
+
+    Ok((running, handle))
+}
+
+/// Derivatives streams are opt-in since not every spot symbol lists on USD-M futures
+fn derivatives_enabled() -> bool {
+    std::env::var("COLLECT_DERIVATIVES").is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+type ParseFn = fn(&str, &Sender<Bytes>) -> Result<(), Box<dyn std::error::Error>>;
+
+type IngestorFn = fn(&str) -> Result<BinanceIngestor, Box<dyn std::error::Error>>;
+
+/// Running flag plus the processing and ingestion threads of one stream
+type StreamHandles = (Arc<AtomicBool>, [std::thread::JoinHandle<()>; 2]);
+
+/// Connect a futures stream and start its parser and ingestion threads
+///
+/// Unlike the racing spot connections, a failing derivatives stream is logged
+/// but does not stop the collector.
+fn spawn_derivatives_stream(
+    mut ingestor: BinanceIngestor,
+    name: &'static str,
+    parse: ParseFn,
+    tx: Sender<Bytes>,
+) -> Result<StreamHandles, Box<dyn std::error::Error>> {
+    ingestor.connect()?;
+    let ingestor_running = Arc::clone(&ingestor.running);
+    ingestor_running.store(true, Ordering::Relaxed);
+
+    let processing_handle = ingestor.start_processing_thread(move |data| match std::str::from_utf8(data) {
+        Ok(json_str) => {
+            if let Err(err) = parse(json_str, &tx) {
+                warn!("Failed to parse {name} message: {err}");
+            }
+        }
+        Err(err) => warn!("Received invalid UTF-8 {name} data: {err}"),
+    });
+
+    let ingestion_handle = std::thread::spawn(move || match ingestor.run() {
+        Ok(_) => info!("{name} ingestion thread exited cleanly"),
+        Err(err) => error!("{name} ingestion thread error: {err}"),
+    });
+
+    Ok((ingestor_running, [processing_handle, ingestion_handle]))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // CRITICAL: Keep guard alive for entire application lifetime
     let _guard = mm_app::tracing_setup::init_with_stdout("mm_collector", "./logs", tracing::Level::INFO);
@@ -183,6 +424,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let symbol = cli::get_symbol_lowercase("btcusdt");
     info!("Starting dual WS collector for {symbol}");
 
+    let instruments = config_loader::load_instrument_registry_or_default("config/instruments.toml");
+    let instrument = instruments.by_symbol(Exchange::Binance, &symbol.to_uppercase());
+    let instrument_id = instrument.map_or(0, |info| info.id);
+    let price_scale = instrument.map_or(Scale::DEFAULT, |info| info.price_scale);
+    let qty_scale = instrument.map_or(Scale::DEFAULT, |info| info.qty_scale);
+
     // Initialise affinity manager for CPU pinning
     let affinity_manager = AffinityManager::new();
 
@@ -227,6 +474,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     trade_ingestor1_running.store(true, Ordering::Relaxed);
     trade_ingestor2_running.store(true, Ordering::Relaxed);
 
+    let trace_latency = latency_tracing::enabled();
+    if trace_latency {
+        info!("Latency tracing enabled - appending trailers to orderbook batches");
+    }
+
     // Start processing thread for connection 1
     let tx_clone1 = tx.clone();
     let msg_count1 = Arc::clone(&msg_count_conn1);
@@ -241,10 +493,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 debug!("Parser thread 1 pinned to core 0");
             }
 
+            let receive_time = trace_latency.then(time_utils::unix_timestamp_ns);
             match std::str::from_utf8(data) {
                 Ok(json_str) => {
                     // Parse JSON and send as batch message
-                    if let Err(err) = parse_and_send_batch(json_str, &tx_clone1) {
+                    if let Err(err) = parse_and_send_batch(json_str, &tx_clone1, instrument_id, price_scale, qty_scale, receive_time) {
                         warn!("Failed to parse message on conn1: {err}");
                     } else {
                         msg_count1.fetch_add(1, Ordering::Relaxed);
@@ -271,10 +524,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 debug!("Parser thread 2 pinned to core 1");
             }
 
+            let receive_time = trace_latency.then(time_utils::unix_timestamp_ns);
             match std::str::from_utf8(data) {
                 Ok(json_str) => {
                     // Parse JSON and send as batch message
-                    if let Err(err) = parse_and_send_batch(json_str, &tx_clone2) {
+                    if let Err(err) = parse_and_send_batch(json_str, &tx_clone2, instrument_id, price_scale, qty_scale, receive_time) {
                         warn!("Failed to parse message on conn2: {err}");
                     } else {
                         msg_count2.fetch_add(1, Ordering::Relaxed);
@@ -343,14 +597,48 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
     };
 
+    // Optional futures streams: mark price/funding, liquidations and polled open interest
+    let (tx_derivatives, rx_derivatives) = bounded::<Bytes>(aeron_config::DEFAULT_CHANNEL_CAPACITY);
+    let mut derivatives_running = Vec::new();
+    let mut derivatives_handles = Vec::new();
+    if derivatives_enabled() {
+        let streams: [(&'static str, IngestorFn, ParseFn); 2] = [
+            ("mark price", BinanceIngestor::new_mark_price_stream, parse_and_send_mark_price),
+            ("liquidation", BinanceIngestor::new_force_order_stream, parse_and_send_liquidation),
+        ];
+        for (name, new_ingestor, parse) in streams {
+            let spawned =
+                new_ingestor(&symbol).and_then(|ingestor| spawn_derivatives_stream(ingestor, name, parse, tx_derivatives.clone()));
+            match spawned {
+                Ok((flag, handles)) => {
+                    info!("{name} stream established for {symbol}");
+                    derivatives_running.push(flag);
+                    derivatives_handles.extend(handles);
+                }
+                Err(err) => error!("Skipping {name} stream for {symbol}: {err}"),
+            }
+        }
+
+        match spawn_open_interest_poller(symbol.to_uppercase(), tx_derivatives.clone()) {
+            Ok((flag, handle)) => {
+                info!("Polling open interest for {symbol} every {OPEN_INTEREST_POLL_INTERVAL:?}");
+                derivatives_running.push(flag);
+                derivatives_handles.push(handle);
+            }
+            Err(err) => error!("Skipping open interest for {symbol}: {err}"),
+        }
+    }
+
     // Set up Ctrl+C handler
-    shutdown_handler::setup_multi(vec![
+    let mut shutdown_flags = vec![
         Arc::clone(&running),
         Arc::clone(&ingestor1_running),
         Arc::clone(&ingestor2_running),
         Arc::clone(&trade_ingestor1_running),
         Arc::clone(&trade_ingestor2_running),
-    ])?;
+    ];
+    shutdown_flags.extend(derivatives_running.iter().cloned());
+    shutdown_handler::setup_multi(shutdown_flags)?;
 
     // Start WebSocket ingestion threads
     let running_clone1 = Arc::clone(&running);
@@ -402,6 +690,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         rx_trade.clone(),
     );
 
+    // Spawn derivatives publisher thread when futures streams are enabled
+    let derivatives_publisher_handle = (!derivatives_running.is_empty()).then(|| {
+        spawn_channel_publisher(
+            PublisherConfig::new(aeron_config::DERIVATIVES_DATA_CHANNEL, aeron_config::DERIVATIVES_DATA_STREAM_ID, "derivatives data"),
+            rx_derivatives.clone(),
+        )
+    });
+
     // Spawn state publisher thread (synchronous)
     let running_clone5 = Arc::clone(&running);
     let msg_count1_clone = Arc::clone(&msg_count_conn1);
@@ -497,6 +793,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     ingestor2_running.store(false, Ordering::Relaxed);
     trade_ingestor1_running.store(false, Ordering::Relaxed);
     trade_ingestor2_running.store(false, Ordering::Relaxed);
+    for flag in &derivatives_running {
+        flag.store(false, Ordering::Relaxed);
+    }
 
     // Wait for threads to finish
     let _ = ingestion_handle1.join();
@@ -507,12 +806,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = processing_handle2.join();
     let _ = trade_processing_handle1.join();
     let _ = trade_processing_handle2.join();
+    for handle in derivatives_handles {
+        let _ = handle.join();
+    }
 
     // Close channels and wait for publishers to finish
     drop(tx); // Close depth sender
     drop(tx_trade); // Close trade sender
+    drop(tx_derivatives); // Close derivatives sender
     let _ = publisher_handle.join();
     let _ = trade_publisher_handle.join();
+    if let Some(handle) = derivatives_publisher_handle {
+        let _ = handle.join();
+    }
     let _ = state_handle.join();
     let _ = heartbeat_handle.join();
 
@@ -10,16 +10,20 @@ use mm_aeron::Subscriber;
 use mm_app::aeron_config;
 use mm_app::cli;
 use mm_app::monitoring;
+use mm_app::orderbook_helpers::BinanceSnapshots;
 use mm_app::shutdown_handler;
 use mm_binary::CompressedString;
-use mm_binary::OrderBookBatchMessage;
-use mm_binary::from_fixed_point;
+use mm_binary::OrderBookBatchView;
+use mm_binary::ValidationLevel;
+use mm_binary::latency::split_trailer;
 use mm_binary::messages::PricingOutputMessage;
 use mm_binary::messages::TradeMessage;
 use mm_binary::to_fixed_point;
+use mm_orderbook::BatchOutcome;
 use mm_orderbook::OrderBook;
-use mm_strategy::FixedPoint;
+use mm_orderbook::OrderBookManager;
 use mm_strategy::MarketState;
+use mm_strategy::Price;
 use mm_strategy::StrategyConfig;
 use mm_strategy::drift_estimator::DriftEstimator;
 use mm_strategy::drift_estimator::Trade;
@@ -31,24 +35,26 @@ const HEARTBEAT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 const MIDPOINT_LOG_INTERVAL: Duration = Duration::from_secs(1);
 const PRICING_PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
 
+/// Minimum order book batches between snapshot fetches, to stay inside the REST rate limit
+const SNAPSHOT_REFETCH_INTERVAL: usize = 20;
+
 fn publish_pricing_output(
     orderbook: &OrderBook,
     drift_estimator: &DriftEstimator,
     symbol: &str,
     publisher: &mut Publisher,
-    last_trade_price: Option<FixedPoint>,
+    last_trade_price: Option<Price>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Get best bid and ask (now returns i64 fixed-point)
-    let (bid_price_i64, bid_volume_i64) = orderbook.best_bid().ok_or("No best bid")?;
-    let (ask_price_i64, ask_volume_i64) = orderbook.best_ask().ok_or("No best ask")?;
+    let (bid_price, bid_volume) = orderbook.best_bid().ok_or("No best bid")?;
+    let (ask_price, ask_volume) = orderbook.best_ask().ok_or("No best ask")?;
 
     // Build market state
     let state = MarketState {
         timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64,
-        bid_price: FixedPoint(bid_price_i64),
-        ask_price: FixedPoint(ask_price_i64),
-        bid_volume: FixedPoint(bid_volume_i64),
-        ask_volume: FixedPoint(ask_volume_i64),
+        bid_price,
+        ask_price,
+        bid_volume,
+        ask_volume,
         last_trade_price,
         last_trade_size: None,
     };
@@ -60,8 +66,7 @@ fn publish_pricing_output(
     let drift_bps = drift_estimator.estimate_drift_bps(&state);
 
     // Calculate fair value: micro-price + drift adjustment
-    let drift_adjustment = micro * FixedPoint::from_f64(drift_bps / 10000.0);
-    let fair_value = micro + drift_adjustment;
+    let fair_value = micro.apply_bps(drift_bps);
 
     // Calculate volatility from drift estimator
     let volatility = drift_estimator.current_volatility();
@@ -137,19 +142,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     pricing_publisher.add_publication(aeron_config::PRICING_OUTPUT_CHANNEL, aeron_config::PRICING_OUTPUT_STREAM_ID)?;
     info!("Publishing pricing output on stream {}", aeron_config::PRICING_OUTPUT_STREAM_ID);
 
-    // Fetch full orderbook snapshot via HTTP
+    // Fetch full orderbook snapshot via HTTP; sequence gaps refetch it
     info!("Fetching initial orderbook snapshot for {symbol}");
-    let snapshot = mm_app::orderbook_helpers::fetch_orderbook_snapshot(&symbol, 100)?;
-    info!("Received snapshot with {} bids, {} asks", snapshot.bids.len(), snapshot.asks.len());
-
-    // Initialise orderbook with snapshot data
-    let mut orderbook = OrderBook::new(&symbol);
-    for (price_fixed, qty_fixed) in &snapshot.bids {
-        orderbook.update_bid(*price_fixed, *qty_fixed);
-    }
-    for (price_fixed, qty_fixed) in &snapshot.asks {
-        orderbook.update_ask(*price_fixed, *qty_fixed);
-    }
+    let mut books =
+        OrderBookManager::new(BinanceSnapshots { depth: 100 }).with_max_levels(50).with_refetch_interval(SNAPSHOT_REFETCH_INTERVAL);
+    books.track(&symbol, 0)?;
 
     // Set up Ctrl+C handler
     let running = Arc::new(AtomicBool::new(true));
@@ -172,7 +169,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut last_midpoint_log = std::time::Instant::now();
     let mut last_pricing_publish = std::time::Instant::now();
     let mut orderbook_synchronised = false;
-    let mut last_trade_price: Option<FixedPoint> = None;
+    let mut last_trade_price: Option<Price> = None;
 
     while running.load(Ordering::Relaxed) {
         // Periodically check for stale heartbeats
@@ -182,12 +179,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         // Check if we should publish pricing output
         if orderbook_synchronised && last_pricing_publish.elapsed() > PRICING_PUBLISH_INTERVAL {
-            match publish_pricing_output(&orderbook, &drift_estimator, &symbol, &mut pricing_publisher, last_trade_price) {
-                Ok(_) => {}
-                Err(err) if err.to_string().contains("back pressure") => {
+            let published = books
+                .book(&symbol)
+                .map(|orderbook| publish_pricing_output(orderbook, &drift_estimator, &symbol, &mut pricing_publisher, last_trade_price));
+            match published {
+                Some(Ok(_)) | None => {}
+                Some(Err(err)) if err.to_string().contains("back pressure") => {
                     // Silently ignore back-pressure (no subscribers, fire-and-forget stream)
                 }
-                Err(err) => {
+                Some(Err(err)) => {
                     warn!("Failed to publish pricing output: {err}");
                 }
             }
@@ -201,15 +201,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(trade_msg) => {
                     trade_count += 1;
 
-                    let trade = Trade {
-                        timestamp: trade_msg.timestamp,
-                        price: FixedPoint(trade_msg.price),
-                        quantity: FixedPoint(trade_msg.quantity),
-                        side: trade_msg.trade_side(),
-                        is_aggressor: trade_msg.is_aggressor != 0,
-                    };
-
-                    last_trade_price = Some(FixedPoint(trade_msg.price));
+                    let trade = Trade::from(&trade_msg);
+                    last_trade_price = Some(trade.price);
                     drift_estimator.add_trade(trade);
 
                     if trade_count.is_multiple_of(100) {
@@ -236,8 +229,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         };
 
+        // Latency trailers are not forwarded: pricing output is off the quoting path
+        let (payload, _trace) = split_trailer(&data);
+
         // Parse as orderbook batch message (market data stream only has orderbook messages)
-        let batch = match OrderBookBatchMessage::from_bytes(&data) {
+        let batch = match OrderBookBatchView::new(payload, ValidationLevel::Standard) {
             Ok(m) => m,
             Err(err) => {
                 warn!("Failed to deserialize message: {err} (msg len: {} bytes)", data.len());
@@ -255,15 +251,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             debug!("Processed {msg_count} batch messages");
         }
 
-        // Verify symbol matches (skip messages for other symbols)
-        let msg_symbol = batch.symbol().decode(batch.encoding());
-        if msg_symbol != symbol {
-            continue;
+        // Apply in sequence, skipping messages for other symbols; gaps and crossed books refetch the snapshot
+        match books.apply_batch_view(&batch) {
+            BatchOutcome::Applied => {}
+            BatchOutcome::Resynced | BatchOutcome::OutOfSync => {
+                orderbook_synchronised = false;
+                continue;
+            }
+            BatchOutcome::Skipped | BatchOutcome::Untracked => continue,
         }
-
-        // Apply batch update to orderbook
-        orderbook.apply_batch(&batch);
-        orderbook.trim_book();
+        let Some(orderbook) = books.book(&symbol) else {
+            continue;
+        };
 
         // Log once when orderbook is synchronised (has both bids and asks from live updates)
         if !orderbook_synchronised && orderbook.best_bid().is_some() && orderbook.best_ask().is_some() {
@@ -274,12 +273,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Log mid-point every 5 seconds
         if orderbook_synchronised && last_midpoint_log.elapsed() > MIDPOINT_LOG_INTERVAL {
             if let (Some((bid_price, _)), Some((ask_price, _))) = (orderbook.best_bid(), orderbook.best_ask()) {
-                let spread_i64 = ask_price - bid_price;
-                if spread_i64 >= 0 {
-                    let mid_point = from_fixed_point((bid_price + ask_price) / 2);
-                    let bid_f64 = from_fixed_point(bid_price);
-                    let ask_f64 = from_fixed_point(ask_price);
-                    let spread_f64 = from_fixed_point(spread_i64);
+                let spread = ask_price - bid_price;
+                if spread >= Price::ZERO {
+                    let mid_point = bid_price.midpoint(ask_price).to_f64();
+                    let bid_f64 = bid_price.to_f64();
+                    let ask_f64 = ask_price.to_f64();
+                    let spread_f64 = spread.to_f64();
                     info!("Mid-point: {:.2} (bid: {:.2}, ask: {:.2}, spread: {:.2})", mid_point, bid_f64, ask_f64, spread_f64);
                     last_midpoint_log = Instant::now();
                 }
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use mm_aeron::Subscriber;
+use mm_app::aeron_config;
+use mm_app::shutdown_handler;
+use mm_app::time_utils;
+use mm_binary::journal::JournalWriter;
+use tracing::info;
+
+const DEFAULT_JOURNAL_DIR: &str = "./data/journal";
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const STATS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Every stream the pipeline publishes on
+const RECORDED_STREAMS: [(&str, i32); 13] = [
+    (aeron_config::MARKET_DATA_CHANNEL, aeron_config::MARKET_DATA_STREAM_ID),
+    (aeron_config::STATE_CHANNEL, aeron_config::STATE_STREAM_ID),
+    (aeron_config::HEARTBEAT_CHANNEL, aeron_config::HEARTBEAT_STREAM_ID),
+    (aeron_config::TRADE_DATA_CHANNEL, aeron_config::TRADE_DATA_STREAM_ID),
+    (aeron_config::PRICING_OUTPUT_CHANNEL, aeron_config::PRICING_OUTPUT_STREAM_ID),
+    (aeron_config::STRATEGY_QUOTES_CHANNEL, aeron_config::STRATEGY_QUOTES_STREAM_ID),
+    (aeron_config::ORDER_FILLS_CHANNEL, aeron_config::ORDER_FILLS_STREAM_ID),
+    (aeron_config::POSITION_CHANNEL, aeron_config::POSITION_STREAM_ID),
+    (aeron_config::ORDER_ENTRY_CHANNEL, aeron_config::ORDER_ENTRY_STREAM_ID),
+    (aeron_config::ORDER_RESPONSE_CHANNEL, aeron_config::ORDER_RESPONSE_STREAM_ID),
+    (aeron_config::DERIVATIVES_DATA_CHANNEL, aeron_config::DERIVATIVES_DATA_STREAM_ID),
+    (aeron_config::CONTROL_CHANNEL, aeron_config::CONTROL_STREAM_ID),
+    (aeron_config::RISK_EVENT_CHANNEL, aeron_config::RISK_EVENT_STREAM_ID),
+];
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize logging
+    let _guard = mm_app::tracing_setup::init_with_stdout("mm_recorder", "./logs", tracing::Level::INFO);
+
+    let journal_dir = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_JOURNAL_DIR.to_string());
+    let mut journal = JournalWriter::create(&journal_dir)?;
+    info!("Recording to {} (segment {})", journal_dir, journal.current_segment().segment_id);
+
+    // One subscriber per stream so every frame keeps its stream ID
+    let mut subscribers = Vec::with_capacity(RECORDED_STREAMS.len());
+    for (channel, stream_id) in RECORDED_STREAMS {
+        let mut subscriber = Subscriber::new();
+        subscriber.add_subscription(channel, stream_id)?;
+        subscribers.push((stream_id, subscriber));
+    }
+    info!("Subscribed to {} streams", subscribers.len());
+
+    // Set up shutdown handler
+    let running = Arc::new(AtomicBool::new(true));
+    shutdown_handler::setup(Arc::clone(&running))?;
+
+    let mut frame_count = 0u64;
+    let mut byte_count = 0u64;
+    let mut last_flush = Instant::now();
+    let mut last_stats = Instant::now();
+
+    while running.load(Ordering::Relaxed) {
+        let mut idle = true;
+        for (stream_id, subscriber) in &mut subscribers {
+            while let Ok(Some(data)) = subscriber.try_receive() {
+                journal.append(*stream_id, time_utils::unix_timestamp_ns(), &data)?;
+                frame_count += 1;
+                byte_count += data.len() as u64;
+                idle = false;
+            }
+        }
+
+        if last_flush.elapsed() > FLUSH_INTERVAL {
+            journal.flush()?;
+            last_flush = Instant::now();
+        }
+
+        if last_stats.elapsed() > STATS_INTERVAL {
+            info!(
+                "Recorded {} frames ({:.1} MB) | segment {}",
+                frame_count,
+                byte_count as f64 / 1_000_000.0,
+                journal.current_segment().segment_id
+            );
+            last_stats = Instant::now();
+        }
+
+        // Small sleep to avoid busy-waiting
+        if idle {
+            std::thread::sleep(Duration::from_micros(100));
+        }
+    }
+
+    journal.finish()?;
+    info!("Recorder stopped after {frame_count} frames");
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
@@ -10,25 +11,44 @@ use mm_aeron::Subscriber;
 use mm_app::aeron_config;
 use mm_app::cli;
 use mm_app::config_loader;
+use mm_app::latency_tracing::LatencyHistograms;
 use mm_app::monitoring;
-use mm_app::orderbook_sync::OrderbookSyncState;
+use mm_app::orderbook_helpers::BinanceSnapshots;
 use mm_app::shutdown_handler;
+use mm_app::time_utils;
+use mm_binary::AnyMessage;
 use mm_binary::CompressedString;
 use mm_binary::Exchange;
 use mm_binary::OrderBookBatchMessage;
-use mm_binary::from_fixed_point;
+use mm_binary::QuoteLadderMessage;
+use mm_binary::SequenceTracker;
+use mm_binary::decode_any;
+use mm_binary::latency::HopTimestamps;
+use mm_binary::latency::PipelineStage;
+use mm_binary::latency::split_trailer;
 use mm_binary::messages::OrderFillMessage;
 use mm_binary::messages::OrderSide;
 use mm_binary::messages::QuoteMessage;
-use mm_orderbook::OrderBook;
+use mm_binary::order_messages::OrderAckMessage;
+use mm_binary::order_messages::OrderRejectMessage;
+use mm_binary::order_messages::OrderRequestType;
+use mm_binary::order_messages::RejectReason;
+use mm_orderbook::BatchOutcome;
+use mm_orderbook::OrderBookManager;
 use mm_sim_executor::OrderBookSimulator;
 use mm_sim_executor::SimulatedFill;
-use mm_strategy::FixedPoint;
+use mm_strategy::Price;
+use mm_strategy::Qty;
+use mm_strategy::SpecViolation;
 use tracing::debug;
 use tracing::info;
 use tracing::warn;
 
 const HEARTBEAT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+const LATENCY_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Minimum order book batches between snapshot fetches, to stay inside the REST rate limit
+const SNAPSHOT_REFETCH_INTERVAL: usize = 20;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
@@ -38,6 +58,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config_file = config_loader::load_simulator_config_or_default("config/simulator.toml");
     let symbol = cli::get_symbol_uppercase(&config_file.symbol);
     let config = config_file.simulator;
+    let fees = config_loader::load_fee_schedule_or_default("config/fees.toml");
 
     info!("Starting order fill simulator for {symbol}");
     info!(
@@ -48,7 +69,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         config.track_queue_position
     );
 
-    let mut simulator = OrderBookSimulator::new(config);
+    let instrument = config_loader::load_instrument_spec_or_default("config/instruments.toml", Exchange::Binance, &symbol);
+    let mut simulator = OrderBookSimulator::new(config).with_instrument(instrument).with_fees(fees);
 
     // Connect to Aeron - need separate subscribers for each stream!
     let mut market_data_subscriber = Subscriber::new();
@@ -59,27 +81,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     strategy_quotes_subscriber.add_subscription(aeron_config::STRATEGY_QUOTES_CHANNEL, aeron_config::STRATEGY_QUOTES_STREAM_ID)?;
     info!("Subscribed to strategy quotes on stream {}", aeron_config::STRATEGY_QUOTES_STREAM_ID);
 
+    let mut order_entry_subscriber = Subscriber::new();
+    order_entry_subscriber.add_subscription(aeron_config::ORDER_ENTRY_CHANNEL, aeron_config::ORDER_ENTRY_STREAM_ID)?;
+    info!("Subscribed to order entry on stream {}", aeron_config::ORDER_ENTRY_STREAM_ID);
+
+    let mut order_response_publisher = Publisher::new();
+    order_response_publisher.add_publication(aeron_config::ORDER_RESPONSE_CHANNEL, aeron_config::ORDER_RESPONSE_STREAM_ID)?;
+    info!("Publishing order responses on stream {}", aeron_config::ORDER_RESPONSE_STREAM_ID);
+
     // Create publisher for order fills
     let mut fill_publisher = Publisher::new();
     fill_publisher.add_publication(aeron_config::ORDER_FILLS_CHANNEL, aeron_config::ORDER_FILLS_STREAM_ID)?;
     info!("Publishing order fills on stream {}", aeron_config::ORDER_FILLS_STREAM_ID);
 
-    // Fetch initial orderbook snapshot
+    // Fetch initial orderbook snapshot; sequence gaps refetch it
     info!("Fetching initial orderbook snapshot for {symbol}");
-    let snapshot = mm_app::orderbook_helpers::fetch_orderbook_snapshot(&symbol, 100)?;
-    info!("Received snapshot with {} bids, {} asks", snapshot.bids.len(), snapshot.asks.len());
-
-    // Initialize orderbook synchronization state
-    let mut sync_state = OrderbookSyncState::new(snapshot.last_update_id);
-
-    // Initialize orderbook
-    let mut orderbook = OrderBook::new(&symbol);
-    for (price_fixed, qty_fixed) in &snapshot.bids {
-        orderbook.update_bid(*price_fixed, *qty_fixed);
-    }
-    for (price_fixed, qty_fixed) in &snapshot.asks {
-        orderbook.update_ask(*price_fixed, *qty_fixed);
-    }
+    let mut books = OrderBookManager::new(BinanceSnapshots { depth: 100 }).with_refetch_interval(SNAPSHOT_REFETCH_INTERVAL);
+    books.track(&symbol, 0)?;
 
     // Set up shutdown handler
     let running = Arc::new(AtomicBool::new(true));
@@ -100,7 +118,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut fill_count = 0u64;
     let mut last_heartbeat_check = Instant::now();
     let mut orderbook_synchronized = false;
-    let last_trade_price: Option<FixedPoint> = None;
+    let last_trade_price: Option<Price> = None;
+    let mut fill_sequence = 0u64;
+    let mut response_sequence = 0u64;
+    let mut client_orders: HashMap<u64, u64> = HashMap::new();
+    let mut quote_sequence_tracker = SequenceTracker::new();
+    let mut latency_histograms = LatencyHistograms::new();
+    let mut last_latency_report = Instant::now();
 
     while running.load(Ordering::Relaxed) {
         // Check heartbeat
@@ -109,23 +133,80 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             last_heartbeat_check = Instant::now();
         }
 
-        // Try to receive from strategy quotes subscriber first (non-blocking)
+        if last_latency_report.elapsed() > LATENCY_REPORT_INTERVAL {
+            if latency_histograms.traces() > 0 {
+                latency_histograms.log_summary();
+                latency_histograms.reset();
+            }
+            last_latency_report = Instant::now();
+        }
+
+        // Explicit order requests are answered with an ack or reject for their client order ID
+        if let Ok(Some(data)) = order_entry_subscriber.try_receive() {
+            // Orders that filled or were swept by a quote no longer answer to their client ID
+            client_orders.retain(|_, order_id| simulator.active_orders().contains_key(order_id));
+
+            let timestamp = time_utils::unix_timestamp_ns();
+            match handle_order_request(&data, &mut simulator, &mut client_orders, timestamp, response_sequence) {
+                Ok(Some(response)) => {
+                    order_response_publisher.publish(Bytes::from(response.to_bytes()))?;
+                    response_sequence += 1;
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Failed to handle order request: {err}"),
+            }
+            continue;
+        }
+
+        // Try to receive from strategy quotes subscriber
         if let Ok(Some(data)) = strategy_quotes_subscriber.try_receive() {
+            let receive_time = time_utils::unix_timestamp_ns();
+            let (payload, trace) = split_trailer(&data);
+
+            if payload.first() == Some(&QuoteLadderMessage::MESSAGE_TYPE) {
+                match QuoteLadderMessage::from_bytes(payload) {
+                    Ok(ladder) => {
+                        let parse_time = time_utils::unix_timestamp_ns();
+                        if !monitoring::log_sequence_event("Strategy quote", quote_sequence_tracker.observe(ladder.sequence())) {
+                            continue;
+                        }
+                        quote_count += 1;
+
+                        let levels = |levels: &[mm_binary::PriceLevel]| {
+                            levels.iter().map(|level| (Price(level.price), Qty(level.size))).collect::<Vec<_>>()
+                        };
+                        let timestamp = time_utils::unix_timestamp_ns();
+                        let order_ids = simulator.replace_ladder(&levels(ladder.bids()), &levels(ladder.asks()), timestamp);
+
+                        if let Some(mut trace) = trace {
+                            let publish_time = time_utils::unix_timestamp_ns();
+                            trace.push_hop(HopTimestamps::new(PipelineStage::Simulator, receive_time, parse_time, publish_time));
+                            latency_histograms.record(&trace);
+                        }
+
+                        debug!(
+                            "Received ladder {}: {} bids, {} asks | Placed {} orders",
+                            ladder.quote_id(),
+                            ladder.bids().len(),
+                            ladder.asks().len(),
+                            order_ids.len()
+                        );
+                    }
+                    Err(err) => warn!("Failed to parse quote ladder message: {err}"),
+                }
+                continue;
+            }
+
             // Try to parse as QuoteMessage
-            if let Ok(quote_msg) = QuoteMessage::from_bytes(&data) {
+            if let Ok(quote_msg) = QuoteMessage::from_bytes(payload) {
+                let parse_time = time_utils::unix_timestamp_ns();
+                if !monitoring::log_sequence_event("Strategy quote", quote_sequence_tracker.observe(quote_msg.sequence)) {
+                    continue;
+                }
                 quote_count += 1;
 
                 // Convert quote message to StrategyQuote
-                let quote = mm_strategy::StrategyQuote {
-                    timestamp: quote_msg.timestamp,
-                    bid_price: FixedPoint(quote_msg.bid_price),
-                    bid_size: FixedPoint(quote_msg.bid_size),
-                    ask_price: FixedPoint(quote_msg.ask_price),
-                    ask_size: FixedPoint(quote_msg.ask_size),
-                    fair_value: FixedPoint(quote_msg.fair_value),
-                    inventory: FixedPoint(quote_msg.inventory),
-                    confidence: from_fixed_point(quote_msg.confidence),
-                };
+                let quote = mm_strategy::StrategyQuote::from(&quote_msg);
 
                 // Cancel previous orders (simplified: cancel all and replace)
                 simulator.cancel_all_orders();
@@ -135,6 +216,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 let order_ids = simulator.place_orders_from_quote(&quote, timestamp);
 
+                // The simulator ends the traced path: placing orders counts as its publish
+                if let Some(mut trace) = trace {
+                    let publish_time = time_utils::unix_timestamp_ns();
+                    trace.push_hop(HopTimestamps::new(PipelineStage::Simulator, receive_time, parse_time, publish_time));
+                    latency_histograms.record(&trace);
+                }
+
                 debug!(
                     "Received quote: bid ${:.2} x {}, ask ${:.2} x {} | Placed {} orders",
                     quote.bid_price.to_f64(),
@@ -156,65 +244,44 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // No data available, check for fills to publish
                 if !simulator.drain_fills().is_empty() {
                     let fills = simulator.drain_fills();
-                    publish_fills(&fills, &mut fill_publisher)?;
+                    publish_fills(&fills, &mut fill_publisher, &mut fill_sequence)?;
                     fill_count += fills.len() as u64;
                 }
                 continue;
             }
         };
 
+        // Trailers on market data are only forwarded by the strategy
+        let (payload, _trace) = split_trailer(&data);
+
         // Try to parse as OrderBookBatchMessage (market data stream only has orderbook messages)
-        if let Ok(batch) = OrderBookBatchMessage::from_bytes(&data) {
+        if let Ok(batch) = OrderBookBatchMessage::from_bytes(payload) {
             msg_count += 1;
 
-            // Check if this update should be processed based on sequence IDs
-            if !sync_state.should_process_update(&batch) {
-                // Check if we should resync (threshold of consecutive skips reached)
-                if sync_state.should_resync() {
-                    warn!("Attempting automatic resync - fetching fresh snapshot");
-
-                    match mm_app::orderbook_helpers::fetch_orderbook_snapshot(&symbol, 100) {
-                        Ok(fresh_snapshot) => {
-                            info!("Received fresh snapshot with {} bids, {} asks", fresh_snapshot.bids.len(), fresh_snapshot.asks.len());
-
-                            // Reset orderbook
-                            orderbook = OrderBook::new(&symbol);
-                            for (price_fixed, qty_fixed) in &fresh_snapshot.bids {
-                                orderbook.update_bid(*price_fixed, *qty_fixed);
-                            }
-                            for (price_fixed, qty_fixed) in &fresh_snapshot.asks {
-                                orderbook.update_ask(*price_fixed, *qty_fixed);
-                            }
-
-                            // Reset sync state
-                            sync_state = OrderbookSyncState::new(fresh_snapshot.last_update_id);
-                            orderbook_synchronized = false;
-
-                            info!("Orderbook resync complete - waiting for synchronization");
-                        }
-                        Err(err) => {
-                            warn!("Failed to fetch snapshot for resync: {err}");
-                        }
-                    }
+            // Apply in sequence; gaps and crossed books refetch the snapshot
+            match books.apply_batch(&batch) {
+                BatchOutcome::Applied => {}
+                BatchOutcome::Resynced | BatchOutcome::OutOfSync => {
+                    orderbook_synchronized = false;
+                    continue;
                 }
-
-                continue; // Skip this update - not synced yet or sequence gap
+                BatchOutcome::Skipped | BatchOutcome::Untracked => continue,
             }
+            let Some(orderbook) = books.book(&symbol) else {
+                continue;
+            };
 
-            // Apply batch update to orderbook
-            orderbook.apply_batch(&batch);
-
-            if !orderbook_synchronized && sync_state.is_synchronized() && orderbook.best_bid().is_some() && orderbook.best_ask().is_some() {
+            if !orderbook_synchronized && orderbook.best_bid().is_some() && orderbook.best_ask().is_some() {
                 orderbook_synchronized = true;
                 let best_bid = orderbook.best_bid().unwrap();
                 let best_ask = orderbook.best_ask().unwrap();
-                let spread = from_fixed_point(best_ask.0) - from_fixed_point(best_bid.0);
+                let spread = (best_ask.0 - best_bid.0).to_f64();
                 info!(
                     "Orderbook synchronized - ready to simulate fills | Best bid: ${:.2} x {}, Best ask: ${:.2} x {}, Spread: ${:.2}",
-                    from_fixed_point(best_bid.0),
-                    from_fixed_point(best_bid.1),
-                    from_fixed_point(best_ask.0),
-                    from_fixed_point(best_ask.1),
+                    best_bid.0.to_f64(),
+                    best_bid.1.to_f64(),
+                    best_ask.0.to_f64(),
+                    best_ask.1.to_f64(),
                     spread
                 );
             }
@@ -222,12 +289,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Update simulator with new market data
             let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64;
 
-            simulator.update_market_data(&orderbook, timestamp, last_trade_price);
+            simulator.update_market_data(orderbook, timestamp, last_trade_price);
 
             // Check for fills
             let fills = simulator.drain_fills();
             if !fills.is_empty() {
-                publish_fills(&fills, &mut fill_publisher)?;
+                publish_fills(&fills, &mut fill_publisher, &mut fill_sequence)?;
                 fill_count += fills.len() as u64;
 
                 for fill in &fills {
@@ -241,8 +308,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             if msg_count.is_multiple_of(1000) {
-                if let Some(mid_i64) = orderbook.mid_price() {
-                    let mid = from_fixed_point(mid_i64);
+                if let Some(mid) = orderbook.mid_price() {
+                    let mid = mid.to_f64();
                     let position = simulator.position();
                     debug!(
                         "Processed {} OB updates, {} quotes, {} fills | Mid: ${:.2} | Active orders: {} | Position: {}",
@@ -268,7 +335,152 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn publish_fills(fills: &[SimulatedFill], publisher: &mut Publisher) -> Result<(), Box<dyn std::error::Error>> {
+/// Apply an order request to the simulator, returning the ack or reject to publish
+///
+/// `client_orders` maps each resting client order ID to the simulator's order ID.
+/// Anything other than an order request is ignored.
+fn handle_order_request(
+    data: &[u8],
+    simulator: &mut OrderBookSimulator,
+    client_orders: &mut HashMap<u64, u64>,
+    timestamp: u64,
+    sequence: u64,
+) -> Result<Option<AnyMessage>, Box<dyn std::error::Error>> {
+    let response = match decode_any(data)? {
+        AnyMessage::NewOrder(msg) => {
+            let exchange = msg.exchange()?;
+            let side = msg.order_side()?;
+            let reject = |reason| {
+                OrderRejectMessage::new_with_sequence(
+                    exchange,
+                    OrderRequestType::New,
+                    reason,
+                    msg.symbol(),
+                    msg.encoding_scheme(),
+                    timestamp,
+                    msg.client_order_id,
+                    sequence,
+                )
+                .into()
+            };
+            if client_orders.contains_key(&msg.client_order_id) {
+                return Ok(Some(reject(RejectReason::DuplicateClientOrderId)));
+            }
+            let order_id = match simulator.place_order(side, Price(msg.price), Qty(msg.quantity), timestamp) {
+                Ok(order_id) => order_id,
+                Err(violation) => return Ok(Some(reject(reject_reason(&violation)))),
+            };
+            client_orders.insert(msg.client_order_id, order_id);
+            OrderAckMessage::new_with_sequence(
+                exchange,
+                OrderRequestType::New,
+                msg.symbol(),
+                msg.encoding_scheme(),
+                timestamp,
+                msg.client_order_id,
+                order_id,
+                side,
+                msg.price,
+                msg.quantity,
+                sequence,
+            )
+            .into()
+        }
+        AnyMessage::CancelOrder(msg) => {
+            let exchange = msg.exchange()?;
+            let resting = client_orders
+                .remove(&msg.client_order_id)
+                .and_then(|order_id| simulator.active_orders().get(&order_id).map(|order| (order_id, order.side, order.price)));
+            match resting {
+                Some((order_id, order_side, price)) => {
+                    simulator.cancel_order(order_id);
+                    OrderAckMessage::new_with_sequence(
+                        exchange,
+                        OrderRequestType::Cancel,
+                        msg.symbol(),
+                        msg.encoding_scheme(),
+                        timestamp,
+                        msg.client_order_id,
+                        order_id,
+                        order_side,
+                        price.to_i64(),
+                        0,
+                        sequence,
+                    )
+                    .into()
+                }
+                None => OrderRejectMessage::new_with_sequence(
+                    exchange,
+                    OrderRequestType::Cancel,
+                    RejectReason::UnknownOrder,
+                    msg.symbol(),
+                    msg.encoding_scheme(),
+                    timestamp,
+                    msg.client_order_id,
+                    sequence,
+                )
+                .into(),
+            }
+        }
+        AnyMessage::ReplaceOrder(msg) => {
+            let exchange = msg.exchange()?;
+            let side = msg.order_side()?;
+            let reject = |reason| {
+                OrderRejectMessage::new_with_sequence(
+                    exchange,
+                    OrderRequestType::Replace,
+                    reason,
+                    msg.symbol(),
+                    msg.encoding_scheme(),
+                    timestamp,
+                    msg.client_order_id,
+                    sequence,
+                )
+                .into()
+            };
+            let Some(&orig_order_id) = client_orders.get(&msg.orig_client_order_id) else {
+                return Ok(Some(reject(RejectReason::UnknownOrder)));
+            };
+            if msg.client_order_id != msg.orig_client_order_id && client_orders.contains_key(&msg.client_order_id) {
+                return Ok(Some(reject(RejectReason::DuplicateClientOrderId)));
+            }
+            // A rejected replacement leaves the original order resting
+            let order_id = match simulator.place_order(side, Price(msg.price), Qty(msg.quantity), timestamp) {
+                Ok(order_id) => order_id,
+                Err(violation) => return Ok(Some(reject(reject_reason(&violation)))),
+            };
+            simulator.cancel_order(orig_order_id);
+            client_orders.remove(&msg.orig_client_order_id);
+            client_orders.insert(msg.client_order_id, order_id);
+            OrderAckMessage::new_with_sequence(
+                exchange,
+                OrderRequestType::Replace,
+                msg.symbol(),
+                msg.encoding_scheme(),
+                timestamp,
+                msg.client_order_id,
+                order_id,
+                side,
+                msg.price,
+                msg.quantity,
+                sequence,
+            )
+            .into()
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(response))
+}
+
+fn reject_reason(violation: &SpecViolation) -> RejectReason {
+    match violation {
+        SpecViolation::OffTick { .. } => RejectReason::InvalidPrice,
+        SpecViolation::OffLot { .. } | SpecViolation::BelowMinNotional { .. } => RejectReason::InvalidQuantity,
+    }
+}
+
+fn publish_fills(fills: &[SimulatedFill], publisher: &mut Publisher, sequence: &mut u64) -> Result<(), Box<dyn std::error::Error>> {
     let (symbol, encoding) = CompressedString::from_str("BTCUSDT")?;
 
     for fill in fills {
@@ -277,7 +489,7 @@ fn publish_fills(fills: &[SimulatedFill], publisher: &mut Publisher) -> Result<(
             mm_strategy::OrderSide::Ask => 1u8,
         };
 
-        let fill_msg = OrderFillMessage::new(
+        let fill_msg = OrderFillMessage::new_with_sequence(
             Exchange::Binance,
             symbol,
             encoding,
@@ -287,10 +499,12 @@ fn publish_fills(fills: &[SimulatedFill], publisher: &mut Publisher) -> Result<(
             fill.quantity.to_i64(),
             if side_byte == 0 { OrderSide::Bid } else { OrderSide::Ask },
             fill.is_maker,
+            *sequence,
         );
 
         let bytes = Bytes::from(fill_msg.to_bytes().to_vec());
         publisher.publish(bytes)?;
+        *sequence += 1;
     }
 
     Ok(())
@@ -11,18 +11,36 @@ use mm_app::aeron_config;
 use mm_app::cli;
 use mm_app::config_loader;
 use mm_app::monitoring;
-use mm_app::orderbook_sync::OrderbookSyncState;
+use mm_app::orderbook_helpers::BinanceSnapshots;
 use mm_app::shutdown_handler;
+use mm_app::time_utils;
+use mm_binary::AnyMessage;
 use mm_binary::CompressedString;
-use mm_binary::OrderBookBatchMessage;
+use mm_binary::Exchange;
+use mm_binary::OrderBookBatchView;
+use mm_binary::QuoteLadderMessage;
+use mm_binary::RiskEventMessage;
+use mm_binary::SequenceTracker;
+use mm_binary::ValidationLevel;
+use mm_binary::control_messages::KillReason;
+use mm_binary::control_messages::RiskEventKind;
+use mm_binary::decode_any;
 use mm_binary::from_fixed_point;
+use mm_binary::latency::HopTimestamps;
+use mm_binary::latency::LatencyTrailer;
+use mm_binary::latency::PipelineStage;
+use mm_binary::latency::split_trailer;
 use mm_binary::messages::OrderFillMessage;
-use mm_binary::messages::PositionMessage;
-use mm_binary::messages::QuoteMessage;
 use mm_binary::messages::TradeMessage;
+use mm_orderbook::BatchOutcome;
+use mm_orderbook::BookView;
 use mm_orderbook::OrderBook;
-use mm_strategy::FixedPoint;
+use mm_orderbook::OrderBookManager;
+use mm_strategy::FundingFeed;
+use mm_strategy::FundingSettlement;
 use mm_strategy::MarketState;
+use mm_strategy::Price;
+use mm_strategy::Qty;
 use mm_strategy::drift_estimator::Trade;
 use mm_strategy::quote_engine::QuoteEngine;
 use tracing::debug;
@@ -31,6 +49,22 @@ use tracing::warn;
 
 const HEARTBEAT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 
+/// Minimum order book batches between snapshot fetches, to stay inside the REST rate limit
+const SNAPSHOT_REFETCH_INTERVAL: usize = 20;
+
+/// Strategy ID stamped on quotes and risk events, and matched against control commands
+const STRATEGY_ID: u8 = 0;
+
+/// Default distance between ladder levels when only `ladder_levels` is configured
+const DEFAULT_LADDER_SPACING_BPS: f64 = 5.0;
+
+/// Publish a multi-level ladder instead of a single quote
+#[derive(Debug, Clone, Copy)]
+struct LadderConfig {
+    levels: usize,
+    spacing_bps: f64,
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
     let _guard = mm_app::tracing_setup::init_with_stdout("mm_strategy", "./logs", tracing::Level::INFO);
@@ -39,16 +73,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config_file = config_loader::load_strategy_config_or_default("config/strategy.toml");
     let symbol = cli::get_symbol_uppercase(&config_file.symbol);
     let config = config_file.strategy;
+    let fees = config_loader::load_fee_schedule_or_default("config/fees.toml");
     let quote_publish_interval = Duration::from_millis(config_file.quote_publish_interval_ms.unwrap_or(100));
+    let ladder = config_file
+        .ladder_levels
+        .filter(|&levels| levels > 1)
+        .map(|levels| LadderConfig { levels, spacing_bps: config_file.ladder_spacing_bps.unwrap_or(DEFAULT_LADDER_SPACING_BPS) });
 
     info!("Starting market making strategy for {symbol}");
     info!(
         "Strategy config: min_spread={}bps, max_position={}, base_size={}",
         config.min_spread_bps, config.max_position_size, config.base_quote_size
     );
+    if let Some(ladder) = ladder {
+        info!("Publishing {}-level quote ladders spaced {}bps apart", ladder.levels, ladder.spacing_bps);
+    }
 
     // Initialize quote engine
-    let mut quote_engine = QuoteEngine::new(config);
+    let instrument = config_loader::load_instrument_spec_or_default("config/instruments.toml", Exchange::Binance, &symbol);
+    let mut quote_engine = QuoteEngine::new(config).with_instrument(instrument);
 
     // Connect to Aeron - need separate subscribers for each stream!
     let mut market_data_subscriber = Subscriber::new();
@@ -63,6 +106,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     order_fills_subscriber.add_subscription(aeron_config::ORDER_FILLS_CHANNEL, aeron_config::ORDER_FILLS_STREAM_ID)?;
     info!("Subscribed to order fills on stream {}", aeron_config::ORDER_FILLS_STREAM_ID);
 
+    // Subscribe to derivatives data for funding settlements
+    let mut derivatives_subscriber = Subscriber::new();
+    derivatives_subscriber.add_subscription(aeron_config::DERIVATIVES_DATA_CHANNEL, aeron_config::DERIVATIVES_DATA_STREAM_ID)?;
+    info!("Subscribed to derivatives data on stream {}", aeron_config::DERIVATIVES_DATA_STREAM_ID);
+
+    // Subscribe to control commands (kill switch, resume, parameter updates)
+    let mut control_subscriber = Subscriber::new();
+    control_subscriber.add_subscription(aeron_config::CONTROL_CHANNEL, aeron_config::CONTROL_STREAM_ID)?;
+    info!("Subscribed to control commands on stream {}", aeron_config::CONTROL_STREAM_ID);
+
     // Create publishers
     let mut quote_publisher = Publisher::new();
     quote_publisher.add_publication(aeron_config::STRATEGY_QUOTES_CHANNEL, aeron_config::STRATEGY_QUOTES_STREAM_ID)?;
@@ -72,22 +125,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     position_publisher.add_publication(aeron_config::POSITION_CHANNEL, aeron_config::POSITION_STREAM_ID)?;
     info!("Publishing position updates on stream {}", aeron_config::POSITION_STREAM_ID);
 
-    // Fetch initial orderbook snapshot
-    info!("Fetching initial orderbook snapshot for {symbol}");
-    let snapshot = mm_app::orderbook_helpers::fetch_orderbook_snapshot(&symbol, 100)?;
-    info!("Received snapshot with {} bids, {} asks", snapshot.bids.len(), snapshot.asks.len());
-
-    // Initialize orderbook synchronization state
-    let mut sync_state = OrderbookSyncState::new(snapshot.last_update_id);
+    let mut risk_event_publisher = Publisher::new();
+    risk_event_publisher.add_publication(aeron_config::RISK_EVENT_CHANNEL, aeron_config::RISK_EVENT_STREAM_ID)?;
+    info!("Publishing risk events on stream {}", aeron_config::RISK_EVENT_STREAM_ID);
 
-    // Initialize orderbook
-    let mut orderbook = OrderBook::new(&symbol);
-    for (price_fixed, qty_fixed) in &snapshot.bids {
-        orderbook.update_bid(*price_fixed, *qty_fixed);
-    }
-    for (price_fixed, qty_fixed) in &snapshot.asks {
-        orderbook.update_ask(*price_fixed, *qty_fixed);
-    }
+    // Fetch initial orderbook snapshot; sequence gaps refetch it
+    info!("Fetching initial orderbook snapshot for {symbol}");
+    let mut books = OrderBookManager::new(BinanceSnapshots { depth: 100 }).with_refetch_interval(SNAPSHOT_REFETCH_INTERVAL);
+    books.track(&symbol, 0)?;
 
     // Set up shutdown handler
     let running = Arc::new(AtomicBool::new(true));
@@ -107,7 +152,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut last_heartbeat_check = Instant::now();
     let mut last_quote_publish = Instant::now();
     let mut orderbook_synchronized = false;
-    let mut last_trade_price: Option<FixedPoint> = None;
+    let mut last_trade_price: Option<Price> = None;
+    // Latest traced market update and its strategy hop, forwarded on the next quote
+    let mut pending_trace: Option<(LatencyTrailer, HopTimestamps)> = None;
+    let mut quote_sequence = 0u64;
+    let mut position_sequence = 0u64;
+    let mut risk_event_sequence = 0u64;
+    let mut fill_sequence_tracker = SequenceTracker::new();
+    let mut funding = FundingFeed::new(CompressedString::from_str(&symbol)?.0, quote_engine.instrument().contract);
 
     while running.load(Ordering::Relaxed) {
         // Check heartbeat
@@ -115,18 +167,48 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let heartbeat_stale = monitoring::is_heartbeat_stale(&last_heartbeat_timestamp, aeron_config::HEARTBEAT_TIMEOUT_MS);
 
             // Kill strategy if heartbeat is stale
-            if heartbeat_stale {
+            if heartbeat_stale && !quote_engine.risk_manager_mut().is_killed() {
                 warn!("Heartbeat stale, killing strategy");
                 quote_engine.risk_manager_mut().kill("Heartbeat timeout".to_string());
+
+                let code = KillReason::HeartbeatTimeout as u8;
+                let event = RiskEvent {
+                    kind: RiskEventKind::Killed,
+                    code,
+                    value: 0.0,
+                    limit: aeron_config::HEARTBEAT_TIMEOUT_MS as f64,
+                    source_id: u32::from(STRATEGY_ID),
+                };
+                if let Err(err) = publish_risk_event(event, &mut risk_event_publisher, &mut risk_event_sequence) {
+                    warn!("Failed to publish risk event: {err}");
+                }
             }
 
             last_heartbeat_check = Instant::now();
         }
 
+        // Control commands take priority over everything else (non-blocking)
+        if let Ok(Some(data)) = control_subscriber.try_receive() {
+            match handle_control(&data, &mut quote_engine) {
+                Ok(Some(event)) => {
+                    if let Err(err) = publish_risk_event(event, &mut risk_event_publisher, &mut risk_event_sequence) {
+                        warn!("Failed to publish risk event: {err}");
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Failed to handle control message: {err}"),
+            }
+            continue;
+        }
+
         // Try to receive from order fills subscriber first (non-blocking)
         if let Ok(Some(data)) = order_fills_subscriber.try_receive() {
             // Try to parse as OrderFillMessage
             if let Ok(fill_msg) = OrderFillMessage::from_bytes(&data) {
+                if !monitoring::log_sequence_event("Order fill", fill_sequence_tracker.observe(fill_msg.sequence)) {
+                    continue;
+                }
+
                 info!(
                     "Received fill: {} {} @ {} ({})",
                     if fill_msg.side == 0 { "BUY" } else { "SELL" },
@@ -136,21 +218,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
 
                 // Update position tracker
-                let fill_side = if fill_msg.side == 0 { mm_strategy::OrderSide::Bid } else { mm_strategy::OrderSide::Ask };
                 let mut position = *quote_engine.inventory_manager_mut().position();
-                position.apply_fill(fill_side, FixedPoint(fill_msg.fill_price), FixedPoint(fill_msg.fill_quantity));
+                let fee = position.apply_fill_message(&fill_msg, &fees);
                 quote_engine.inventory_manager_mut().update_position(position);
 
                 // Publish position update
-                if let Err(err) = publish_position(&position, &orderbook, &mut position_publisher) {
-                    warn!("Failed to publish position update: {err}");
+                if let Some(orderbook) = books.book(&symbol) {
+                    if let Err(err) = publish_position(&position, orderbook, &mut position_publisher, &mut position_sequence) {
+                        warn!("Failed to publish position update: {err}");
+                    }
                 }
 
                 info!(
-                    "Position updated: {} @ avg ${:.2} | Realized PnL: ${:.2}",
+                    "Position updated: {} @ avg ${:.2} | Realized PnL: ${:.2} | Fee: ${:.4} | Net fees: ${:.2}",
                     position.quantity.to_f64(),
                     position.avg_entry_price.to_f64(),
-                    position.realized_pnl.to_f64()
+                    position.realized_pnl.to_f64(),
+                    fee.to_f64(),
+                    (position.fees_paid - position.rebates_earned).to_f64()
                 );
             } else {
                 warn!("Failed to parse order fill message");
@@ -158,69 +243,76 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             continue;
         }
 
+        // Settle funding on a perpetual position when its funding time passes (non-blocking)
+        if let Ok(Some(data)) = derivatives_subscriber.try_receive() {
+            match funding.observe_frame(&data) {
+                Ok(Some(settlement)) => {
+                    settle_funding(settlement, &mut quote_engine, books.book(&symbol), &mut position_publisher, &mut position_sequence);
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Failed to parse derivatives message: {err}"),
+            }
+            continue;
+        }
+
         // Receive messages from market data subscriber (blocking with timeout)
         let data = match market_data_subscriber.receive() {
             Ok(d) => d,
             Err(_err) => {
                 // No data available, check if we should publish quotes
                 if orderbook_synchronized && last_quote_publish.elapsed() > quote_publish_interval {
-                    publish_quotes(&mut quote_engine, &orderbook, last_trade_price, &mut quote_publisher)?;
+                    if let Some(orderbook) = books.book(&symbol) {
+                        publish_quotes(
+                            &mut quote_engine,
+                            orderbook,
+                            last_trade_price,
+                            pending_trace.take(),
+                            ladder,
+                            &mut quote_publisher,
+                            &mut quote_sequence,
+                        )?;
+                    }
                     last_quote_publish = Instant::now();
                 }
                 continue;
             }
         };
 
-        // Try to parse as OrderBookBatchMessage first
-        if let Ok(batch) = OrderBookBatchMessage::from_bytes(&data) {
-            msg_count += 1;
+        let receive_time = time_utils::unix_timestamp_ns();
+        let (payload, trace) = split_trailer(&data);
 
-            // Check if this update should be processed based on sequence IDs
-            if !sync_state.should_process_update(&batch) {
-                // Check if we should resync (threshold of consecutive skips reached)
-                if sync_state.should_resync() {
-                    warn!("Attempting automatic resync - fetching fresh snapshot");
-
-                    match mm_app::orderbook_helpers::fetch_orderbook_snapshot(&symbol, 100) {
-                        Ok(fresh_snapshot) => {
-                            info!("Received fresh snapshot with {} bids, {} asks", fresh_snapshot.bids.len(), fresh_snapshot.asks.len());
-
-                            // Reset orderbook
-                            orderbook = OrderBook::new(&symbol);
-                            for (price_fixed, qty_fixed) in &fresh_snapshot.bids {
-                                orderbook.update_bid(*price_fixed, *qty_fixed);
-                            }
-                            for (price_fixed, qty_fixed) in &fresh_snapshot.asks {
-                                orderbook.update_ask(*price_fixed, *qty_fixed);
-                            }
-
-                            // Reset sync state
-                            sync_state = OrderbookSyncState::new(fresh_snapshot.last_update_id);
-                            orderbook_synchronized = false;
-
-                            info!("Orderbook resync complete - waiting for synchronization");
-                        }
-                        Err(err) => {
-                            warn!("Failed to fetch snapshot for resync: {err}");
-                        }
-                    }
+        // Try to read as an OrderBookBatch view first (zero-copy)
+        if let Ok(batch) = OrderBookBatchView::new(payload, ValidationLevel::Standard) {
+            msg_count += 1;
+            let parse_time = time_utils::unix_timestamp_ns();
+
+            // Apply in sequence; gaps and crossed books refetch the snapshot
+            match books.apply_batch_view(&batch) {
+                BatchOutcome::Applied => {}
+                BatchOutcome::Resynced | BatchOutcome::OutOfSync => {
+                    orderbook_synchronized = false;
+                    continue;
                 }
-
-                continue; // Skip this update - not synced yet or sequence gap
+                BatchOutcome::Skipped | BatchOutcome::Untracked => continue,
             }
+            let Some(orderbook) = books.book(&symbol) else {
+                continue;
+            };
 
-            // Apply batch update to orderbook
-            orderbook.apply_batch(&batch);
+            if let Some(trace) = trace {
+                // Publish time is stamped when the quote goes out
+                pending_trace = Some((trace, HopTimestamps::new(PipelineStage::Strategy, receive_time, parse_time, 0)));
+            }
 
-            if !orderbook_synchronized && sync_state.is_synchronized() && orderbook.best_bid().is_some() && orderbook.best_ask().is_some() {
+            if !orderbook_synchronized && orderbook.best_bid().is_some() && orderbook.best_ask().is_some() {
                 orderbook_synchronized = true;
                 info!("Orderbook synchronized - starting quote generation");
             }
 
             if msg_count.is_multiple_of(1000) {
-                if let (Some(mid_i64), Some(spread_i64)) = (orderbook.mid_price(), orderbook.spread()) {
-                    let mid = from_fixed_point(mid_i64);
-                    let spread = from_fixed_point(spread_i64);
+                if let (Some(mid), Some(spread)) = (orderbook.mid_price(), orderbook.spread()) {
+                    let mid = mid.to_f64();
+                    let spread = spread.to_f64();
                     let spread_bps = (spread / mid) * 10000.0;
                     debug!(
                         "Processed {} messages | Mid: ${:.2} | Spread: {:.2}bps | Inventory: {}",
@@ -236,16 +328,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         // Try to parse as TradeMessage (can appear in market data stream)
-        if let Ok(trade_msg) = TradeMessage::from_bytes(&data) {
-            let trade = Trade {
-                timestamp: trade_msg.timestamp,
-                price: FixedPoint(trade_msg.price),
-                quantity: FixedPoint(trade_msg.quantity),
-                side: trade_msg.trade_side(),
-                is_aggressor: trade_msg.is_aggressor != 0,
-            };
-
-            last_trade_price = Some(FixedPoint(trade_msg.price));
+        if let Ok(trade_msg) = TradeMessage::from_bytes(payload) {
+            let trade = Trade::from(&trade_msg);
+            last_trade_price = Some(trade.price);
             quote_engine.drift_estimator_mut().add_trade(trade);
             continue;
         }
@@ -261,45 +346,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 fn publish_quotes(
     quote_engine: &mut QuoteEngine,
     orderbook: &OrderBook,
-    last_trade_price: Option<FixedPoint>,
+    last_trade_price: Option<Price>,
+    trace: Option<(LatencyTrailer, HopTimestamps)>,
+    ladder: Option<LadderConfig>,
     publisher: &mut Publisher,
+    sequence: &mut u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Build market state
-    let default_price = 50_000 * mm_binary::FIXED_POINT_MULTIPLIER;
-    let (bid_price, bid_volume) = orderbook.best_bid().unwrap_or((default_price, 0));
-    let (ask_price, ask_volume) = orderbook.best_ask().unwrap_or((default_price, 0));
+    let default_price = Price::from_int(50_000);
+    let (bid_price, bid_volume) = orderbook.best_bid().unwrap_or((default_price, Qty::ZERO));
+    let (ask_price, ask_volume) = orderbook.best_ask().unwrap_or((default_price, Qty::ZERO));
 
     let state = MarketState {
         timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64,
-        bid_price: FixedPoint(bid_price),
-        ask_price: FixedPoint(ask_price),
-        bid_volume: FixedPoint(bid_volume),
-        ask_volume: FixedPoint(ask_volume),
+        bid_price,
+        ask_price,
+        bid_volume,
+        ask_volume,
         last_trade_price,
         last_trade_size: None,
     };
 
-    // Generate quote
-    if let Some(quote) = quote_engine.generate_quotes(&state) {
-        // Create and publish QuoteMessage
-        let (symbol, encoding) = CompressedString::from_str("BTCUSDT")?;
-
-        let quote_msg = QuoteMessage::new(
-            0, // strategy_id
-            symbol,
-            encoding,
-            quote.timestamp,
-            quote.bid_price.to_i64(),
-            quote.bid_size.to_i64(),
-            quote.ask_price.to_i64(),
-            quote.ask_size.to_i64(),
-            quote.fair_value.to_i64(),
-            quote.inventory.to_i64(),
-            mm_binary::to_fixed_point(quote.confidence),
+    // Spread floor from what one base quote would cost to trade through the book
+    let reference_notional = state.mid_price().saturating_mul(Qty::from_f64(quote_engine.config().base_quote_size));
+    quote_engine.set_depth(orderbook.depth_profile(reference_notional));
+
+    let (symbol, encoding) = CompressedString::from_str("BTCUSDT")?;
+
+    let mut frame = if let Some(ladder) = ladder {
+        let quotes = quote_engine.generate_ladder_quotes(&state, ladder.levels, ladder.spacing_bps);
+        let Some(top) = quotes.first() else {
+            return Ok(());
+        };
+
+        // The sequence doubles as the quote ID: every ladder fully replaces the last
+        let mut ladder_msg = QuoteLadderMessage::new(STRATEGY_ID, symbol, encoding, top.timestamp, *sequence);
+        ladder_msg.set_sequence(*sequence);
+        ladder_msg.set_valuation(top.fair_value.to_i64(), top.inventory.to_i64(), mm_binary::to_fixed_point(top.confidence));
+        for quote in &quotes {
+            ladder_msg.add_bid(quote.bid_price.to_i64(), quote.bid_size.to_i64());
+            ladder_msg.add_ask(quote.ask_price.to_i64(), quote.ask_size.to_i64());
+        }
+
+        debug!(
+            "Published {}-level ladder: best bid ${:.2} | best ask ${:.2} | fv ${:.2}",
+            quotes.len(),
+            top.bid_price.to_f64(),
+            top.ask_price.to_f64(),
+            top.fair_value.to_f64()
         );
+        ladder_msg.to_bytes()
+    } else {
+        let Some(quote) = quote_engine.generate_quotes(&state) else {
+            return Ok(());
+        };
 
-        let bytes = Bytes::from(quote_msg.to_bytes().to_vec());
-        publisher.publish(bytes)?;
+        let quote_msg = quote.to_message(STRATEGY_ID, symbol, encoding, *sequence);
 
         debug!(
             "Published quote: bid ${:.2} x {} | ask ${:.2} x {} | fv ${:.2} | conf {:.2}",
@@ -310,34 +412,125 @@ fn publish_quotes(
             quote.fair_value.to_f64(),
             quote.confidence
         );
+        quote_msg.to_bytes().to_vec()
+    };
+
+    if let Some((mut trace, hop)) = trace {
+        trace.push_hop(HopTimestamps { publish_time: time_utils::unix_timestamp_ns(), ..hop });
+        trace.append_to(&mut frame);
     }
+    publisher.publish(Bytes::from(frame))?;
+    *sequence += 1;
+
+    Ok(())
+}
+
+/// A risk event to publish, before it is stamped with a timestamp and sequence
+struct RiskEvent {
+    kind: RiskEventKind,
+    /// Kill reason or parameter, depending on `kind`
+    code: u8,
+    value: f64,
+    limit: f64,
+    /// Who caused the event: the commanding source, or this strategy
+    source_id: u32,
+}
+
+/// Apply a control command addressed to this strategy, returning the risk event to publish
+///
+/// Commands for other strategies, and anything other than a command, are ignored.
+fn handle_control(data: &[u8], quote_engine: &mut QuoteEngine) -> Result<Option<RiskEvent>, Box<dyn std::error::Error>> {
+    let event = match decode_any(data)? {
+        AnyMessage::KillSwitch(msg) if msg.applies_to(STRATEGY_ID) => {
+            let reason = msg.reason()?;
+            warn!("Kill switch received from source {}: {:?}", msg.source_id, reason);
+            quote_engine.risk_manager_mut().kill(format!("{reason:?} (source {})", msg.source_id));
+            RiskEvent { kind: RiskEventKind::Killed, code: reason as u8, value: 0.0, limit: 0.0, source_id: msg.source_id }
+        }
+        AnyMessage::Resume(msg) if msg.applies_to(STRATEGY_ID) => {
+            info!("Resume received from source {}", msg.source_id);
+            quote_engine.risk_manager_mut().resume();
+            RiskEvent { kind: RiskEventKind::Resumed, code: 0, value: 0.0, limit: 0.0, source_id: msg.source_id }
+        }
+        AnyMessage::StrategyParamUpdate(msg) if msg.applies_to(STRATEGY_ID) => {
+            let param = msg.param()?;
+            let (kind, limit) = match quote_engine.apply_param(param, msg.value) {
+                Ok(previous) => (RiskEventKind::ParamUpdated, previous),
+                Err(err) => {
+                    warn!("Rejected parameter update from source {}: {err}", msg.source_id);
+                    (RiskEventKind::ParamRejected, 0.0)
+                }
+            };
+            RiskEvent { kind, code: param as u8, value: msg.value, limit, source_id: msg.source_id }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(event))
+}
+
+fn publish_risk_event(event: RiskEvent, publisher: &mut Publisher, sequence: &mut u64) -> Result<(), Box<dyn std::error::Error>> {
+    let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64;
+    let msg = RiskEventMessage::new_with_sequence(
+        STRATEGY_ID,
+        event.source_id,
+        event.kind,
+        event.code,
+        event.value,
+        event.limit,
+        timestamp,
+        *sequence,
+    );
+
+    let bytes = Bytes::from(AnyMessage::from(msg).to_bytes());
+    publisher.publish(bytes)?;
+    *sequence += 1;
 
     Ok(())
 }
 
+/// Book one funding settlement on the position and publish it
+///
+/// Marked at the venue mark price, or at the book mid before the first one arrives.
+fn settle_funding(
+    settlement: FundingSettlement,
+    quote_engine: &mut QuoteEngine,
+    orderbook: Option<&OrderBook>,
+    publisher: &mut Publisher,
+    sequence: &mut u64,
+) {
+    let rate = settlement.rate;
+    let Some(mark_price) = settlement.mark_price.or_else(|| orderbook.and_then(|book| book.mid_price())) else {
+        warn!("Missed funding settlement at rate {rate}: no mark price");
+        return;
+    };
+
+    let mut position = *quote_engine.inventory_manager_mut().position();
+    let payment = position.apply_funding(rate, mark_price);
+    quote_engine.inventory_manager_mut().update_position(position);
+    info!("Funding settled at rate {rate}: {:.4} | Total funding: {:.4}", payment.to_f64(), position.funding.to_f64());
+
+    if let Some(orderbook) = orderbook {
+        if let Err(err) = publish_position(&position, orderbook, publisher, sequence) {
+            warn!("Failed to publish position update: {err}");
+        }
+    }
+}
+
 fn publish_position(
     position: &mm_strategy::Position,
     orderbook: &OrderBook,
     publisher: &mut Publisher,
+    sequence: &mut u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let default_mid = 50_000 * mm_binary::FIXED_POINT_MULTIPLIER;
-    let mark_price_i64 = orderbook.mid_price().unwrap_or(default_mid);
-    let unrealized_pnl = position.unrealized_pnl(FixedPoint(mark_price_i64));
-
+    let mark_price = orderbook.mid_price().unwrap_or(Price::from_int(50_000));
     let (symbol, encoding) = CompressedString::from_str("BTCUSDT")?;
-
-    let pos_msg = PositionMessage::new(
-        symbol,
-        encoding,
-        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64,
-        position.quantity.to_i64(),
-        position.avg_entry_price.to_i64(),
-        unrealized_pnl.to_i64(),
-        position.realized_pnl.to_i64(),
-    );
+    let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos() as u64;
+    let pos_msg = position.to_message(symbol, encoding, timestamp, mark_price, *sequence);
 
     let bytes = Bytes::from(pos_msg.to_bytes().to_vec());
     publisher.publish(bytes)?;
+    *sequence += 1;
 
     Ok(())
 }
@@ -3,7 +3,13 @@ use std::path::Path;
 use config::Config;
 use config::ConfigError;
 use config::File;
+use mm_binary::Exchange;
+use mm_binary::InstrumentConfig;
+use mm_binary::InstrumentRegistry;
 use mm_sim_executor::SimulatorConfig;
+use mm_strategy::FeeSchedule;
+use mm_strategy::FeeTierConfig;
+use mm_strategy::InstrumentSpec;
 use mm_strategy::StrategyConfig;
 use serde::Deserialize;
 
@@ -14,6 +20,9 @@ pub struct StrategyConfigFile {
     pub strategy: StrategyConfig,
     pub quote_publish_interval_ms: Option<u64>,
     pub max_daily_loss: Option<f64>,
+    /// Levels per side; more than 1 publishes `QuoteLadderMessage` instead of `QuoteMessage`
+    pub ladder_levels: Option<usize>,
+    pub ladder_spacing_bps: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +32,19 @@ pub struct SimulatorConfigFile {
     pub simulator: SimulatorConfig,
 }
 
+/// Venue fee schedule shared by the strategy, simulator and backtest
+#[derive(Debug, Deserialize)]
+pub struct FeesConfigFile {
+    /// Venue fees charged on fills; empty means none
+    #[serde(default)]
+    pub fee_tiers: Vec<FeeTierConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InstrumentsConfigFile {
+    pub instruments: Vec<InstrumentConfig>,
+}
+
 pub fn load_strategy_config<P: AsRef<Path>>(path: P) -> Result<StrategyConfigFile, ConfigError> {
     let config = Config::builder().add_source(File::from(path.as_ref())).build()?;
 
@@ -35,6 +57,68 @@ pub fn load_simulator_config<P: AsRef<Path>>(path: P) -> Result<SimulatorConfigF
     config.try_deserialize()
 }
 
+pub fn load_fee_schedule<P: AsRef<Path>>(path: P) -> Result<FeeSchedule, ConfigError> {
+    let config = Config::builder().add_source(File::from(path.as_ref())).build()?;
+    let file: FeesConfigFile = config.try_deserialize()?;
+
+    Ok(FeeTierConfig::schedule(&file.fee_tiers))
+}
+
+/// Load the shared fee schedule, falling back to no fees
+pub fn load_fee_schedule_or_default(path: &str) -> FeeSchedule {
+    match load_fee_schedule(path) {
+        Ok(fees) => {
+            tracing::info!("Loaded fee schedule from {path}");
+            fees
+        }
+        Err(err) => {
+            tracing::warn!("Failed to load fee schedule from {}: {}. Charging no fees.", path, err);
+            FeeSchedule::zero()
+        }
+    }
+}
+
+pub fn load_instrument_registry<P: AsRef<Path>>(path: P) -> Result<InstrumentRegistry, ConfigError> {
+    let config = Config::builder().add_source(File::from(path.as_ref())).build()?;
+    let file: InstrumentsConfigFile = config.try_deserialize()?;
+
+    InstrumentRegistry::from_configs(file.instruments).map_err(|err| ConfigError::Message(err.to_string()))
+}
+
+/// Load the instrument registry, falling back to an empty one (messages then carry symbols only)
+pub fn load_instrument_registry_or_default(path: &str) -> InstrumentRegistry {
+    match load_instrument_registry(path) {
+        Ok(registry) => {
+            tracing::info!("Loaded {} instruments from {path}", registry.len());
+            registry
+        }
+        Err(err) => {
+            tracing::warn!("Failed to load instruments from {}: {}. Falling back to symbol-only messages.", path, err);
+            InstrumentRegistry::new()
+        }
+    }
+}
+
+/// Trading rules for a venue symbol from the registry file, unconstrained if it is not listed
+pub fn load_instrument_spec_or_default(path: &str, venue: Exchange, symbol: &str) -> InstrumentSpec {
+    let registry = load_instrument_registry_or_default(path);
+    match registry.by_symbol(venue, symbol).and_then(InstrumentSpec::from_info) {
+        Some(spec) => {
+            tracing::info!(
+                "Instrument rules for {symbol}: tick={}, lot={}, min_notional={}",
+                spec.tick_size,
+                spec.lot_size,
+                spec.min_notional
+            );
+            spec
+        }
+        None => {
+            tracing::warn!("No usable instrument rules for {symbol}; quotes will not be rounded to ticks or lots");
+            InstrumentSpec::UNCONSTRAINED
+        }
+    }
+}
+
 /// Load strategy config with fallback to default
 pub fn load_strategy_config_or_default(path: &str) -> StrategyConfigFile {
     match load_strategy_config(path) {
@@ -49,6 +133,8 @@ pub fn load_strategy_config_or_default(path: &str) -> StrategyConfigFile {
                 strategy: StrategyConfig::default(),
                 quote_publish_interval_ms: Some(100),
                 max_daily_loss: Some(1000.0),
+                ladder_levels: None,
+                ladder_spacing_bps: None,
             }
         }
     }
@@ -79,6 +165,8 @@ mod tests {
             strategy: StrategyConfig::default(),
             quote_publish_interval_ms: Some(100),
             max_daily_loss: Some(1000.0),
+            ladder_levels: None,
+            ladder_spacing_bps: None,
         };
 
         assert_eq!(strategy.symbol, "BTCUSDT");
@@ -89,4 +177,10 @@ mod tests {
         assert_eq!(simulator.symbol, "BTCUSDT");
         assert_eq!(simulator.simulator.order_placement_latency_us, 10_000);
     }
+
+    #[test]
+    fn test_shared_fee_schedule_loads() {
+        let fees = load_fee_schedule(concat!(env!("CARGO_MANIFEST_DIR"), "/../config/fees.toml")).unwrap();
+        assert_eq!(fees, FeeSchedule::zero());
+    }
 }
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use hdrhistogram::Histogram;
+use mm_binary::LatencyTrailer;
+use mm_binary::latency::PipelineStage;
+use tracing::info;
+
+/// Longest latency tracked (60 seconds); larger samples are clamped
+const MAX_TRACKABLE_NS: u64 = 60_000_000_000;
+
+/// Histogram precision in significant decimal digits
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+/// Whether the collector should start latency traces
+///
+/// Off by default. Downstream processes always strip trailers and forward any they receive.
+pub fn enabled() -> bool {
+    std::env::var("LATENCY_TRACING").is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// One measured interval along the pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    /// Exchange event time to collector receive (includes clock skew between hosts)
+    ExchangeToReceive,
+    /// Receive to parse within one process
+    Parse(PipelineStage),
+    /// Receive to publish within one process
+    Process(PipelineStage),
+    /// Publish by one process to receive by the next
+    Transit(PipelineStage, PipelineStage),
+    /// Exchange event time to the last publish on the path
+    EndToEnd,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::ExchangeToReceive => write!(f, "exchange->receive"),
+            Segment::Parse(stage) => write!(f, "{} parse", stage.as_str()),
+            Segment::Process(stage) => write!(f, "{} process", stage.as_str()),
+            Segment::Transit(from, to) => write!(f, "{}->{}", from.as_str(), to.as_str()),
+            Segment::EndToEnd => write!(f, "end-to-end"),
+        }
+    }
+}
+
+/// Per-hop HDR histograms built from the trailers that reach the end of the pipeline
+///
+/// Unlike `mm_ws::metrics::PerformanceMetrics`, which measures one process, this
+/// attributes latency to each process and each transport hop between them.
+#[derive(Debug, Default)]
+pub struct LatencyHistograms {
+    segments: BTreeMap<Segment, Histogram<u64>>,
+    traces: u64,
+}
+
+impl LatencyHistograms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every interval in a trailer; unset (zero) timestamps are skipped
+    pub fn record(&mut self, trailer: &LatencyTrailer) {
+        let hops = trailer.hops();
+        self.traces += 1;
+
+        if let Some(first) = hops.first() {
+            self.record_interval(Segment::ExchangeToReceive, trailer.exchange_time, first.receive_time);
+        }
+
+        for hop in hops {
+            self.record_interval(Segment::Parse(hop.stage), hop.receive_time, hop.parse_time);
+            self.record_interval(Segment::Process(hop.stage), hop.receive_time, hop.publish_time);
+        }
+
+        for pair in hops.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            self.record_interval(Segment::Transit(from.stage, to.stage), from.publish_time, to.receive_time);
+        }
+
+        self.record_interval(Segment::EndToEnd, trailer.exchange_time, trailer.last_publish_time());
+    }
+
+    fn record_interval(&mut self, segment: Segment, start: u64, end: u64) {
+        if start == 0 || end == 0 {
+            return;
+        }
+        let histogram = self
+            .segments
+            .entry(segment)
+            .or_insert_with(|| Histogram::new_with_bounds(1, MAX_TRACKABLE_NS, SIGNIFICANT_DIGITS).expect("valid histogram bounds"));
+        // Clock skew between hosts can make an interval negative; count it as zero
+        histogram.saturating_record(end.saturating_sub(start));
+    }
+
+    pub fn get(&self, segment: Segment) -> Option<&Histogram<u64>> {
+        self.segments.get(&segment)
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = (&Segment, &Histogram<u64>)> {
+        self.segments.iter()
+    }
+
+    /// Number of trailers recorded
+    pub fn traces(&self) -> u64 {
+        self.traces
+    }
+
+    pub fn reset(&mut self) {
+        self.segments.clear();
+        self.traces = 0;
+    }
+
+    pub fn log_summary(&self) {
+        info!("=== Latency by hop ({} traces, microseconds) ===", self.traces);
+        for (segment, histogram) in &self.segments {
+            info!(
+                "  {:<22} n={:<8} p50={:>9.1} p99={:>9.1} p99.9={:>9.1} max={:>9.1}",
+                segment.to_string(),
+                histogram.len(),
+                histogram.value_at_quantile(0.50) as f64 / 1000.0,
+                histogram.value_at_quantile(0.99) as f64 / 1000.0,
+                histogram.value_at_quantile(0.999) as f64 / 1000.0,
+                histogram.max() as f64 / 1000.0
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_binary::latency::HopTimestamps;
+
+    fn max_of(histograms: &LatencyHistograms, segment: Segment) -> u64 {
+        histograms.get(segment).unwrap().max()
+    }
+
+    #[test]
+    fn test_histograms_per_hop() {
+        let mut trailer = LatencyTrailer::new(1_000_000);
+        trailer.push_hop(HopTimestamps::new(PipelineStage::Collector, 3_000_000, 3_010_000, 3_020_000));
+        trailer.push_hop(HopTimestamps::new(PipelineStage::Strategy, 3_030_000, 3_035_000, 3_100_000));
+        trailer.push_hop(HopTimestamps::new(PipelineStage::Simulator, 3_120_000, 3_121_000, 0));
+
+        let mut histograms = LatencyHistograms::new();
+        histograms.record(&trailer);
+
+        assert_eq!(histograms.traces(), 1);
+        assert_eq!(histograms.get(Segment::ExchangeToReceive).unwrap().len(), 1);
+
+        // HDR histograms keep 3 significant digits
+        let close = |actual: u64, expected: u64| actual.abs_diff(expected) <= expected / 1000;
+        assert!(close(max_of(&histograms, Segment::ExchangeToReceive), 2_000_000));
+        assert!(close(max_of(&histograms, Segment::Parse(PipelineStage::Collector)), 10_000));
+        assert!(close(max_of(&histograms, Segment::Transit(PipelineStage::Collector, PipelineStage::Strategy)), 10_000));
+        assert!(close(max_of(&histograms, Segment::Transit(PipelineStage::Strategy, PipelineStage::Simulator)), 20_000));
+
+        // The simulator hop never published, so end-to-end stops at the strategy
+        assert!(close(max_of(&histograms, Segment::EndToEnd), 2_100_000));
+        assert!(histograms.get(Segment::Process(PipelineStage::Simulator)).is_none());
+    }
+}
@@ -8,6 +8,8 @@ use std::time::Instant;
 use mm_aeron::Subscriber;
 use mm_binary::CollectorStateMessage;
 use mm_binary::HeartbeatMessage;
+use mm_binary::SequenceEvent;
+use mm_binary::SequenceTracker;
 use tracing::debug;
 use tracing::warn;
 
@@ -54,7 +56,7 @@ pub fn spawn_heartbeat_monitor(
         }
         tracing::info!("Heartbeat monitor subscribed to stream {}", config.stream_id);
 
-        let mut last_sequence: Option<u64> = None;
+        let mut sequence_tracker = SequenceTracker::new();
 
         while running.load(Ordering::Relaxed) {
             match subscriber.receive() {
@@ -69,18 +71,8 @@ pub fn spawn_heartbeat_monitor(
                         let latency_ms = now.saturating_sub(hb_msg.timestamp);
 
                         // Check for sequence gaps
-                        if let Some(last_seq) = last_sequence {
-                            let expected = last_seq.wrapping_add(1);
-                            if hb_msg.sequence != expected {
-                                warn!(
-                                    "Heartbeat sequence gap detected: expected {expected}, got {} (gap: {})",
-                                    hb_msg.sequence,
-                                    hb_msg.sequence.saturating_sub(expected)
-                                );
-                            }
-                        }
+                        log_sequence_event("Heartbeat", sequence_tracker.observe(hb_msg.sequence));
 
-                        last_sequence = Some(hb_msg.sequence);
                         debug!("Heartbeat received: seq={}, latency={}ms", hb_msg.sequence, latency_ms);
                     }
                 }
@@ -146,6 +138,37 @@ pub fn spawn_state_monitor(
     Ok(handle)
 }
 
+/// Log anything other than an in-order sequence on a stream
+///
+/// Returns true if the message should be processed (i.e. it is not a duplicate or
+/// too stale to tell). A publisher restart is logged and delivered; the tracker has
+/// already reset.
+pub fn log_sequence_event(stream: &str, event: SequenceEvent) -> bool {
+    match event {
+        SequenceEvent::First | SequenceEvent::InOrder => true,
+        SequenceEvent::Gap { expected, received, missed } => {
+            warn!("{stream} sequence gap detected: expected {expected}, got {received} (missed: {missed})");
+            true
+        }
+        SequenceEvent::Reordered { sequence } => {
+            warn!("{stream} sequence {sequence} arrived out of order");
+            true
+        }
+        SequenceEvent::Duplicate { sequence } => {
+            warn!("{stream} duplicate sequence {sequence} dropped");
+            false
+        }
+        SequenceEvent::Stale { sequence, highest } => {
+            warn!("{stream} stale sequence {sequence} dropped, {highest} already seen");
+            false
+        }
+        SequenceEvent::Restart { previous, sequence } => {
+            warn!("{stream} publisher restarted: sequence went from {previous} back to {sequence}");
+            true
+        }
+    }
+}
+
 /// Helper to check if heartbeat is stale and log errors
 pub fn is_heartbeat_stale(last_timestamp: &Arc<AtomicU64>, timeout_ms: u64) -> bool {
     let now = time_utils::unix_timestamp_ms();
@@ -1,5 +1,7 @@
 use mm_http::binance::BinanceClient;
 use mm_http::binance::OrderbookSnapshot;
+use mm_orderbook::BookSnapshot;
+use mm_orderbook::SnapshotProvider;
 
 /// Fetch orderbook snapshot from Binance
 ///
@@ -12,3 +14,16 @@ pub fn fetch_orderbook_snapshot(symbol: &str, depth: u16) -> Result<OrderbookSna
         binance_client.orderbook(symbol, depth).await
     })?)
 }
+
+/// Binance REST depth snapshots for an `OrderBookManager`
+#[derive(Debug, Clone, Copy)]
+pub struct BinanceSnapshots {
+    pub depth: u16,
+}
+
+impl SnapshotProvider for BinanceSnapshots {
+    fn fetch_snapshot(&mut self, symbol: &str) -> Result<BookSnapshot, Box<dyn std::error::Error>> {
+        let snapshot = fetch_orderbook_snapshot(symbol, self.depth)?;
+        Ok(BookSnapshot { last_update_id: snapshot.last_update_id, bids: snapshot.bids, asks: snapshot.asks })
+    }
+}
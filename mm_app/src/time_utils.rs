@@ -7,6 +7,12 @@ pub fn unix_timestamp_ms() -> u64 {
     SystemTime::now().duration_since(UNIX_EPOCH).expect("System time before Unix epoch").as_millis() as u64
 }
 
+#[inline]
+/// Returns the current Unix timestamp in nanoseconds
+pub fn unix_timestamp_ns() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("System time before Unix epoch").as_nanos() as u64
+}
+
 #[inline]
 /// Returns the current Unix timestamp in seconds
 pub fn unix_timestamp_s() -> u64 {
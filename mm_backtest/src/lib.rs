@@ -1,7 +1,8 @@
 use std::collections::VecDeque;
 
 use mm_binary::messages::TradeSide;
-use mm_strategy::FixedPoint;
+use mm_strategy::Price;
+use mm_strategy::Qty;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
@@ -19,6 +20,9 @@ pub enum BacktestError {
     #[error("CSV error: {0}")]
     Csv(#[from] csv::Error),
 
+    #[error("Journal error: {0}")]
+    Journal(#[from] mm_binary::journal::JournalError),
+
     #[error("Parse error: {0}")]
     Parse(String),
 
@@ -158,8 +162,8 @@ impl Default for BacktestConfig {
 pub struct BacktestFill {
     pub timestamp: u64,
     pub side: mm_strategy::OrderSide,
-    pub price: FixedPoint,
-    pub quantity: FixedPoint,
+    pub price: Price,
+    pub quantity: Qty,
     pub is_maker: bool,
 }
 
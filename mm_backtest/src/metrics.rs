@@ -1,8 +1,10 @@
 use std::collections::VecDeque;
 
-use mm_strategy::FixedPoint;
+use mm_strategy::Notional;
 use mm_strategy::OrderSide;
 use mm_strategy::Position;
+use mm_strategy::Price;
+use mm_strategy::Qty;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -19,7 +21,7 @@ pub struct BacktestMetrics {
     pub sell_trades: u64,
     pub total_volume: f64,
 
-    // PnL metrics
+    // PnL metrics; `total_pnl` is net of fees, rebates and funding
     pub initial_capital: f64,
     pub final_capital: f64,
     pub total_pnl: f64,
@@ -27,6 +29,12 @@ pub struct BacktestMetrics {
     pub realized_pnl: f64,
     pub unrealized_pnl: f64,
 
+    // PnL breakdown: gross_pnl - fees_paid + rebates_earned + funding_pnl = total_pnl
+    pub gross_pnl: f64,
+    pub fees_paid: f64,
+    pub rebates_earned: f64,
+    pub funding_pnl: f64,
+
     // Performance metrics
     pub sharpe_ratio: f64,
     pub max_drawdown: f64,
@@ -62,6 +70,10 @@ impl Default for BacktestMetrics {
             total_pnl_pct: 0.0,
             realized_pnl: 0.0,
             unrealized_pnl: 0.0,
+            gross_pnl: 0.0,
+            fees_paid: 0.0,
+            rebates_earned: 0.0,
+            funding_pnl: 0.0,
             sharpe_ratio: 0.0,
             max_drawdown: 0.0,
             max_drawdown_pct: 0.0,
@@ -120,8 +132,14 @@ impl PerformanceTracker {
     }
 
     /// Record a fill
-    pub fn record_fill(&mut self, timestamp: u64, side: OrderSide, price: f64, quantity: f64, pnl_change: f64) {
-        self.trades.push(TradeRecord { _timestamp: timestamp, side, _price: price, quantity, pnl: pnl_change });
+    pub fn record_fill(&mut self, timestamp: u64, side: OrderSide, price: Price, quantity: Qty, pnl_change: Notional) {
+        self.trades.push(TradeRecord {
+            _timestamp: timestamp,
+            side,
+            _price: price.to_f64(),
+            quantity: quantity.to_f64(),
+            pnl: pnl_change.to_f64(),
+        });
     }
 
     /// Update equity curve
@@ -138,8 +156,8 @@ impl PerformanceTracker {
     }
 
     /// Update position
-    pub fn update_position(&mut self, timestamp: u64, position: f64) {
-        self.position_samples.push_back((timestamp, position));
+    pub fn update_position(&mut self, timestamp: u64, position: Qty) {
+        self.position_samples.push_back((timestamp, position.to_f64()));
 
         // Keep last 10000 samples
         if self.position_samples.len() > 10000 {
@@ -154,7 +172,9 @@ impl PerformanceTracker {
     }
 
     /// Calculate final metrics
-    pub fn calculate_metrics(&self, position: &Position, mark_price: FixedPoint) -> BacktestMetrics {
+    ///
+    /// PnL is converted to the quote asset at `mark_price` for inverse contracts.
+    pub fn calculate_metrics(&self, position: &Position, mark_price: Price) -> BacktestMetrics {
         let start_time = self.equity_curve.front().map(|(t, _)| *t).unwrap_or(0);
         let end_time = self.equity_curve.back().map(|(t, _)| *t).unwrap_or(0);
         let duration_seconds = (end_time - start_time) as f64 / 1_000_000_000.0;
@@ -166,9 +186,11 @@ impl PerformanceTracker {
         let total_volume: f64 = self.trades.iter().map(|t| t.quantity).sum();
 
         // Calculate PnL metrics
-        let unrealized_pnl = position.unrealized_pnl(mark_price).to_f64();
-        let realized_pnl = position.realized_pnl.to_f64();
-        let total_pnl = realized_pnl + unrealized_pnl;
+        let contract = position.contract;
+        let unrealized_pnl = contract.to_quote(position.unrealized_pnl(mark_price), mark_price).to_f64();
+        let realized_pnl = contract.to_quote(position.realized_pnl, mark_price).to_f64();
+        let breakdown = position.pnl_breakdown(mark_price).in_quote(contract, mark_price);
+        let total_pnl = breakdown.net.to_f64();
         let final_capital = self.initial_capital + total_pnl;
         let total_pnl_pct = (total_pnl / self.initial_capital) * 100.0;
 
@@ -218,6 +240,10 @@ impl PerformanceTracker {
             total_pnl_pct,
             realized_pnl,
             unrealized_pnl,
+            gross_pnl: breakdown.gross.to_f64(),
+            fees_paid: breakdown.fees_paid.to_f64(),
+            rebates_earned: breakdown.rebates_earned.to_f64(),
+            funding_pnl: breakdown.funding.to_f64(),
             sharpe_ratio,
             max_drawdown,
             max_drawdown_pct,
@@ -293,26 +319,50 @@ impl PerformanceTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mm_strategy::ContractKind;
 
     #[test]
     fn test_performance_tracker() {
         let mut tracker = PerformanceTracker::new(10000.0);
 
         // Record some trades
-        tracker.record_fill(1000, OrderSide::Bid, 50000.0, 0.1, 10.0);
-        tracker.record_fill(2000, OrderSide::Ask, 50010.0, 0.1, 5.0);
+        tracker.record_fill(1000, OrderSide::Bid, Price::from_f64(50000.0), Qty::from_f64(0.1), Notional::from_f64(10.0));
+        tracker.record_fill(2000, OrderSide::Ask, Price::from_f64(50010.0), Qty::from_f64(0.1), Notional::from_f64(5.0));
 
         // Update equity
         tracker.update_equity(1000, 10010.0);
         tracker.update_equity(2000, 10015.0);
 
-        let position = Position { quantity: FixedPoint::ZERO, avg_entry_price: FixedPoint::ZERO, realized_pnl: FixedPoint::from_f64(15.0) };
+        let position = Position {
+            realized_pnl: Notional::from_f64(15.0),
+            fees_paid: Notional::from_f64(2.0),
+            rebates_earned: Notional::from_f64(0.5),
+            funding: Notional::from_f64(-0.25),
+            ..Position::new()
+        };
 
-        let metrics = tracker.calculate_metrics(&position, FixedPoint::from_f64(50010.0));
+        let metrics = tracker.calculate_metrics(&position, Price::from_f64(50010.0));
 
         assert_eq!(metrics.total_trades, 2);
         assert_eq!(metrics.buy_trades, 1);
         assert_eq!(metrics.sell_trades, 1);
         assert_eq!(metrics.realized_pnl, 15.0);
+        assert_eq!(metrics.gross_pnl, 15.0);
+        assert_eq!(metrics.total_pnl, 13.25);
+        assert_eq!(metrics.final_capital, 10013.25);
+    }
+
+    #[test]
+    fn test_inverse_pnl_reported_in_quote() {
+        let tracker = PerformanceTracker::new(10000.0);
+        let contract = ContractKind::InversePerp { multiplier: Notional::from_f64(100.0) };
+        let mut position = Position::new().with_contract(contract);
+        position.apply_fill(OrderSide::Bid, Price::from_f64(40000.0), Qty::from_f64(100.0));
+
+        // 0.25 BTC bought at 40_000 is worth 0.2 BTC at 50_000: 0.05 BTC, 2_500 USD
+        let metrics = tracker.calculate_metrics(&position, Price::from_f64(50000.0));
+        assert_eq!(metrics.unrealized_pnl, 2500.0);
+        assert_eq!(metrics.total_pnl, 2500.0);
+        assert_eq!(metrics.final_capital, 12500.0);
     }
 }
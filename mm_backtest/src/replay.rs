@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -6,6 +7,8 @@ use mm_aeron::Publisher;
 use mm_binary::CompressedString;
 use mm_binary::Exchange;
 use mm_binary::OrderBookBatchMessage;
+use mm_binary::journal::JournalFrame;
+use mm_binary::journal::JournalReader;
 use mm_binary::messages::TradeMessage;
 use mm_binary::messages::UpdateType;
 use mm_binary::to_fixed_point;
@@ -171,6 +174,81 @@ impl DataReplayEngine {
     }
 }
 
+/// Replays a recorded journal, publishing each frame unchanged on the stream it was captured from
+pub struct JournalReplayEngine {
+    reader: JournalReader,
+    publishers: HashMap<i32, Publisher>,
+    next_frame: Option<JournalFrame>,
+    end_time: u64,
+    replay_speed: f64,
+    start_instant: Instant,
+    simulated_start_time: u64,
+    frames_published: u64,
+}
+
+impl JournalReplayEngine {
+    /// Replay frames received within `[start_time, end_time]` (nanoseconds since epoch)
+    pub fn new(mut reader: JournalReader, replay_speed: f64, start_time: u64, end_time: u64) -> Result<Self, BacktestError> {
+        let mut next_frame = reader.next_frame()?;
+        while next_frame.as_ref().is_some_and(|frame| frame.receive_time < start_time) {
+            next_frame = reader.next_frame()?;
+        }
+        let simulated_start_time = next_frame.as_ref().map_or(start_time, |frame| frame.receive_time);
+
+        Ok(Self {
+            reader,
+            publishers: HashMap::new(),
+            next_frame,
+            end_time,
+            replay_speed,
+            start_instant: Instant::now(),
+            simulated_start_time,
+            frames_published: 0,
+        })
+    }
+
+    /// Publish frames recorded on `stream_id`; frames from streams without a publisher are skipped
+    pub fn add_stream(&mut self, stream_id: i32, publisher: Publisher) {
+        self.publishers.insert(stream_id, publisher);
+    }
+
+    fn current_simulated_time(&self) -> u64 {
+        let elapsed_simulated_ns = (self.start_instant.elapsed().as_nanos() as f64 * self.replay_speed) as u64;
+        self.simulated_start_time + elapsed_simulated_ns
+    }
+
+    /// Publish every frame recorded up to the current simulated time
+    pub fn tick(&mut self) -> Result<usize, BacktestError> {
+        let current_sim_time = self.current_simulated_time();
+        let mut frames_published = 0;
+
+        while let Some(frame) = self.next_frame.take_if(|frame| frame.receive_time <= current_sim_time) {
+            if frame.receive_time > self.end_time {
+                self.next_frame = None;
+                break;
+            }
+
+            if let Some(publisher) = self.publishers.get_mut(&frame.stream_id) {
+                publisher.publish(Bytes::from(frame.payload)).map_err(|e| BacktestError::Parse(format!("Aeron publish error: {}", e)))?;
+                frames_published += 1;
+            }
+            self.next_frame = self.reader.next_frame()?;
+        }
+
+        self.frames_published += frames_published as u64;
+        Ok(frames_published)
+    }
+
+    pub fn frames_published(&self) -> u64 {
+        self.frames_published
+    }
+
+    /// Check if replay is complete
+    pub fn is_complete(&self) -> bool {
+        self.next_frame.is_none()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mm_binary::messages::TradeSide;
@@ -6,7 +6,10 @@ use criterion::criterion_main;
 use mm_binary::CompressedString;
 use mm_binary::Exchange;
 use mm_binary::MarketDataMessage;
+use mm_binary::OrderBookBatchMessage;
+use mm_binary::OrderBookBatchView;
 use mm_binary::PricingOutputMessage;
+use mm_binary::ValidationLevel;
 use mm_binary::from_fixed_point;
 use mm_binary::messages::UpdateType;
 use mm_binary::to_fixed_point;
@@ -83,11 +86,71 @@ fn bench_pricing_message(c: &mut Criterion) {
     c.bench_function("deserialize pricing message", |b| b.iter(|| PricingOutputMessage::from_bytes(black_box(&bytes))));
 }
 
+fn orderbook_snapshot(levels: i64, delta: bool) -> OrderBookBatchMessage {
+    let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+    let tick = to_fixed_point(0.01);
+    let mid = to_fixed_point(50000.0);
+
+    let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Snapshot, symbol, encoding, 1_000_000_000);
+    for i in 0..levels {
+        // Realistic sizes: a few significant digits, varying per level
+        let size = to_fixed_point(0.001) * (1 + (i * 7919) % 5000);
+        msg.add_bid(mid - (i + 1) * tick, size);
+        msg.add_ask(mid + (i + 1) * tick, size);
+    }
+    if delta {
+        msg.set_delta_encoding(tick);
+    }
+    msg
+}
+
+fn bench_orderbook_batch_encoding(c: &mut Criterion) {
+    for levels in [20, 100, 1000] {
+        let plain = orderbook_snapshot(levels, false);
+        let delta = orderbook_snapshot(levels, true);
+        let plain_bytes = plain.to_bytes();
+        let delta_bytes = delta.to_bytes();
+
+        println!(
+            "orderbook batch {levels} levels/side: fixed {} bytes, delta {} bytes ({:.1}%)",
+            plain_bytes.len(),
+            delta_bytes.len(),
+            100.0 * delta_bytes.len() as f64 / plain_bytes.len() as f64
+        );
+
+        let mut group = c.benchmark_group(format!("orderbook batch {levels} levels"));
+        group.bench_function("encode fixed", |b| b.iter(|| black_box(&plain).to_bytes()));
+        group.bench_function("encode delta", |b| b.iter(|| black_box(&delta).to_bytes()));
+        group.bench_function("decode fixed", |b| b.iter(|| OrderBookBatchMessage::from_bytes(black_box(&plain_bytes))));
+        group.bench_function("decode delta", |b| b.iter(|| OrderBookBatchMessage::from_bytes(black_box(&delta_bytes))));
+        group.bench_function("view fixed", |b| {
+            b.iter(|| {
+                OrderBookBatchView::new(black_box(&plain_bytes), ValidationLevel::Standard)
+                    .map(|view| view.bids().map(|l| l.size).sum::<i64>())
+            })
+        });
+        group.bench_function("view delta", |b| {
+            b.iter(|| {
+                OrderBookBatchView::new(black_box(&delta_bytes), ValidationLevel::Standard)
+                    .map(|view| view.bids().map(|l| l.size).sum::<i64>())
+            })
+        });
+        group.finish();
+    }
+}
+
 fn bench_fixed_point(c: &mut Criterion) {
     c.bench_function("to_fixed_point", |b| b.iter(|| to_fixed_point(black_box(12345.6789))));
 
     c.bench_function("from_fixed_point", |b| b.iter(|| from_fixed_point(black_box(1234567890))));
 }
 
-criterion_group!(benches, bench_string_compression, bench_market_data_message, bench_pricing_message, bench_fixed_point);
+criterion_group!(
+    benches,
+    bench_string_compression,
+    bench_market_data_message,
+    bench_pricing_message,
+    bench_orderbook_batch_encoding,
+    bench_fixed_point
+);
 criterion_main!(benches);
@@ -0,0 +1,27 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mm_binary::CompressedString;
+use mm_binary::compressed_string::EncodingScheme;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((bits, rest)) = data.split_first_chunk::<16>() else {
+        return;
+    };
+    let symbol = CompressedString {
+        low: u64::from_le_bytes(bits[..8].try_into().unwrap()),
+        high: u64::from_le_bytes(bits[8..].try_into().unwrap()),
+    };
+    let scheme = match rest.first().map_or(0, |byte| byte & 0x03) {
+        0 => EncodingScheme::Hex4Bit,
+        1 => EncodingScheme::Alphabetic5Bit,
+        2 => EncodingScheme::AlphaNumeric6Bit,
+        _ => EncodingScheme::Ascii7Bit,
+    };
+
+    // Decoding arbitrary bits never panics, and whatever it yields re-encodes if it is a valid symbol
+    let decoded = symbol.decode(scheme);
+    if let Ok((encoded, scheme)) = CompressedString::from_str(&decoded) {
+        assert_eq!(encoded.decode(scheme), decoded);
+    }
+});
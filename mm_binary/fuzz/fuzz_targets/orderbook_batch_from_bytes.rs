@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mm_binary::OrderBookBatchMessage;
+
+fuzz_target!(|data: &[u8]| {
+    // Any frame that decodes must re-encode to bytes that decode the same way
+    if let Ok(msg) = OrderBookBatchMessage::from_bytes(data) {
+        let bytes = msg.to_bytes();
+        let decoded = OrderBookBatchMessage::from_bytes(&bytes).expect("re-encoded batch decodes");
+        assert_eq!(decoded.to_bytes(), bytes);
+    }
+});
@@ -3,6 +3,11 @@ use std::arch::x86_64::_mm_crc32_u64;
 
 const CRC32C_POLYNOMIAL: u32 = 0x1EDC6F41;
 
+/// Reflected IEEE 802.3 polynomial, as used by zlib and venue order book checksums
+const CRC32_POLYNOMIAL: u32 = 0xEDB88320;
+
+const CRC32_TABLE: [u32; 256] = generate_crc32_table();
+
 #[inline]
 pub fn calculate_crc32c(data: &[u8]) -> u32 {
     #[cfg(target_arch = "x86_64")]
@@ -54,6 +59,36 @@ fn software_crc32c(data: &[u8]) -> u32 {
     !crc
 }
 
+/// Standard CRC-32 (IEEE), the checksum OKX and Kraken publish for their order books
+pub fn calculate_crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in data {
+        let index = ((crc ^ byte as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC32_TABLE[index];
+    }
+
+    !crc
+}
+
+const fn generate_crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ CRC32_POLYNOMIAL } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
 fn generate_crc32c_table() -> [u32; 256] {
     let mut table = [0u32; 256];
 
@@ -97,6 +132,12 @@ mod tests {
         assert_eq!(calculate_crc32c(data1), calculate_crc32c(data2));
     }
 
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(calculate_crc32(b""), 0);
+        assert_eq!(calculate_crc32(b"123456789"), 0xCBF43926);
+    }
+
     #[ignore]
     #[test]
     fn test_software_vs_hardware() {
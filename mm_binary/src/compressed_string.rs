@@ -51,9 +51,9 @@ impl CompressedString {
             return Err(ProtocolError::StringTooLong { length: s.len(), max: 32 });
         }
 
-        // Hex4Bit requires all characters to be hex digits
+        // Hex4Bit codes are 1..=15 with 0 as terminator, so only 0-9 and A-E fit
         for (i, ch) in s.chars().enumerate() {
-            if !ch.is_ascii_hexdigit() {
+            if !matches!(ch, '0'..='9' | 'A'..='E') {
                 return Err(ProtocolError::InvalidCharacter { char: ch, position: i });
             }
         }
@@ -64,8 +64,7 @@ impl CompressedString {
         for ch in s.chars() {
             let value = match ch {
                 '0'..='9' => (ch as u8 - b'0') + 1,
-                'A'..='F' => (ch as u8 - b'A') + 11,
-                'a'..='f' => (ch as u8 - b'a') + 11,
+                'A'..='E' => (ch as u8 - b'A') + 11,
                 _ => unreachable!(),
             };
 
@@ -133,6 +132,11 @@ impl CompressedString {
             }
         }
 
+        // 'A' encodes as zero, so the decoder reads a trailing 'A' or a run of three as padding
+        if let Some(position) = s.find("AAA").or_else(|| s.ends_with('A').then(|| s.len() - 1)) {
+            return Err(ProtocolError::InvalidCharacter { char: 'A', position });
+        }
+
         let mut result = CompressedString::new();
         let mut bit_pos = 0u32;
 
@@ -417,6 +421,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unrepresentable_symbols_use_wider_scheme() {
+        // 'F' has no 4-bit code and trailing 'A' reads as 6-bit padding
+        let test_cases =
+            vec![("0FF1CE", EncodingScheme::AlphaNumeric6Bit), ("1INCHA", EncodingScheme::Ascii7Bit), ("XAAA1", EncodingScheme::Ascii7Bit)];
+
+        for (input, expected_scheme) in test_cases {
+            let (compressed, scheme) = CompressedString::from_str(input).unwrap();
+            assert_eq!(scheme, expected_scheme);
+            assert_eq!(compressed.decode(scheme), input);
+        }
+    }
+
     #[test]
     fn test_ascii7bit_encoding() {
         let test_cases = vec![("BTC-USDT", "BTC-USDT"), ("eth_usdt", "eth_usdt"), ("test@123", "test@123")];
@@ -0,0 +1,658 @@
+//! Control plane: kill switch, resume, runtime parameter updates and risk events
+//!
+//! Control messages are addressed to a strategy rather than an instrument, so they
+//! carry a `strategy_id` instead of a symbol. Like the derivatives messages they store
+//! their full type ID in `header`.
+
+use crate::SCHEMA_VERSION;
+use crate::SCHEMA_VERSION_OFFSET;
+use crate::check_schema_version;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+
+/// `strategy_id` that addresses every strategy listening on the control stream
+pub const ALL_STRATEGIES: u8 = u8::MAX;
+
+/// Halt a strategy: cancel quotes and stop quoting until resumed
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct KillSwitchMessage {
+    pub header: u8,
+    pub strategy_id: u8,
+    pub reason: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub timestamp: u64,
+    /// Process or operator that issued the command
+    pub source_id: u32,
+    pub crc32: u32,
+    pub _final_pad: [u8; 16],
+}
+
+/// Clear a kill switch and allow the strategy to quote again
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct ResumeMessage {
+    pub header: u8,
+    pub strategy_id: u8,
+    pub _pad: [u8; 5],
+    pub version: u8,
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub source_id: u32,
+    pub crc32: u32,
+    pub _final_pad: [u8; 16],
+}
+
+/// Set one strategy parameter to a new value at runtime
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyParamUpdateMessage {
+    pub header: u8,
+    pub strategy_id: u8,
+    pub param: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub value: f64,
+    pub source_id: u32,
+    pub crc32: u32,
+    pub _final_pad: [u8; 8],
+}
+
+/// Audit record of a risk state change, published by the strategy itself
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct RiskEventMessage {
+    pub header: u8,
+    pub strategy_id: u8,
+    pub kind: u8,
+    /// `KillReason` for kill events, `StrategyParam` for parameter events, 0 otherwise
+    pub code: u8,
+    pub _pad: [u8; 3],
+    pub version: u8,
+    pub sequence: u64,
+    pub timestamp: u64,
+    /// Observed value (new parameter value, daily PnL, position, ...)
+    pub value: f64,
+    /// Limit or previous value the observation is compared against
+    pub limit: f64,
+    pub source_id: u32,
+    pub crc32: u32,
+    pub _final_pad: [u8; 16],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum KillReason {
+    /// Manual halt by an operator
+    Operator = 0,
+    /// Market data heartbeat went stale
+    HeartbeatTimeout = 1,
+    DailyLossLimit = 2,
+    PositionLimit = 3,
+    /// Halt requested by another process (risk daemon, gateway, ...)
+    External = 4,
+}
+
+impl KillReason {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(KillReason::Operator),
+            1 => Some(KillReason::HeartbeatTimeout),
+            2 => Some(KillReason::DailyLossLimit),
+            3 => Some(KillReason::PositionLimit),
+            4 => Some(KillReason::External),
+            _ => None,
+        }
+    }
+}
+
+/// Strategy parameters that can be changed at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StrategyParam {
+    MinSpreadBps = 0,
+    VolatilityFactor = 1,
+    InventorySkewFactor = 2,
+    MaxPositionSize = 3,
+    MaxOrderSize = 4,
+    TargetInventory = 5,
+    BaseQuoteSize = 6,
+    RiskAversion = 7,
+    MinConfidence = 8,
+    /// Daily loss limit as a positive amount in quote currency
+    MaxDailyLoss = 9,
+}
+
+impl StrategyParam {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(StrategyParam::MinSpreadBps),
+            1 => Some(StrategyParam::VolatilityFactor),
+            2 => Some(StrategyParam::InventorySkewFactor),
+            3 => Some(StrategyParam::MaxPositionSize),
+            4 => Some(StrategyParam::MaxOrderSize),
+            5 => Some(StrategyParam::TargetInventory),
+            6 => Some(StrategyParam::BaseQuoteSize),
+            7 => Some(StrategyParam::RiskAversion),
+            8 => Some(StrategyParam::MinConfidence),
+            9 => Some(StrategyParam::MaxDailyLoss),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RiskEventKind {
+    Killed = 0,
+    Resumed = 1,
+    ParamUpdated = 2,
+    /// Parameter update refused (unknown key or out-of-range value)
+    ParamRejected = 3,
+    DailyLossBreached = 4,
+    PositionLimitBreached = 5,
+}
+
+impl RiskEventKind {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(RiskEventKind::Killed),
+            1 => Some(RiskEventKind::Resumed),
+            2 => Some(RiskEventKind::ParamUpdated),
+            3 => Some(RiskEventKind::ParamRejected),
+            4 => Some(RiskEventKind::DailyLossBreached),
+            5 => Some(RiskEventKind::PositionLimitBreached),
+            _ => None,
+        }
+    }
+}
+
+#[inline]
+fn validate_header(header: u8, expected: u8) -> Result<()> {
+    if header != expected {
+        return Err(ProtocolError::InvalidMessageType { msg_type: header });
+    }
+    Ok(())
+}
+
+impl KillSwitchMessage {
+    pub const SIZE: usize = 48;
+    pub const MESSAGE_TYPE: u8 = 19;
+
+    pub fn new(strategy_id: u8, source_id: u32, reason: KillReason, timestamp: u64) -> Self {
+        Self::new_with_sequence(strategy_id, source_id, reason, timestamp, 0)
+    }
+
+    pub fn new_with_sequence(strategy_id: u8, source_id: u32, reason: KillReason, timestamp: u64, sequence: u64) -> Self {
+        let mut msg = KillSwitchMessage {
+            header: Self::MESSAGE_TYPE,
+            strategy_id,
+            reason: reason as u8,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            timestamp,
+            source_id,
+            crc32: 0,
+            _final_pad: [0; 16],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn reason(&self) -> Result<KillReason> {
+        KillReason::from_u8(self.reason).ok_or(ProtocolError::InvalidHeader { byte: self.reason })
+    }
+
+    /// Whether this command targets `strategy_id` (directly or via `ALL_STRATEGIES`)
+    #[inline]
+    pub fn applies_to(&self, strategy_id: u8) -> bool {
+        self.strategy_id == strategy_id || self.strategy_id == ALL_STRATEGIES
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE)?;
+        self.reason()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 28) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; KillSwitchMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl ResumeMessage {
+    pub const SIZE: usize = 48;
+    pub const MESSAGE_TYPE: u8 = 20;
+
+    pub fn new(strategy_id: u8, source_id: u32, timestamp: u64) -> Self {
+        Self::new_with_sequence(strategy_id, source_id, timestamp, 0)
+    }
+
+    pub fn new_with_sequence(strategy_id: u8, source_id: u32, timestamp: u64, sequence: u64) -> Self {
+        let mut msg = ResumeMessage {
+            header: Self::MESSAGE_TYPE,
+            strategy_id,
+            _pad: [0; 5],
+            version: SCHEMA_VERSION,
+            sequence,
+            timestamp,
+            source_id,
+            crc32: 0,
+            _final_pad: [0; 16],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn applies_to(&self, strategy_id: u8) -> bool {
+        self.strategy_id == strategy_id || self.strategy_id == ALL_STRATEGIES
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE)
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 28) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; ResumeMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl StrategyParamUpdateMessage {
+    pub const SIZE: usize = 48;
+    pub const MESSAGE_TYPE: u8 = 21;
+
+    pub fn new(strategy_id: u8, source_id: u32, param: StrategyParam, value: f64, timestamp: u64) -> Self {
+        Self::new_with_sequence(strategy_id, source_id, param, value, timestamp, 0)
+    }
+
+    pub fn new_with_sequence(strategy_id: u8, source_id: u32, param: StrategyParam, value: f64, timestamp: u64, sequence: u64) -> Self {
+        let mut msg = StrategyParamUpdateMessage {
+            header: Self::MESSAGE_TYPE,
+            strategy_id,
+            param: param as u8,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            timestamp,
+            value,
+            source_id,
+            crc32: 0,
+            _final_pad: [0; 8],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn param(&self) -> Result<StrategyParam> {
+        StrategyParam::from_u8(self.param).ok_or(ProtocolError::InvalidHeader { byte: self.param })
+    }
+
+    #[inline]
+    pub fn applies_to(&self, strategy_id: u8) -> bool {
+        self.strategy_id == strategy_id || self.strategy_id == ALL_STRATEGIES
+    }
+
+    /// Range checks on `value` are left to the strategy; only the key is validated here
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE)?;
+        self.param()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 36) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; StrategyParamUpdateMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl RiskEventMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 22;
+
+    pub fn new(strategy_id: u8, source_id: u32, kind: RiskEventKind, code: u8, value: f64, limit: f64, timestamp: u64) -> Self {
+        Self::new_with_sequence(strategy_id, source_id, kind, code, value, limit, timestamp, 0)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        strategy_id: u8,
+        source_id: u32,
+        kind: RiskEventKind,
+        code: u8,
+        value: f64,
+        limit: f64,
+        timestamp: u64,
+        sequence: u64,
+    ) -> Self {
+        let mut msg = RiskEventMessage {
+            header: Self::MESSAGE_TYPE,
+            strategy_id,
+            kind: kind as u8,
+            code,
+            _pad: [0; 3],
+            version: SCHEMA_VERSION,
+            sequence,
+            timestamp,
+            value,
+            limit,
+            source_id,
+            crc32: 0,
+            _final_pad: [0; 16],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn kind(&self) -> Result<RiskEventKind> {
+        RiskEventKind::from_u8(self.kind).ok_or(ProtocolError::InvalidHeader { byte: self.kind })
+    }
+
+    /// Kill reason, for `Killed` events
+    #[inline]
+    pub fn kill_reason(&self) -> Option<KillReason> {
+        match self.kind() {
+            Ok(RiskEventKind::Killed) => KillReason::from_u8(self.code),
+            _ => None,
+        }
+    }
+
+    /// Parameter key, for `ParamUpdated` and `ParamRejected` events
+    #[inline]
+    pub fn param(&self) -> Option<StrategyParam> {
+        match self.kind() {
+            Ok(RiskEventKind::ParamUpdated | RiskEventKind::ParamRejected) => StrategyParam::from_u8(self.code),
+            _ => None,
+        }
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE)?;
+        self.kind()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 44) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; RiskEventMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_message_sizes() {
+        assert_eq!(std::mem::size_of::<KillSwitchMessage>(), KillSwitchMessage::SIZE);
+        assert_eq!(std::mem::size_of::<ResumeMessage>(), ResumeMessage::SIZE);
+        assert_eq!(std::mem::size_of::<StrategyParamUpdateMessage>(), StrategyParamUpdateMessage::SIZE);
+        assert_eq!(std::mem::size_of::<RiskEventMessage>(), RiskEventMessage::SIZE);
+        assert_eq!(std::mem::align_of::<RiskEventMessage>(), 16);
+    }
+
+    #[test]
+    fn test_kill_resume_roundtrip() {
+        let kill = KillSwitchMessage::new_with_sequence(ALL_STRATEGIES, 7, KillReason::Operator, 1, 3);
+        let decoded = KillSwitchMessage::from_bytes(&kill.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.reason().unwrap(), KillReason::Operator);
+        assert_eq!(decoded.sequence, 3);
+        assert!(decoded.applies_to(0));
+        assert!(decoded.applies_to(42));
+
+        let resume = ResumeMessage::new(5, 7, 2);
+        let decoded = ResumeMessage::from_bytes(&resume.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert!(decoded.applies_to(5));
+        assert!(!decoded.applies_to(0));
+    }
+
+    #[test]
+    fn test_param_update_and_risk_event_roundtrip() {
+        let update = StrategyParamUpdateMessage::new(0, 7, StrategyParam::MinSpreadBps, 12.5, 1);
+        let decoded = StrategyParamUpdateMessage::from_bytes(&update.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.param().unwrap(), StrategyParam::MinSpreadBps);
+        assert_eq!(decoded.value, 12.5);
+
+        let event = RiskEventMessage::new(0, 0, RiskEventKind::ParamUpdated, StrategyParam::MinSpreadBps as u8, 12.5, 5.0, 1);
+        let decoded = RiskEventMessage::from_bytes(&event.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.param(), Some(StrategyParam::MinSpreadBps));
+        assert_eq!(decoded.kill_reason(), None);
+        assert_eq!(decoded.limit, 5.0);
+
+        let killed = RiskEventMessage::new(0, 0, RiskEventKind::Killed, KillReason::HeartbeatTimeout as u8, 0.0, 0.0, 1);
+        assert_eq!(killed.kill_reason(), Some(KillReason::HeartbeatTimeout));
+    }
+
+    #[test]
+    fn test_control_validation() {
+        let mut bytes = StrategyParamUpdateMessage::new(0, 0, StrategyParam::MaxDailyLoss, 500.0, 1).to_bytes();
+        bytes[16] ^= 0xFF;
+        assert!(matches!(StrategyParamUpdateMessage::from_bytes(&bytes), Err(ProtocolError::InvalidChecksum { .. })));
+
+        let mut kill = KillSwitchMessage::new(0, 0, KillReason::External, 1);
+        kill.reason = 99;
+        assert!(matches!(kill.validate_basic(), Err(ProtocolError::InvalidHeader { byte: 99 })));
+
+        let resume = ResumeMessage::new(0, 0, 1).to_bytes();
+        assert!(KillSwitchMessage::from_bytes(&resume).unwrap().validate_basic().is_err());
+        assert!(matches!(RiskEventMessage::from_bytes(&resume), Err(ProtocolError::InvalidLength { .. })));
+    }
+}
@@ -0,0 +1,706 @@
+//! Perpetual-futures market data: mark/index price, funding, open interest and liquidations
+//!
+//! Their type IDs (15-18) do not fit the 4-bit type nibble that the spot and order
+//! messages pack into `header`, so `header` holds the full type ID and the symbol
+//! encoding gets its own byte. Read as a nibble the IDs are meaningless (16 looks like
+//! type 1), so these messages travel enveloped and `decode_any` is the only supported
+//! way to tell them apart.
+//!
+//! None of them carries a scale: prices and quantities are always at `Scale::DEFAULT`.
+
+use crate::Exchange;
+use crate::SCHEMA_VERSION;
+use crate::SCHEMA_VERSION_OFFSET;
+use crate::check_schema_version;
+use crate::compressed_string::CompressedString;
+use crate::compressed_string::EncodingScheme;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::messages::TradeSide;
+
+/// Mark and index price for a perpetual contract
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct MarkPriceMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub encoding: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub mark_price: i64,
+    pub index_price: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+/// Current funding rate and when it is next applied
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct FundingRateMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub encoding: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    /// Rate per funding interval in fixed-point (0.0001 = 1 bp)
+    pub funding_rate: i64,
+    /// Next funding time in nanoseconds since the epoch
+    pub next_funding_time: u64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+/// Total open interest for a contract
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct OpenInterestMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub encoding: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    /// Open interest in contracts/base units
+    pub open_interest: i64,
+    /// Open interest in quote currency (0 if the venue doesn't report it)
+    pub open_interest_value: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+/// Forced liquidation order executed by the venue
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct LiquidationMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub encoding: u8,
+    pub side: u8, // TradeSide of the liquidation order (Sell = a long was liquidated)
+    pub _pad: [u8; 3],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub price: i64,
+    pub avg_price: i64,
+    pub quantity: i64,
+    pub filled_quantity: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+#[inline]
+fn encoding_from_u8(encoding: u8) -> EncodingScheme {
+    match encoding & 0x3 {
+        0 => EncodingScheme::Hex4Bit,
+        1 => EncodingScheme::Alphabetic5Bit,
+        2 => EncodingScheme::AlphaNumeric6Bit,
+        3 => EncodingScheme::Ascii7Bit,
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+fn validate_header(header: u8, expected: u8, exchange: u8, encoding: u8) -> Result<()> {
+    if header != expected {
+        return Err(ProtocolError::InvalidMessageType { msg_type: header });
+    }
+    Exchange::from_u8(exchange).ok_or(ProtocolError::InvalidExchange { id: exchange })?;
+    if encoding > 3 {
+        return Err(ProtocolError::InvalidEncodingScheme { scheme: encoding });
+    }
+    Ok(())
+}
+
+impl MarkPriceMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 15;
+
+    pub fn new(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        mark_price: i64,
+        index_price: i64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, symbol, encoding, timestamp, mark_price, index_price, 0)
+    }
+
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        mark_price: i64,
+        index_price: i64,
+        sequence: u64,
+    ) -> Self {
+        let mut msg = MarkPriceMessage {
+            header: Self::MESSAGE_TYPE,
+            exchange: exchange as u8,
+            encoding: encoding as u8,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            mark_price,
+            index_price,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_u8(self.encoding)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    /// Mark minus index, the premium that drives funding
+    #[inline]
+    pub fn basis(&self) -> i64 {
+        self.mark_price - self.index_price
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE, self.exchange, self.encoding)
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; MarkPriceMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl FundingRateMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 16;
+
+    pub fn new(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        funding_rate: i64,
+        next_funding_time: u64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, symbol, encoding, timestamp, funding_rate, next_funding_time, 0)
+    }
+
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        funding_rate: i64,
+        next_funding_time: u64,
+        sequence: u64,
+    ) -> Self {
+        let mut msg = FundingRateMessage {
+            header: Self::MESSAGE_TYPE,
+            exchange: exchange as u8,
+            encoding: encoding as u8,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            funding_rate,
+            next_funding_time,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_u8(self.encoding)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE, self.exchange, self.encoding)
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; FundingRateMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl OpenInterestMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 17;
+
+    pub fn new(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        open_interest: i64,
+        open_interest_value: i64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, symbol, encoding, timestamp, open_interest, open_interest_value, 0)
+    }
+
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        open_interest: i64,
+        open_interest_value: i64,
+        sequence: u64,
+    ) -> Self {
+        let mut msg = OpenInterestMessage {
+            header: Self::MESSAGE_TYPE,
+            exchange: exchange as u8,
+            encoding: encoding as u8,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            open_interest,
+            open_interest_value,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_u8(self.encoding)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE, self.exchange, self.encoding)
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; OpenInterestMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl LiquidationMessage {
+    pub const SIZE: usize = 80;
+    pub const MESSAGE_TYPE: u8 = 18;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        side: TradeSide,
+        price: i64,
+        avg_price: i64,
+        quantity: i64,
+        filled_quantity: i64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, symbol, encoding, timestamp, side, price, avg_price, quantity, filled_quantity, 0)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        side: TradeSide,
+        price: i64,
+        avg_price: i64,
+        quantity: i64,
+        filled_quantity: i64,
+        sequence: u64,
+    ) -> Self {
+        let mut msg = LiquidationMessage {
+            header: Self::MESSAGE_TYPE,
+            exchange: exchange as u8,
+            encoding: encoding as u8,
+            side: side as u8,
+            _pad: [0; 3],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            price,
+            avg_price,
+            quantity,
+            filled_quantity,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        self.header
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_u8(self.encoding)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    #[inline]
+    pub fn trade_side(&self) -> TradeSide {
+        if self.side == 0 { TradeSide::Buy } else { TradeSide::Sell }
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        validate_header(self.header, Self::MESSAGE_TYPE, self.exchange, self.encoding)?;
+        if self.side > 1 {
+            return Err(ProtocolError::InvalidHeader { byte: self.side });
+        }
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 72) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; LiquidationMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fixed_point;
+
+    fn btcusdt() -> (CompressedString, EncodingScheme) {
+        CompressedString::from_str("BTCUSDT").unwrap()
+    }
+
+    #[test]
+    fn test_derivatives_message_sizes() {
+        assert_eq!(std::mem::size_of::<MarkPriceMessage>(), MarkPriceMessage::SIZE);
+        assert_eq!(std::mem::size_of::<FundingRateMessage>(), FundingRateMessage::SIZE);
+        assert_eq!(std::mem::size_of::<OpenInterestMessage>(), OpenInterestMessage::SIZE);
+        assert_eq!(std::mem::size_of::<LiquidationMessage>(), LiquidationMessage::SIZE);
+        assert_eq!(std::mem::align_of::<LiquidationMessage>(), 16);
+    }
+
+    #[test]
+    fn test_mark_price_and_funding_roundtrip() {
+        let (symbol, encoding) = btcusdt();
+
+        let mark = MarkPriceMessage::new(Exchange::Bybit, symbol, encoding, 1, to_fixed_point(50010.5), to_fixed_point(50000.0));
+        let decoded = MarkPriceMessage::from_bytes(&mark.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.exchange().unwrap(), Exchange::Bybit);
+        assert_eq!(decoded.basis(), to_fixed_point(10.5));
+        assert_eq!(decoded.symbol().decode(decoded.encoding_scheme()), "BTCUSDT");
+
+        let funding =
+            FundingRateMessage::new_with_sequence(Exchange::Okx, symbol, encoding, 1, to_fixed_point(0.0001), 28_800_000_000_000, 9);
+        let decoded = FundingRateMessage::from_bytes(&funding.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.funding_rate, 10_000);
+        assert_eq!(decoded.next_funding_time, 28_800_000_000_000);
+        assert_eq!(decoded.sequence, 9);
+    }
+
+    #[test]
+    fn test_open_interest_and_liquidation_roundtrip() {
+        let (symbol, encoding) = btcusdt();
+
+        let oi = OpenInterestMessage::new(Exchange::Bitget, symbol, encoding, 1, to_fixed_point(1234.5), 0);
+        let decoded = OpenInterestMessage::from_bytes(&oi.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.open_interest, to_fixed_point(1234.5));
+
+        let liq = LiquidationMessage::new(
+            Exchange::Binance,
+            symbol,
+            encoding,
+            1,
+            TradeSide::Sell,
+            to_fixed_point(49000.0),
+            to_fixed_point(49010.0),
+            to_fixed_point(0.5),
+            to_fixed_point(0.5),
+        );
+        let decoded = LiquidationMessage::from_bytes(&liq.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.trade_side(), TradeSide::Sell);
+        assert_eq!(decoded.avg_price, to_fixed_point(49010.0));
+    }
+
+    #[test]
+    fn test_derivatives_validation() {
+        let (symbol, encoding) = btcusdt();
+        let mut bytes = MarkPriceMessage::new(Exchange::Binance, symbol, encoding, 1, 2, 3).to_bytes();
+
+        bytes[40] ^= 0x01;
+        assert!(matches!(MarkPriceMessage::from_bytes(&bytes), Err(ProtocolError::InvalidChecksum { .. })));
+        assert!(MarkPriceMessage::from_bytes(&bytes[..32]).is_err());
+
+        let mut liq = LiquidationMessage::new(Exchange::Binance, symbol, encoding, 1, TradeSide::Buy, 1, 1, 1, 1);
+        liq.header = FundingRateMessage::MESSAGE_TYPE;
+        assert!(matches!(liq.validate_basic(), Err(ProtocolError::InvalidMessageType { msg_type: 16 })));
+    }
+}
@@ -0,0 +1,631 @@
+use crate::SCHEMA_VERSION;
+use crate::check_schema_version;
+use crate::control_messages::KillSwitchMessage;
+use crate::control_messages::ResumeMessage;
+use crate::control_messages::RiskEventMessage;
+use crate::control_messages::StrategyParamUpdateMessage;
+use crate::derivatives_messages::FundingRateMessage;
+use crate::derivatives_messages::LiquidationMessage;
+use crate::derivatives_messages::MarkPriceMessage;
+use crate::derivatives_messages::OpenInterestMessage;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::messages::CollectorStateMessage;
+use crate::messages::HeartbeatMessage;
+use crate::messages::MarketDataMessage;
+use crate::messages::OrderFillMessage;
+use crate::messages::PositionMessage;
+use crate::messages::PricingOutputMessage;
+use crate::messages::QuoteMessage;
+use crate::messages::TradeMessage;
+use crate::order_messages::CancelOrderMessage;
+use crate::order_messages::NewOrderMessage;
+use crate::order_messages::OrderAckMessage;
+use crate::order_messages::OrderRejectMessage;
+use crate::order_messages::ReplaceOrderMessage;
+use crate::orderbook_message::OrderBookBatchMessage;
+use crate::quote_ladder::QuoteLadderMessage;
+
+/// Schema identifier for the core mm_binary message set
+pub const CORE_SCHEMA_ID: u16 = 1;
+
+/// Largest fixed-size payload, the size of the aligned buffer `decode_any` copies into
+///
+/// Derived from every fixed-size message so a layout that grows one cannot outgrow the buffer.
+const MAX_FIXED_PAYLOAD: usize = max_size(&[
+    MarketDataMessage::SIZE,
+    PricingOutputMessage::SIZE,
+    HeartbeatMessage::SIZE,
+    CollectorStateMessage::SIZE,
+    TradeMessage::SIZE,
+    QuoteMessage::SIZE,
+    PositionMessage::SIZE,
+    OrderFillMessage::SIZE,
+    NewOrderMessage::SIZE,
+    CancelOrderMessage::SIZE,
+    ReplaceOrderMessage::SIZE,
+    OrderAckMessage::SIZE,
+    OrderRejectMessage::SIZE,
+    MarkPriceMessage::SIZE,
+    FundingRateMessage::SIZE,
+    OpenInterestMessage::SIZE,
+    LiquidationMessage::SIZE,
+    KillSwitchMessage::SIZE,
+    ResumeMessage::SIZE,
+    StrategyParamUpdateMessage::SIZE,
+    RiskEventMessage::SIZE,
+]);
+
+const fn max_size(sizes: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        if sizes[i] > max {
+            max = sizes[i];
+        }
+        i += 1;
+    }
+    max
+}
+
+/// Message type discriminator carried in the envelope header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum MessageType {
+    MarketData = 1,
+    PricingOutput = 2,
+    Heartbeat = 3,
+    CollectorState = 4,
+    Trade = 5,
+    Quote = 6,
+    Position = 7,
+    OrderFill = 8,
+    OrderBookBatch = 9,
+    NewOrder = 10,
+    CancelOrder = 11,
+    ReplaceOrder = 12,
+    OrderAck = 13,
+    OrderReject = 14,
+    MarkPrice = 15,
+    FundingRate = 16,
+    OpenInterest = 17,
+    Liquidation = 18,
+    KillSwitch = 19,
+    Resume = 20,
+    StrategyParamUpdate = 21,
+    RiskEvent = 22,
+    QuoteLadder = 23,
+}
+
+impl MessageType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(MessageType::MarketData),
+            2 => Some(MessageType::PricingOutput),
+            3 => Some(MessageType::Heartbeat),
+            4 => Some(MessageType::CollectorState),
+            5 => Some(MessageType::Trade),
+            6 => Some(MessageType::Quote),
+            7 => Some(MessageType::Position),
+            8 => Some(MessageType::OrderFill),
+            9 => Some(MessageType::OrderBookBatch),
+            10 => Some(MessageType::NewOrder),
+            11 => Some(MessageType::CancelOrder),
+            12 => Some(MessageType::ReplaceOrder),
+            13 => Some(MessageType::OrderAck),
+            14 => Some(MessageType::OrderReject),
+            15 => Some(MessageType::MarkPrice),
+            16 => Some(MessageType::FundingRate),
+            17 => Some(MessageType::OpenInterest),
+            18 => Some(MessageType::Liquidation),
+            19 => Some(MessageType::KillSwitch),
+            20 => Some(MessageType::Resume),
+            21 => Some(MessageType::StrategyParamUpdate),
+            22 => Some(MessageType::RiskEvent),
+            23 => Some(MessageType::QuoteLadder),
+            _ => None,
+        }
+    }
+}
+
+/// Common header prepended to every enveloped message
+///
+/// 16 bytes so the payload that follows keeps the 16-byte alignment of the frame.
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeHeader {
+    pub msg_type: u8,
+    pub version: u8,
+    pub schema_id: u16,
+    /// Payload length in bytes (excluding this header)
+    pub length: u32,
+    pub _reserved: [u8; 8],
+}
+
+impl EnvelopeHeader {
+    pub const SIZE: usize = 16;
+
+    pub fn new(msg_type: MessageType, length: u32) -> Self {
+        Self { msg_type: msg_type as u8, version: SCHEMA_VERSION, schema_id: CORE_SCHEMA_ID, length, _reserved: [0; 8] }
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> Result<MessageType> {
+        MessageType::from_u8(self.msg_type).ok_or(ProtocolError::InvalidMessageType { msg_type: self.msg_type })
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0] = self.msg_type;
+        bytes[1] = self.version;
+        bytes[2..4].copy_from_slice(&self.schema_id.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.length.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::BufferTooSmall { required: Self::SIZE, actual: bytes.len() });
+        }
+
+        Ok(Self {
+            msg_type: bytes[0],
+            version: bytes[1],
+            schema_id: u16::from_le_bytes([bytes[2], bytes[3]]),
+            length: u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            _reserved: [0; 8],
+        })
+    }
+}
+
+/// Any message from the core schema, decoded from an envelope
+pub enum AnyMessage {
+    MarketData(MarketDataMessage),
+    PricingOutput(PricingOutputMessage),
+    Heartbeat(HeartbeatMessage),
+    CollectorState(CollectorStateMessage),
+    Trade(TradeMessage),
+    Quote(QuoteMessage),
+    Position(PositionMessage),
+    OrderFill(OrderFillMessage),
+    OrderBookBatch(OrderBookBatchMessage),
+    NewOrder(NewOrderMessage),
+    CancelOrder(CancelOrderMessage),
+    ReplaceOrder(ReplaceOrderMessage),
+    OrderAck(OrderAckMessage),
+    OrderReject(OrderRejectMessage),
+    MarkPrice(MarkPriceMessage),
+    FundingRate(FundingRateMessage),
+    OpenInterest(OpenInterestMessage),
+    Liquidation(LiquidationMessage),
+    KillSwitch(KillSwitchMessage),
+    Resume(ResumeMessage),
+    StrategyParamUpdate(StrategyParamUpdateMessage),
+    RiskEvent(RiskEventMessage),
+    QuoteLadder(QuoteLadderMessage),
+}
+
+impl AnyMessage {
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            AnyMessage::MarketData(_) => MessageType::MarketData,
+            AnyMessage::PricingOutput(_) => MessageType::PricingOutput,
+            AnyMessage::Heartbeat(_) => MessageType::Heartbeat,
+            AnyMessage::CollectorState(_) => MessageType::CollectorState,
+            AnyMessage::Trade(_) => MessageType::Trade,
+            AnyMessage::Quote(_) => MessageType::Quote,
+            AnyMessage::Position(_) => MessageType::Position,
+            AnyMessage::OrderFill(_) => MessageType::OrderFill,
+            AnyMessage::OrderBookBatch(_) => MessageType::OrderBookBatch,
+            AnyMessage::NewOrder(_) => MessageType::NewOrder,
+            AnyMessage::CancelOrder(_) => MessageType::CancelOrder,
+            AnyMessage::ReplaceOrder(_) => MessageType::ReplaceOrder,
+            AnyMessage::OrderAck(_) => MessageType::OrderAck,
+            AnyMessage::OrderReject(_) => MessageType::OrderReject,
+            AnyMessage::MarkPrice(_) => MessageType::MarkPrice,
+            AnyMessage::FundingRate(_) => MessageType::FundingRate,
+            AnyMessage::OpenInterest(_) => MessageType::OpenInterest,
+            AnyMessage::Liquidation(_) => MessageType::Liquidation,
+            AnyMessage::KillSwitch(_) => MessageType::KillSwitch,
+            AnyMessage::Resume(_) => MessageType::Resume,
+            AnyMessage::StrategyParamUpdate(_) => MessageType::StrategyParamUpdate,
+            AnyMessage::RiskEvent(_) => MessageType::RiskEvent,
+            AnyMessage::QuoteLadder(_) => MessageType::QuoteLadder,
+        }
+    }
+
+    /// Serialize as envelope header + payload
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            AnyMessage::MarketData(msg) => encode(MessageType::MarketData, &msg.to_bytes()),
+            AnyMessage::PricingOutput(msg) => encode(MessageType::PricingOutput, &msg.to_bytes()),
+            AnyMessage::Heartbeat(msg) => encode(MessageType::Heartbeat, &msg.to_bytes()),
+            AnyMessage::CollectorState(msg) => encode(MessageType::CollectorState, &msg.to_bytes()),
+            AnyMessage::Trade(msg) => encode(MessageType::Trade, &msg.to_bytes()),
+            AnyMessage::Quote(msg) => encode(MessageType::Quote, &msg.to_bytes()),
+            AnyMessage::Position(msg) => encode(MessageType::Position, &msg.to_bytes()),
+            AnyMessage::OrderFill(msg) => encode(MessageType::OrderFill, &msg.to_bytes()),
+            AnyMessage::OrderBookBatch(msg) => encode(MessageType::OrderBookBatch, &msg.to_bytes()),
+            AnyMessage::NewOrder(msg) => encode(MessageType::NewOrder, &msg.to_bytes()),
+            AnyMessage::CancelOrder(msg) => encode(MessageType::CancelOrder, &msg.to_bytes()),
+            AnyMessage::ReplaceOrder(msg) => encode(MessageType::ReplaceOrder, &msg.to_bytes()),
+            AnyMessage::OrderAck(msg) => encode(MessageType::OrderAck, &msg.to_bytes()),
+            AnyMessage::OrderReject(msg) => encode(MessageType::OrderReject, &msg.to_bytes()),
+            AnyMessage::MarkPrice(msg) => encode(MessageType::MarkPrice, &msg.to_bytes()),
+            AnyMessage::FundingRate(msg) => encode(MessageType::FundingRate, &msg.to_bytes()),
+            AnyMessage::OpenInterest(msg) => encode(MessageType::OpenInterest, &msg.to_bytes()),
+            AnyMessage::Liquidation(msg) => encode(MessageType::Liquidation, &msg.to_bytes()),
+            AnyMessage::KillSwitch(msg) => encode(MessageType::KillSwitch, &msg.to_bytes()),
+            AnyMessage::Resume(msg) => encode(MessageType::Resume, &msg.to_bytes()),
+            AnyMessage::StrategyParamUpdate(msg) => encode(MessageType::StrategyParamUpdate, &msg.to_bytes()),
+            AnyMessage::RiskEvent(msg) => encode(MessageType::RiskEvent, &msg.to_bytes()),
+            AnyMessage::QuoteLadder(msg) => encode(MessageType::QuoteLadder, &msg.to_bytes()),
+        }
+    }
+
+    /// Invoke the handler callback matching this message's type
+    pub fn dispatch<H: MessageHandler + ?Sized>(&self, handler: &mut H) {
+        match self {
+            AnyMessage::MarketData(msg) => handler.on_market_data(msg),
+            AnyMessage::PricingOutput(msg) => handler.on_pricing_output(msg),
+            AnyMessage::Heartbeat(msg) => handler.on_heartbeat(msg),
+            AnyMessage::CollectorState(msg) => handler.on_collector_state(msg),
+            AnyMessage::Trade(msg) => handler.on_trade(msg),
+            AnyMessage::Quote(msg) => handler.on_quote(msg),
+            AnyMessage::Position(msg) => handler.on_position(msg),
+            AnyMessage::OrderFill(msg) => handler.on_order_fill(msg),
+            AnyMessage::OrderBookBatch(msg) => handler.on_orderbook_batch(msg),
+            AnyMessage::NewOrder(msg) => handler.on_new_order(msg),
+            AnyMessage::CancelOrder(msg) => handler.on_cancel_order(msg),
+            AnyMessage::ReplaceOrder(msg) => handler.on_replace_order(msg),
+            AnyMessage::OrderAck(msg) => handler.on_order_ack(msg),
+            AnyMessage::OrderReject(msg) => handler.on_order_reject(msg),
+            AnyMessage::MarkPrice(msg) => handler.on_mark_price(msg),
+            AnyMessage::FundingRate(msg) => handler.on_funding_rate(msg),
+            AnyMessage::OpenInterest(msg) => handler.on_open_interest(msg),
+            AnyMessage::Liquidation(msg) => handler.on_liquidation(msg),
+            AnyMessage::KillSwitch(msg) => handler.on_kill_switch(msg),
+            AnyMessage::Resume(msg) => handler.on_resume(msg),
+            AnyMessage::StrategyParamUpdate(msg) => handler.on_strategy_param_update(msg),
+            AnyMessage::RiskEvent(msg) => handler.on_risk_event(msg),
+            AnyMessage::QuoteLadder(msg) => handler.on_quote_ladder(msg),
+        }
+    }
+}
+
+impl From<MarketDataMessage> for AnyMessage {
+    fn from(msg: MarketDataMessage) -> Self {
+        AnyMessage::MarketData(msg)
+    }
+}
+
+impl From<PricingOutputMessage> for AnyMessage {
+    fn from(msg: PricingOutputMessage) -> Self {
+        AnyMessage::PricingOutput(msg)
+    }
+}
+
+impl From<HeartbeatMessage> for AnyMessage {
+    fn from(msg: HeartbeatMessage) -> Self {
+        AnyMessage::Heartbeat(msg)
+    }
+}
+
+impl From<CollectorStateMessage> for AnyMessage {
+    fn from(msg: CollectorStateMessage) -> Self {
+        AnyMessage::CollectorState(msg)
+    }
+}
+
+impl From<TradeMessage> for AnyMessage {
+    fn from(msg: TradeMessage) -> Self {
+        AnyMessage::Trade(msg)
+    }
+}
+
+impl From<QuoteMessage> for AnyMessage {
+    fn from(msg: QuoteMessage) -> Self {
+        AnyMessage::Quote(msg)
+    }
+}
+
+impl From<PositionMessage> for AnyMessage {
+    fn from(msg: PositionMessage) -> Self {
+        AnyMessage::Position(msg)
+    }
+}
+
+impl From<OrderFillMessage> for AnyMessage {
+    fn from(msg: OrderFillMessage) -> Self {
+        AnyMessage::OrderFill(msg)
+    }
+}
+
+impl From<OrderBookBatchMessage> for AnyMessage {
+    fn from(msg: OrderBookBatchMessage) -> Self {
+        AnyMessage::OrderBookBatch(msg)
+    }
+}
+
+impl From<NewOrderMessage> for AnyMessage {
+    fn from(msg: NewOrderMessage) -> Self {
+        AnyMessage::NewOrder(msg)
+    }
+}
+
+impl From<CancelOrderMessage> for AnyMessage {
+    fn from(msg: CancelOrderMessage) -> Self {
+        AnyMessage::CancelOrder(msg)
+    }
+}
+
+impl From<ReplaceOrderMessage> for AnyMessage {
+    fn from(msg: ReplaceOrderMessage) -> Self {
+        AnyMessage::ReplaceOrder(msg)
+    }
+}
+
+impl From<OrderAckMessage> for AnyMessage {
+    fn from(msg: OrderAckMessage) -> Self {
+        AnyMessage::OrderAck(msg)
+    }
+}
+
+impl From<OrderRejectMessage> for AnyMessage {
+    fn from(msg: OrderRejectMessage) -> Self {
+        AnyMessage::OrderReject(msg)
+    }
+}
+
+impl From<MarkPriceMessage> for AnyMessage {
+    fn from(msg: MarkPriceMessage) -> Self {
+        AnyMessage::MarkPrice(msg)
+    }
+}
+
+impl From<FundingRateMessage> for AnyMessage {
+    fn from(msg: FundingRateMessage) -> Self {
+        AnyMessage::FundingRate(msg)
+    }
+}
+
+impl From<OpenInterestMessage> for AnyMessage {
+    fn from(msg: OpenInterestMessage) -> Self {
+        AnyMessage::OpenInterest(msg)
+    }
+}
+
+impl From<LiquidationMessage> for AnyMessage {
+    fn from(msg: LiquidationMessage) -> Self {
+        AnyMessage::Liquidation(msg)
+    }
+}
+
+impl From<KillSwitchMessage> for AnyMessage {
+    fn from(msg: KillSwitchMessage) -> Self {
+        AnyMessage::KillSwitch(msg)
+    }
+}
+
+impl From<ResumeMessage> for AnyMessage {
+    fn from(msg: ResumeMessage) -> Self {
+        AnyMessage::Resume(msg)
+    }
+}
+
+impl From<StrategyParamUpdateMessage> for AnyMessage {
+    fn from(msg: StrategyParamUpdateMessage) -> Self {
+        AnyMessage::StrategyParamUpdate(msg)
+    }
+}
+
+impl From<RiskEventMessage> for AnyMessage {
+    fn from(msg: RiskEventMessage) -> Self {
+        AnyMessage::RiskEvent(msg)
+    }
+}
+
+impl From<QuoteLadderMessage> for AnyMessage {
+    fn from(msg: QuoteLadderMessage) -> Self {
+        AnyMessage::QuoteLadder(msg)
+    }
+}
+
+/// Callbacks for enveloped messages; every method defaults to a no-op so
+/// consumers only implement the types they care about
+pub trait MessageHandler {
+    fn on_market_data(&mut self, _msg: &MarketDataMessage) {}
+    fn on_pricing_output(&mut self, _msg: &PricingOutputMessage) {}
+    fn on_heartbeat(&mut self, _msg: &HeartbeatMessage) {}
+    fn on_collector_state(&mut self, _msg: &CollectorStateMessage) {}
+    fn on_trade(&mut self, _msg: &TradeMessage) {}
+    fn on_quote(&mut self, _msg: &QuoteMessage) {}
+    fn on_position(&mut self, _msg: &PositionMessage) {}
+    fn on_order_fill(&mut self, _msg: &OrderFillMessage) {}
+    fn on_orderbook_batch(&mut self, _msg: &OrderBookBatchMessage) {}
+    fn on_new_order(&mut self, _msg: &NewOrderMessage) {}
+    fn on_cancel_order(&mut self, _msg: &CancelOrderMessage) {}
+    fn on_replace_order(&mut self, _msg: &ReplaceOrderMessage) {}
+    fn on_order_ack(&mut self, _msg: &OrderAckMessage) {}
+    fn on_order_reject(&mut self, _msg: &OrderRejectMessage) {}
+    fn on_mark_price(&mut self, _msg: &MarkPriceMessage) {}
+    fn on_funding_rate(&mut self, _msg: &FundingRateMessage) {}
+    fn on_open_interest(&mut self, _msg: &OpenInterestMessage) {}
+    fn on_liquidation(&mut self, _msg: &LiquidationMessage) {}
+    fn on_kill_switch(&mut self, _msg: &KillSwitchMessage) {}
+    fn on_resume(&mut self, _msg: &ResumeMessage) {}
+    fn on_strategy_param_update(&mut self, _msg: &StrategyParamUpdateMessage) {}
+    fn on_risk_event(&mut self, _msg: &RiskEventMessage) {}
+    fn on_quote_ladder(&mut self, _msg: &QuoteLadderMessage) {}
+}
+
+/// Prefix a serialized payload with an envelope header
+pub fn encode(msg_type: MessageType, payload: &[u8]) -> Vec<u8> {
+    let header = EnvelopeHeader::new(msg_type, payload.len() as u32);
+    let mut bytes = Vec::with_capacity(EnvelopeHeader::SIZE + payload.len());
+    bytes.extend_from_slice(&header.to_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+/// Decode an enveloped frame into whichever message it carries
+pub fn decode_any(bytes: &[u8]) -> Result<AnyMessage> {
+    let header = EnvelopeHeader::from_bytes(bytes)?;
+    if header.schema_id != CORE_SCHEMA_ID {
+        return Err(ProtocolError::UnknownSchema { schema_id: header.schema_id });
+    }
+    check_schema_version(header.version)?;
+
+    let msg_type = header.message_type()?;
+    let length = header.length as usize;
+    let available = bytes.len() - EnvelopeHeader::SIZE;
+    if available < length {
+        return Err(ProtocolError::BufferTooSmall { required: EnvelopeHeader::SIZE + length, actual: bytes.len() });
+    }
+    let payload = &bytes[EnvelopeHeader::SIZE..EnvelopeHeader::SIZE + length];
+
+    // Variable-length messages decode from unaligned bytes
+    match msg_type {
+        MessageType::OrderBookBatch => return Ok(AnyMessage::OrderBookBatch(OrderBookBatchMessage::from_bytes(payload)?)),
+        MessageType::QuoteLadder => return Ok(AnyMessage::QuoteLadder(QuoteLadderMessage::from_bytes(payload)?)),
+        _ => {}
+    }
+
+    // Fixed-size decoders require 16-byte alignment; Aeron fragments don't guarantee it
+    #[repr(C, align(16))]
+    struct AlignedPayload([u8; MAX_FIXED_PAYLOAD]);
+
+    if length > MAX_FIXED_PAYLOAD {
+        return Err(ProtocolError::InvalidLength { expected: MAX_FIXED_PAYLOAD, actual: length });
+    }
+    let mut aligned = AlignedPayload([0u8; MAX_FIXED_PAYLOAD]);
+    aligned.0[..length].copy_from_slice(payload);
+    let payload = &aligned.0[..length];
+
+    let msg = match msg_type {
+        MessageType::MarketData => AnyMessage::MarketData(MarketDataMessage::from_bytes(payload)?),
+        MessageType::PricingOutput => AnyMessage::PricingOutput(PricingOutputMessage::from_bytes(payload)?),
+        MessageType::Heartbeat => AnyMessage::Heartbeat(HeartbeatMessage::from_bytes(payload)?),
+        MessageType::CollectorState => AnyMessage::CollectorState(CollectorStateMessage::from_bytes(payload)?),
+        MessageType::Trade => AnyMessage::Trade(TradeMessage::from_bytes(payload)?),
+        MessageType::Quote => AnyMessage::Quote(QuoteMessage::from_bytes(payload)?),
+        MessageType::Position => AnyMessage::Position(PositionMessage::from_bytes(payload)?),
+        MessageType::OrderFill => AnyMessage::OrderFill(OrderFillMessage::from_bytes(payload)?),
+        MessageType::NewOrder => AnyMessage::NewOrder(NewOrderMessage::from_bytes(payload)?),
+        MessageType::CancelOrder => AnyMessage::CancelOrder(CancelOrderMessage::from_bytes(payload)?),
+        MessageType::ReplaceOrder => AnyMessage::ReplaceOrder(ReplaceOrderMessage::from_bytes(payload)?),
+        MessageType::OrderAck => AnyMessage::OrderAck(OrderAckMessage::from_bytes(payload)?),
+        MessageType::OrderReject => AnyMessage::OrderReject(OrderRejectMessage::from_bytes(payload)?),
+        MessageType::MarkPrice => AnyMessage::MarkPrice(MarkPriceMessage::from_bytes(payload)?),
+        MessageType::FundingRate => AnyMessage::FundingRate(FundingRateMessage::from_bytes(payload)?),
+        MessageType::OpenInterest => AnyMessage::OpenInterest(OpenInterestMessage::from_bytes(payload)?),
+        MessageType::Liquidation => AnyMessage::Liquidation(LiquidationMessage::from_bytes(payload)?),
+        MessageType::KillSwitch => AnyMessage::KillSwitch(KillSwitchMessage::from_bytes(payload)?),
+        MessageType::Resume => AnyMessage::Resume(ResumeMessage::from_bytes(payload)?),
+        MessageType::StrategyParamUpdate => AnyMessage::StrategyParamUpdate(StrategyParamUpdateMessage::from_bytes(payload)?),
+        MessageType::RiskEvent => AnyMessage::RiskEvent(RiskEventMessage::from_bytes(payload)?),
+        MessageType::OrderBookBatch | MessageType::QuoteLadder => unreachable!(),
+    };
+
+    Ok(msg)
+}
+
+/// Decode an enveloped frame and hand it to the matching handler callback
+pub fn dispatch<H: MessageHandler + ?Sized>(bytes: &[u8], handler: &mut H) -> Result<MessageType> {
+    let msg = decode_any(bytes)?;
+    msg.dispatch(handler);
+    Ok(msg.message_type())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CompressedString;
+    use crate::Exchange;
+    use crate::messages::TradeSide;
+    use crate::messages::UpdateType;
+    use crate::to_fixed_point;
+
+    #[derive(Default)]
+    struct Counter {
+        trades: usize,
+        batches: usize,
+        heartbeats: usize,
+    }
+
+    impl MessageHandler for Counter {
+        fn on_trade(&mut self, _msg: &TradeMessage) {
+            self.trades += 1;
+        }
+
+        fn on_orderbook_batch(&mut self, msg: &OrderBookBatchMessage) {
+            assert_eq!(msg.bids().len(), 1);
+            self.batches += 1;
+        }
+
+        fn on_heartbeat(&mut self, _msg: &HeartbeatMessage) {
+            self.heartbeats += 1;
+        }
+    }
+
+    #[test]
+    fn test_envelope_header_roundtrip() {
+        let header = EnvelopeHeader::new(MessageType::Quote, 112);
+        let decoded = EnvelopeHeader::from_bytes(&header.to_bytes()).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.message_type().unwrap(), MessageType::Quote);
+        assert_eq!(std::mem::size_of::<EnvelopeHeader>(), EnvelopeHeader::SIZE);
+    }
+
+    #[test]
+    fn test_mixed_stream_dispatch() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+
+        let trade = TradeMessage::new(Exchange::Binance, symbol, encoding, 1, 2, to_fixed_point(50000.0), 1, TradeSide::Buy, true);
+        let mut batch = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Update, symbol, encoding, 1);
+        batch.add_bid(to_fixed_point(50000.0), to_fixed_point(1.0));
+        let heartbeat = HeartbeatMessage::new(1, 2);
+
+        let frames = [AnyMessage::from(trade).to_bytes(), AnyMessage::from(batch).to_bytes(), AnyMessage::from(heartbeat).to_bytes()];
+
+        let mut counter = Counter::default();
+        for frame in &frames {
+            dispatch(frame, &mut counter).unwrap();
+        }
+
+        assert_eq!(counter.trades, 1);
+        assert_eq!(counter.batches, 1);
+        assert_eq!(counter.heartbeats, 1);
+    }
+
+    #[test]
+    fn test_decode_any_unaligned() {
+        let (symbol, encoding) = CompressedString::from_str("ETHUSDT").unwrap();
+        let quote = QuoteMessage::new(0, symbol, encoding, 1, 2, 3, 4, 5, 6, 7, 8);
+        let frame = AnyMessage::from(quote).to_bytes();
+
+        let mut buffer = vec![0u8; frame.len() + 3];
+        buffer[3..].copy_from_slice(&frame);
+
+        match decode_any(&buffer[3..]).unwrap() {
+            AnyMessage::Quote(decoded) => assert_eq!(decoded.ask_size, 5),
+            _ => panic!("expected quote"),
+        }
+    }
+
+    #[test]
+    fn test_decode_any_errors() {
+        let heartbeat = AnyMessage::from(HeartbeatMessage::new(1, 2)).to_bytes();
+
+        let mut unknown_type = heartbeat.clone();
+        unknown_type[0] = 0xFF;
+        assert!(matches!(decode_any(&unknown_type), Err(ProtocolError::InvalidMessageType { msg_type: 0xFF })));
+
+        let mut unknown_schema = heartbeat.clone();
+        unknown_schema[2] = 0x42;
+        assert!(matches!(decode_any(&unknown_schema), Err(ProtocolError::UnknownSchema { .. })));
+
+        assert!(matches!(decode_any(&heartbeat[..20]), Err(ProtocolError::BufferTooSmall { .. })));
+        assert!(matches!(decode_any(&heartbeat[..4]), Err(ProtocolError::BufferTooSmall { .. })));
+    }
+}
@@ -12,6 +12,14 @@ pub enum ProtocolError {
     InvalidCharacter { char: char, position: usize },
     InvalidMessageType { msg_type: u8 },
     BufferTooSmall { required: usize, actual: usize },
+    UnknownSchema { schema_id: u16 },
+    UnsupportedSchemaVersion { version: u8, max: u8 },
+    IncompatibleSchemaVersion { version: u8, min: u8 },
+    DuplicateInstrument { id: u32 },
+    InvalidVarint { offset: usize },
+    InvalidScale { decimals: u8 },
+    FixedPointOverflow { decimals: u8 },
+    InvalidOrderSide { side: u8 },
 }
 
 impl fmt::Display for ProtocolError {
@@ -47,6 +55,30 @@ impl fmt::Display for ProtocolError {
             ProtocolError::BufferTooSmall { required, actual } => {
                 write!(f, "Buffer too small: {} bytes required, only {} bytes available", required, actual)
             }
+            ProtocolError::UnknownSchema { schema_id } => {
+                write!(f, "Unknown schema ID: {}", schema_id)
+            }
+            ProtocolError::UnsupportedSchemaVersion { version, max } => {
+                write!(f, "Schema version {} is newer than supported version {}", version, max)
+            }
+            ProtocolError::IncompatibleSchemaVersion { version, min } => {
+                write!(f, "Schema version {} is older than minimum compatible version {}", version, min)
+            }
+            ProtocolError::DuplicateInstrument { id } => {
+                write!(f, "Duplicate or invalid instrument ID {} (IDs and venue symbols must be unique and non-zero)", id)
+            }
+            ProtocolError::InvalidVarint { offset } => {
+                write!(f, "Malformed varint at offset {}", offset)
+            }
+            ProtocolError::InvalidScale { decimals } => {
+                write!(f, "Invalid fixed-point scale: {} decimal places (max {})", decimals, crate::fixed_point::Scale::MAX_DECIMALS)
+            }
+            ProtocolError::FixedPointOverflow { decimals } => {
+                write!(f, "Value does not fit in i64 at {} decimal places", decimals)
+            }
+            ProtocolError::InvalidOrderSide { side } => {
+                write!(f, "Invalid order side: {} (must be 0 or 1)", side)
+            }
         }
     }
 }
@@ -1,6 +1,54 @@
+use serde::Deserialize;
+
 pub const FIXED_POINT_MULTIPLIER: i64 = 100_000_000;
 pub const DECIMAL_PLACES: u32 = 8;
 
+/// Number of decimal places a fixed-point value carries
+///
+/// The global `DECIMAL_PLACES` suits BTC-like instruments; very low-priced assets
+/// need more places and very high-priced ones fewer to keep headroom in an i64.
+/// Values at different scales must be rescaled before they are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "u8")]
+pub struct Scale(u8);
+
+impl Scale {
+    /// Largest scale whose multiplier fits in an i64
+    pub const MAX_DECIMALS: u8 = 18;
+    /// The global scale (`DECIMAL_PLACES`)
+    pub const DEFAULT: Self = Scale(DECIMAL_PLACES as u8);
+
+    #[inline]
+    pub const fn new(decimals: u8) -> Option<Self> {
+        if decimals <= Self::MAX_DECIMALS { Some(Scale(decimals)) } else { None }
+    }
+
+    #[inline(always)]
+    pub const fn decimals(self) -> u8 {
+        self.0
+    }
+
+    /// 10^decimals
+    #[inline(always)]
+    pub const fn multiplier(self) -> i64 {
+        10i64.pow(self.0 as u32)
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl TryFrom<u8> for Scale {
+    type Error = crate::ProtocolError;
+
+    fn try_from(decimals: u8) -> Result<Self, Self::Error> {
+        Self::new(decimals).ok_or(crate::ProtocolError::InvalidScale { decimals })
+    }
+}
+
 #[inline(always)]
 pub fn to_fixed_point(value: f64) -> i64 {
     (value * FIXED_POINT_MULTIPLIER as f64).round() as i64
@@ -11,16 +59,49 @@ pub fn from_fixed_point(value: i64) -> f64 {
     value as f64 / FIXED_POINT_MULTIPLIER as f64
 }
 
+#[inline(always)]
+pub fn to_fixed_point_scaled(value: f64, scale: Scale) -> i64 {
+    (value * scale.multiplier() as f64).round() as i64
+}
+
+#[inline(always)]
+pub fn from_fixed_point_scaled(value: i64, scale: Scale) -> f64 {
+    value as f64 / scale.multiplier() as f64
+}
+
+/// Convert a fixed-point value between scales
+///
+/// Narrowing truncates toward zero; returns `None` if widening overflows.
+#[inline]
+pub fn rescale(value: i64, from: Scale, to: Scale) -> Option<i64> {
+    match from.0.cmp(&to.0) {
+        std::cmp::Ordering::Equal => Some(value),
+        std::cmp::Ordering::Less => value.checked_mul(Scale(to.0 - from.0).multiplier()),
+        std::cmp::Ordering::Greater => Some(value / Scale(from.0 - to.0).multiplier()),
+    }
+}
+
 /// Parse a JSON decimal string directly to fixed-point i64
 ///
 /// Handles strings like "42250.15" or "0.00000123" without allocating
 /// Supports up to 8 decimal places for satoshi precision
 ///
 pub fn parse_json_decimal_to_fixed_point(bytes: &[u8]) -> Result<i64, crate::ProtocolError> {
+    parse_json_decimal_to_fixed_point_scaled(bytes, Scale::DEFAULT)
+}
+
+/// Parse a JSON decimal string to fixed-point at `scale`
+///
+/// Digits beyond `scale` decimal places are truncated; values that do not fit
+/// in an i64 at that scale are rejected rather than wrapped.
+pub fn parse_json_decimal_to_fixed_point_scaled(bytes: &[u8], scale: Scale) -> Result<i64, crate::ProtocolError> {
     if bytes.is_empty() {
         return Err(crate::ProtocolError::InvalidLength { expected: 1, actual: 0 });
     }
 
+    let overflow = || crate::ProtocolError::FixedPointOverflow { decimals: scale.decimals() };
+    let decimals = scale.decimals() as usize;
+
     let mut result: i64 = 0;
     let mut decimal_pos: Option<usize> = None;
     let mut negative = false;
@@ -36,8 +117,11 @@ pub fn parse_json_decimal_to_fixed_point(bytes: &[u8]) -> Result<i64, crate::Pro
     while pos < bytes.len() {
         match bytes[pos] {
             b'0'..=b'9' => {
-                let digit = (bytes[pos] - b'0') as i64;
-                result = result * 10 + digit;
+                // Digits past the target scale are truncated, so stop accumulating them
+                if decimal_pos.is_none_or(|dot_pos| pos - dot_pos <= decimals) {
+                    let digit = (bytes[pos] - b'0') as i64;
+                    result = result.checked_mul(10).and_then(|r| r.checked_add(digit)).ok_or_else(overflow)?;
+                }
             }
             b'.' => {
                 if decimal_pos.is_some() {
@@ -50,18 +134,13 @@ pub fn parse_json_decimal_to_fixed_point(bytes: &[u8]) -> Result<i64, crate::Pro
         pos += 1;
     }
 
-    // Calculate how many decimal places we read
-    let decimals_read = if let Some(dot_pos) = decimal_pos { bytes.len() - dot_pos - 1 } else { 0 };
+    // Calculate how many decimal places we kept
+    let decimals_read = if let Some(dot_pos) = decimal_pos { (bytes.len() - dot_pos - 1).min(decimals) } else { 0 };
 
-    // Scale to fixed-point (pad or truncate to 8 decimal places)
-    if decimals_read < DECIMAL_PLACES as usize {
-        // Pad with zeros
-        let scale_factor = 10i64.pow(DECIMAL_PLACES - decimals_read as u32);
-        result *= scale_factor;
-    } else if decimals_read > DECIMAL_PLACES as usize {
-        // Truncate excess decimals
-        let scale_factor = 10i64.pow((decimals_read - DECIMAL_PLACES as usize) as u32);
-        result /= scale_factor;
+    // Pad with zeros up to the target scale
+    if decimals_read < decimals {
+        let scale_factor = 10i64.pow((decimals - decimals_read) as u32);
+        result = result.checked_mul(scale_factor).ok_or_else(overflow)?;
     }
 
     if negative {
@@ -125,6 +204,57 @@ mod tests {
         assert_eq!(parse_json_decimal_to_fixed_point(b"42250.15678901").unwrap(), 4225015678901);
     }
 
+    #[test]
+    fn test_scale_bounds() {
+        assert_eq!(Scale::DEFAULT.multiplier(), FIXED_POINT_MULTIPLIER);
+        assert_eq!(Scale::new(18).unwrap().multiplier(), 1_000_000_000_000_000_000);
+        assert!(Scale::new(19).is_none());
+        assert!(Scale::try_from(19).is_err());
+    }
+
+    #[test]
+    fn test_scaled_conversion() {
+        // SHIB-like prices keep their significant digits at a wider scale
+        let shib = Scale::new(12).unwrap();
+        assert_eq!(to_fixed_point(0.00001234567), 1235);
+        assert_eq!(to_fixed_point_scaled(0.00001234567, shib), 12_345_670);
+        assert_eq!(from_fixed_point_scaled(12_345_670, shib), 0.00001234567);
+
+        // High-priced instruments can trade precision for range
+        let coarse = Scale::new(2).unwrap();
+        assert_eq!(to_fixed_point_scaled(1e15, coarse), 100_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_parse_json_decimal_scaled() {
+        let shib = Scale::new(12).unwrap();
+        assert_eq!(parse_json_decimal_to_fixed_point_scaled(b"0.00001234567", shib).unwrap(), 12_345_670);
+        assert_eq!(parse_json_decimal_to_fixed_point_scaled(b"-0.000000000001", shib).unwrap(), -1);
+
+        let cents = Scale::new(2).unwrap();
+        assert_eq!(parse_json_decimal_to_fixed_point_scaled(b"42250.159", cents).unwrap(), 4_225_015);
+        assert_eq!(parse_json_decimal_to_fixed_point_scaled(b"7", Scale::new(0).unwrap()).unwrap(), 7);
+
+        // Long fractional tails are truncated, not accumulated into an overflow
+        assert_eq!(parse_json_decimal_to_fixed_point(b"1.12345678901234567890123").unwrap(), 112_345_678);
+
+        // Out of range at the requested scale
+        let max = Scale::new(18).unwrap();
+        assert!(matches!(
+            parse_json_decimal_to_fixed_point_scaled(b"100", max),
+            Err(crate::ProtocolError::FixedPointOverflow { decimals: 18 })
+        ));
+    }
+
+    #[test]
+    fn test_rescale() {
+        let shib = Scale::new(12).unwrap();
+        assert_eq!(rescale(12_345_670, shib, Scale::DEFAULT), Some(1_234));
+        assert_eq!(rescale(1_234, Scale::DEFAULT, shib), Some(12_340_000));
+        assert_eq!(rescale(42, shib, shib), Some(42));
+        assert_eq!(rescale(i64::MAX / 10, Scale::DEFAULT, shib), None);
+    }
+
     #[test]
     fn test_parse_json_decimal_edge_cases() {
         // Zero
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::CompressedString;
+use crate::Exchange;
+use crate::compressed_string::EncodingScheme;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::fixed_point::FIXED_POINT_MULTIPLIER;
+use crate::fixed_point::Scale;
+use crate::fixed_point::parse_json_decimal_to_fixed_point_scaled;
+use crate::serde_helpers::deserialize_exchange;
+
+/// Instrument ID carried on the wire when none has been assigned; consumers fall
+/// back to the compressed symbol
+pub const UNKNOWN_INSTRUMENT_ID: u32 = 0;
+
+/// How an instrument settles; sizes its contracts with `InstrumentInfo::multiplier`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractType {
+    #[default]
+    Spot,
+    /// Quote-settled perpetual; the multiplier is base units per contract
+    LinearPerp,
+    /// Base-settled perpetual; the multiplier is quote units per contract
+    InversePerp,
+}
+
+/// One instrument entry as written in config
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstrumentConfig {
+    /// Wire ID; must be non-zero and agreed on by every process sharing a stream
+    pub id: u32,
+    pub symbol: String,
+    #[serde(deserialize_with = "deserialize_exchange")]
+    pub venue: Exchange,
+    pub base_asset: String,
+    pub quote_asset: String,
+    /// Minimum price increment (decimal string, e.g. "0.01")
+    pub tick_size: String,
+    /// Minimum quantity increment (decimal string, e.g. "0.00001")
+    pub lot_size: String,
+    /// Minimum order value in the quote asset (decimal string, e.g. "5"); none if omitted
+    #[serde(default)]
+    pub min_notional: Option<String>,
+    /// Decimal places for prices (defaults to `Scale::DEFAULT`)
+    #[serde(default)]
+    pub price_scale: Scale,
+    /// Decimal places for quantities (defaults to `Scale::DEFAULT`)
+    #[serde(default)]
+    pub qty_scale: Scale,
+    /// Settlement style (defaults to spot)
+    #[serde(default)]
+    pub contract: ContractType,
+    /// Contract size (decimal string, e.g. "100"); defaults to 1
+    #[serde(default)]
+    pub multiplier: Option<String>,
+}
+
+/// Static metadata for a tradable instrument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstrumentInfo {
+    pub id: u32,
+    pub symbol: String,
+    pub venue: Exchange,
+    pub base_asset: String,
+    pub quote_asset: String,
+    /// Minimum price increment in fixed-point at `price_scale`
+    pub tick_size: i64,
+    /// Minimum quantity increment in fixed-point at `qty_scale`
+    pub lot_size: i64,
+    /// Minimum order value in the quote asset, in fixed-point at `price_scale` (0 = none)
+    pub min_notional: i64,
+    /// Decimal places of every price for this instrument
+    pub price_scale: Scale,
+    /// Decimal places of every quantity for this instrument
+    pub qty_scale: Scale,
+    pub contract: ContractType,
+    /// Contract size in fixed-point at `Scale::DEFAULT` (1.0 for spot)
+    pub multiplier: i64,
+    /// Wire encoding of `symbol`, if it fits in a `CompressedString`
+    pub compressed: Option<(CompressedString, EncodingScheme)>,
+}
+
+impl InstrumentInfo {
+    pub fn new(id: u32, symbol: &str, venue: Exchange, base_asset: &str, quote_asset: &str, tick_size: i64, lot_size: i64) -> Self {
+        Self {
+            id,
+            symbol: symbol.to_string(),
+            venue,
+            base_asset: base_asset.to_string(),
+            quote_asset: quote_asset.to_string(),
+            tick_size,
+            lot_size,
+            min_notional: 0,
+            price_scale: Scale::DEFAULT,
+            qty_scale: Scale::DEFAULT,
+            contract: ContractType::Spot,
+            multiplier: FIXED_POINT_MULTIPLIER,
+            compressed: CompressedString::from_str(symbol).ok(),
+        }
+    }
+
+    /// Set non-default scales; `tick_size` and `lot_size` must already be expressed at them
+    pub fn with_scales(mut self, price_scale: Scale, qty_scale: Scale) -> Self {
+        self.price_scale = price_scale;
+        self.qty_scale = qty_scale;
+        self
+    }
+
+    /// Set the minimum order value; must already be expressed at `price_scale`
+    pub fn with_min_notional(mut self, min_notional: i64) -> Self {
+        self.min_notional = min_notional;
+        self
+    }
+
+    /// Set the settlement style and contract size (fixed-point at `Scale::DEFAULT`)
+    pub fn with_contract(mut self, contract: ContractType, multiplier: i64) -> Self {
+        self.contract = contract;
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Parse a decimal price string at this instrument's price scale
+    #[inline]
+    pub fn parse_price(&self, bytes: &[u8]) -> Result<i64> {
+        parse_json_decimal_to_fixed_point_scaled(bytes, self.price_scale)
+    }
+
+    /// Parse a decimal quantity string at this instrument's quantity scale
+    #[inline]
+    pub fn parse_qty(&self, bytes: &[u8]) -> Result<i64> {
+        parse_json_decimal_to_fixed_point_scaled(bytes, self.qty_scale)
+    }
+}
+
+impl TryFrom<InstrumentConfig> for InstrumentInfo {
+    type Error = ProtocolError;
+
+    fn try_from(config: InstrumentConfig) -> Result<Self> {
+        let tick_size = parse_json_decimal_to_fixed_point_scaled(config.tick_size.as_bytes(), config.price_scale)?;
+        let lot_size = parse_json_decimal_to_fixed_point_scaled(config.lot_size.as_bytes(), config.qty_scale)?;
+        let min_notional = match &config.min_notional {
+            Some(value) => parse_json_decimal_to_fixed_point_scaled(value.as_bytes(), config.price_scale)?,
+            None => 0,
+        };
+        let multiplier = match &config.multiplier {
+            Some(value) => parse_json_decimal_to_fixed_point_scaled(value.as_bytes(), Scale::DEFAULT)?,
+            None => FIXED_POINT_MULTIPLIER,
+        };
+        let info = Self::new(config.id, &config.symbol, config.venue, &config.base_asset, &config.quote_asset, tick_size, lot_size);
+        Ok(info
+            .with_scales(config.price_scale, config.qty_scale)
+            .with_min_notional(min_notional)
+            .with_contract(config.contract, multiplier))
+    }
+}
+
+/// Maps symbols to compact `u32` instrument IDs and holds per-instrument metadata
+///
+/// Messages that carry an instrument ID are resolved with a single hash lookup;
+/// messages that only carry a `CompressedString` are resolved on the packed
+/// symbol bits without decoding to a `String`.
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentRegistry {
+    instruments: Vec<InstrumentInfo>,
+    by_id: HashMap<u32, usize>,
+    by_symbol: HashMap<(Exchange, String), usize>,
+    by_compressed: HashMap<(Exchange, u64, u64), usize>,
+}
+
+impl InstrumentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a registry from config entries
+    pub fn from_configs(configs: impl IntoIterator<Item = InstrumentConfig>) -> Result<Self> {
+        let mut registry = Self::new();
+        for config in configs {
+            registry.register(config.try_into()?)?;
+        }
+        Ok(registry)
+    }
+
+    /// Add an instrument; IDs and (venue, symbol) pairs must be unique
+    pub fn register(&mut self, info: InstrumentInfo) -> Result<u32> {
+        let symbol_key = (info.venue, info.symbol.clone());
+        if info.id == UNKNOWN_INSTRUMENT_ID || self.by_id.contains_key(&info.id) || self.by_symbol.contains_key(&symbol_key) {
+            return Err(ProtocolError::DuplicateInstrument { id: info.id });
+        }
+
+        let index = self.instruments.len();
+        self.by_id.insert(info.id, index);
+        self.by_symbol.insert(symbol_key, index);
+        if let Some((compressed, _)) = info.compressed {
+            self.by_compressed.insert((info.venue, compressed.low, compressed.high), index);
+        }
+
+        let id = info.id;
+        self.instruments.push(info);
+        Ok(id)
+    }
+
+    #[inline]
+    pub fn get(&self, id: u32) -> Option<&InstrumentInfo> {
+        self.by_id.get(&id).map(|&index| &self.instruments[index])
+    }
+
+    /// Look up the ID for a venue symbol
+    #[inline]
+    pub fn id(&self, venue: Exchange, symbol: &str) -> Option<u32> {
+        self.by_symbol.get(&(venue, symbol.to_string())).map(|&index| self.instruments[index].id)
+    }
+
+    #[inline]
+    pub fn by_symbol(&self, venue: Exchange, symbol: &str) -> Option<&InstrumentInfo> {
+        self.id(venue, symbol).and_then(|id| self.get(id))
+    }
+
+    /// Fallback lookup for messages that carry only a compressed symbol
+    #[inline]
+    pub fn by_compressed(&self, venue: Exchange, symbol: CompressedString) -> Option<&InstrumentInfo> {
+        self.by_compressed.get(&(venue, symbol.low, symbol.high)).map(|&index| &self.instruments[index])
+    }
+
+    /// Resolve a message's instrument: by ID when set, otherwise by compressed symbol
+    #[inline]
+    pub fn resolve(&self, venue: Exchange, instrument_id: u32, symbol: CompressedString) -> Option<&InstrumentInfo> {
+        if instrument_id != UNKNOWN_INSTRUMENT_ID {
+            return self.get(instrument_id);
+        }
+        self.by_compressed(venue, symbol)
+    }
+
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &InstrumentInfo> {
+        self.instruments.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> InstrumentRegistry {
+        let mut registry = InstrumentRegistry::new();
+        registry.register(InstrumentInfo::new(1, "BTCUSDT", Exchange::Binance, "BTC", "USDT", 1_000_000, 1_000)).unwrap();
+        registry.register(InstrumentInfo::new(2, "ETHUSDT", Exchange::Binance, "ETH", "USDT", 1_000_000, 10_000)).unwrap();
+        registry
+    }
+
+    #[test]
+    fn test_lookup_by_id_and_symbol() {
+        let registry = registry();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.id(Exchange::Binance, "ETHUSDT"), Some(2));
+        assert_eq!(registry.id(Exchange::Okx, "ETHUSDT"), None);
+
+        let btc = registry.get(1).unwrap();
+        assert_eq!(btc.base_asset, "BTC");
+        assert_eq!(btc.tick_size, 1_000_000);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_compressed_symbol() {
+        let registry = registry();
+        let (symbol, _) = CompressedString::from_str("ETHUSDT").unwrap();
+
+        assert_eq!(registry.resolve(Exchange::Binance, 1, symbol).unwrap().symbol, "BTCUSDT");
+        assert_eq!(registry.resolve(Exchange::Binance, UNKNOWN_INSTRUMENT_ID, symbol).unwrap().id, 2);
+        assert!(registry.resolve(Exchange::Kraken, UNKNOWN_INSTRUMENT_ID, symbol).is_none());
+    }
+
+    #[test]
+    fn test_duplicates_rejected() {
+        let mut registry = registry();
+        let dup_id = InstrumentInfo::new(1, "SOLUSDT", Exchange::Binance, "SOL", "USDT", 1, 1);
+        let dup_symbol = InstrumentInfo::new(3, "BTCUSDT", Exchange::Binance, "BTC", "USDT", 1, 1);
+        let zero_id = InstrumentInfo::new(UNKNOWN_INSTRUMENT_ID, "SOLUSDT", Exchange::Binance, "SOL", "USDT", 1, 1);
+
+        assert!(registry.register(dup_id).is_err());
+        assert!(registry.register(dup_symbol).is_err());
+        assert!(registry.register(zero_id).is_err());
+
+        // Same symbol on another venue is a different instrument
+        assert!(registry.register(InstrumentInfo::new(3, "BTCUSDT", Exchange::Okx, "BTC", "USDT", 1, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_from_json_config() {
+        let json = r#"[
+            {"id": 7, "symbol": "BTCUSDT", "venue": "binance", "base_asset": "BTC", "quote_asset": "USDT", "tick_size": "0.01", "lot_size": "0.00001",
+             "min_notional": "5"}
+        ]"#;
+        let configs: Vec<InstrumentConfig> = serde_json::from_str(json).unwrap();
+        let registry = InstrumentRegistry::from_configs(configs).unwrap();
+
+        let btc = registry.by_symbol(Exchange::Binance, "BTCUSDT").unwrap();
+        assert_eq!(btc.id, 7);
+        assert_eq!(btc.tick_size, 1_000_000);
+        assert_eq!(btc.lot_size, 1_000);
+        assert_eq!(btc.min_notional, 500_000_000);
+        assert_eq!(btc.price_scale, Scale::DEFAULT);
+        assert_eq!((btc.contract, btc.multiplier), (ContractType::Spot, FIXED_POINT_MULTIPLIER));
+    }
+
+    #[test]
+    fn test_config_contract() {
+        let json = r#"[
+            {"id": 3, "symbol": "BTCUSD_PERP", "venue": "binance", "base_asset": "BTC", "quote_asset": "USD", "tick_size": "0.1", "lot_size": "1",
+             "contract": "inverse_perp", "multiplier": "100"}
+        ]"#;
+        let configs: Vec<InstrumentConfig> = serde_json::from_str(json).unwrap();
+        let registry = InstrumentRegistry::from_configs(configs).unwrap();
+
+        let perp = registry.get(3).unwrap();
+        assert_eq!(perp.contract, ContractType::InversePerp);
+        assert_eq!(perp.multiplier, 100 * FIXED_POINT_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_config_scales() {
+        let json = r#"[
+            {"id": 9, "symbol": "SHIBUSDT", "venue": "binance", "base_asset": "SHIB", "quote_asset": "USDT",
+             "tick_size": "0.00000001", "lot_size": "1", "price_scale": 12, "qty_scale": 0}
+        ]"#;
+        let configs: Vec<InstrumentConfig> = serde_json::from_str(json).unwrap();
+        let registry = InstrumentRegistry::from_configs(configs).unwrap();
+
+        let shib = registry.get(9).unwrap();
+        assert_eq!(shib.tick_size, 10_000);
+        assert_eq!(shib.lot_size, 1);
+        assert_eq!(shib.parse_price(b"0.000012345678").unwrap(), 12_345_678);
+        assert_eq!(shib.parse_qty(b"1500000").unwrap(), 1_500_000);
+
+        let invalid = r#"[{"id": 1, "symbol": "X", "venue": "binance", "base_asset": "X", "quote_asset": "Y",
+            "tick_size": "1", "lot_size": "1", "price_scale": 19}]"#;
+        assert!(serde_json::from_str::<Vec<InstrumentConfig>>(invalid).is_err());
+    }
+}
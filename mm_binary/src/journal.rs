@@ -0,0 +1,529 @@
+//! Append-only journal of received frames for lossless capture and replay
+//!
+//! A journal is a directory of segment files plus an index. Each segment starts
+//! with a 16-byte header (`magic | version: u32 | segment_id: u32`) followed by
+//! records laid out as (little-endian):
+//! `length: u32 | crc32c: u32 | stream_id: i32 | reserved: u32 | receive_time: u64 | payload`
+//!
+//! The CRC covers everything after itself, payload included, and payloads are
+//! stored exactly as received, so replay is bit-for-bit. The index holds one
+//! fixed-size entry per sealed segment with its frame count and time range. A
+//! segment that was never sealed (the writer crashed) is still found by scanning
+//! the directory and is read up to its last complete record.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::checksum;
+
+/// Identifies a segment file
+pub const SEGMENT_MAGIC: [u8; 8] = *b"MMJOURNL";
+
+/// Segment format version; bump on any layout change
+pub const JOURNAL_VERSION: u32 = 1;
+
+/// Index file name inside the journal directory
+pub const INDEX_FILE: &str = "journal.idx";
+
+/// Largest payload accepted, so a corrupt length cannot trigger a huge allocation
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+const SEGMENT_HEADER_SIZE: usize = 16;
+const RECORD_HEADER_SIZE: usize = 24;
+const INDEX_ENTRY_SIZE: usize = 32;
+
+#[derive(Debug)]
+pub enum JournalError {
+    Io(io::Error),
+    InvalidSegment { segment_id: u32 },
+    UnsupportedVersion { version: u32 },
+    CorruptRecord { segment_id: u32, offset: u64 },
+    CorruptIndex { entry: usize },
+    FrameTooLarge { length: usize, max: usize },
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::Io(err) => write!(f, "Journal I/O error: {}", err),
+            JournalError::InvalidSegment { segment_id } => {
+                write!(f, "Invalid header in journal segment {}", segment_id)
+            }
+            JournalError::UnsupportedVersion { version } => {
+                write!(f, "Unsupported journal version {} (expected {})", version, JOURNAL_VERSION)
+            }
+            JournalError::CorruptRecord { segment_id, offset } => {
+                write!(f, "Corrupt journal record in segment {} at offset {}", segment_id, offset)
+            }
+            JournalError::CorruptIndex { entry } => {
+                write!(f, "Corrupt journal index entry {}", entry)
+            }
+            JournalError::FrameTooLarge { length, max } => {
+                write!(f, "Journal frame too large: {} bytes exceeds maximum of {}", length, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+impl From<io::Error> for JournalError {
+    fn from(err: io::Error) -> Self {
+        JournalError::Io(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, JournalError>;
+
+/// Index entry describing a sealed segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SegmentInfo {
+    pub segment_id: u32,
+    pub frame_count: u64,
+    pub first_receive_time: u64,
+    pub last_receive_time: u64,
+}
+
+impl SegmentInfo {
+    fn new(segment_id: u32) -> Self {
+        Self { segment_id, ..Default::default() }
+    }
+
+    fn observe(&mut self, receive_time: u64) {
+        if self.frame_count == 0 {
+            self.first_receive_time = receive_time;
+        }
+        self.last_receive_time = receive_time;
+        self.frame_count += 1;
+    }
+
+    fn to_bytes(self) -> [u8; INDEX_ENTRY_SIZE] {
+        let mut bytes = [0u8; INDEX_ENTRY_SIZE];
+        bytes[0..4].copy_from_slice(&self.segment_id.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.frame_count.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.first_receive_time.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.last_receive_time.to_le_bytes());
+        let crc = checksum::calculate_crc32c(&bytes[8..]);
+        bytes[4..8].copy_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; INDEX_ENTRY_SIZE]) -> Option<Self> {
+        let read_u64 = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let crc = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if crc != checksum::calculate_crc32c(&bytes[8..]) {
+            return None;
+        }
+        Some(Self {
+            segment_id: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            frame_count: read_u64(8),
+            first_receive_time: read_u64(16),
+            last_receive_time: read_u64(24),
+        })
+    }
+}
+
+/// One recorded frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalFrame {
+    pub stream_id: i32,
+    /// Nanoseconds since the epoch when the recorder received the frame
+    pub receive_time: u64,
+    pub payload: Vec<u8>,
+}
+
+fn segment_path(dir: &Path, segment_id: u32) -> PathBuf {
+    dir.join(format!("segment-{:08}.mmj", segment_id))
+}
+
+/// Segment IDs present in the directory, ascending
+pub fn list_segments(dir: impl AsRef<Path>) -> Result<Vec<u32>> {
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let id = name.to_str().and_then(|name| name.strip_prefix("segment-")?.strip_suffix(".mmj")?.parse().ok());
+        if let Some(id) = id {
+            ids.push(id);
+        }
+    }
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+/// Sealed segments recorded in the index; a missing index means none were sealed
+pub fn read_index(dir: impl AsRef<Path>) -> Result<Vec<SegmentInfo>> {
+    let bytes = match fs::read(dir.as_ref().join(INDEX_FILE)) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    // A trailing partial entry is an index write cut short; its segment is still scanned
+    bytes
+        .chunks_exact(INDEX_ENTRY_SIZE)
+        .enumerate()
+        .map(|(entry, chunk)| SegmentInfo::from_bytes(chunk.try_into().unwrap()).ok_or(JournalError::CorruptIndex { entry }))
+        .collect()
+}
+
+/// Appends frames to size-bounded segments and indexes each segment when sealed
+pub struct JournalWriter {
+    dir: PathBuf,
+    max_segment_bytes: u64,
+    index: File,
+    segment: BufWriter<File>,
+    segment_bytes: u64,
+    current: SegmentInfo,
+    record: Vec<u8>,
+    sealed: bool,
+}
+
+impl JournalWriter {
+    pub const DEFAULT_SEGMENT_BYTES: u64 = 256 * 1024 * 1024;
+
+    pub fn create(dir: impl AsRef<Path>) -> Result<Self> {
+        Self::with_segment_size(dir, Self::DEFAULT_SEGMENT_BYTES)
+    }
+
+    /// Open a journal, starting a new segment after any already in the directory
+    pub fn with_segment_size(dir: impl AsRef<Path>, max_segment_bytes: u64) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let segment_id = list_segments(&dir)?.last().map_or(0, |id| id + 1);
+        let index = OpenOptions::new().create(true).append(true).open(dir.join(INDEX_FILE))?;
+        let segment = Self::open_segment(&dir, segment_id)?;
+
+        Ok(Self {
+            dir,
+            max_segment_bytes,
+            index,
+            segment,
+            segment_bytes: SEGMENT_HEADER_SIZE as u64,
+            current: SegmentInfo::new(segment_id),
+            record: Vec::new(),
+            sealed: false,
+        })
+    }
+
+    fn open_segment(dir: &Path, segment_id: u32) -> Result<BufWriter<File>> {
+        let file = OpenOptions::new().create_new(true).write(true).open(segment_path(dir, segment_id))?;
+        let mut segment = BufWriter::new(file);
+
+        let mut header = [0u8; SEGMENT_HEADER_SIZE];
+        header[0..8].copy_from_slice(&SEGMENT_MAGIC);
+        header[8..12].copy_from_slice(&JOURNAL_VERSION.to_le_bytes());
+        header[12..16].copy_from_slice(&segment_id.to_le_bytes());
+        segment.write_all(&header)?;
+        Ok(segment)
+    }
+
+    /// Append one frame exactly as received
+    pub fn append(&mut self, stream_id: i32, receive_time: u64, payload: &[u8]) -> Result<()> {
+        if payload.len() > MAX_FRAME_SIZE {
+            return Err(JournalError::FrameTooLarge { length: payload.len(), max: MAX_FRAME_SIZE });
+        }
+
+        let record_len = (RECORD_HEADER_SIZE + payload.len()) as u64;
+        if self.current.frame_count > 0 && self.segment_bytes + record_len > self.max_segment_bytes {
+            self.roll()?;
+        }
+
+        self.record.clear();
+        self.record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        self.record.extend_from_slice(&[0u8; 4]);
+        self.record.extend_from_slice(&stream_id.to_le_bytes());
+        self.record.extend_from_slice(&[0u8; 4]);
+        self.record.extend_from_slice(&receive_time.to_le_bytes());
+        self.record.extend_from_slice(payload);
+        let crc = checksum::calculate_crc32c(&self.record[8..]);
+        self.record[4..8].copy_from_slice(&crc.to_le_bytes());
+
+        self.segment.write_all(&self.record)?;
+        self.segment_bytes += record_len;
+        self.current.observe(receive_time);
+        Ok(())
+    }
+
+    /// Push buffered records to the OS
+    pub fn flush(&mut self) -> Result<()> {
+        self.segment.flush()?;
+        Ok(())
+    }
+
+    /// Segment currently being written
+    pub fn current_segment(&self) -> SegmentInfo {
+        self.current
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Seal the active segment and record it in the index
+    pub fn finish(mut self) -> Result<()> {
+        self.seal()
+    }
+
+    fn seal(&mut self) -> Result<()> {
+        self.sealed = true;
+        self.segment.flush()?;
+        self.segment.get_ref().sync_data()?;
+        self.index.write_all(&self.current.to_bytes())?;
+        self.index.sync_data()?;
+        Ok(())
+    }
+
+    fn roll(&mut self) -> Result<()> {
+        self.seal()?;
+        let segment_id = self.current.segment_id + 1;
+        self.segment = Self::open_segment(&self.dir, segment_id)?;
+        self.segment_bytes = SEGMENT_HEADER_SIZE as u64;
+        self.current = SegmentInfo::new(segment_id);
+        self.sealed = false;
+        Ok(())
+    }
+}
+
+impl Drop for JournalWriter {
+    fn drop(&mut self) {
+        if !self.sealed {
+            let _ = self.seal();
+        }
+    }
+}
+
+struct OpenSegment {
+    segment_id: u32,
+    reader: BufReader<File>,
+    offset: u64,
+    frames_read: u64,
+}
+
+/// Reads frames back in recording order across all segments
+pub struct JournalReader {
+    dir: PathBuf,
+    pending: VecDeque<u32>,
+    sealed: HashMap<u32, SegmentInfo>,
+    current: Option<OpenSegment>,
+}
+
+impl JournalReader {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        Self::open_from(dir, 0)
+    }
+
+    /// Skip sealed segments that end before `start_time`
+    ///
+    /// Filtering is per segment, so frames slightly older than `start_time` may
+    /// still be returned from the first segment.
+    pub fn open_from(dir: impl AsRef<Path>, start_time: u64) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let sealed: HashMap<u32, SegmentInfo> = read_index(&dir)?.into_iter().map(|info| (info.segment_id, info)).collect();
+
+        let pending = list_segments(&dir)?
+            .into_iter()
+            .filter(|id| sealed.get(id).is_none_or(|info| info.frame_count > 0 && info.last_receive_time >= start_time))
+            .collect();
+
+        Ok(Self { dir, pending, sealed, current: None })
+    }
+
+    /// Next frame, or `None` once every segment is exhausted
+    pub fn next_frame(&mut self) -> Result<Option<JournalFrame>> {
+        loop {
+            if self.current.is_none() {
+                let Some(segment_id) = self.pending.pop_front() else {
+                    return Ok(None);
+                };
+                self.current = Some(self.open_segment(segment_id)?);
+            }
+
+            if let Some(frame) = self.read_record()? {
+                return Ok(Some(frame));
+            }
+            self.current = None;
+        }
+    }
+
+    fn open_segment(&self, segment_id: u32) -> Result<OpenSegment> {
+        let mut reader = BufReader::new(File::open(segment_path(&self.dir, segment_id))?);
+
+        let mut header = [0u8; SEGMENT_HEADER_SIZE];
+        reader.read_exact(&mut header).map_err(|_| JournalError::InvalidSegment { segment_id })?;
+        if header[0..8] != SEGMENT_MAGIC || u32::from_le_bytes(header[12..16].try_into().unwrap()) != segment_id {
+            return Err(JournalError::InvalidSegment { segment_id });
+        }
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        if version != JOURNAL_VERSION {
+            return Err(JournalError::UnsupportedVersion { version });
+        }
+
+        Ok(OpenSegment { segment_id, reader, offset: SEGMENT_HEADER_SIZE as u64, frames_read: 0 })
+    }
+
+    /// Read one record from the current segment; `None` at its end
+    fn read_record(&mut self) -> Result<Option<JournalFrame>> {
+        let segment = self.current.as_mut().expect("segment open");
+        let corrupt = JournalError::CorruptRecord { segment_id: segment.segment_id, offset: segment.offset };
+
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        let header_read = read_full(&mut segment.reader, &mut header)?;
+        let complete = if header_read == RECORD_HEADER_SIZE {
+            let length = u32::from_le_bytes(header[0..4].try_into().unwrap()) as usize;
+            if length > MAX_FRAME_SIZE {
+                return Err(corrupt);
+            }
+            let mut payload = vec![0u8; length];
+            if read_full(&mut segment.reader, &mut payload)? == length { Some((length, payload)) } else { None }
+        } else {
+            None
+        };
+
+        let Some((length, payload)) = complete else {
+            // Clean end, or a record cut short by a crash before the segment was sealed
+            if let Some(info) = self.sealed.get(&segment.segment_id) {
+                if header_read > 0 || info.frame_count != segment.frames_read {
+                    return Err(corrupt);
+                }
+            }
+            return Ok(None);
+        };
+
+        let expected_crc = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let mut crc_input = Vec::with_capacity(RECORD_HEADER_SIZE - 8 + length);
+        crc_input.extend_from_slice(&header[8..]);
+        crc_input.extend_from_slice(&payload);
+        if checksum::calculate_crc32c(&crc_input) != expected_crc {
+            return Err(corrupt);
+        }
+
+        segment.offset += (RECORD_HEADER_SIZE + length) as u64;
+        segment.frames_read += 1;
+        Ok(Some(JournalFrame {
+            stream_id: i32::from_le_bytes(header[8..12].try_into().unwrap()),
+            receive_time: u64::from_le_bytes(header[16..24].try_into().unwrap()),
+            payload,
+        }))
+    }
+}
+
+impl Iterator for JournalReader {
+    type Item = Result<JournalFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame().transpose()
+    }
+}
+
+/// Like `read_exact`, but returns the bytes read instead of failing at end of file
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::HeartbeatMessage;
+
+    fn temp_journal(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mm_journal_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_journal_roundtrip_across_segments() {
+        let dir = temp_journal("roundtrip");
+        let heartbeat = HeartbeatMessage::new(1_700_000_000_000, 42).to_bytes();
+
+        let mut writer = JournalWriter::with_segment_size(&dir, 256).unwrap();
+        for i in 0..10u64 {
+            writer.append(12, 1_000 + i, &heartbeat).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let index = read_index(&dir).unwrap();
+        assert!(index.len() > 1);
+        assert_eq!(index.iter().map(|info| info.frame_count).sum::<u64>(), 10);
+        assert_eq!(index[0].first_receive_time, 1_000);
+
+        let frames: Vec<JournalFrame> = JournalReader::open(&dir).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(frames.len(), 10);
+        assert!(frames.iter().all(|frame| frame.stream_id == 12 && frame.payload == heartbeat));
+        assert_eq!(frames[9].receive_time, 1_009);
+
+        // Segments that end before the start time are skipped
+        let late = JournalReader::open_from(&dir, 1_009).unwrap().count();
+        assert!(late < 10);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unsealed_segment_with_torn_tail() {
+        let dir = temp_journal("torn");
+        let mut writer = JournalWriter::create(&dir).unwrap();
+        writer.append(10, 1, b"first").unwrap();
+        writer.append(10, 2, b"second").unwrap();
+        writer.flush().unwrap();
+        // Simulate a crash: no seal, no index entry
+        std::mem::forget(writer);
+
+        let path = segment_path(&dir, 0);
+        let len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new().write(true).open(&path).unwrap().set_len(len - 3).unwrap();
+
+        let frames: Vec<JournalFrame> = JournalReader::open(&dir).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].payload, b"first");
+
+        // A restarted writer continues in a new segment
+        let mut writer = JournalWriter::create(&dir).unwrap();
+        writer.append(10, 3, b"third").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(list_segments(&dir).unwrap(), vec![0, 1]);
+        assert_eq!(JournalReader::open(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_record_is_rejected() {
+        let dir = temp_journal("corrupt");
+        let mut writer = JournalWriter::create(&dir).unwrap();
+        writer.append(10, 1, b"payload").unwrap();
+        writer.finish().unwrap();
+
+        let path = segment_path(&dir, 0);
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        let result = JournalReader::open(&dir).unwrap().next_frame();
+        assert!(matches!(result, Err(JournalError::CorruptRecord { segment_id: 0, offset: 16 })));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
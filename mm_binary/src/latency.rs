@@ -0,0 +1,238 @@
+//! Optional latency trailer carrying per-hop timestamps across the pipeline
+//!
+//! Each process on the collector → pricing → strategy → simulator path records when
+//! it received, parsed and published a message. The trailer is appended after the
+//! payload and located from the end of the frame by its magic and CRC, so frames
+//! without one are passed through untouched. All timestamps are nanoseconds since
+//! the epoch; 0 means not recorded.
+
+use crate::checksum;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+
+/// Hops a trailer can hold (one per pipeline stage)
+pub const MAX_HOPS: usize = 4;
+
+/// "LATC", stored in the last 4 bytes of the trailer
+pub const TRAILER_MAGIC: u32 = 0x4354_414C;
+
+/// Process that stamped a hop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[repr(u8)]
+pub enum PipelineStage {
+    #[default]
+    Collector = 0,
+    Pricing = 1,
+    Strategy = 2,
+    Simulator = 3,
+}
+
+impl PipelineStage {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PipelineStage::Collector),
+            1 => Some(PipelineStage::Pricing),
+            2 => Some(PipelineStage::Strategy),
+            3 => Some(PipelineStage::Simulator),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineStage::Collector => "collector",
+            PipelineStage::Pricing => "pricing",
+            PipelineStage::Strategy => "strategy",
+            PipelineStage::Simulator => "simulator",
+        }
+    }
+}
+
+/// Timestamps recorded by one process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HopTimestamps {
+    pub stage: PipelineStage,
+    /// Frame handed to this process (socket read or Aeron poll)
+    pub receive_time: u64,
+    /// Frame decoded
+    pub parse_time: u64,
+    /// Output handed to the publisher
+    pub publish_time: u64,
+}
+
+impl HopTimestamps {
+    pub fn new(stage: PipelineStage, receive_time: u64, parse_time: u64, publish_time: u64) -> Self {
+        Self { stage, receive_time, parse_time, publish_time }
+    }
+}
+
+/// Exchange event time plus up to `MAX_HOPS` hops
+///
+/// Wire layout (little-endian, `SIZE` bytes):
+/// `exchange_time: u64 | [receive, parse, publish: u64; MAX_HOPS] | stages: [u8; MAX_HOPS] |
+/// hop_count: u8 | reserved | crc32: u32 | magic: u32`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LatencyTrailer {
+    pub exchange_time: u64,
+    hops: [HopTimestamps; MAX_HOPS],
+    hop_count: u8,
+}
+
+impl LatencyTrailer {
+    pub const SIZE: usize = 128;
+
+    const STAGES_OFFSET: usize = 8 + MAX_HOPS * 24;
+    const HOP_COUNT_OFFSET: usize = Self::STAGES_OFFSET + MAX_HOPS;
+    const CRC_OFFSET: usize = Self::SIZE - 8;
+    const MAGIC_OFFSET: usize = Self::SIZE - 4;
+
+    pub fn new(exchange_time: u64) -> Self {
+        Self { exchange_time, ..Default::default() }
+    }
+
+    /// Append a hop; returns false (and drops the hop) when the trailer is full
+    pub fn push_hop(&mut self, hop: HopTimestamps) -> bool {
+        let count = self.hop_count as usize;
+        if count == MAX_HOPS {
+            return false;
+        }
+        self.hops[count] = hop;
+        self.hop_count += 1;
+        true
+    }
+
+    #[inline]
+    pub fn hops(&self) -> &[HopTimestamps] {
+        &self.hops[..self.hop_count as usize]
+    }
+
+    /// Latest publish time on the path, or the exchange time if no hop has published yet
+    pub fn last_publish_time(&self) -> u64 {
+        self.hops().iter().rev().map(|hop| hop.publish_time).find(|&time| time != 0).unwrap_or(self.exchange_time)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.exchange_time.to_le_bytes());
+
+        for (i, hop) in self.hops.iter().enumerate() {
+            let offset = 8 + i * 24;
+            bytes[offset..offset + 8].copy_from_slice(&hop.receive_time.to_le_bytes());
+            bytes[offset + 8..offset + 16].copy_from_slice(&hop.parse_time.to_le_bytes());
+            bytes[offset + 16..offset + 24].copy_from_slice(&hop.publish_time.to_le_bytes());
+            bytes[Self::STAGES_OFFSET + i] = hop.stage as u8;
+        }
+        bytes[Self::HOP_COUNT_OFFSET] = self.hop_count;
+
+        let crc = checksum::calculate_crc32c(&bytes[..Self::CRC_OFFSET]);
+        bytes[Self::CRC_OFFSET..Self::MAGIC_OFFSET].copy_from_slice(&crc.to_le_bytes());
+        bytes[Self::MAGIC_OFFSET..].copy_from_slice(&TRAILER_MAGIC.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::BufferTooSmall { required: Self::SIZE, actual: bytes.len() });
+        }
+        let bytes = &bytes[..Self::SIZE];
+
+        let magic = u32::from_le_bytes(bytes[Self::MAGIC_OFFSET..].try_into().unwrap());
+        if magic != TRAILER_MAGIC {
+            return Err(ProtocolError::InvalidHeader { byte: bytes[Self::MAGIC_OFFSET] });
+        }
+
+        let expected = u32::from_le_bytes(bytes[Self::CRC_OFFSET..Self::MAGIC_OFFSET].try_into().unwrap());
+        let actual = checksum::calculate_crc32c(&bytes[..Self::CRC_OFFSET]);
+        if expected != actual {
+            return Err(ProtocolError::InvalidChecksum { expected, actual });
+        }
+
+        let hop_count = bytes[Self::HOP_COUNT_OFFSET];
+        if hop_count as usize > MAX_HOPS {
+            return Err(ProtocolError::InvalidLength { expected: MAX_HOPS, actual: hop_count as usize });
+        }
+
+        let read_u64 = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let mut trailer = Self::new(read_u64(0));
+        for i in 0..hop_count as usize {
+            let stage_byte = bytes[Self::STAGES_OFFSET + i];
+            let stage = PipelineStage::from_u8(stage_byte).ok_or(ProtocolError::InvalidHeader { byte: stage_byte })?;
+            let offset = 8 + i * 24;
+            trailer.push_hop(HopTimestamps::new(stage, read_u64(offset), read_u64(offset + 8), read_u64(offset + 16)));
+        }
+        Ok(trailer)
+    }
+
+    /// Append the encoded trailer to an outgoing frame
+    pub fn append_to(&self, frame: &mut Vec<u8>) {
+        frame.extend_from_slice(&self.to_bytes());
+    }
+}
+
+/// Split a received frame into its payload and trailer, if one is present
+///
+/// Must run before decoding messages whose length is implied by the frame, such
+/// as `OrderBookBatchMessage`, which reads its CRC from the last 4 bytes.
+pub fn split_trailer(frame: &[u8]) -> (&[u8], Option<LatencyTrailer>) {
+    let Some(split) = frame.len().checked_sub(LatencyTrailer::SIZE) else {
+        return (frame, None);
+    };
+    match LatencyTrailer::from_bytes(&frame[split..]) {
+        Ok(trailer) => (&frame[..split], Some(trailer)),
+        Err(_) => (frame, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CompressedString;
+    use crate::Exchange;
+    use crate::OrderBookBatchMessage;
+    use crate::messages::UpdateType;
+
+    #[test]
+    fn test_trailer_roundtrip() {
+        let mut trailer = LatencyTrailer::new(1_000);
+        assert!(trailer.push_hop(HopTimestamps::new(PipelineStage::Collector, 2_000, 2_500, 3_000)));
+        assert!(trailer.push_hop(HopTimestamps::new(PipelineStage::Strategy, 4_000, 4_100, 0)));
+
+        let decoded = LatencyTrailer::from_bytes(&trailer.to_bytes()).unwrap();
+        assert_eq!(decoded, trailer);
+        assert_eq!(decoded.hops()[1].stage, PipelineStage::Strategy);
+        assert_eq!(decoded.last_publish_time(), 3_000);
+
+        for stage in [PipelineStage::Pricing, PipelineStage::Simulator] {
+            assert!(trailer.push_hop(HopTimestamps::new(stage, 1, 1, 1)));
+        }
+        assert!(!trailer.push_hop(HopTimestamps::default()));
+    }
+
+    #[test]
+    fn test_split_trailer_from_batch_frame() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let mut batch = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Update, symbol, encoding, 1);
+        batch.add_bid(100, 1);
+        let payload = batch.to_bytes();
+
+        let (untouched, none) = split_trailer(&payload);
+        assert_eq!(untouched.len(), payload.len());
+        assert!(none.is_none());
+
+        let mut frame = payload.clone();
+        LatencyTrailer::new(42).append_to(&mut frame);
+        let (stripped, trailer) = split_trailer(&frame);
+        assert_eq!(trailer.unwrap().exchange_time, 42);
+        assert_eq!(OrderBookBatchMessage::from_bytes(stripped).unwrap().bids().len(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_trailer_is_ignored() {
+        let mut frame = vec![0u8; 16];
+        LatencyTrailer::new(42).append_to(&mut frame);
+        frame[20] ^= 0xFF;
+
+        assert!(matches!(LatencyTrailer::from_bytes(&frame[16..]), Err(ProtocolError::InvalidChecksum { .. })));
+        assert!(split_trailer(&frame).1.is_none());
+    }
+}
@@ -1,3 +1,20 @@
+//! Binary wire protocol shared by the market making processes
+//!
+//! # Message type IDs
+//!
+//! [`MessageType`] assigns every message an ID, carried in the envelope header.
+//! The first byte of a bare message (`header`) is laid out per message:
+//!
+//! - market data, pricing output, heartbeat and collector state pack flag bits into it
+//! - types 4-12 (trade, quote, position, fill and the order messages) put the ID in
+//!   the high nibble; nibbles 13-15 are unused
+//! - the order book batch (`0x03`), derivatives (15-18), control (19-22) and quote
+//!   ladder (23) messages store the full ID in the byte
+//!
+//! The schemes overlap, so peeking a nibble only identifies messages on a stream
+//! known to carry nibble-typed messages. Streams that mix schemes, such as the
+//! derivatives and control streams, are enveloped and must be read with [`decode_any`].
+
 use tikv_jemallocator::Jemalloc;
 
 #[global_allocator]
@@ -5,11 +22,22 @@ static GLOBAL: Jemalloc = Jemalloc;
 
 pub mod checksum;
 pub mod compressed_string;
+pub mod control_messages;
+pub mod derivatives_messages;
+pub mod envelope;
 pub mod errors;
 pub mod fixed_point;
+pub mod instrument;
+pub mod journal;
+pub mod latency;
 pub mod messages;
+pub mod order_messages;
 pub mod orderbook_message;
+pub mod quote_ladder;
+pub mod sequence;
 pub mod serde_helpers;
+pub mod varint;
+pub mod views;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod simd_x86;
@@ -18,23 +46,96 @@ pub mod simd_x86;
 pub mod simd_arm;
 
 pub use compressed_string::CompressedString;
+pub use control_messages::KillSwitchMessage;
+pub use control_messages::ResumeMessage;
+pub use control_messages::RiskEventMessage;
+pub use control_messages::StrategyParamUpdateMessage;
+pub use derivatives_messages::FundingRateMessage;
+pub use derivatives_messages::LiquidationMessage;
+pub use derivatives_messages::MarkPriceMessage;
+pub use derivatives_messages::OpenInterestMessage;
+pub use envelope::AnyMessage;
+pub use envelope::EnvelopeHeader;
+pub use envelope::MessageHandler;
+pub use envelope::MessageType;
+pub use envelope::decode_any;
 pub use errors::ProtocolError;
 pub use fixed_point::DECIMAL_PLACES;
 pub use fixed_point::FIXED_POINT_MULTIPLIER;
+pub use fixed_point::Scale;
 pub use fixed_point::from_fixed_point;
+pub use fixed_point::from_fixed_point_scaled;
 pub use fixed_point::parse_json_decimal_to_fixed_point;
+pub use fixed_point::parse_json_decimal_to_fixed_point_scaled;
+pub use fixed_point::rescale;
 pub use fixed_point::to_fixed_point;
+pub use fixed_point::to_fixed_point_scaled;
+pub use instrument::ContractType;
+pub use instrument::InstrumentConfig;
+pub use instrument::InstrumentInfo;
+pub use instrument::InstrumentRegistry;
+pub use latency::LatencyTrailer;
 pub use messages::CollectorState;
 pub use messages::CollectorStateMessage;
 pub use messages::HeartbeatMessage;
 pub use messages::MarketDataMessage;
 pub use messages::PricingOutputMessage;
+pub use order_messages::CancelOrderMessage;
+pub use order_messages::NewOrderMessage;
+pub use order_messages::OrderAckMessage;
+pub use order_messages::OrderRejectMessage;
+pub use order_messages::ReplaceOrderMessage;
 pub use orderbook_message::OrderBookBatchMessage;
 pub use orderbook_message::PriceLevel;
+pub use quote_ladder::QuoteLadderMessage;
+pub use sequence::SequenceEvent;
+pub use sequence::SequenceTracker;
+pub use views::MarketDataMessageRef;
+pub use views::OrderBookBatchView;
 
-pub const PROTOCOL_VERSION: u16 = 0x0200;
+/// Wire protocol version; the high byte is the schema version stamped into every message
+///
+/// - 2: original unversioned layouts with 8-bit sequences
+/// - 3: u64 sequences, message envelope and stamped schema version
+/// - 4: delta/varint level encoding for order book batches
+/// - 5: variable-length quote ladder messages
+/// - 6: fee, rebate and funding totals on position messages (80 -> 112 bytes)
+pub const PROTOCOL_VERSION: u16 = 0x0600;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Layout version stamped into every message; bump on any wire layout change
+pub const SCHEMA_VERSION: u8 = (PROTOCOL_VERSION >> 8) as u8;
+
+/// Oldest schema version decoders still accept
+///
+/// Raise this only when an older layout can no longer be decoded by the current code.
+pub const MIN_SCHEMA_VERSION: u8 = 3;
+
+/// Byte offset of the schema version in every fixed-size message
+pub const SCHEMA_VERSION_OFFSET: usize = 7;
+
+/// Accept `version` if this build can decode it
+#[inline]
+pub fn check_schema_version(version: u8) -> errors::Result<()> {
+    if version > SCHEMA_VERSION {
+        return Err(ProtocolError::UnsupportedSchemaVersion { version, max: SCHEMA_VERSION });
+    }
+    if version < MIN_SCHEMA_VERSION {
+        return Err(ProtocolError::IncompatibleSchemaVersion { version, min: MIN_SCHEMA_VERSION });
+    }
+    Ok(())
+}
+
+/// Like [`check_schema_version`], for a message whose layout last changed at `min`
+#[inline]
+pub fn check_schema_version_since(version: u8, min: u8) -> errors::Result<()> {
+    check_schema_version(version)?;
+    if version < min {
+        return Err(ProtocolError::IncompatibleSchemaVersion { version, min });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum Exchange {
     Binance = 0,
@@ -65,13 +166,34 @@ impl Exchange {
             _ => None,
         }
     }
+
+    /// Parse a venue name, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "binance" => Some(Exchange::Binance),
+            "coinbase" => Some(Exchange::Coinbase),
+            "kraken" => Some(Exchange::Kraken),
+            "bybit" => Some(Exchange::Bybit),
+            "okx" => Some(Exchange::Okx),
+            "bitfinex" => Some(Exchange::Bitfinex),
+            "kucoin" => Some(Exchange::KuCoin),
+            "huobi" => Some(Exchange::Huobi),
+            "gateio" | "gate.io" => Some(Exchange::GateIo),
+            "bitget" => Some(Exchange::Bitget),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValidationLevel {
+    /// Length only
     None,
+    /// Schema version, message type and exchange
     Basic,
+    /// Basic plus CRC32C
     Standard,
+    /// Standard plus exact frame length
     Strict,
 }
 
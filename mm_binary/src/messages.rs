@@ -1,4 +1,8 @@
 use crate::Exchange;
+use crate::SCHEMA_VERSION;
+use crate::SCHEMA_VERSION_OFFSET;
+use crate::check_schema_version;
+use crate::check_schema_version_since;
 use crate::compressed_string::CompressedString;
 use crate::compressed_string::EncodingScheme;
 use crate::errors::ProtocolError;
@@ -8,8 +12,9 @@ use crate::errors::Result;
 #[derive(Debug, Clone, Copy)]
 pub struct MarketDataMessage {
     pub header: u8,
-    pub sequence: u8,
     pub _pad: [u8; 6],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -25,8 +30,9 @@ pub struct MarketDataMessage {
 #[derive(Debug, Clone, Copy)]
 pub struct PricingOutputMessage {
     pub header: u8,
-    pub sequence: u8,
     pub _pad: [u8; 6],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -41,7 +47,8 @@ pub struct PricingOutputMessage {
 #[derive(Debug, Clone, Copy)]
 pub struct HeartbeatMessage {
     pub header: u8,
-    pub _pad: [u8; 7],
+    pub _pad: [u8; 6],
+    pub version: u8,
     pub timestamp: u64,
     pub sequence: u64,
     pub crc32: u32,
@@ -54,7 +61,8 @@ pub struct CollectorStateMessage {
     pub header: u8,
     pub connection_id: u8,
     pub state: u8,
-    pub _pad: [u8; 5],
+    pub _pad: [u8; 4],
+    pub version: u8,
     pub timestamp: u64,
     pub messages_received: u64,
     pub crc32: u32,
@@ -62,14 +70,17 @@ pub struct CollectorStateMessage {
 }
 
 /// Trade message for tracking individual trades
+///
+/// Carries no scale: price and quantity are always at `Scale::DEFAULT`.
 #[repr(C, align(16))]
 #[derive(Debug, Clone, Copy)]
 pub struct TradeMessage {
     pub header: u8,
     pub side: u8,         // TradeSide
     pub is_aggressor: u8, // 0 = maker, 1 = taker
-    pub sequence: u8,
     pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -77,7 +88,7 @@ pub struct TradeMessage {
     pub price: i64,
     pub quantity: i64,
     pub crc32: u32,
-    pub _final_pad: [u8; 4],
+    pub _final_pad: [u8; 12],
 }
 
 /// Quote message for strategy output
@@ -86,8 +97,9 @@ pub struct TradeMessage {
 pub struct QuoteMessage {
     pub header: u8,
     pub strategy_id: u8,
-    pub sequence: u8,
     pub _pad: [u8; 5],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -107,8 +119,9 @@ pub struct QuoteMessage {
 #[derive(Debug, Clone, Copy)]
 pub struct PositionMessage {
     pub header: u8,
-    pub sequence: u8,
     pub _pad: [u8; 6],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -116,6 +129,12 @@ pub struct PositionMessage {
     pub avg_entry_price: i64,
     pub unrealized_pnl: i64,
     pub realized_pnl: i64,
+    /// Cumulative fees paid to the venue (fixed-point, non-negative)
+    pub fees_paid: i64,
+    /// Cumulative maker rebates earned (fixed-point, non-negative)
+    pub rebates_earned: i64,
+    /// Cumulative funding received, negative when paid (fixed-point)
+    pub funding_pnl: i64,
     pub crc32: u32,
     pub _final_pad: [u8; 12],
 }
@@ -127,8 +146,9 @@ pub struct OrderFillMessage {
     pub header: u8,
     pub side: u8,     // OrderSide
     pub is_maker: u8, // 0 = taker, 1 = maker
-    pub sequence: u8,
     pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
     pub symbol_low: u64,
     pub symbol_high: u64,
     pub timestamp: u64,
@@ -136,7 +156,7 @@ pub struct OrderFillMessage {
     pub fill_price: i64,
     pub fill_quantity: i64,
     pub crc32: u32,
-    pub _final_pad: [u8; 4],
+    pub _final_pad: [u8; 12],
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -170,7 +190,7 @@ pub enum OrderSide {
 }
 
 impl MarketDataMessage {
-    pub const SIZE: usize = 72;
+    pub const SIZE: usize = 80;
 
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -198,7 +218,7 @@ impl MarketDataMessage {
         ask_price: i64,
         bid_size: i64,
         ask_size: i64,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 0 << 7;
@@ -208,8 +228,9 @@ impl MarketDataMessage {
 
         let mut msg = MarketDataMessage {
             header,
-            sequence,
             _pad: [0; 6],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -280,7 +301,7 @@ impl MarketDataMessage {
     }
 
     fn calculate_crc32(&self) -> u32 {
-        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 64) };
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 72) };
         crate::checksum::calculate_crc32c(bytes)
     }
 
@@ -307,6 +328,8 @@ impl MarketDataMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -330,7 +353,7 @@ impl MarketDataMessage {
 }
 
 impl PricingOutputMessage {
-    pub const SIZE: usize = 72;
+    pub const SIZE: usize = 80;
 
     pub fn new(
         strategy_id: u8,
@@ -353,7 +376,7 @@ impl PricingOutputMessage {
         fair_value: i64,
         confidence_score: i64,
         volatility: i64,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 1 << 7;
@@ -362,8 +385,9 @@ impl PricingOutputMessage {
 
         let mut msg = PricingOutputMessage {
             header,
-            sequence,
             _pad: [0; 6],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -427,7 +451,7 @@ impl PricingOutputMessage {
     }
 
     fn calculate_crc32(&self) -> u32 {
-        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 64) };
         crate::checksum::calculate_crc32c(bytes)
     }
 
@@ -454,6 +478,8 @@ impl PricingOutputMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -482,7 +508,8 @@ impl HeartbeatMessage {
         let mut header = 0u8;
         header |= 2 << 7; // Message type 2 for heartbeat
 
-        let mut msg = HeartbeatMessage { header, _pad: [0; 7], timestamp, sequence, crc32: 0, _final_pad: [0; 12] };
+        let mut msg =
+            HeartbeatMessage { header, _pad: [0; 6], version: SCHEMA_VERSION, timestamp, sequence, crc32: 0, _final_pad: [0; 12] };
 
         msg.crc32 = msg.calculate_crc32();
         msg
@@ -537,6 +564,8 @@ impl HeartbeatMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -565,7 +594,8 @@ impl CollectorStateMessage {
             header,
             connection_id,
             state: state as u8,
-            _pad: [0; 5],
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
             timestamp,
             messages_received,
             crc32: 0,
@@ -637,6 +667,8 @@ impl CollectorStateMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -655,7 +687,7 @@ impl CollectorStateMessage {
 }
 
 impl TradeMessage {
-    pub const SIZE: usize = 72;
+    pub const SIZE: usize = 80;
 
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -683,7 +715,7 @@ impl TradeMessage {
         quantity: i64,
         side: TradeSide,
         is_aggressor: bool,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 4 << 4; // Message type 4
@@ -694,8 +726,9 @@ impl TradeMessage {
             header,
             side: side as u8,
             is_aggressor: is_aggressor as u8,
-            sequence,
             _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -703,7 +736,7 @@ impl TradeMessage {
             price,
             quantity,
             crc32: 0,
-            _final_pad: [0; 4],
+            _final_pad: [0; 12],
         };
 
         msg.crc32 = msg.calculate_crc32();
@@ -752,10 +785,10 @@ impl TradeMessage {
 
     fn calculate_crc32(&self) -> u32 {
         // Calculate CRC over all fields BEFORE the crc32 field
-        // Fields: header(1) + side(1) + is_aggressor(1) + sequence(1) + _pad(4) +
+        // Fields: header(1) + side(1) + is_aggressor(1) + _pad(5) + sequence(8) +
         //         symbol_low(8) + symbol_high(8) + timestamp(8) + trade_id(8) +
-        //         price(8) + quantity(8) = 56 bytes
-        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        //         price(8) + quantity(8) = 64 bytes
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 64) };
         crate::checksum::calculate_crc32c(bytes)
     }
 
@@ -783,6 +816,8 @@ impl TradeMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         // Check if aligned, if not, copy to aligned buffer
         if (bytes.as_ptr() as usize).is_multiple_of(16) {
             // Fast path: already aligned
@@ -792,9 +827,9 @@ impl TradeMessage {
         } else {
             // Slow path: copy to aligned buffer
             #[repr(C, align(16))]
-            struct AlignedBuffer([u8; 72]);
+            struct AlignedBuffer([u8; 80]);
 
-            let mut aligned = AlignedBuffer([0u8; 72]);
+            let mut aligned = AlignedBuffer([0u8; 80]);
             aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
 
             let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
@@ -805,7 +840,7 @@ impl TradeMessage {
 }
 
 impl QuoteMessage {
-    pub const SIZE: usize = 104;
+    pub const SIZE: usize = 112;
 
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -850,7 +885,7 @@ impl QuoteMessage {
         fair_value: i64,
         inventory: i64,
         confidence: i64,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 5 << 4; // Message type 5
@@ -859,8 +894,9 @@ impl QuoteMessage {
         let mut msg = QuoteMessage {
             header,
             strategy_id,
-            sequence,
             _pad: [0; 5],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -909,7 +945,7 @@ impl QuoteMessage {
     }
 
     fn calculate_crc32(&self) -> u32 {
-        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 88) };
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 96) };
         crate::checksum::calculate_crc32c(bytes)
     }
 
@@ -936,6 +972,8 @@ impl QuoteMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -946,8 +984,17 @@ impl QuoteMessage {
     }
 }
 
+/// Clamp a widened sum back into the `i64` range
+#[inline]
+fn saturate_i128(value: i128) -> i64 {
+    value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
 impl PositionMessage {
-    pub const SIZE: usize = 72;
+    pub const SIZE: usize = 112;
+
+    /// Oldest schema version with this layout; earlier frames are 80 bytes without cost totals
+    pub const MIN_VERSION: u8 = 6;
 
     pub fn new(
         symbol: CompressedString,
@@ -970,7 +1017,7 @@ impl PositionMessage {
         avg_entry_price: i64,
         unrealized_pnl: i64,
         realized_pnl: i64,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 6 << 4; // Message type 6
@@ -978,8 +1025,9 @@ impl PositionMessage {
 
         let mut msg = PositionMessage {
             header,
-            sequence,
             _pad: [0; 6],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -987,6 +1035,9 @@ impl PositionMessage {
             avg_entry_price,
             unrealized_pnl,
             realized_pnl,
+            fees_paid: 0,
+            rebates_earned: 0,
+            funding_pnl: 0,
             crc32: 0,
             _final_pad: [0; 12],
         };
@@ -995,6 +1046,28 @@ impl PositionMessage {
         msg
     }
 
+    /// Attach the fee, rebate and funding totals
+    pub fn with_costs(mut self, fees_paid: i64, rebates_earned: i64, funding_pnl: i64) -> Self {
+        self.fees_paid = fees_paid;
+        self.rebates_earned = rebates_earned;
+        self.funding_pnl = funding_pnl;
+        self.crc32 = self.calculate_crc32();
+        self
+    }
+
+    /// Trading PnL before fees, rebates and funding (fixed-point), saturating at the `i64` range
+    #[inline]
+    pub fn gross_pnl(&self) -> i64 {
+        saturate_i128(self.realized_pnl as i128 + self.unrealized_pnl as i128)
+    }
+
+    /// Gross PnL less fees, plus rebates and funding (fixed-point), saturating at the `i64` range
+    #[inline]
+    pub fn net_pnl(&self) -> i64 {
+        let gross = self.realized_pnl as i128 + self.unrealized_pnl as i128;
+        saturate_i128(gross - self.fees_paid as i128 + self.rebates_earned as i128 + self.funding_pnl as i128)
+    }
+
     #[inline]
     pub fn message_type(&self) -> u8 {
         (self.header >> 4) & 0xF
@@ -1025,7 +1098,7 @@ impl PositionMessage {
     }
 
     fn calculate_crc32(&self) -> u32 {
-        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 56) };
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 96) };
         crate::checksum::calculate_crc32c(bytes)
     }
 
@@ -1048,6 +1121,11 @@ impl PositionMessage {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        // Check the version first so an older, shorter layout is reported as such
+        if let Some(&version) = bytes.get(SCHEMA_VERSION_OFFSET) {
+            check_schema_version_since(version, Self::MIN_VERSION)?;
+        }
+
         if bytes.len() < Self::SIZE {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
@@ -1063,7 +1141,7 @@ impl PositionMessage {
 }
 
 impl OrderFillMessage {
-    pub const SIZE: usize = 72;
+    pub const SIZE: usize = 80;
 
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -1091,7 +1169,7 @@ impl OrderFillMessage {
         fill_quantity: i64,
         side: OrderSide,
         is_maker: bool,
-        sequence: u8,
+        sequence: u64,
     ) -> Self {
         let mut header = 0u8;
         header |= 7 << 4; // Message type 7
@@ -1102,8 +1180,9 @@ impl OrderFillMessage {
             header,
             side: side as u8,
             is_maker: is_maker as u8,
-            sequence,
             _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
             symbol_low: symbol.low,
             symbol_high: symbol.high,
             timestamp,
@@ -1111,7 +1190,7 @@ impl OrderFillMessage {
             fill_price,
             fill_quantity,
             crc32: 0,
-            _final_pad: [0; 4],
+            _final_pad: [0; 12],
         };
 
         msg.crc32 = msg.calculate_crc32();
@@ -1186,6 +1265,8 @@ impl OrderFillMessage {
             return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
         if !(bytes.as_ptr() as usize).is_multiple_of(16) {
             return Err(ProtocolError::InvalidAlignment { address: bytes.as_ptr() as usize });
         }
@@ -1214,6 +1295,51 @@ mod tests {
         assert!(size == 72 || size == 80);
     }
 
+    #[test]
+    fn test_message_sizes_match_layout() {
+        assert_eq!(std::mem::size_of::<MarketDataMessage>(), MarketDataMessage::SIZE);
+        assert_eq!(std::mem::size_of::<PricingOutputMessage>(), PricingOutputMessage::SIZE);
+        assert_eq!(std::mem::size_of::<TradeMessage>(), TradeMessage::SIZE);
+        assert_eq!(std::mem::size_of::<QuoteMessage>(), QuoteMessage::SIZE);
+        assert_eq!(std::mem::size_of::<PositionMessage>(), PositionMessage::SIZE);
+        assert_eq!(std::mem::size_of::<OrderFillMessage>(), OrderFillMessage::SIZE);
+    }
+
+    #[repr(C, align(16))]
+    struct Aligned<const N: usize>([u8; N]);
+
+    #[test]
+    fn test_wide_sequence_roundtrip() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let sequence = u64::from(u8::MAX) + 1_000;
+
+        let fill = OrderFillMessage::new_with_sequence(Exchange::Binance, symbol, encoding, 1, 2, 3, 4, OrderSide::Ask, true, sequence);
+        assert_eq!(OrderFillMessage::from_bytes(&Aligned(fill.to_bytes()).0).unwrap().sequence, sequence);
+
+        let position = PositionMessage::new_with_sequence(symbol, encoding, 1, 2, 3, 4, 5, sequence).with_costs(6, 7, -8);
+        let decoded = PositionMessage::from_bytes(&Aligned(position.to_bytes()).0).unwrap();
+        assert_eq!(decoded.sequence, sequence);
+        assert_eq!(decoded.realized_pnl, 5);
+        assert_eq!((decoded.gross_pnl(), decoded.net_pnl()), (9, 2));
+
+        // Totals near the limits saturate instead of wrapping
+        let extreme = PositionMessage::new(symbol, encoding, 1, 0, 0, i64::MAX, 1).with_costs(0, 0, 0);
+        assert_eq!(extreme.gross_pnl(), i64::MAX);
+        let extreme = extreme.with_costs(-1, 0, i64::MAX);
+        assert_eq!(extreme.net_pnl(), i64::MAX);
+        let loss = PositionMessage::new(symbol, encoding, 1, 0, 0, i64::MIN, -1).with_costs(i64::MAX, 0, 0);
+        assert_eq!((loss.gross_pnl(), loss.net_pnl()), (i64::MIN, i64::MIN));
+        // Only the final sum is clamped, so offsetting terms still cancel
+        let offset = PositionMessage::new(symbol, encoding, 1, 0, 0, i64::MAX, 1).with_costs(1, 0, 0);
+        assert_eq!(offset.net_pnl(), i64::MAX);
+
+        let trade = TradeMessage::new_with_sequence(Exchange::Binance, symbol, encoding, 1, 2, 3, 4, TradeSide::Buy, false, sequence);
+        assert_eq!(TradeMessage::from_bytes(&trade.to_bytes()).unwrap().sequence, sequence);
+
+        let quote = QuoteMessage::new_with_sequence(0, symbol, encoding, 1, 2, 3, 4, 5, 6, 7, 8, sequence);
+        assert_eq!(QuoteMessage::from_bytes(&Aligned(quote.to_bytes()).0).unwrap().sequence, sequence);
+    }
+
     #[test]
     fn test_message_alignment() {
         assert_eq!(std::mem::align_of::<MarketDataMessage>(), 16);
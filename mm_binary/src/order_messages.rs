@@ -0,0 +1,1219 @@
+use crate::Exchange;
+use crate::SCHEMA_VERSION;
+use crate::SCHEMA_VERSION_OFFSET;
+use crate::check_schema_version;
+use crate::compressed_string::CompressedString;
+use crate::compressed_string::EncodingScheme;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::messages::OrderSide;
+
+/// New order request from a strategy to an execution venue (simulator or gateway)
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct NewOrderMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub side: u8,          // OrderSide
+    pub time_in_force: u8, // TimeInForce
+    pub flags: u8,         // OrderFlags
+    pub strategy_id: u8,
+    pub _pad: [u8; 1],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub client_order_id: u64,
+    pub price: i64,
+    pub quantity: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 12],
+}
+
+/// Cancel request for a resting order, identified by its client order ID
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct CancelOrderMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub strategy_id: u8,
+    pub _pad: [u8; 4],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub client_order_id: u64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 12],
+}
+
+/// Cancel/replace request: atomically swaps a resting order for a new price and quantity
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct ReplaceOrderMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub side: u8,          // OrderSide
+    pub time_in_force: u8, // TimeInForce
+    pub flags: u8,         // OrderFlags
+    pub strategy_id: u8,
+    pub _pad: [u8; 1],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub orig_client_order_id: u64,
+    pub client_order_id: u64,
+    pub price: i64,
+    pub quantity: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+/// Acknowledgement of an accepted new, cancel or replace request
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct OrderAckMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub side: u8,     // OrderSide
+    pub ack_type: u8, // OrderRequestType
+    pub _pad: [u8; 3],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub client_order_id: u64,
+    pub exchange_order_id: u64,
+    pub price: i64,
+    pub leaves_quantity: i64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 4],
+}
+
+/// Rejection of a new, cancel or replace request
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct OrderRejectMessage {
+    pub header: u8,
+    pub exchange: u8,
+    pub reason: u8,           // RejectReason
+    pub rejected_request: u8, // OrderRequestType
+    pub _pad: [u8; 3],
+    pub version: u8,
+    pub sequence: u64,
+    pub symbol_low: u64,
+    pub symbol_high: u64,
+    pub timestamp: u64,
+    pub client_order_id: u64,
+    pub crc32: u32,
+    pub _final_pad: [u8; 12],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TimeInForce {
+    /// Good till cancelled
+    Gtc = 0,
+    /// Immediate or cancel
+    Ioc = 1,
+    /// Fill or kill
+    Fok = 2,
+}
+
+impl TimeInForce {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TimeInForce::Gtc),
+            1 => Some(TimeInForce::Ioc),
+            2 => Some(TimeInForce::Fok),
+            _ => None,
+        }
+    }
+}
+
+/// Order instruction bit flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OrderFlags(pub u8);
+
+impl OrderFlags {
+    pub const NONE: Self = OrderFlags(0);
+    /// Reject instead of taking liquidity
+    pub const POST_ONLY: Self = OrderFlags(1 << 0);
+    /// Only allowed to decrease the current position
+    pub const REDUCE_ONLY: Self = OrderFlags(1 << 1);
+
+    #[inline]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub fn is_post_only(self) -> bool {
+        self.contains(Self::POST_ONLY)
+    }
+
+    #[inline]
+    pub fn is_reduce_only(self) -> bool {
+        self.contains(Self::REDUCE_ONLY)
+    }
+}
+
+impl std::ops::BitOr for OrderFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        OrderFlags(self.0 | rhs.0)
+    }
+}
+
+/// Which request an ack or reject refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OrderRequestType {
+    New = 0,
+    Cancel = 1,
+    Replace = 2,
+}
+
+impl OrderRequestType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(OrderRequestType::New),
+            1 => Some(OrderRequestType::Cancel),
+            2 => Some(OrderRequestType::Replace),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RejectReason {
+    Unknown = 0,
+    InvalidPrice = 1,
+    InvalidQuantity = 2,
+    UnknownOrder = 3,
+    DuplicateClientOrderId = 4,
+    PostOnlyWouldCross = 5,
+    ReduceOnlyWouldIncrease = 6,
+    InsufficientBalance = 7,
+    RiskLimitExceeded = 8,
+    RateLimited = 9,
+    MarketClosed = 10,
+}
+
+impl RejectReason {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(RejectReason::Unknown),
+            1 => Some(RejectReason::InvalidPrice),
+            2 => Some(RejectReason::InvalidQuantity),
+            3 => Some(RejectReason::UnknownOrder),
+            4 => Some(RejectReason::DuplicateClientOrderId),
+            5 => Some(RejectReason::PostOnlyWouldCross),
+            6 => Some(RejectReason::ReduceOnlyWouldIncrease),
+            7 => Some(RejectReason::InsufficientBalance),
+            8 => Some(RejectReason::RiskLimitExceeded),
+            9 => Some(RejectReason::RateLimited),
+            10 => Some(RejectReason::MarketClosed),
+            _ => None,
+        }
+    }
+}
+
+#[inline]
+fn encoding_from_header(header: u8) -> EncodingScheme {
+    match header & 0x3 {
+        0 => EncodingScheme::Hex4Bit,
+        1 => EncodingScheme::Alphabetic5Bit,
+        2 => EncodingScheme::AlphaNumeric6Bit,
+        3 => EncodingScheme::Ascii7Bit,
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+fn order_side_from_u8(side: u8) -> Result<OrderSide> {
+    match side {
+        0 => Ok(OrderSide::Bid),
+        1 => Ok(OrderSide::Ask),
+        _ => Err(ProtocolError::InvalidOrderSide { side }),
+    }
+}
+
+impl NewOrderMessage {
+    pub const SIZE: usize = 80;
+    pub const MESSAGE_TYPE: u8 = 8;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        quantity: i64,
+        time_in_force: TimeInForce,
+        flags: OrderFlags,
+    ) -> Self {
+        Self::new_with_sequence(
+            exchange,
+            strategy_id,
+            symbol,
+            encoding,
+            timestamp,
+            client_order_id,
+            side,
+            price,
+            quantity,
+            time_in_force,
+            flags,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        quantity: i64,
+        time_in_force: TimeInForce,
+        flags: OrderFlags,
+        sequence: u64,
+    ) -> Self {
+        let mut header = 0u8;
+        header |= Self::MESSAGE_TYPE << 4; // Message type 8
+        header |= encoding as u8;
+
+        let mut msg = NewOrderMessage {
+            header,
+            exchange: exchange as u8,
+            side: side as u8,
+            time_in_force: time_in_force as u8,
+            flags: flags.0,
+            strategy_id,
+            _pad: [0; 1],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            client_order_id,
+            price,
+            quantity,
+            crc32: 0,
+            _final_pad: [0; 12],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header >> 4) & 0xF
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_header(self.header)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    #[inline]
+    pub fn order_side(&self) -> Result<OrderSide> {
+        order_side_from_u8(self.side)
+    }
+
+    #[inline]
+    pub fn time_in_force(&self) -> Result<TimeInForce> {
+        TimeInForce::from_u8(self.time_in_force).ok_or(ProtocolError::InvalidHeader { byte: self.time_in_force })
+    }
+
+    #[inline]
+    pub fn flags(&self) -> OrderFlags {
+        OrderFlags(self.flags)
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        self.order_side()?;
+        self.time_in_force()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 64) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; NewOrderMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl CancelOrderMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 9;
+
+    pub fn new(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, strategy_id, symbol, encoding, timestamp, client_order_id, 0)
+    }
+
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        sequence: u64,
+    ) -> Self {
+        let mut header = 0u8;
+        header |= Self::MESSAGE_TYPE << 4; // Message type 9
+        header |= encoding as u8;
+
+        let mut msg = CancelOrderMessage {
+            header,
+            exchange: exchange as u8,
+            strategy_id,
+            _pad: [0; 4],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            client_order_id,
+            crc32: 0,
+            _final_pad: [0; 12],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header >> 4) & 0xF
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_header(self.header)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 48) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; CancelOrderMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl ReplaceOrderMessage {
+    pub const SIZE: usize = 80;
+    pub const MESSAGE_TYPE: u8 = 10;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        orig_client_order_id: u64,
+        client_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        quantity: i64,
+        time_in_force: TimeInForce,
+        flags: OrderFlags,
+    ) -> Self {
+        Self::new_with_sequence(
+            exchange,
+            strategy_id,
+            symbol,
+            encoding,
+            timestamp,
+            orig_client_order_id,
+            client_order_id,
+            side,
+            price,
+            quantity,
+            time_in_force,
+            flags,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        strategy_id: u8,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        orig_client_order_id: u64,
+        client_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        quantity: i64,
+        time_in_force: TimeInForce,
+        flags: OrderFlags,
+        sequence: u64,
+    ) -> Self {
+        let mut header = 0u8;
+        header |= Self::MESSAGE_TYPE << 4; // Message type 10
+        header |= encoding as u8;
+
+        let mut msg = ReplaceOrderMessage {
+            header,
+            exchange: exchange as u8,
+            side: side as u8,
+            time_in_force: time_in_force as u8,
+            flags: flags.0,
+            strategy_id,
+            _pad: [0; 1],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            orig_client_order_id,
+            client_order_id,
+            price,
+            quantity,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header >> 4) & 0xF
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_header(self.header)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    #[inline]
+    pub fn order_side(&self) -> Result<OrderSide> {
+        order_side_from_u8(self.side)
+    }
+
+    #[inline]
+    pub fn time_in_force(&self) -> Result<TimeInForce> {
+        TimeInForce::from_u8(self.time_in_force).ok_or(ProtocolError::InvalidHeader { byte: self.time_in_force })
+    }
+
+    #[inline]
+    pub fn flags(&self) -> OrderFlags {
+        OrderFlags(self.flags)
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        self.order_side()?;
+        self.time_in_force()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 72) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; ReplaceOrderMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl OrderAckMessage {
+    pub const SIZE: usize = 80;
+    pub const MESSAGE_TYPE: u8 = 11;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        exchange: Exchange,
+        ack_type: OrderRequestType,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        exchange_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        leaves_quantity: i64,
+    ) -> Self {
+        Self::new_with_sequence(
+            exchange,
+            ack_type,
+            symbol,
+            encoding,
+            timestamp,
+            client_order_id,
+            exchange_order_id,
+            side,
+            price,
+            leaves_quantity,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        ack_type: OrderRequestType,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        exchange_order_id: u64,
+        side: OrderSide,
+        price: i64,
+        leaves_quantity: i64,
+        sequence: u64,
+    ) -> Self {
+        let mut header = 0u8;
+        header |= Self::MESSAGE_TYPE << 4; // Message type 11
+        header |= encoding as u8;
+
+        let mut msg = OrderAckMessage {
+            header,
+            exchange: exchange as u8,
+            side: side as u8,
+            ack_type: ack_type as u8,
+            _pad: [0; 3],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            client_order_id,
+            exchange_order_id,
+            price,
+            leaves_quantity,
+            crc32: 0,
+            _final_pad: [0; 4],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header >> 4) & 0xF
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_header(self.header)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    #[inline]
+    pub fn order_side(&self) -> Result<OrderSide> {
+        order_side_from_u8(self.side)
+    }
+
+    #[inline]
+    pub fn ack_type(&self) -> Result<OrderRequestType> {
+        OrderRequestType::from_u8(self.ack_type).ok_or(ProtocolError::InvalidHeader { byte: self.ack_type })
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        self.order_side()?;
+        self.ack_type()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 72) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; OrderAckMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+impl OrderRejectMessage {
+    pub const SIZE: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 12;
+
+    pub fn new(
+        exchange: Exchange,
+        rejected_request: OrderRequestType,
+        reason: RejectReason,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+    ) -> Self {
+        Self::new_with_sequence(exchange, rejected_request, reason, symbol, encoding, timestamp, client_order_id, 0)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_sequence(
+        exchange: Exchange,
+        rejected_request: OrderRequestType,
+        reason: RejectReason,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        client_order_id: u64,
+        sequence: u64,
+    ) -> Self {
+        let mut header = 0u8;
+        header |= Self::MESSAGE_TYPE << 4; // Message type 12
+        header |= encoding as u8;
+
+        let mut msg = OrderRejectMessage {
+            header,
+            exchange: exchange as u8,
+            reason: reason as u8,
+            rejected_request: rejected_request as u8,
+            _pad: [0; 3],
+            version: SCHEMA_VERSION,
+            sequence,
+            symbol_low: symbol.low,
+            symbol_high: symbol.high,
+            timestamp,
+            client_order_id,
+            crc32: 0,
+            _final_pad: [0; 12],
+        };
+
+        msg.crc32 = msg.calculate_crc32();
+        msg
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header >> 4) & 0xF
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.exchange).ok_or(ProtocolError::InvalidExchange { id: self.exchange })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_header(self.header)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.symbol_low, high: self.symbol_high }
+    }
+
+    #[inline]
+    pub fn reason(&self) -> Result<RejectReason> {
+        RejectReason::from_u8(self.reason).ok_or(ProtocolError::InvalidHeader { byte: self.reason })
+    }
+
+    #[inline]
+    pub fn rejected_request(&self) -> Result<OrderRequestType> {
+        OrderRequestType::from_u8(self.rejected_request).ok_or(ProtocolError::InvalidHeader { byte: self.rejected_request })
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        self.reason()?;
+        self.rejected_request()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = self.calculate_crc32();
+        if calculated != self.crc32 {
+            return Err(ProtocolError::InvalidChecksum { expected: self.crc32, actual: calculated });
+        }
+        Ok(())
+    }
+
+    fn calculate_crc32(&self) -> u32 {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, 48) };
+        crate::checksum::calculate_crc32c(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let bytes = unsafe { std::slice::from_raw_parts(self as *const _ as *const u8, Self::SIZE) };
+        let mut result = [0u8; Self::SIZE];
+        result.copy_from_slice(bytes);
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// - `bytes.len() >= Self::SIZE`
+    /// - `bytes.as_ptr()` is aligned to 16 bytes
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        debug_assert!(bytes.len() >= Self::SIZE);
+        debug_assert!((bytes.as_ptr() as usize).is_multiple_of(16));
+        unsafe { &*(bytes.as_ptr() as *const Self) }
+    }
+
+    /// Parse from bytes, handling unaligned buffers (Aeron doesn't guarantee 16-byte alignment)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: Self::SIZE, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[SCHEMA_VERSION_OFFSET])?;
+
+        #[repr(C, align(16))]
+        struct AlignedBuffer([u8; OrderRejectMessage::SIZE]);
+
+        let mut aligned = AlignedBuffer([0u8; Self::SIZE]);
+        aligned.0.copy_from_slice(&bytes[..Self::SIZE]);
+
+        let msg = unsafe { Self::from_bytes_unchecked(&aligned.0) };
+        msg.validate_checksum()?;
+        Ok(*msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fixed_point;
+
+    fn btcusdt() -> (CompressedString, EncodingScheme) {
+        CompressedString::from_str("BTCUSDT").unwrap()
+    }
+
+    #[test]
+    fn test_order_message_sizes() {
+        assert_eq!(std::mem::size_of::<NewOrderMessage>(), NewOrderMessage::SIZE);
+        assert_eq!(std::mem::size_of::<CancelOrderMessage>(), CancelOrderMessage::SIZE);
+        assert_eq!(std::mem::size_of::<ReplaceOrderMessage>(), ReplaceOrderMessage::SIZE);
+        assert_eq!(std::mem::size_of::<OrderAckMessage>(), OrderAckMessage::SIZE);
+        assert_eq!(std::mem::size_of::<OrderRejectMessage>(), OrderRejectMessage::SIZE);
+        assert_eq!(std::mem::align_of::<NewOrderMessage>(), 16);
+    }
+
+    #[test]
+    fn test_new_order_roundtrip() {
+        let (symbol, encoding) = btcusdt();
+        let msg = NewOrderMessage::new(
+            Exchange::Binance,
+            3,
+            symbol,
+            encoding,
+            1234567890,
+            42,
+            OrderSide::Ask,
+            to_fixed_point(50001.5),
+            to_fixed_point(0.25),
+            TimeInForce::Gtc,
+            OrderFlags::POST_ONLY | OrderFlags::REDUCE_ONLY,
+        );
+
+        let bytes = msg.to_bytes();
+        let decoded = NewOrderMessage::from_bytes(&bytes).unwrap();
+        decoded.validate_basic().unwrap();
+
+        assert_eq!(decoded.exchange().unwrap(), Exchange::Binance);
+        assert_eq!(decoded.strategy_id, 3);
+        assert_eq!(decoded.symbol().decode(decoded.encoding_scheme()), "BTCUSDT");
+        assert_eq!(decoded.client_order_id, 42);
+        assert_eq!(decoded.order_side().unwrap(), OrderSide::Ask);
+        assert_eq!(decoded.price, to_fixed_point(50001.5));
+        assert_eq!(decoded.quantity, to_fixed_point(0.25));
+        assert_eq!(decoded.time_in_force().unwrap(), TimeInForce::Gtc);
+        assert!(decoded.flags().is_post_only());
+        assert!(decoded.flags().is_reduce_only());
+    }
+
+    #[test]
+    fn test_cancel_and_replace_roundtrip() {
+        let (symbol, encoding) = btcusdt();
+
+        let cancel = CancelOrderMessage::new(Exchange::Binance, 1, symbol, encoding, 100, 7);
+        let decoded = CancelOrderMessage::from_bytes(&cancel.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.client_order_id, 7);
+
+        let replace = ReplaceOrderMessage::new(
+            Exchange::Binance,
+            1,
+            symbol,
+            encoding,
+            200,
+            7,
+            8,
+            OrderSide::Bid,
+            to_fixed_point(49999.0),
+            to_fixed_point(1.0),
+            TimeInForce::Ioc,
+            OrderFlags::NONE,
+        );
+        let decoded = ReplaceOrderMessage::from_bytes(&replace.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.orig_client_order_id, 7);
+        assert_eq!(decoded.client_order_id, 8);
+        assert_eq!(decoded.time_in_force().unwrap(), TimeInForce::Ioc);
+        assert!(!decoded.flags().is_post_only());
+    }
+
+    #[test]
+    fn test_ack_and_reject_roundtrip() {
+        let (symbol, encoding) = btcusdt();
+
+        let ack = OrderAckMessage::new(
+            Exchange::Binance,
+            OrderRequestType::New,
+            symbol,
+            encoding,
+            300,
+            42,
+            9001,
+            OrderSide::Bid,
+            to_fixed_point(50000.0),
+            to_fixed_point(0.5),
+        );
+        let decoded = OrderAckMessage::from_bytes(&ack.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.ack_type().unwrap(), OrderRequestType::New);
+        assert_eq!(decoded.exchange_order_id, 9001);
+        assert_eq!(decoded.leaves_quantity, to_fixed_point(0.5));
+
+        let reject =
+            OrderRejectMessage::new(Exchange::Binance, OrderRequestType::New, RejectReason::PostOnlyWouldCross, symbol, encoding, 400, 43);
+        let decoded = OrderRejectMessage::from_bytes(&reject.to_bytes()).unwrap();
+        decoded.validate_basic().unwrap();
+        assert_eq!(decoded.reason().unwrap(), RejectReason::PostOnlyWouldCross);
+        assert_eq!(decoded.rejected_request().unwrap(), OrderRequestType::New);
+        assert_eq!(decoded.client_order_id, 43);
+    }
+
+    #[test]
+    fn test_order_message_corruption_detected() {
+        let (symbol, encoding) = btcusdt();
+        let msg = NewOrderMessage::new(
+            Exchange::Binance,
+            0,
+            symbol,
+            encoding,
+            1,
+            1,
+            OrderSide::Bid,
+            to_fixed_point(100.0),
+            to_fixed_point(1.0),
+            TimeInForce::Gtc,
+            OrderFlags::NONE,
+        );
+
+        let mut bytes = msg.to_bytes();
+        bytes[48] ^= 0x01; // flip a bit in the price
+        assert!(matches!(NewOrderMessage::from_bytes(&bytes), Err(ProtocolError::InvalidChecksum { .. })));
+
+        assert!(matches!(NewOrderMessage::from_bytes(&bytes[..10]), Err(ProtocolError::InvalidLength { .. })));
+    }
+
+    #[test]
+    fn test_invalid_side_rejected() {
+        let (symbol, encoding) = btcusdt();
+        let mut msg = NewOrderMessage::new(
+            Exchange::Binance,
+            0,
+            symbol,
+            encoding,
+            1,
+            1,
+            OrderSide::Ask,
+            to_fixed_point(100.0),
+            to_fixed_point(1.0),
+            TimeInForce::Gtc,
+            OrderFlags::NONE,
+        );
+        msg.side = 2;
+        msg.crc32 = msg.calculate_crc32();
+
+        // The checksum is valid, so only the field check catches it
+        let decoded = NewOrderMessage::from_bytes(&msg.to_bytes()).unwrap();
+        assert!(matches!(decoded.order_side(), Err(ProtocolError::InvalidOrderSide { side: 2 })));
+        assert!(matches!(decoded.validate_basic(), Err(ProtocolError::InvalidOrderSide { side: 2 })));
+    }
+
+    #[test]
+    fn test_unaligned_order_message() {
+        let (symbol, encoding) = btcusdt();
+        let msg = CancelOrderMessage::new(Exchange::Okx, 2, symbol, encoding, 5, 99);
+
+        let mut buffer = [0u8; CancelOrderMessage::SIZE + 1];
+        buffer[1..].copy_from_slice(&msg.to_bytes());
+
+        let decoded = CancelOrderMessage::from_bytes(&buffer[1..]).unwrap();
+        assert_eq!(decoded.exchange().unwrap(), Exchange::Okx);
+        assert_eq!(decoded.client_order_id, 99);
+    }
+
+    #[test]
+    fn test_reject_reason_values() {
+        assert_eq!(RejectReason::from_u8(5), Some(RejectReason::PostOnlyWouldCross));
+        assert_eq!(RejectReason::from_u8(10), Some(RejectReason::MarketClosed));
+        assert_eq!(RejectReason::from_u8(11), None);
+        assert_eq!(TimeInForce::from_u8(3), None);
+    }
+}
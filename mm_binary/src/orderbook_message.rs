@@ -1,10 +1,14 @@
 use crate::CompressedString;
 use crate::Exchange;
+use crate::SCHEMA_VERSION;
+use crate::check_schema_version;
 use crate::checksum;
 use crate::compressed_string::EncodingScheme;
 use crate::errors::ProtocolError;
 use crate::errors::Result;
+use crate::fixed_point::Scale;
 use crate::messages::UpdateType;
+use crate::varint;
 
 /// Header for orderbook batch messages
 #[repr(C, packed)]
@@ -34,6 +38,18 @@ pub struct OrderBookBatchHeader {
     pub final_update_id: u64,
     /// Previous update ID for continuity checking (Binance pu field)
     pub prev_update_id: u64,
+    /// Per-stream publisher sequence number
+    pub sequence: u64,
+    /// Schema version of this layout
+    pub version: u8,
+    /// Encoding flags (`FLAG_DELTA_ENCODED`, `FLAG_SCALED`)
+    pub flags: u8,
+    /// Decimal places of level prices (meaningful only with `FLAG_SCALED`)
+    pub price_scale: u8,
+    /// Decimal places of level sizes (meaningful only with `FLAG_SCALED`)
+    pub qty_scale: u8,
+    /// Registry instrument ID (0 = unassigned, resolve by symbol)
+    pub instrument_id: u32,
 }
 
 /// Single price level (bid or ask)
@@ -72,15 +88,70 @@ impl PriceLevel {
     }
 }
 
+/// Read the tick size that prefixes a delta-encoded body
+pub(crate) fn read_delta_tick(bytes: &[u8], offset: &mut usize) -> Result<i64> {
+    let start = *offset;
+    let tick = varint::read_varint(bytes, offset)?;
+    if tick == 0 || tick > i64::MAX as u64 {
+        return Err(ProtocolError::InvalidVarint { offset: start });
+    }
+    Ok(tick as i64)
+}
+
+/// Read one delta-encoded level; `anchor` is the side's first price once it has been read
+#[inline]
+pub(crate) fn read_delta_level(bytes: &[u8], offset: &mut usize, anchor: Option<i64>, tick: i64) -> Result<PriceLevel> {
+    let start = *offset;
+    let raw_price = varint::read_zigzag(bytes, offset)?;
+    let price = match anchor {
+        None => raw_price,
+        Some(first) => {
+            raw_price.checked_mul(tick).and_then(|delta| first.checked_add(delta)).ok_or(ProtocolError::InvalidVarint { offset: start })?
+        }
+    };
+    let size = varint::read_zigzag(bytes, offset)?;
+    Ok(PriceLevel::new(price, size))
+}
+
+/// Scale declared by a header byte, or the default when the frame is unscaled
+#[inline]
+pub(crate) fn header_scale(flags: u8, decimals: u8) -> Scale {
+    if flags & OrderBookBatchMessage::FLAG_SCALED == 0 {
+        return Scale::DEFAULT;
+    }
+    Scale::new(decimals).unwrap_or_default()
+}
+
+fn read_delta_side(bytes: &[u8], offset: &mut usize, count: usize, tick: i64) -> Result<Vec<PriceLevel>> {
+    // Each level takes at least two bytes; don't trust the count for the allocation
+    let mut levels = Vec::with_capacity(count.min((bytes.len() - *offset) / 2));
+    let mut anchor = None;
+    for _ in 0..count {
+        let level = read_delta_level(bytes, offset, anchor, tick)?;
+        anchor.get_or_insert(level.price);
+        levels.push(level);
+    }
+    Ok(levels)
+}
+
 /// Builder for creating orderbook batch messages
+#[derive(Debug)]
 pub struct OrderBookBatchMessage {
     header: OrderBookBatchHeader,
     bids: Vec<PriceLevel>,
     asks: Vec<PriceLevel>,
+    /// Tick size for delta encoding (0 = fixed 16-byte levels)
+    delta_tick: i64,
 }
 
 impl OrderBookBatchMessage {
-    pub const HEADER_SIZE: usize = 56; // Updated from 32 to include sequence IDs
+    pub const HEADER_SIZE: usize = 72; // Exchange update IDs + per-stream sequence + schema version
+    pub const VERSION_OFFSET: usize = 64;
+    pub const FLAGS_OFFSET: usize = 65;
+    /// Levels are delta/varint encoded instead of fixed 16-byte pairs
+    pub const FLAG_DELTA_ENCODED: u8 = 0x01;
+    /// `price_scale`/`qty_scale` are set; without it levels use `Scale::DEFAULT`
+    pub const FLAG_SCALED: u8 = 0x02;
     pub const MESSAGE_TYPE: u8 = 0x03;
 
     /// Create a new orderbook batch message
@@ -113,9 +184,64 @@ impl OrderBookBatchMessage {
             first_update_id,
             final_update_id,
             prev_update_id,
+            sequence: 0,
+            version: SCHEMA_VERSION,
+            flags: 0,
+            price_scale: 0,
+            qty_scale: 0,
+            instrument_id: 0,
         };
 
-        Self { header, bids: Vec::new(), asks: Vec::new() }
+        Self { header, bids: Vec::new(), asks: Vec::new(), delta_tick: 0 }
+    }
+
+    /// Set the per-stream publisher sequence number
+    #[inline]
+    pub fn set_sequence(&mut self, sequence: u64) {
+        self.header.sequence = sequence;
+    }
+
+    /// Set the registry instrument ID
+    #[inline]
+    pub fn set_instrument_id(&mut self, instrument_id: u32) {
+        self.header.instrument_id = instrument_id;
+    }
+
+    /// Declare the decimal scales of level prices and sizes
+    #[inline]
+    pub fn set_scales(&mut self, price_scale: Scale, qty_scale: Scale) {
+        self.header.price_scale = price_scale.decimals();
+        self.header.qty_scale = qty_scale.decimals();
+        self.header.flags |= Self::FLAG_SCALED;
+    }
+
+    /// Decimal scale of level prices
+    #[inline]
+    pub fn price_scale(&self) -> Scale {
+        header_scale(self.header.flags, self.header.price_scale)
+    }
+
+    /// Decimal scale of level sizes
+    #[inline]
+    pub fn qty_scale(&self) -> Scale {
+        header_scale(self.header.flags, self.header.qty_scale)
+    }
+
+    /// Encode levels compactly: prices as tick deltas from each side's first level,
+    /// sizes as zig-zag varints. A `tick_size` of 0 restores the fixed layout.
+    ///
+    /// If a price is not a whole number of ticks from its side's first level the
+    /// encoder falls back to a tick of 1, and if prices are too far apart for an `i64`
+    /// delta it keeps the fixed layout, so the encoding is always lossless.
+    #[inline]
+    pub fn set_delta_encoding(&mut self, tick_size: i64) {
+        self.delta_tick = tick_size.max(0);
+    }
+
+    /// Whether levels are (or will be) delta encoded
+    #[inline]
+    pub fn is_delta_encoded(&self) -> bool {
+        self.delta_tick > 0
     }
 
     /// Add a bid level
@@ -146,13 +272,64 @@ impl OrderBookBatchMessage {
 
     /// Get the total message size in bytes
     pub fn size(&self) -> usize {
+        if let Some(tick) = self.wire_tick() {
+            return Self::HEADER_SIZE + self.delta_levels_len(tick) + 4;
+        }
         Self::HEADER_SIZE + (self.bids.len() + self.asks.len()) * PriceLevel::SIZE + 4 // +4 for CRC32
     }
 
+    /// Tick actually used on the wire: the configured tick if every level is a whole
+    /// number of ticks from its side's first level, otherwise 1. `None` means the fixed
+    /// layout, either because delta encoding is off or a price delta overflows `i64`.
+    fn wire_tick(&self) -> Option<i64> {
+        if !self.is_delta_encoded() {
+            return None;
+        }
+        let mut aligned = true;
+        for levels in [&self.bids, &self.asks] {
+            let Some(first) = levels.first() else { continue };
+            for level in levels {
+                let delta = level.price.checked_sub(first.price)?;
+                aligned &= delta % self.delta_tick == 0;
+            }
+        }
+        Some(if aligned { self.delta_tick } else { 1 })
+    }
+
+    /// Delta body: tick varint, then per side the first level as zig-zag (price, size)
+    /// and every following level as zig-zag (ticks from first price, size)
+    fn write_delta_levels(&self, out: &mut Vec<u8>, tick: i64) {
+        varint::write_varint(out, tick as u64);
+
+        for levels in [&self.bids, &self.asks] {
+            let Some(first) = levels.first() else { continue };
+            varint::write_zigzag(out, first.price);
+            varint::write_zigzag(out, first.size);
+            for level in &levels[1..] {
+                varint::write_zigzag(out, (level.price - first.price) / tick);
+                varint::write_zigzag(out, level.size);
+            }
+        }
+    }
+
+    /// Length of the body `write_delta_levels` produces, without encoding it
+    fn delta_levels_len(&self, tick: i64) -> usize {
+        let mut len = varint::varint_len(tick as u64);
+        for levels in [&self.bids, &self.asks] {
+            let Some(first) = levels.first() else { continue };
+            len += varint::zigzag_len(first.price) + varint::zigzag_len(first.size);
+            for level in &levels[1..] {
+                len += varint::zigzag_len((level.price - first.price) / tick) + varint::zigzag_len(level.size);
+            }
+        }
+        len
+    }
+
     /// Serialize to bytes
     pub fn to_bytes(&self) -> Vec<u8> {
-        let total_size = self.size();
-        let mut bytes = Vec::with_capacity(total_size);
+        // Delta frames are normally far smaller than the fixed layout, so this rarely regrows
+        let capacity = Self::HEADER_SIZE + (self.bids.len() + self.asks.len()) * PriceLevel::SIZE + 4;
+        let mut bytes = Vec::with_capacity(capacity);
 
         // Update header counts
         let mut header = self.header;
@@ -172,15 +349,26 @@ impl OrderBookBatchMessage {
         bytes.extend_from_slice(&header.first_update_id.to_le_bytes());
         bytes.extend_from_slice(&header.final_update_id.to_le_bytes());
         bytes.extend_from_slice(&header.prev_update_id.to_le_bytes());
-
-        // Serialize bids
-        for bid in &self.bids {
-            bytes.extend_from_slice(&bid.to_bytes());
-        }
-
-        // Serialize asks
-        for ask in &self.asks {
-            bytes.extend_from_slice(&ask.to_bytes());
+        bytes.extend_from_slice(&header.sequence.to_le_bytes());
+        bytes.push(header.version);
+        let tick = self.wire_tick();
+        bytes.push(if tick.is_some() { header.flags | Self::FLAG_DELTA_ENCODED } else { header.flags & !Self::FLAG_DELTA_ENCODED });
+        bytes.push(header.price_scale);
+        bytes.push(header.qty_scale);
+        bytes.extend_from_slice(&header.instrument_id.to_le_bytes());
+
+        if let Some(tick) = tick {
+            self.write_delta_levels(&mut bytes, tick);
+        } else {
+            // Serialize bids
+            for bid in &self.bids {
+                bytes.extend_from_slice(&bid.to_bytes());
+            }
+
+            // Serialize asks
+            for ask in &self.asks {
+                bytes.extend_from_slice(&ask.to_bytes());
+            }
         }
 
         // Calculate and append CRC32C
@@ -196,6 +384,8 @@ impl OrderBookBatchMessage {
             return Err(ProtocolError::BufferTooSmall { required: Self::HEADER_SIZE + 4, actual: bytes.len() });
         }
 
+        check_schema_version(bytes[Self::VERSION_OFFSET])?;
+
         // Verify CRC32C
         let crc_offset = bytes.len() - 4;
         let expected_crc = u32::from_le_bytes(bytes[crc_offset..].try_into().unwrap());
@@ -222,25 +412,44 @@ impl OrderBookBatchMessage {
             first_update_id: u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
             final_update_id: u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
             prev_update_id: u64::from_le_bytes(bytes[48..56].try_into().unwrap()),
+            sequence: u64::from_le_bytes(bytes[56..64].try_into().unwrap()),
+            version: bytes[64],
+            flags: bytes[Self::FLAGS_OFFSET],
+            price_scale: bytes[66],
+            qty_scale: bytes[67],
+            instrument_id: u32::from_le_bytes(bytes[68..72].try_into().unwrap()),
         };
 
+        if header.flags & Self::FLAG_SCALED != 0 {
+            Scale::try_from(header.price_scale)?;
+            Scale::try_from(header.qty_scale)?;
+        }
+
         let mut offset = Self::HEADER_SIZE;
+        let body = &bytes[..crc_offset];
+
+        if header.flags & Self::FLAG_DELTA_ENCODED != 0 {
+            let tick = read_delta_tick(body, &mut offset)?;
+            let bids = read_delta_side(body, &mut offset, header.num_bids as usize, tick)?;
+            let asks = read_delta_side(body, &mut offset, header.num_asks as usize, tick)?;
+            return Ok(Self { header, bids, asks, delta_tick: tick });
+        }
 
         // Parse bids
         let mut bids = Vec::with_capacity(header.num_bids as usize);
         for _ in 0..header.num_bids {
-            bids.push(PriceLevel::from_bytes(&bytes[offset..])?);
+            bids.push(PriceLevel::from_bytes(&body[offset..])?);
             offset += PriceLevel::SIZE;
         }
 
         // Parse asks
         let mut asks = Vec::with_capacity(header.num_asks as usize);
         for _ in 0..header.num_asks {
-            asks.push(PriceLevel::from_bytes(&bytes[offset..])?);
+            asks.push(PriceLevel::from_bytes(&body[offset..])?);
             offset += PriceLevel::SIZE;
         }
 
-        Ok(Self { header, bids, asks })
+        Ok(Self { header, bids, asks, delta_tick: 0 })
     }
 
     /// Get the exchange
@@ -297,6 +506,21 @@ impl OrderBookBatchMessage {
     pub fn prev_update_id(&self) -> u64 {
         self.header.prev_update_id
     }
+
+    /// Get the per-stream publisher sequence number
+    pub fn sequence(&self) -> u64 {
+        self.header.sequence
+    }
+
+    /// Get the registry instrument ID (0 if unassigned)
+    pub fn instrument_id(&self) -> u32 {
+        self.header.instrument_id
+    }
+
+    /// Get the schema version this batch was encoded with
+    pub fn version(&self) -> u8 {
+        self.header.version
+    }
 }
 
 #[cfg(test)]
@@ -322,5 +546,118 @@ mod tests {
         assert_eq!(decoded.bids().len(), 2);
         assert_eq!(decoded.asks().len(), 2);
         assert_eq!(decoded.timestamp(), 1234567890);
+        assert_eq!(decoded.sequence(), 0);
+    }
+
+    #[test]
+    fn test_orderbook_batch_sequence() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+
+        let mut msg = OrderBookBatchMessage::new_with_ids(Exchange::Binance, UpdateType::Update, symbol, encoding, 1, 100, 105, 99);
+        msg.set_sequence(u64::from(u8::MAX) + 1);
+        msg.set_instrument_id(7);
+        msg.add_bid(to_fixed_point(50000.0), to_fixed_point(1.0));
+
+        let decoded = OrderBookBatchMessage::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(decoded.sequence(), 256);
+        assert_eq!(decoded.instrument_id(), 7);
+        assert_eq!(decoded.first_update_id(), 100);
+        assert_eq!(decoded.final_update_id(), 105);
+        assert_eq!(decoded.prev_update_id(), 99);
+    }
+
+    #[test]
+    fn test_orderbook_batch_delta_encoding() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let tick = to_fixed_point(0.01);
+
+        let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Snapshot, symbol, encoding, 1);
+        for i in 0..20 {
+            msg.add_bid(to_fixed_point(50000.0) - i * tick, to_fixed_point(0.5) + i);
+            msg.add_ask(to_fixed_point(50000.01) + i * 3 * tick, to_fixed_point(1.25));
+        }
+        let plain = msg.to_bytes();
+
+        msg.set_delta_encoding(tick);
+        let delta = msg.to_bytes();
+        assert_eq!(delta.len(), msg.size());
+        assert!(delta.len() * 2 < plain.len(), "delta {} vs plain {}", delta.len(), plain.len());
+        assert_eq!(delta[OrderBookBatchMessage::FLAGS_OFFSET] & OrderBookBatchMessage::FLAG_DELTA_ENCODED, 1);
+
+        let decoded = OrderBookBatchMessage::from_bytes(&delta).unwrap();
+        assert!(decoded.is_delta_encoded());
+        let levels = |levels: &[PriceLevel]| levels.iter().map(|level| (level.price, level.size)).collect::<Vec<_>>();
+        assert_eq!(levels(decoded.bids()), levels(msg.bids()));
+        assert_eq!(levels(decoded.asks()), levels(msg.asks()));
+        assert_eq!(decoded.to_bytes(), delta);
+    }
+
+    #[test]
+    fn test_orderbook_batch_delta_off_tick_is_lossless() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+
+        let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Update, symbol, encoding, 1);
+        msg.add_bid(to_fixed_point(100.0), 1);
+        msg.add_bid(to_fixed_point(99.995), 0);
+        msg.add_ask(-5, i64::MIN);
+        msg.set_delta_encoding(to_fixed_point(0.01));
+
+        let decoded = OrderBookBatchMessage::from_bytes(&msg.to_bytes()).unwrap();
+        let (bid_price, bid_size) = (decoded.bids()[1].price, decoded.bids()[1].size);
+        let (ask_price, ask_size) = (decoded.asks()[0].price, decoded.asks()[0].size);
+        assert_eq!((bid_price, bid_size), (to_fixed_point(99.995), 0));
+        assert_eq!((ask_price, ask_size), (-5, i64::MIN));
+
+        // Deltas that overflow i64 keep the fixed layout
+        let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Update, symbol, encoding, 1);
+        msg.add_bids([(i64::MAX, 1), (i64::MIN, 2)]);
+        msg.set_delta_encoding(1);
+        let bytes = msg.to_bytes();
+        assert_eq!(bytes.len(), OrderBookBatchMessage::HEADER_SIZE + 2 * PriceLevel::SIZE + 4);
+        assert_eq!(msg.size(), bytes.len());
+        let decoded = OrderBookBatchMessage::from_bytes(&bytes).unwrap();
+        assert_eq!((decoded.bids()[1].price, decoded.bids()[1].size), (i64::MIN, 2));
+    }
+
+    #[test]
+    fn test_orderbook_batch_delta_truncated() {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+
+        let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Update, symbol, encoding, 1);
+        msg.add_bids([(to_fixed_point(100.0), 1), (to_fixed_point(99.0), 2)]);
+        msg.set_delta_encoding(to_fixed_point(1.0));
+        let bytes = msg.to_bytes();
+
+        // Drop the last level but keep a valid CRC so only the body is short
+        let mut truncated = bytes[..bytes.len() - 6].to_vec();
+        let crc = checksum::calculate_crc32c(&truncated);
+        truncated.extend_from_slice(&crc.to_le_bytes());
+        assert!(OrderBookBatchMessage::from_bytes(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_orderbook_batch_scales() {
+        let (symbol, encoding) = CompressedString::from_str("SHIBUSDT").unwrap();
+        let price_scale = Scale::new(12).unwrap();
+        let qty_scale = Scale::new(0).unwrap();
+
+        let mut msg = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Snapshot, symbol, encoding, 1);
+        assert_eq!(msg.price_scale(), Scale::DEFAULT);
+        msg.add_bid(crate::to_fixed_point_scaled(0.00001234, price_scale), 1_000_000);
+        msg.set_scales(price_scale, qty_scale);
+
+        let bytes = msg.to_bytes();
+        let decoded = OrderBookBatchMessage::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.price_scale(), price_scale);
+        assert_eq!(decoded.qty_scale(), qty_scale);
+        let price = decoded.bids()[0].price;
+        assert_eq!(price, 12_340_000);
+
+        // Out-of-range scale bytes are rejected
+        let mut corrupt = bytes[..bytes.len() - 4].to_vec();
+        corrupt[66] = Scale::MAX_DECIMALS + 1;
+        let crc = checksum::calculate_crc32c(&corrupt);
+        corrupt.extend_from_slice(&crc.to_le_bytes());
+        assert!(matches!(OrderBookBatchMessage::from_bytes(&corrupt), Err(ProtocolError::InvalidScale { .. })));
     }
 }
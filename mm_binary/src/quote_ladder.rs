@@ -0,0 +1,332 @@
+use crate::CompressedString;
+use crate::SCHEMA_VERSION;
+use crate::check_schema_version;
+use crate::checksum;
+use crate::compressed_string::EncodingScheme;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::orderbook_message::PriceLevel;
+
+/// Header for quote ladder messages
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteLadderHeader {
+    /// Message type (`QuoteLadderMessage::MESSAGE_TYPE`)
+    pub header: u8,
+    /// Strategy that owns the ladder
+    pub strategy_id: u8,
+    /// Symbol encoding scheme
+    pub encoding: u8,
+    pub _pad: u8,
+    /// Number of bid levels, best first
+    pub num_bids: u16,
+    /// Number of ask levels, best first
+    pub num_asks: u16,
+    /// Compressed symbol (low 64 bits)
+    pub symbol_low: u64,
+    /// Compressed symbol (high 64 bits)
+    pub symbol_high: u64,
+    /// Strategy timestamp in nanoseconds
+    pub timestamp: u64,
+    /// Ladder identifier; a new ID replaces every level of the previous ladder
+    pub quote_id: u64,
+    /// Per-stream publisher sequence number
+    pub sequence: u64,
+    /// Fair value the ladder was built around (fixed-point)
+    pub fair_value: i64,
+    /// Strategy inventory when the ladder was built (fixed-point)
+    pub inventory: i64,
+    /// Schema version of this layout
+    pub version: u8,
+    pub _pad2: [u8; 3],
+    /// Registry instrument ID (0 = unassigned, resolve by symbol)
+    pub instrument_id: u32,
+    /// Confidence of the top level (fixed-point)
+    pub confidence: i64,
+}
+
+/// Multi-level quote: N bid and ask levels replacing the strategy's previous ladder
+#[derive(Debug)]
+pub struct QuoteLadderMessage {
+    header: QuoteLadderHeader,
+    bids: Vec<PriceLevel>,
+    asks: Vec<PriceLevel>,
+}
+
+impl QuoteLadderMessage {
+    pub const HEADER_SIZE: usize = 80;
+    pub const VERSION_OFFSET: usize = 64;
+    pub const MESSAGE_TYPE: u8 = 23;
+
+    /// Create an empty ladder
+    pub fn new(strategy_id: u8, symbol: CompressedString, encoding: EncodingScheme, timestamp: u64, quote_id: u64) -> Self {
+        Self {
+            header: QuoteLadderHeader {
+                header: Self::MESSAGE_TYPE,
+                strategy_id,
+                encoding: encoding as u8,
+                _pad: 0,
+                num_bids: 0,
+                num_asks: 0,
+                symbol_low: symbol.low,
+                symbol_high: symbol.high,
+                timestamp,
+                quote_id,
+                sequence: 0,
+                fair_value: 0,
+                inventory: 0,
+                version: SCHEMA_VERSION,
+                _pad2: [0; 3],
+                instrument_id: 0,
+                confidence: 0,
+            },
+            bids: Vec::new(),
+            asks: Vec::new(),
+        }
+    }
+
+    /// Set the per-stream publisher sequence number
+    pub fn set_sequence(&mut self, sequence: u64) {
+        self.header.sequence = sequence;
+    }
+
+    /// Set the registry instrument ID
+    pub fn set_instrument_id(&mut self, instrument_id: u32) {
+        self.header.instrument_id = instrument_id;
+    }
+
+    /// Set the fair value, inventory and confidence the ladder was built from
+    pub fn set_valuation(&mut self, fair_value: i64, inventory: i64, confidence: i64) {
+        self.header.fair_value = fair_value;
+        self.header.inventory = inventory;
+        self.header.confidence = confidence;
+    }
+
+    /// Add a bid level; levels are kept in insertion order (best first)
+    pub fn add_bid(&mut self, price: i64, size: i64) {
+        self.bids.push(PriceLevel::new(price, size));
+    }
+
+    /// Add an ask level; levels are kept in insertion order (best first)
+    pub fn add_ask(&mut self, price: i64, size: i64) {
+        self.asks.push(PriceLevel::new(price, size));
+    }
+
+    /// Get the total message size in bytes
+    pub fn size(&self) -> usize {
+        Self::HEADER_SIZE + (self.bids.len() + self.asks.len()) * PriceLevel::SIZE + 4 // +4 for CRC32
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size());
+
+        let mut header = self.header;
+        header.num_bids = self.bids.len() as u16;
+        header.num_asks = self.asks.len() as u16;
+
+        bytes.push(header.header);
+        bytes.push(header.strategy_id);
+        bytes.push(header.encoding);
+        bytes.push(0);
+        bytes.extend_from_slice(&header.num_bids.to_le_bytes());
+        bytes.extend_from_slice(&header.num_asks.to_le_bytes());
+        bytes.extend_from_slice(&header.symbol_low.to_le_bytes());
+        bytes.extend_from_slice(&header.symbol_high.to_le_bytes());
+        bytes.extend_from_slice(&header.timestamp.to_le_bytes());
+        bytes.extend_from_slice(&header.quote_id.to_le_bytes());
+        bytes.extend_from_slice(&header.sequence.to_le_bytes());
+        bytes.extend_from_slice(&header.fair_value.to_le_bytes());
+        bytes.extend_from_slice(&header.inventory.to_le_bytes());
+        bytes.push(header.version);
+        bytes.extend_from_slice(&[0; 3]);
+        bytes.extend_from_slice(&header.instrument_id.to_le_bytes());
+        bytes.extend_from_slice(&header.confidence.to_le_bytes());
+
+        for level in self.bids.iter().chain(&self.asks) {
+            bytes.extend_from_slice(&level.to_bytes());
+        }
+
+        // Calculate and append CRC32C
+        let crc = checksum::calculate_crc32c(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+
+        bytes
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::HEADER_SIZE + 4 {
+            return Err(ProtocolError::BufferTooSmall { required: Self::HEADER_SIZE + 4, actual: bytes.len() });
+        }
+
+        check_schema_version(bytes[Self::VERSION_OFFSET])?;
+
+        // Verify CRC32C
+        let crc_offset = bytes.len() - 4;
+        let expected_crc = u32::from_le_bytes(bytes[crc_offset..].try_into().unwrap());
+        let actual_crc = checksum::calculate_crc32c(&bytes[..crc_offset]);
+        if expected_crc != actual_crc {
+            return Err(ProtocolError::InvalidChecksum { expected: expected_crc, actual: actual_crc });
+        }
+
+        if bytes[0] != Self::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type: bytes[0] });
+        }
+
+        let read_u64 = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let header = QuoteLadderHeader {
+            header: bytes[0],
+            strategy_id: bytes[1],
+            encoding: bytes[2],
+            _pad: 0,
+            num_bids: u16::from_le_bytes([bytes[4], bytes[5]]),
+            num_asks: u16::from_le_bytes([bytes[6], bytes[7]]),
+            symbol_low: read_u64(8),
+            symbol_high: read_u64(16),
+            timestamp: read_u64(24),
+            quote_id: read_u64(32),
+            sequence: read_u64(40),
+            fair_value: read_u64(48) as i64,
+            inventory: read_u64(56) as i64,
+            version: bytes[Self::VERSION_OFFSET],
+            _pad2: [0; 3],
+            instrument_id: u32::from_le_bytes(bytes[68..72].try_into().unwrap()),
+            confidence: read_u64(72) as i64,
+        };
+
+        // The level counts must account for the whole body, so a ladder is never applied partially
+        let num_levels = header.num_bids as usize + header.num_asks as usize;
+        let expected = Self::HEADER_SIZE + num_levels * PriceLevel::SIZE + 4;
+        if bytes.len() != expected {
+            return Err(ProtocolError::InvalidLength { expected, actual: bytes.len() });
+        }
+
+        let mut levels = bytes[Self::HEADER_SIZE..crc_offset].chunks_exact(PriceLevel::SIZE).map(PriceLevel::from_bytes);
+        let bids = levels.by_ref().take(header.num_bids as usize).collect::<Result<Vec<_>>>()?;
+        let asks = levels.collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { header, bids, asks })
+    }
+
+    /// Get the owning strategy
+    pub fn strategy_id(&self) -> u8 {
+        self.header.strategy_id
+    }
+
+    /// Get the ladder identifier
+    pub fn quote_id(&self) -> u64 {
+        self.header.quote_id
+    }
+
+    /// Get the symbol
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: self.header.symbol_low, high: self.header.symbol_high }
+    }
+
+    /// Get the encoding scheme
+    pub fn encoding(&self) -> EncodingScheme {
+        match self.header.encoding {
+            0 => EncodingScheme::Hex4Bit,
+            1 => EncodingScheme::Alphabetic5Bit,
+            2 => EncodingScheme::AlphaNumeric6Bit,
+            _ => EncodingScheme::Ascii7Bit,
+        }
+    }
+
+    /// Get the timestamp
+    pub fn timestamp(&self) -> u64 {
+        self.header.timestamp
+    }
+
+    /// Get the per-stream publisher sequence number
+    pub fn sequence(&self) -> u64 {
+        self.header.sequence
+    }
+
+    /// Get the registry instrument ID (0 if unassigned)
+    pub fn instrument_id(&self) -> u32 {
+        self.header.instrument_id
+    }
+
+    pub fn fair_value(&self) -> i64 {
+        self.header.fair_value
+    }
+
+    pub fn inventory(&self) -> i64 {
+        self.header.inventory
+    }
+
+    pub fn confidence(&self) -> i64 {
+        self.header.confidence
+    }
+
+    /// Get bid levels, best first
+    pub fn bids(&self) -> &[PriceLevel] {
+        &self.bids
+    }
+
+    /// Get ask levels, best first
+    pub fn asks(&self) -> &[PriceLevel] {
+        &self.asks
+    }
+
+    /// Get the schema version this ladder was encoded with
+    pub fn version(&self) -> u8 {
+        self.header.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fixed_point;
+
+    fn ladder() -> QuoteLadderMessage {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let mut msg = QuoteLadderMessage::new(3, symbol, encoding, 1_700_000_000_000, 77);
+        msg.set_sequence(9);
+        msg.set_valuation(to_fixed_point(50_000.5), to_fixed_point(-0.25), to_fixed_point(0.9));
+        for i in 0..3 {
+            msg.add_bid(to_fixed_point(50_000.0 - i as f64), to_fixed_point(0.1));
+        }
+        msg.add_ask(to_fixed_point(50_001.0), to_fixed_point(0.2));
+        msg
+    }
+
+    #[test]
+    fn test_quote_ladder_roundtrip() {
+        let msg = ladder();
+        let bytes = msg.to_bytes();
+        assert_eq!(bytes.len(), msg.size());
+
+        let decoded = QuoteLadderMessage::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.strategy_id(), 3);
+        assert_eq!(decoded.quote_id(), 77);
+        assert_eq!(decoded.sequence(), 9);
+        assert_eq!(decoded.symbol().decode(decoded.encoding()), "BTCUSDT");
+        assert_eq!(decoded.fair_value(), to_fixed_point(50_000.5));
+        assert_eq!(decoded.inventory(), to_fixed_point(-0.25));
+        assert_eq!(decoded.bids().len(), 3);
+        assert_eq!(decoded.asks().len(), 1);
+        let (price, size) = (decoded.bids()[2].price, decoded.asks()[0].size);
+        assert_eq!((price, size), (to_fixed_point(49_998.0), to_fixed_point(0.2)));
+        assert_eq!(decoded.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_quote_ladder_rejects_inconsistent_counts() {
+        let bytes = ladder().to_bytes();
+
+        // Claim an extra bid but keep a valid CRC so only the length is wrong
+        let mut corrupt = bytes[..bytes.len() - 4].to_vec();
+        corrupt[4] += 1;
+        let crc = checksum::calculate_crc32c(&corrupt);
+        corrupt.extend_from_slice(&crc.to_le_bytes());
+        assert!(matches!(QuoteLadderMessage::from_bytes(&corrupt), Err(ProtocolError::InvalidLength { .. })));
+
+        let mut flipped = bytes.clone();
+        flipped[QuoteLadderMessage::HEADER_SIZE] ^= 1;
+        assert!(matches!(QuoteLadderMessage::from_bytes(&flipped), Err(ProtocolError::InvalidChecksum { .. })));
+    }
+}
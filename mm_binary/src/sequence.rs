@@ -0,0 +1,235 @@
+/// Number of sequences behind the highest seen that are remembered for duplicate/reorder detection
+const WINDOW_SIZE: u64 = 64;
+
+/// Outcome of observing a sequence number on a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// First message seen on this stream
+    First,
+    /// Exactly the next expected sequence
+    InOrder,
+    /// One or more sequences were skipped
+    Gap { expected: u64, received: u64, missed: u64 },
+    /// A previously missed sequence arrived late
+    Reordered { sequence: u64 },
+    /// Sequence already seen within the window
+    Duplicate { sequence: u64 },
+    /// Arrived further behind `highest` than the window reaches, so it cannot be
+    /// told apart from a duplicate. Tracking is left as it was.
+    Stale { sequence: u64, highest: u64 },
+    /// The publisher started over: sequence 0 arrived after the window had moved past it.
+    /// Tracking restarts from 0.
+    Restart { previous: u64, sequence: u64 },
+}
+
+/// Per-stream sequence tracker
+///
+/// Keeps a sliding bitmap of the last 64 sequences so late arrivals can be told
+/// apart from duplicates without allocating. Anything further back is reported
+/// as stale, except sequence 0, which marks a publisher restart.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    highest: Option<u64>,
+    /// Bit `i` set means `highest - i` has been received
+    window: u64,
+    received: u64,
+    gaps: u64,
+    missed: u64,
+    duplicates: u64,
+    reordered: u64,
+    stale: u64,
+    restarts: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a sequence number and classify it
+    pub fn observe(&mut self, sequence: u64) -> SequenceEvent {
+        let Some(highest) = self.highest else {
+            self.highest = Some(sequence);
+            self.window = 1;
+            self.received = 1;
+            return SequenceEvent::First;
+        };
+
+        if sequence > highest {
+            let advance = sequence - highest;
+            self.window = if advance >= WINDOW_SIZE { 1 } else { (self.window << advance) | 1 };
+            self.highest = Some(sequence);
+            self.received += 1;
+
+            if advance == 1 {
+                return SequenceEvent::InOrder;
+            }
+
+            let missed = advance - 1;
+            self.gaps += 1;
+            self.missed += missed;
+            return SequenceEvent::Gap { expected: highest + 1, received: sequence, missed };
+        }
+
+        let offset = highest - sequence;
+        if offset >= WINDOW_SIZE {
+            if sequence != 0 {
+                self.stale += 1;
+                return SequenceEvent::Stale { sequence, highest };
+            }
+            self.highest = Some(sequence);
+            self.window = 1;
+            self.received += 1;
+            self.restarts += 1;
+            return SequenceEvent::Restart { previous: highest, sequence };
+        }
+        if self.window & (1 << offset) != 0 {
+            self.duplicates += 1;
+            return SequenceEvent::Duplicate { sequence };
+        }
+
+        self.window |= 1 << offset;
+        self.received += 1;
+        self.reordered += 1;
+        self.missed = self.missed.saturating_sub(1);
+        SequenceEvent::Reordered { sequence }
+    }
+
+    /// Next sequence expected on this stream
+    pub fn expected(&self) -> Option<u64> {
+        self.highest.map(|highest| highest + 1)
+    }
+
+    /// Forget all state, e.g. after a publisher restart
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Messages accepted (in order, after a gap, or reordered)
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Number of gap events
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    /// Sequences skipped that have not (yet) arrived late
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    pub fn reordered(&self) -> u64 {
+        self.reordered
+    }
+
+    /// Arrivals too far behind the window to classify
+    pub fn stale(&self) -> u64 {
+        self.stale
+    }
+
+    pub fn restarts(&self) -> u64 {
+        self.restarts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_order_sequence() {
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.observe(0), SequenceEvent::First);
+        for seq in 1..1000 {
+            assert_eq!(tracker.observe(seq), SequenceEvent::InOrder);
+        }
+        assert_eq!(tracker.received(), 1000);
+        assert_eq!(tracker.gaps(), 0);
+        assert_eq!(tracker.expected(), Some(1000));
+    }
+
+    #[test]
+    fn test_no_wrap_past_u8() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(255);
+        assert_eq!(tracker.observe(256), SequenceEvent::InOrder);
+    }
+
+    #[test]
+    fn test_gap_then_late_arrival() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(10);
+        assert_eq!(tracker.observe(14), SequenceEvent::Gap { expected: 11, received: 14, missed: 3 });
+        assert_eq!(tracker.missed(), 3);
+
+        assert_eq!(tracker.observe(12), SequenceEvent::Reordered { sequence: 12 });
+        assert_eq!(tracker.missed(), 2);
+
+        assert_eq!(tracker.observe(12), SequenceEvent::Duplicate { sequence: 12 });
+        assert_eq!(tracker.observe(14), SequenceEvent::Duplicate { sequence: 14 });
+        assert_eq!(tracker.observe(15), SequenceEvent::InOrder);
+        assert_eq!(tracker.duplicates(), 2);
+        assert_eq!(tracker.reordered(), 1);
+    }
+
+    #[test]
+    fn test_publisher_restart() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(0);
+        tracker.observe(1000);
+
+        // Sequence 0 once the window has moved past it starts over
+        assert_eq!(tracker.observe(0), SequenceEvent::Restart { previous: 1000, sequence: 0 });
+        assert_eq!(tracker.observe(1), SequenceEvent::InOrder);
+        assert_eq!(tracker.restarts(), 1);
+        assert_eq!(tracker.expected(), Some(2));
+    }
+
+    #[test]
+    fn test_late_zero_is_not_a_restart() {
+        let mut tracker = SequenceTracker::new();
+        for seq in 0..=5 {
+            tracker.observe(seq);
+        }
+
+        // A repeated 0 inside the window is a duplicate, not a new stream
+        assert_eq!(tracker.observe(0), SequenceEvent::Duplicate { sequence: 0 });
+        assert_eq!(tracker.observe(6), SequenceEvent::InOrder);
+
+        // A 0 that never arrived is just late
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(1);
+        tracker.observe(2);
+        assert_eq!(tracker.observe(0), SequenceEvent::Reordered { sequence: 0 });
+        assert_eq!(tracker.observe(3), SequenceEvent::InOrder);
+        assert_eq!((tracker.restarts(), tracker.gaps()), (0, 0));
+    }
+
+    #[test]
+    fn test_stale_beyond_window_leaves_tracking_alone() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(0);
+        tracker.observe(1000);
+
+        assert_eq!(tracker.observe(5), SequenceEvent::Stale { sequence: 5, highest: 1000 });
+        assert_eq!(tracker.observe(1001), SequenceEvent::InOrder);
+        assert_eq!(tracker.observe(1000), SequenceEvent::Duplicate { sequence: 1000 });
+        assert_eq!(tracker.stale(), 1);
+        assert_eq!((tracker.restarts(), tracker.gaps()), (0, 1));
+        assert_eq!(tracker.expected(), Some(1002));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(100);
+        tracker.reset();
+        assert_eq!(tracker.observe(0), SequenceEvent::First);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use serde::Deserializer;
 
+use crate::Exchange;
 use crate::fixed_point::parse_json_decimal_to_fixed_point;
 
 /// Deserialize a JSON string field directly to i64 fixed-point
@@ -33,6 +34,15 @@ where
         .collect()
 }
 
+/// Deserialize an exchange by name (case-insensitive, e.g. "binance")
+pub fn deserialize_exchange<'de, D>(deserializer: D) -> Result<Exchange, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    Exchange::from_name(&s).ok_or_else(|| serde::de::Error::custom(format!("unknown exchange: {s}")))
+}
+
 #[cfg(test)]
 mod tests {
     use serde::Deserialize;
@@ -1,11 +1,17 @@
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
 use crate::CompressedString;
 use crate::MarketDataMessage;
 
 #[cfg(target_arch = "x86_64")]
 #[target_feature(enable = "avx2")]
+/// # Safety
+///
+/// This function requires AVX2 support and should only be called when the
+/// "avx2" target feature is available. The caller must ensure that the
+/// messages array contains valid MarketDataMessage instances.
 pub unsafe fn batch_validate_timestamps_avx2(messages: &[MarketDataMessage; 4], threshold: u64) -> [bool; 4] {
     let timestamps = _mm256_set_epi64x(
         messages[3].timestamp as i64,
@@ -56,6 +62,11 @@ pub unsafe fn batch_symbol_compare_avx512(messages: &[MarketDataMessage; 8], tar
 
 #[cfg(target_arch = "x86_64")]
 #[target_feature(enable = "avx2")]
+/// # Safety
+///
+/// This function requires AVX2 support and should only be called when the
+/// "avx2" target feature is available. The caller must ensure that the
+/// messages array contains valid MarketDataMessage instances.
 pub unsafe fn batch_price_compare_avx2(messages: &[MarketDataMessage; 4], threshold: i64) -> ([bool; 4], [bool; 4]) {
     let bid_prices = _mm256_set_epi64x(messages[3].bid_price, messages[2].bid_price, messages[1].bid_price, messages[0].bid_price);
 
@@ -79,7 +90,7 @@ pub unsafe fn batch_price_compare_avx2(messages: &[MarketDataMessage; 4], thresh
 mod tests {
     use super::*;
     use crate::Exchange;
-    use crate::compressed_string::EncodingScheme;
+    use crate::compressed_string::CompressedString;
     use crate::messages::UpdateType;
 
     #[cfg(target_arch = "x86_64")]
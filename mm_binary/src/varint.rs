@@ -0,0 +1,120 @@
+//! LEB128 varints and zig-zag mapping for compact integer encoding
+
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+
+/// Longest LEB128 encoding of a u64
+pub const MAX_VARINT_LEN: usize = 10;
+
+/// Map signed to unsigned so small magnitudes stay small: 0, -1, 1, -2, ... -> 0, 1, 2, 3, ...
+#[inline]
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[inline]
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Number of bytes `write_varint` emits for `value`
+#[inline]
+pub fn varint_len(value: u64) -> usize {
+    // Each byte carries 7 bits; zero still takes one byte
+    let bits = 64 - (value | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Number of bytes `write_zigzag` emits for `value`
+#[inline]
+pub fn zigzag_len(value: i64) -> usize {
+    varint_len(zigzag_encode(value))
+}
+
+#[inline]
+pub fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Decode a varint starting at `*offset`, advancing it past the encoded bytes
+#[inline]
+pub fn read_varint(bytes: &[u8], offset: &mut usize) -> Result<u64> {
+    let start = *offset;
+    let mut value = 0u64;
+
+    for i in 0..MAX_VARINT_LEN {
+        let Some(&byte) = bytes.get(start + i) else {
+            return Err(ProtocolError::BufferTooSmall { required: start + i + 1, actual: bytes.len() });
+        };
+
+        // The tenth byte may only contribute the top bit of a u64
+        if i == MAX_VARINT_LEN - 1 && byte > 1 {
+            return Err(ProtocolError::InvalidVarint { offset: start });
+        }
+
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            *offset = start + i + 1;
+            return Ok(value);
+        }
+    }
+
+    Err(ProtocolError::InvalidVarint { offset: start })
+}
+
+#[inline]
+pub fn write_zigzag(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, zigzag_encode(value));
+}
+
+#[inline]
+pub fn read_zigzag(bytes: &[u8], offset: &mut usize) -> Result<i64> {
+    read_varint(bytes, offset).map(zigzag_decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zigzag() {
+        for (value, encoded) in [(0i64, 0u64), (-1, 1), (1, 2), (-2, 3), (i64::MAX, u64::MAX - 1), (i64::MIN, u64::MAX)] {
+            assert_eq!(zigzag_encode(value), encoded);
+            assert_eq!(zigzag_decode(encoded), value);
+        }
+    }
+
+    #[test]
+    fn test_varint_roundtrip() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            write_varint(&mut out, value);
+            let mut offset = 0;
+            assert_eq!(read_varint(&out, &mut offset).unwrap(), value);
+            assert_eq!(offset, out.len());
+        }
+    }
+
+    #[test]
+    fn test_varint_len() {
+        for value in [0u64, 1, 127, 128, 16_383, 16_384, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            write_varint(&mut out, value);
+            assert_eq!(varint_len(value), out.len());
+        }
+        assert_eq!(zigzag_len(-64), 1);
+        assert_eq!(zigzag_len(64), 2);
+        assert_eq!(zigzag_len(i64::MIN), MAX_VARINT_LEN);
+    }
+
+    #[test]
+    fn test_varint_malformed() {
+        let mut offset = 0;
+        assert!(matches!(read_varint(&[0x80, 0x80], &mut offset), Err(ProtocolError::BufferTooSmall { .. })));
+        assert!(matches!(read_varint(&[0xFF; 11], &mut offset), Err(ProtocolError::InvalidVarint { offset: 0 })));
+    }
+}
@@ -0,0 +1,590 @@
+//! Borrowed, zero-copy views over encoded messages
+//!
+//! Views read fields straight out of the receive buffer instead of copying into an
+//! owned struct, and need no alignment. How much is checked up front is chosen with
+//! [`ValidationLevel`]; anything skipped can still be checked later with
+//! `validate_checksum`.
+
+use crate::CompressedString;
+use crate::Exchange;
+use crate::ValidationLevel;
+use crate::check_schema_version;
+use crate::checksum;
+use crate::compressed_string::EncodingScheme;
+use crate::errors::ProtocolError;
+use crate::errors::Result;
+use crate::fixed_point::Scale;
+use crate::messages::MarketDataMessage;
+use crate::messages::UpdateType;
+use crate::orderbook_message::OrderBookBatchMessage;
+use crate::orderbook_message::PriceLevel;
+use crate::orderbook_message::header_scale;
+use crate::orderbook_message::read_delta_level;
+use crate::orderbook_message::read_delta_tick;
+
+#[inline]
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+#[inline]
+fn read_i64(bytes: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+#[inline]
+fn encoding_from_bits(bits: u8) -> EncodingScheme {
+    match bits & 0x3 {
+        0 => EncodingScheme::Hex4Bit,
+        1 => EncodingScheme::Alphabetic5Bit,
+        2 => EncodingScheme::AlphaNumeric6Bit,
+        _ => EncodingScheme::Ascii7Bit,
+    }
+}
+
+/// Borrowed view of an encoded [`MarketDataMessage`]
+#[derive(Debug, Clone, Copy)]
+pub struct MarketDataMessageRef<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> MarketDataMessageRef<'a> {
+    const SEQUENCE_OFFSET: usize = 8;
+    const SYMBOL_OFFSET: usize = 16;
+    const TIMESTAMP_OFFSET: usize = 32;
+    const BID_PRICE_OFFSET: usize = 40;
+    const ASK_PRICE_OFFSET: usize = 48;
+    const BID_SIZE_OFFSET: usize = 56;
+    const ASK_SIZE_OFFSET: usize = 64;
+    const CRC_OFFSET: usize = 72;
+
+    pub fn new(bytes: &'a [u8], level: ValidationLevel) -> Result<Self> {
+        if bytes.len() < MarketDataMessage::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: MarketDataMessage::SIZE, actual: bytes.len() });
+        }
+
+        let view = Self { bytes: &bytes[..MarketDataMessage::SIZE] };
+
+        if level == ValidationLevel::None {
+            return Ok(view);
+        }
+
+        check_schema_version(bytes[crate::SCHEMA_VERSION_OFFSET])?;
+        view.validate_basic()?;
+
+        if level == ValidationLevel::Basic {
+            return Ok(view);
+        }
+
+        view.validate_checksum()?;
+
+        if level == ValidationLevel::Strict && bytes.len() != MarketDataMessage::SIZE {
+            return Err(ProtocolError::InvalidLength { expected: MarketDataMessage::SIZE, actual: bytes.len() });
+        }
+
+        Ok(view)
+    }
+
+    #[inline]
+    pub fn header(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.header() >> 7) & 1
+    }
+
+    #[inline]
+    pub fn update_type(&self) -> UpdateType {
+        if (self.header() >> 6) & 1 == 0 { UpdateType::Snapshot } else { UpdateType::Update }
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        let id = (self.header() >> 2) & 0xF;
+        Exchange::from_u8(id).ok_or(ProtocolError::InvalidExchange { id })
+    }
+
+    #[inline]
+    pub fn encoding_scheme(&self) -> EncodingScheme {
+        encoding_from_bits(self.header())
+    }
+
+    #[inline]
+    pub fn version(&self) -> u8 {
+        self.bytes[crate::SCHEMA_VERSION_OFFSET]
+    }
+
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        read_u64(self.bytes, Self::SEQUENCE_OFFSET)
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: read_u64(self.bytes, Self::SYMBOL_OFFSET), high: read_u64(self.bytes, Self::SYMBOL_OFFSET + 8) }
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> u64 {
+        read_u64(self.bytes, Self::TIMESTAMP_OFFSET)
+    }
+
+    #[inline]
+    pub fn bid_price(&self) -> i64 {
+        read_i64(self.bytes, Self::BID_PRICE_OFFSET)
+    }
+
+    #[inline]
+    pub fn ask_price(&self) -> i64 {
+        read_i64(self.bytes, Self::ASK_PRICE_OFFSET)
+    }
+
+    #[inline]
+    pub fn bid_size(&self) -> i64 {
+        read_i64(self.bytes, Self::BID_SIZE_OFFSET)
+    }
+
+    #[inline]
+    pub fn ask_size(&self) -> i64 {
+        read_i64(self.bytes, Self::ASK_SIZE_OFFSET)
+    }
+
+    #[inline]
+    pub fn crc32(&self) -> u32 {
+        read_u32(self.bytes, Self::CRC_OFFSET)
+    }
+
+    pub fn validate_basic(&self) -> Result<()> {
+        let msg_type = self.message_type();
+        if msg_type != 0 {
+            return Err(ProtocolError::InvalidMessageType { msg_type });
+        }
+        self.exchange()?;
+        Ok(())
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let calculated = checksum::calculate_crc32c(&self.bytes[..Self::CRC_OFFSET]);
+        let expected = self.crc32();
+        if calculated != expected {
+            return Err(ProtocolError::InvalidChecksum { expected, actual: calculated });
+        }
+        Ok(())
+    }
+
+    /// Raw encoded bytes backing this view
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Copy into an owned message
+    pub fn to_message(&self) -> MarketDataMessage {
+        let mut pad = [0u8; 6];
+        pad.copy_from_slice(&self.bytes[1..7]);
+        MarketDataMessage {
+            header: self.header(),
+            _pad: pad,
+            version: self.version(),
+            sequence: self.sequence(),
+            symbol_low: read_u64(self.bytes, Self::SYMBOL_OFFSET),
+            symbol_high: read_u64(self.bytes, Self::SYMBOL_OFFSET + 8),
+            timestamp: self.timestamp(),
+            bid_price: self.bid_price(),
+            ask_price: self.ask_price(),
+            bid_size: self.bid_size(),
+            ask_size: self.ask_size(),
+            crc32: self.crc32(),
+            _final_pad: [0; 4],
+        }
+    }
+}
+
+/// Borrowed view of an encoded [`OrderBookBatchMessage`]
+#[derive(Debug, Clone, Copy)]
+pub struct OrderBookBatchView<'a> {
+    bytes: &'a [u8],
+    num_bids: usize,
+    num_asks: usize,
+    bids_offset: usize,
+    asks_offset: usize,
+    /// Delta-encoding tick (0 = fixed 16-byte levels)
+    tick: i64,
+}
+
+impl<'a> OrderBookBatchView<'a> {
+    pub fn new(bytes: &'a [u8], level: ValidationLevel) -> Result<Self> {
+        let header_size = OrderBookBatchMessage::HEADER_SIZE;
+        if bytes.len() < header_size + 4 {
+            return Err(ProtocolError::BufferTooSmall { required: header_size + 4, actual: bytes.len() });
+        }
+
+        // Level sections are always bounds-checked so accessors can't read past the buffer
+        let num_bids = u16::from_le_bytes([bytes[4], bytes[5]]) as usize;
+        let num_asks = u16::from_le_bytes([bytes[6], bytes[7]]) as usize;
+
+        let (bids_offset, asks_offset, body_end, tick) =
+            if bytes[OrderBookBatchMessage::FLAGS_OFFSET] & OrderBookBatchMessage::FLAG_DELTA_ENCODED != 0 {
+                let body = &bytes[..bytes.len() - 4];
+                let mut offset = header_size;
+                let tick = read_delta_tick(body, &mut offset)?;
+                let bids_offset = offset;
+                skip_delta_levels(body, &mut offset, num_bids, tick)?;
+                let asks_offset = offset;
+                skip_delta_levels(body, &mut offset, num_asks, tick)?;
+                (bids_offset, asks_offset, offset, tick)
+            } else {
+                let asks_offset = header_size + num_bids * PriceLevel::SIZE;
+                (header_size, asks_offset, asks_offset + num_asks * PriceLevel::SIZE, 0)
+            };
+
+        let frame_len = body_end + 4;
+        if bytes.len() < frame_len {
+            return Err(ProtocolError::BufferTooSmall { required: frame_len, actual: bytes.len() });
+        }
+
+        let view = Self { bytes: &bytes[..frame_len], num_bids, num_asks, bids_offset, asks_offset, tick };
+
+        if level == ValidationLevel::None {
+            return Ok(view);
+        }
+
+        if bytes[0] != OrderBookBatchMessage::MESSAGE_TYPE {
+            return Err(ProtocolError::InvalidMessageType { msg_type: bytes[0] });
+        }
+        check_schema_version(bytes[OrderBookBatchMessage::VERSION_OFFSET])?;
+        view.exchange()?;
+        if bytes[OrderBookBatchMessage::FLAGS_OFFSET] & OrderBookBatchMessage::FLAG_SCALED != 0 {
+            Scale::try_from(bytes[66])?;
+            Scale::try_from(bytes[67])?;
+        }
+
+        if level == ValidationLevel::Basic {
+            return Ok(view);
+        }
+
+        view.validate_checksum()?;
+
+        if level == ValidationLevel::Strict && bytes.len() != frame_len {
+            return Err(ProtocolError::InvalidLength { expected: frame_len, actual: bytes.len() });
+        }
+
+        Ok(view)
+    }
+
+    #[inline]
+    pub fn exchange(&self) -> Result<Exchange> {
+        Exchange::from_u8(self.bytes[1]).ok_or(ProtocolError::InvalidExchange { id: self.bytes[1] })
+    }
+
+    #[inline]
+    pub fn update_type(&self) -> UpdateType {
+        if self.bytes[2] == 0 { UpdateType::Snapshot } else { UpdateType::Update }
+    }
+
+    #[inline]
+    pub fn encoding(&self) -> EncodingScheme {
+        encoding_from_bits(self.bytes[3])
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> CompressedString {
+        CompressedString { low: read_u64(self.bytes, 8), high: read_u64(self.bytes, 16) }
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> u64 {
+        read_u64(self.bytes, 24)
+    }
+
+    #[inline]
+    pub fn first_update_id(&self) -> u64 {
+        read_u64(self.bytes, 32)
+    }
+
+    #[inline]
+    pub fn final_update_id(&self) -> u64 {
+        read_u64(self.bytes, 40)
+    }
+
+    #[inline]
+    pub fn prev_update_id(&self) -> u64 {
+        read_u64(self.bytes, 48)
+    }
+
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        read_u64(self.bytes, 56)
+    }
+
+    #[inline]
+    pub fn version(&self) -> u8 {
+        self.bytes[OrderBookBatchMessage::VERSION_OFFSET]
+    }
+
+    #[inline]
+    pub fn instrument_id(&self) -> u32 {
+        read_u32(self.bytes, 68)
+    }
+
+    #[inline]
+    pub fn price_scale(&self) -> Scale {
+        header_scale(self.bytes[OrderBookBatchMessage::FLAGS_OFFSET], self.bytes[66])
+    }
+
+    #[inline]
+    pub fn qty_scale(&self) -> Scale {
+        header_scale(self.bytes[OrderBookBatchMessage::FLAGS_OFFSET], self.bytes[67])
+    }
+
+    #[inline]
+    pub fn is_delta_encoded(&self) -> bool {
+        self.tick > 0
+    }
+
+    #[inline]
+    pub fn num_bids(&self) -> usize {
+        self.num_bids
+    }
+
+    #[inline]
+    pub fn num_asks(&self) -> usize {
+        self.num_asks
+    }
+
+    /// Bid levels in the order they were encoded
+    #[inline]
+    pub fn bids(&self) -> PriceLevelIter<'a> {
+        PriceLevelIter {
+            bytes: &self.bytes[..self.asks_offset],
+            offset: self.bids_offset,
+            remaining: self.num_bids,
+            tick: self.tick,
+            anchor: None,
+        }
+    }
+
+    /// Ask levels in the order they were encoded
+    #[inline]
+    pub fn asks(&self) -> PriceLevelIter<'a> {
+        let end = self.bytes.len() - 4;
+        PriceLevelIter { bytes: &self.bytes[..end], offset: self.asks_offset, remaining: self.num_asks, tick: self.tick, anchor: None }
+    }
+
+    #[inline]
+    pub fn crc32(&self) -> u32 {
+        read_u32(self.bytes, self.bytes.len() - 4)
+    }
+
+    pub fn validate_checksum(&self) -> Result<()> {
+        let crc_offset = self.bytes.len() - 4;
+        let calculated = checksum::calculate_crc32c(&self.bytes[..crc_offset]);
+        let expected = self.crc32();
+        if calculated != expected {
+            return Err(ProtocolError::InvalidChecksum { expected, actual: calculated });
+        }
+        Ok(())
+    }
+
+    /// Raw encoded bytes backing this view (exactly one frame)
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Copy into an owned message
+    pub fn to_message(&self) -> Result<OrderBookBatchMessage> {
+        let mut msg = OrderBookBatchMessage::new_with_ids(
+            self.exchange()?,
+            self.update_type(),
+            self.symbol(),
+            self.encoding(),
+            self.timestamp(),
+            self.first_update_id(),
+            self.final_update_id(),
+            self.prev_update_id(),
+        );
+        msg.set_sequence(self.sequence());
+        msg.set_instrument_id(self.instrument_id());
+        msg.set_delta_encoding(self.tick);
+        if self.bytes[OrderBookBatchMessage::FLAGS_OFFSET] & OrderBookBatchMessage::FLAG_SCALED != 0 {
+            msg.set_scales(self.price_scale(), self.qty_scale());
+        }
+        msg.add_bids(self.bids().map(|level| (level.price, level.size)));
+        msg.add_asks(self.asks().map(|level| (level.price, level.size)));
+        Ok(msg)
+    }
+}
+
+/// Iterator over encoded price levels, fixed or delta encoded
+#[derive(Debug, Clone)]
+pub struct PriceLevelIter<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    remaining: usize,
+    tick: i64,
+    anchor: Option<i64>,
+}
+
+impl Iterator for PriceLevelIter<'_> {
+    type Item = PriceLevel;
+
+    #[inline]
+    fn next(&mut self) -> Option<PriceLevel> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        if self.tick == 0 {
+            let level = PriceLevel::new(read_i64(self.bytes, self.offset), read_i64(self.bytes, self.offset + 8));
+            self.offset += PriceLevel::SIZE;
+            return Some(level);
+        }
+
+        // Bounds were checked when the view was created
+        let level = read_delta_level(self.bytes, &mut self.offset, self.anchor, self.tick).ok()?;
+        self.anchor.get_or_insert(level.price);
+        Some(level)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for PriceLevelIter<'_> {}
+
+fn skip_delta_levels(bytes: &[u8], offset: &mut usize, count: usize, tick: i64) -> Result<()> {
+    let mut anchor = None;
+    for _ in 0..count {
+        let level = read_delta_level(bytes, offset, anchor, tick)?;
+        anchor.get_or_insert(level.price);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market_data() -> MarketDataMessage {
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        MarketDataMessage::new_with_sequence(Exchange::Kraken, UpdateType::Update, symbol, encoding, 123, 100, 101, 5, 6, 77)
+    }
+
+    fn batch() -> OrderBookBatchMessage {
+        let (symbol, encoding) = CompressedString::from_str("ETHUSDT").unwrap();
+        let mut batch = OrderBookBatchMessage::new_with_ids(Exchange::Binance, UpdateType::Update, symbol, encoding, 456, 10, 12, 9);
+        batch.set_sequence(3);
+        batch.add_bids([(200, 1), (199, 2)]);
+        batch.add_ask(201, 3);
+        batch
+    }
+
+    #[test]
+    fn test_market_data_ref_unaligned() {
+        let msg = market_data();
+        let mut buffer = [0u8; MarketDataMessage::SIZE + 1];
+        buffer[1..].copy_from_slice(&msg.to_bytes());
+
+        let view = MarketDataMessageRef::new(&buffer[1..], ValidationLevel::Standard).unwrap();
+        assert_eq!(view.exchange().unwrap(), Exchange::Kraken);
+        assert_eq!(view.update_type(), UpdateType::Update);
+        assert_eq!(view.symbol().decode(view.encoding_scheme()), "BTCUSDT");
+        assert_eq!(view.sequence(), 77);
+        assert_eq!(view.timestamp(), 123);
+        assert_eq!((view.bid_price(), view.ask_price(), view.bid_size(), view.ask_size()), (100, 101, 5, 6));
+        assert_eq!(view.to_message().to_bytes(), msg.to_bytes());
+    }
+
+    #[test]
+    fn test_market_data_ref_lazy_checksum() {
+        let mut bytes = market_data().to_bytes();
+        bytes[40] ^= 0xFF;
+
+        assert!(matches!(MarketDataMessageRef::new(&bytes, ValidationLevel::Standard), Err(ProtocolError::InvalidChecksum { .. })));
+
+        let view = MarketDataMessageRef::new(&bytes, ValidationLevel::Basic).unwrap();
+        assert!(view.validate_checksum().is_err());
+    }
+
+    #[test]
+    fn test_market_data_ref_strict_length() {
+        let mut bytes = market_data().to_bytes().to_vec();
+        bytes.push(0);
+        assert!(MarketDataMessageRef::new(&bytes, ValidationLevel::Standard).is_ok());
+        assert!(MarketDataMessageRef::new(&bytes, ValidationLevel::Strict).is_err());
+    }
+
+    #[test]
+    fn test_orderbook_batch_view() {
+        let msg = batch();
+        let bytes = msg.to_bytes();
+        let view = OrderBookBatchView::new(&bytes, ValidationLevel::Strict).unwrap();
+
+        assert_eq!(view.exchange().unwrap(), Exchange::Binance);
+        assert_eq!(view.symbol().decode(view.encoding()), "ETHUSDT");
+        assert_eq!((view.first_update_id(), view.final_update_id(), view.prev_update_id()), (10, 12, 9));
+        assert_eq!(view.sequence(), 3);
+        assert_eq!(view.bids().len(), 2);
+
+        let bids: Vec<(i64, i64)> = view.bids().map(|level| (level.price, level.size)).collect();
+        let asks: Vec<(i64, i64)> = view.asks().map(|level| (level.price, level.size)).collect();
+        assert_eq!(bids, vec![(200, 1), (199, 2)]);
+        assert_eq!(asks, vec![(201, 3)]);
+
+        assert_eq!(view.to_message().unwrap().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_orderbook_batch_view_truncated_levels() {
+        let bytes = batch().to_bytes();
+        for level in [ValidationLevel::None, ValidationLevel::Standard] {
+            assert!(matches!(OrderBookBatchView::new(&bytes[..bytes.len() - 8], level), Err(ProtocolError::BufferTooSmall { .. })));
+        }
+    }
+
+    #[test]
+    fn test_orderbook_batch_view_lazy_checksum() {
+        let mut bytes = batch().to_bytes();
+        let last_level = bytes.len() - 8;
+        bytes[last_level] ^= 0x01;
+
+        assert!(OrderBookBatchView::new(&bytes, ValidationLevel::Standard).is_err());
+        let view = OrderBookBatchView::new(&bytes, ValidationLevel::None).unwrap();
+        assert!(view.validate_checksum().is_err());
+    }
+
+    #[test]
+    fn test_orderbook_batch_view_delta_encoded() {
+        let mut msg = batch();
+        msg.set_delta_encoding(1);
+        let bytes = msg.to_bytes();
+
+        let view = OrderBookBatchView::new(&bytes, ValidationLevel::Strict).unwrap();
+        assert!(view.is_delta_encoded());
+        assert_eq!(view.asks().len(), 1);
+
+        let bids: Vec<(i64, i64)> = view.bids().map(|level| (level.price, level.size)).collect();
+        let asks: Vec<(i64, i64)> = view.asks().map(|level| (level.price, level.size)).collect();
+        assert_eq!(bids, vec![(200, 1), (199, 2)]);
+        assert_eq!(asks, vec![(201, 3)]);
+        assert_eq!(view.to_message().unwrap().to_bytes(), bytes);
+
+        // Truncated frames must be rejected or stay in bounds, never panic
+        for len in 0..bytes.len() {
+            if let Ok(view) = OrderBookBatchView::new(&bytes[..len], ValidationLevel::None) {
+                assert!(view.bids().count() + view.asks().count() <= 3);
+            }
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Frozen wire layouts. A failure here means the byte layout of a message changed:
+//! bump `SCHEMA_VERSION` and regenerate the fixture instead of editing it by hand.
+
+use mm_binary::CollectorState;
+use mm_binary::CollectorStateMessage;
+use mm_binary::CompressedString;
+use mm_binary::Exchange;
+use mm_binary::FundingRateMessage;
+use mm_binary::HeartbeatMessage;
+use mm_binary::KillSwitchMessage;
+use mm_binary::LiquidationMessage;
+use mm_binary::MIN_SCHEMA_VERSION;
+use mm_binary::MarkPriceMessage;
+use mm_binary::MarketDataMessage;
+use mm_binary::OpenInterestMessage;
+use mm_binary::OrderBookBatchMessage;
+use mm_binary::PricingOutputMessage;
+use mm_binary::ProtocolError;
+use mm_binary::QuoteLadderMessage;
+use mm_binary::ResumeMessage;
+use mm_binary::RiskEventMessage;
+use mm_binary::SCHEMA_VERSION;
+use mm_binary::SCHEMA_VERSION_OFFSET;
+use mm_binary::StrategyParamUpdateMessage;
+use mm_binary::compressed_string::EncodingScheme;
+use mm_binary::control_messages::KillReason;
+use mm_binary::control_messages::RiskEventKind;
+use mm_binary::control_messages::StrategyParam;
+use mm_binary::messages::OrderFillMessage;
+use mm_binary::messages::OrderSide;
+use mm_binary::messages::PositionMessage;
+use mm_binary::messages::QuoteMessage;
+use mm_binary::messages::TradeMessage;
+use mm_binary::messages::TradeSide;
+use mm_binary::messages::UpdateType;
+use mm_binary::order_messages::CancelOrderMessage;
+use mm_binary::order_messages::NewOrderMessage;
+use mm_binary::order_messages::OrderAckMessage;
+use mm_binary::order_messages::OrderFlags;
+use mm_binary::order_messages::OrderRejectMessage;
+use mm_binary::order_messages::OrderRequestType;
+use mm_binary::order_messages::RejectReason;
+use mm_binary::order_messages::ReplaceOrderMessage;
+use mm_binary::order_messages::TimeInForce;
+
+const TIMESTAMP: u64 = 1_700_000_000_000;
+
+fn symbol() -> (CompressedString, EncodingScheme) {
+    CompressedString::from_str("BTCUSDT").unwrap()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn assert_golden(name: &str, bytes: &[u8], expected: &str) {
+    assert_eq!(hex(bytes), expected, "{} layout changed", name);
+}
+
+#[repr(C, align(16))]
+struct Aligned<const N: usize>([u8; N]);
+
+fn market_data() -> [u8; MarketDataMessage::SIZE] {
+    let (symbol, encoding) = symbol();
+    MarketDataMessage::new_with_sequence(
+        Exchange::Binance,
+        UpdateType::Update,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        5_000_000_000_000,
+        5_000_100_000_000,
+        150_000_000,
+        250_000_000,
+        42,
+    )
+    .to_bytes()
+}
+
+#[test]
+fn golden_market_data() {
+    assert_golden(
+        "MarketDataMessage",
+        &market_data(),
+        "41000000000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000005039278c04000000312f2d8c04000080d1f0080000000080b2e60e00000000bdcbecc400000000",
+    );
+}
+
+#[test]
+fn golden_pricing_output() {
+    let (symbol, encoding) = symbol();
+    let msg = PricingOutputMessage::new_with_sequence(1, symbol, encoding, TIMESTAMP, 5_000_050_000_000, 95_000_000, 2_000_000, 42);
+    assert_golden(
+        "PricingOutputMessage",
+        &msg.to_bytes(),
+        "85000000000000062a00000000000000828e3a090500000000000000000000000068e5cf8b0100008040342a8c040000c095a9050000000080841e0000000000f15af1a3000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_heartbeat() {
+    assert_golden(
+        "HeartbeatMessage",
+        &HeartbeatMessage::new(TIMESTAMP, 42).to_bytes(),
+        "00000000000000060068e5cf8b0100002a00000000000000eab6a80400000000",
+    );
+}
+
+#[test]
+fn golden_collector_state() {
+    let msg = CollectorStateMessage::new(3, CollectorState::Receiving, TIMESTAMP, 1_000_000);
+    assert_golden("CollectorStateMessage", &msg.to_bytes(), "c0030200000000060068e5cf8b01000040420f0000000000bf95d78e00000000");
+}
+
+#[test]
+fn golden_trade() {
+    let (symbol, encoding) = symbol();
+    let msg = TradeMessage::new_with_sequence(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        987_654_321,
+        5_000_000_000_000,
+        10_000_000,
+        TradeSide::Sell,
+        true,
+        42,
+    );
+    assert_golden(
+        "TradeMessage",
+        &msg.to_bytes(),
+        "41010100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000b168de3a00000000005039278c040000809698000000000091cc6e19000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_quote() {
+    let (symbol, encoding) = symbol();
+    let msg = QuoteMessage::new_with_sequence(
+        1,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        4_999_000_000_000,
+        100_000_000,
+        5_001_000_000_000,
+        100_000_000,
+        5_000_000_000_000,
+        -20_000_000,
+        90_000_000,
+        42,
+    );
+    assert_golden(
+        "QuoteMessage",
+        &msg.to_bytes(),
+        "51010000000000062a00000000000000828e3a090500000000000000000000000068e5cf8b01000000869eeb8b04000000e1f50500000000001ad4628c04000000e1f50500000000005039278c04000000d3cefeffffffff804a5d0500000000f31eeb54000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_position() {
+    let (symbol, encoding) = symbol();
+    let msg = PositionMessage::new_with_sequence(symbol, encoding, TIMESTAMP, 50_000_000, 4_990_000_000_000, 500_000_000, -120_000_000, 42)
+        .with_costs(2_500_000, 1_000_000, -300_000);
+    assert_golden(
+        "PositionMessage",
+        &msg.to_bytes(),
+        "61000000000000062a00000000000000828e3a090500000000000000000000000068e5cf8b01000080f0fa0200000000006c2dd3890400000065cd1d0000000000f2d8f8ffffffffa02526000000000040420f0000000000206cfbffffffffff52429008000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_order_fill() {
+    let (symbol, encoding) = symbol();
+    let msg = OrderFillMessage::new_with_sequence(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        7,
+        5_000_000_000_000,
+        10_000_000,
+        OrderSide::Ask,
+        true,
+        42,
+    );
+    assert_golden(
+        "OrderFillMessage",
+        &msg.to_bytes(),
+        "71010100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b0100000700000000000000005039278c04000080969800000000000b2589db000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_orderbook_batch() {
+    let (symbol, encoding) = symbol();
+    let mut msg = OrderBookBatchMessage::new_with_ids(Exchange::Binance, UpdateType::Update, symbol, encoding, TIMESTAMP, 100, 105, 99);
+    msg.set_sequence(42);
+    msg.add_bid(5_000_000_000_000, 150_000_000);
+    msg.add_ask(5_000_100_000_000, 250_000_000);
+    assert_golden(
+        "OrderBookBatchMessage",
+        &msg.to_bytes(),
+        "0300010101000100828e3a090500000000000000000000000068e5cf8b0100006400000000000000690000000000000063000000000000002a000000000000000600000000000000005039278c04000080d1f0080000000000312f2d8c04000080b2e60e000000007716e86d",
+    );
+}
+
+#[test]
+fn golden_orderbook_batch_delta() {
+    let (symbol, encoding) = symbol();
+    let mut msg = OrderBookBatchMessage::new_with_ids(Exchange::Binance, UpdateType::Snapshot, symbol, encoding, TIMESTAMP, 100, 105, 99);
+    msg.set_sequence(42);
+    msg.set_delta_encoding(1_000_000);
+    msg.add_bids([(5_000_000_000_000, 150_000_000), (4_999_999_000_000, 20_000_000)]);
+    msg.add_asks([(5_000_001_000_000, 250_000_000), (5_000_003_000_000, 1_000)]);
+    assert_golden(
+        "OrderBookBatchMessage (delta)",
+        &msg.to_bytes(),
+        "0300000102000200828e3a090500000000000000000000000068e5cf8b0100006400000000000000690000000000000063000000000000002a000000000000000601000000000000c0843d80c0caf384a30280c6868f010180b4891380c9c4f484a30280cab5ee0104d00f6c0a11ec",
+    );
+}
+
+#[test]
+fn golden_quote_ladder() {
+    let (symbol, encoding) = symbol();
+    let mut msg = QuoteLadderMessage::new(1, symbol, encoding, TIMESTAMP, 7);
+    msg.set_sequence(42);
+    msg.set_valuation(5_000_050_000_000, -10_000_000, 90_000_000);
+    msg.add_bid(5_000_000_000_000, 10_000_000);
+    msg.add_bid(4_999_000_000_000, 5_000_000);
+    msg.add_ask(5_000_100_000_000, 10_000_000);
+    assert_golden(
+        "QuoteLadderMessage",
+        &msg.to_bytes(),
+        "1701010002000100828e3a090500000000000000000000000068e5cf8b01000007000000000000002a000000000000008040342a8c040000806967ffffffffff0600000000000000804a5d0500000000005039278c040000809698000000000000869eeb8b040000404b4c000000000000312f2d8c0400008096980000000000378b97d0",
+    );
+}
+
+#[test]
+fn golden_new_order() {
+    let (symbol, encoding) = symbol();
+    let msg = NewOrderMessage::new_with_sequence(
+        Exchange::Binance,
+        1,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        1001,
+        OrderSide::Bid,
+        5_000_000_000_000,
+        10_000_000,
+        TimeInForce::Gtc,
+        OrderFlags::POST_ONLY,
+        42,
+    );
+    assert_golden(
+        "NewOrderMessage",
+        &msg.to_bytes(),
+        "81000000010100062a00000000000000828e3a090500000000000000000000000068e5cf8b010000e903000000000000005039278c0400008096980000000000466cfabd000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_cancel_order() {
+    let (symbol, encoding) = symbol();
+    let msg = CancelOrderMessage::new_with_sequence(Exchange::Binance, 1, symbol, encoding, TIMESTAMP, 1001, 42);
+    assert_golden(
+        "CancelOrderMessage",
+        &msg.to_bytes(),
+        "91000100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000e903000000000000c8e89208000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_replace_order() {
+    let (symbol, encoding) = symbol();
+    let msg = ReplaceOrderMessage::new_with_sequence(
+        Exchange::Binance,
+        1,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        1001,
+        1002,
+        OrderSide::Ask,
+        5_001_000_000_000,
+        20_000_000,
+        TimeInForce::Ioc,
+        OrderFlags::REDUCE_ONLY,
+        42,
+    );
+    assert_golden(
+        "ReplaceOrderMessage",
+        &msg.to_bytes(),
+        "a1000101020100062a00000000000000828e3a090500000000000000000000000068e5cf8b010000e903000000000000ea03000000000000001ad4628c040000002d310100000000dfc5a87600000000",
+    );
+}
+
+#[test]
+fn golden_order_ack() {
+    let (symbol, encoding) = symbol();
+    let msg = OrderAckMessage::new_with_sequence(
+        Exchange::Binance,
+        OrderRequestType::New,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        1001,
+        555_000,
+        OrderSide::Bid,
+        5_000_000_000_000,
+        10_000_000,
+        42,
+    );
+    assert_golden(
+        "OrderAckMessage",
+        &msg.to_bytes(),
+        "b1000000000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000e903000000000000f877080000000000005039278c0400008096980000000000478fe9e600000000",
+    );
+}
+
+#[test]
+fn golden_order_reject() {
+    let (symbol, encoding) = symbol();
+    let msg = OrderRejectMessage::new_with_sequence(
+        Exchange::Binance,
+        OrderRequestType::Replace,
+        RejectReason::PostOnlyWouldCross,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        1002,
+        42,
+    );
+    assert_golden(
+        "OrderRejectMessage",
+        &msg.to_bytes(),
+        "c1000502000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000ea03000000000000ec70e4df000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_mark_price() {
+    let (symbol, encoding) = symbol();
+    let msg = MarkPriceMessage::new_with_sequence(Exchange::Bybit, symbol, encoding, TIMESTAMP, 5_000_100_000_000, 5_000_000_000_000, 42);
+    assert_golden(
+        "MarkPriceMessage",
+        &msg.to_bytes(),
+        "0f030100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b01000000312f2d8c040000005039278c040000d2dc94bf00000000",
+    );
+}
+
+#[test]
+fn golden_funding_rate() {
+    let (symbol, encoding) = symbol();
+    let msg = FundingRateMessage::new_with_sequence(Exchange::Okx, symbol, encoding, TIMESTAMP, 10_000, TIMESTAMP + 28_800_000, 42);
+    assert_golden(
+        "FundingRateMessage",
+        &msg.to_bytes(),
+        "10040100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000102700000000000000dc9cd18b01000015a994c900000000",
+    );
+}
+
+#[test]
+fn golden_open_interest() {
+    let (symbol, encoding) = symbol();
+    let msg =
+        OpenInterestMessage::new_with_sequence(Exchange::Bitget, symbol, encoding, TIMESTAMP, 123_450_000_000, 617_250_000_000_000, 42);
+    assert_golden(
+        "OpenInterestMessage",
+        &msg.to_bytes(),
+        "11090100000000062a00000000000000828e3a090500000000000000000000000068e5cf8b010000808231be1c00000000d4fcb6623102005c4b53a500000000",
+    );
+}
+
+#[test]
+fn golden_liquidation() {
+    let (symbol, encoding) = symbol();
+    let msg = LiquidationMessage::new_with_sequence(
+        Exchange::Binance,
+        symbol,
+        encoding,
+        TIMESTAMP,
+        TradeSide::Sell,
+        4_900_000_000_000,
+        4_901_000_000_000,
+        50_000_000,
+        50_000_000,
+        42,
+    );
+    assert_golden(
+        "LiquidationMessage",
+        &msg.to_bytes(),
+        "12000101000000062a00000000000000828e3a090500000000000000000000000068e5cf8b0100000068c2de7404000000325d1a7504000080f0fa020000000080f0fa0200000000ccfbc41700000000",
+    );
+}
+
+#[test]
+fn golden_kill_switch() {
+    let msg = KillSwitchMessage::new_with_sequence(3, 7, KillReason::HeartbeatTimeout, TIMESTAMP, 42);
+    assert_golden(
+        "KillSwitchMessage",
+        &msg.to_bytes(),
+        "13030100000000062a000000000000000068e5cf8b01000007000000a20892d400000000000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_resume() {
+    let msg = ResumeMessage::new_with_sequence(3, 7, TIMESTAMP, 42);
+    assert_golden(
+        "ResumeMessage",
+        &msg.to_bytes(),
+        "14030000000000062a000000000000000068e5cf8b0100000700000032a6609900000000000000000000000000000000",
+    );
+}
+
+#[test]
+fn golden_strategy_param_update() {
+    let msg = StrategyParamUpdateMessage::new_with_sequence(3, 7, StrategyParam::MinSpreadBps, 12.5, TIMESTAMP, 42);
+    assert_golden(
+        "StrategyParamUpdateMessage",
+        &msg.to_bytes(),
+        "15030000000000062a000000000000000068e5cf8b010000000000000000294007000000f688f93c0000000000000000",
+    );
+}
+
+#[test]
+fn golden_risk_event() {
+    let msg = RiskEventMessage::new_with_sequence(3, 0, RiskEventKind::DailyLossBreached, 0, -1250.5, -1000.0, TIMESTAMP, 42);
+    assert_golden(
+        "RiskEventMessage",
+        &msg.to_bytes(),
+        "16030400000000062a000000000000000068e5cf8b01000000000000008a93c00000000000408fc0000000007ff233c400000000000000000000000000000000",
+    );
+}
+
+#[test]
+fn newer_schema_version_is_rejected() {
+    let mut bytes = Aligned(market_data());
+    bytes.0[SCHEMA_VERSION_OFFSET] = SCHEMA_VERSION + 1;
+    assert!(matches!(MarketDataMessage::from_bytes(&bytes.0), Err(ProtocolError::UnsupportedSchemaVersion { .. })));
+}
+
+#[test]
+fn unversioned_layout_is_rejected() {
+    let mut bytes = Aligned(market_data());
+    bytes.0[SCHEMA_VERSION_OFFSET] = 0;
+    assert!(matches!(MarketDataMessage::from_bytes(&bytes.0), Err(ProtocolError::IncompatibleSchemaVersion { .. })));
+}
+
+#[test]
+fn pre_envelope_layout_is_rejected() {
+    let mut bytes = Aligned(market_data());
+    bytes.0[SCHEMA_VERSION_OFFSET] = MIN_SCHEMA_VERSION - 1;
+    assert!(matches!(MarketDataMessage::from_bytes(&bytes.0), Err(ProtocolError::IncompatibleSchemaVersion { version: 2, .. })));
+}
+
+/// PositionMessage as written at schema version 5, before the fee, rebate and funding totals
+const POSITION_V5: &str = "61000000000000052a00000000000000828e3a090500000000000000000000000068e5cf8b01000080f0fa0200000000006c2dd3890400000065cd1d0000000000f2d8f8ffffffffc7e6130500000000";
+
+#[test]
+fn previous_position_layout_is_rejected() {
+    let mut bytes = Aligned([0u8; PositionMessage::SIZE]);
+    for (i, byte) in bytes.0.iter_mut().enumerate().take(POSITION_V5.len() / 2) {
+        *byte = u8::from_str_radix(&POSITION_V5[2 * i..2 * i + 2], 16).unwrap();
+    }
+    assert!(matches!(PositionMessage::from_bytes(&bytes.0[..80]), Err(ProtocolError::IncompatibleSchemaVersion { version: 5, min: 6 })));
+    // Even with room for the new layout, a version 5 frame is not misread
+    assert!(matches!(PositionMessage::from_bytes(&bytes.0), Err(ProtocolError::IncompatibleSchemaVersion { version: 5, .. })));
+}
+
+#[test]
+fn orderbook_batch_version_is_checked() {
+    let (symbol, encoding) = symbol();
+    let mut bytes = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Snapshot, symbol, encoding, TIMESTAMP).to_bytes();
+    assert_eq!(OrderBookBatchMessage::from_bytes(&bytes).unwrap().version(), SCHEMA_VERSION);
+
+    bytes[OrderBookBatchMessage::VERSION_OFFSET] = SCHEMA_VERSION + 1;
+    assert!(matches!(OrderBookBatchMessage::from_bytes(&bytes), Err(ProtocolError::UnsupportedSchemaVersion { .. })));
+}
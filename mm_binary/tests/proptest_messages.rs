@@ -0,0 +1,573 @@
+//! Property tests for every message codec: round-trips, single-bit corruption,
+//! and malformed buffers that must fail with a `ProtocolError` rather than panic.
+
+use mm_binary::CollectorState;
+use mm_binary::CollectorStateMessage;
+use mm_binary::CompressedString;
+use mm_binary::Exchange;
+use mm_binary::FundingRateMessage;
+use mm_binary::HeartbeatMessage;
+use mm_binary::KillSwitchMessage;
+use mm_binary::LatencyTrailer;
+use mm_binary::LiquidationMessage;
+use mm_binary::MarkPriceMessage;
+use mm_binary::MarketDataMessage;
+use mm_binary::OpenInterestMessage;
+use mm_binary::OrderBookBatchMessage;
+use mm_binary::PricingOutputMessage;
+use mm_binary::QuoteLadderMessage;
+use mm_binary::ResumeMessage;
+use mm_binary::RiskEventMessage;
+use mm_binary::StrategyParamUpdateMessage;
+use mm_binary::compressed_string::EncodingScheme;
+use mm_binary::control_messages::KillReason;
+use mm_binary::control_messages::RiskEventKind;
+use mm_binary::control_messages::StrategyParam;
+use mm_binary::latency::HopTimestamps;
+use mm_binary::latency::PipelineStage;
+use mm_binary::messages::OrderFillMessage;
+use mm_binary::messages::OrderSide;
+use mm_binary::messages::PositionMessage;
+use mm_binary::messages::QuoteMessage;
+use mm_binary::messages::TradeMessage;
+use mm_binary::messages::TradeSide;
+use mm_binary::messages::UpdateType;
+use mm_binary::order_messages::CancelOrderMessage;
+use mm_binary::order_messages::NewOrderMessage;
+use mm_binary::order_messages::OrderAckMessage;
+use mm_binary::order_messages::OrderFlags;
+use mm_binary::order_messages::OrderRejectMessage;
+use mm_binary::order_messages::OrderRequestType;
+use mm_binary::order_messages::RejectReason;
+use mm_binary::order_messages::ReplaceOrderMessage;
+use mm_binary::order_messages::TimeInForce;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Room for the largest fixed message plus oversized tails
+const BUFFER_SIZE: usize = 256;
+
+#[repr(C, align(16))]
+struct Aligned([u8; BUFFER_SIZE]);
+
+impl Aligned {
+    fn new(bytes: &[u8]) -> Self {
+        let mut buffer = Aligned([0u8; BUFFER_SIZE]);
+        buffer.0[..bytes.len()].copy_from_slice(bytes);
+        buffer
+    }
+}
+
+fn symbol() -> impl Strategy<Value = (CompressedString, EncodingScheme)> {
+    "[A-Z0-9]{1,12}".prop_map(|s| CompressedString::from_str(&s).unwrap())
+}
+
+fn exchange() -> impl Strategy<Value = Exchange> {
+    (0u8..10).prop_map(|id| Exchange::from_u8(id).unwrap())
+}
+
+fn update_type() -> impl Strategy<Value = UpdateType> {
+    prop_oneof![Just(UpdateType::Snapshot), Just(UpdateType::Update)]
+}
+
+fn trade_side() -> impl Strategy<Value = TradeSide> {
+    prop_oneof![Just(TradeSide::Buy), Just(TradeSide::Sell)]
+}
+
+fn order_side() -> impl Strategy<Value = OrderSide> {
+    prop_oneof![Just(OrderSide::Bid), Just(OrderSide::Ask)]
+}
+
+fn collector_state() -> impl Strategy<Value = CollectorState> {
+    prop_oneof![
+        Just(CollectorState::Connecting),
+        Just(CollectorState::Connected),
+        Just(CollectorState::Receiving),
+        Just(CollectorState::Disconnected),
+        Just(CollectorState::Error),
+    ]
+}
+
+fn time_in_force() -> impl Strategy<Value = TimeInForce> {
+    (0u8..3).prop_map(|value| TimeInForce::from_u8(value).unwrap())
+}
+
+fn order_flags() -> impl Strategy<Value = OrderFlags> {
+    (0u8..4).prop_map(OrderFlags)
+}
+
+fn request_type() -> impl Strategy<Value = OrderRequestType> {
+    (0u8..3).prop_map(|value| OrderRequestType::from_u8(value).unwrap())
+}
+
+fn reject_reason() -> impl Strategy<Value = RejectReason> {
+    (0u8..11).prop_map(|value| RejectReason::from_u8(value).unwrap())
+}
+
+fn kill_reason() -> impl Strategy<Value = KillReason> {
+    (0u8..5).prop_map(|value| KillReason::from_u8(value).unwrap())
+}
+
+fn strategy_param() -> impl Strategy<Value = StrategyParam> {
+    (0u8..10).prop_map(|value| StrategyParam::from_u8(value).unwrap())
+}
+
+fn risk_event_kind() -> impl Strategy<Value = RiskEventKind> {
+    (0u8..6).prop_map(|value| RiskEventKind::from_u8(value).unwrap())
+}
+
+fn market_data() -> impl Strategy<Value = MarketDataMessage> {
+    (exchange(), update_type(), symbol(), any::<u64>(), any::<[i64; 4]>(), any::<u64>()).prop_map(
+        |(exchange, update_type, (symbol, encoding), timestamp, [bid_price, ask_price, bid_size, ask_size], sequence)| {
+            MarketDataMessage::new_with_sequence(
+                exchange,
+                update_type,
+                symbol,
+                encoding,
+                timestamp,
+                bid_price,
+                ask_price,
+                bid_size,
+                ask_size,
+                sequence,
+            )
+        },
+    )
+}
+
+fn pricing_output() -> impl Strategy<Value = PricingOutputMessage> {
+    (any::<u8>(), symbol(), any::<u64>(), any::<[i64; 3]>(), any::<u64>()).prop_map(
+        |(strategy_id, (symbol, encoding), timestamp, [fair_value, confidence, volatility], sequence)| {
+            PricingOutputMessage::new_with_sequence(strategy_id, symbol, encoding, timestamp, fair_value, confidence, volatility, sequence)
+        },
+    )
+}
+
+fn heartbeat() -> impl Strategy<Value = HeartbeatMessage> {
+    (any::<u64>(), any::<u64>()).prop_map(|(timestamp, sequence)| HeartbeatMessage::new(timestamp, sequence))
+}
+
+fn collector_state_message() -> impl Strategy<Value = CollectorStateMessage> {
+    (any::<u8>(), collector_state(), any::<u64>(), any::<u64>())
+        .prop_map(|(connection_id, state, timestamp, received)| CollectorStateMessage::new(connection_id, state, timestamp, received))
+}
+
+fn trade() -> impl Strategy<Value = TradeMessage> {
+    (exchange(), symbol(), any::<[u64; 3]>(), any::<[i64; 2]>(), trade_side(), any::<bool>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, trade_id, sequence], [price, quantity], side, is_aggressor)| {
+            TradeMessage::new_with_sequence(exchange, symbol, encoding, timestamp, trade_id, price, quantity, side, is_aggressor, sequence)
+        },
+    )
+}
+
+fn quote() -> impl Strategy<Value = QuoteMessage> {
+    (any::<u8>(), symbol(), any::<u64>(), any::<[i64; 7]>(), any::<u64>()).prop_map(
+        |(
+            strategy_id,
+            (symbol, encoding),
+            timestamp,
+            [bid_price, bid_size, ask_price, ask_size, fair_value, inventory, confidence],
+            sequence,
+        )| {
+            QuoteMessage::new_with_sequence(
+                strategy_id,
+                symbol,
+                encoding,
+                timestamp,
+                bid_price,
+                bid_size,
+                ask_price,
+                ask_size,
+                fair_value,
+                inventory,
+                confidence,
+                sequence,
+            )
+        },
+    )
+}
+
+fn position() -> impl Strategy<Value = PositionMessage> {
+    (symbol(), any::<u64>(), any::<[i64; 4]>(), any::<[i64; 3]>(), any::<u64>()).prop_map(
+        |((symbol, encoding), timestamp, [quantity, avg_entry_price, unrealized_pnl, realized_pnl], [fees, rebates, funding], sequence)| {
+            PositionMessage::new_with_sequence(
+                symbol,
+                encoding,
+                timestamp,
+                quantity,
+                avg_entry_price,
+                unrealized_pnl,
+                realized_pnl,
+                sequence,
+            )
+            .with_costs(fees, rebates, funding)
+        },
+    )
+}
+
+fn order_fill() -> impl Strategy<Value = OrderFillMessage> {
+    (exchange(), symbol(), any::<[u64; 3]>(), any::<[i64; 2]>(), order_side(), any::<bool>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, order_id, sequence], [price, quantity], side, is_maker)| {
+            OrderFillMessage::new_with_sequence(exchange, symbol, encoding, timestamp, order_id, price, quantity, side, is_maker, sequence)
+        },
+    )
+}
+
+fn new_order() -> impl Strategy<Value = NewOrderMessage> {
+    (exchange(), any::<u8>(), symbol(), any::<[u64; 3]>(), order_side(), any::<[i64; 2]>(), time_in_force(), order_flags()).prop_map(
+        |(exchange, strategy_id, (symbol, encoding), [timestamp, client_order_id, sequence], side, [price, quantity], tif, flags)| {
+            NewOrderMessage::new_with_sequence(
+                exchange,
+                strategy_id,
+                symbol,
+                encoding,
+                timestamp,
+                client_order_id,
+                side,
+                price,
+                quantity,
+                tif,
+                flags,
+                sequence,
+            )
+        },
+    )
+}
+
+fn cancel_order() -> impl Strategy<Value = CancelOrderMessage> {
+    (exchange(), any::<u8>(), symbol(), any::<[u64; 3]>()).prop_map(
+        |(exchange, strategy_id, (symbol, encoding), [timestamp, client_order_id, sequence])| {
+            CancelOrderMessage::new_with_sequence(exchange, strategy_id, symbol, encoding, timestamp, client_order_id, sequence)
+        },
+    )
+}
+
+fn replace_order() -> impl Strategy<Value = ReplaceOrderMessage> {
+    (exchange(), any::<u8>(), symbol(), any::<[u64; 4]>(), order_side(), any::<[i64; 2]>(), time_in_force(), order_flags()).prop_map(
+        |(
+            exchange,
+            strategy_id,
+            (symbol, encoding),
+            [timestamp, orig_id, client_order_id, sequence],
+            side,
+            [price, quantity],
+            tif,
+            flags,
+        )| {
+            ReplaceOrderMessage::new_with_sequence(
+                exchange,
+                strategy_id,
+                symbol,
+                encoding,
+                timestamp,
+                orig_id,
+                client_order_id,
+                side,
+                price,
+                quantity,
+                tif,
+                flags,
+                sequence,
+            )
+        },
+    )
+}
+
+fn order_ack() -> impl Strategy<Value = OrderAckMessage> {
+    (exchange(), request_type(), symbol(), any::<[u64; 4]>(), order_side(), any::<[i64; 2]>()).prop_map(
+        |(exchange, ack_type, (symbol, encoding), [timestamp, client_order_id, exchange_order_id, sequence], side, [price, leaves])| {
+            OrderAckMessage::new_with_sequence(
+                exchange,
+                ack_type,
+                symbol,
+                encoding,
+                timestamp,
+                client_order_id,
+                exchange_order_id,
+                side,
+                price,
+                leaves,
+                sequence,
+            )
+        },
+    )
+}
+
+fn order_reject() -> impl Strategy<Value = OrderRejectMessage> {
+    (exchange(), request_type(), reject_reason(), symbol(), any::<[u64; 3]>()).prop_map(
+        |(exchange, rejected, reason, (symbol, encoding), [timestamp, client_order_id, sequence])| {
+            OrderRejectMessage::new_with_sequence(exchange, rejected, reason, symbol, encoding, timestamp, client_order_id, sequence)
+        },
+    )
+}
+
+fn mark_price() -> impl Strategy<Value = MarkPriceMessage> {
+    (exchange(), symbol(), any::<[u64; 2]>(), any::<[i64; 2]>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, sequence], [mark_price, index_price])| {
+            MarkPriceMessage::new_with_sequence(exchange, symbol, encoding, timestamp, mark_price, index_price, sequence)
+        },
+    )
+}
+
+fn funding_rate() -> impl Strategy<Value = FundingRateMessage> {
+    (exchange(), symbol(), any::<[u64; 3]>(), any::<i64>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, next_funding_time, sequence], funding_rate)| {
+            FundingRateMessage::new_with_sequence(exchange, symbol, encoding, timestamp, funding_rate, next_funding_time, sequence)
+        },
+    )
+}
+
+fn open_interest() -> impl Strategy<Value = OpenInterestMessage> {
+    (exchange(), symbol(), any::<[u64; 2]>(), any::<[i64; 2]>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, sequence], [open_interest, value])| {
+            OpenInterestMessage::new_with_sequence(exchange, symbol, encoding, timestamp, open_interest, value, sequence)
+        },
+    )
+}
+
+fn liquidation() -> impl Strategy<Value = LiquidationMessage> {
+    (exchange(), symbol(), any::<[u64; 2]>(), trade_side(), any::<[i64; 4]>()).prop_map(
+        |(exchange, (symbol, encoding), [timestamp, sequence], side, [price, avg_price, quantity, filled])| {
+            LiquidationMessage::new_with_sequence(exchange, symbol, encoding, timestamp, side, price, avg_price, quantity, filled, sequence)
+        },
+    )
+}
+
+fn kill_switch() -> impl Strategy<Value = KillSwitchMessage> {
+    (any::<u8>(), any::<u32>(), kill_reason(), any::<[u64; 2]>()).prop_map(|(strategy_id, source_id, reason, [timestamp, sequence])| {
+        KillSwitchMessage::new_with_sequence(strategy_id, source_id, reason, timestamp, sequence)
+    })
+}
+
+fn resume() -> impl Strategy<Value = ResumeMessage> {
+    (any::<u8>(), any::<u32>(), any::<[u64; 2]>()).prop_map(|(strategy_id, source_id, [timestamp, sequence])| {
+        ResumeMessage::new_with_sequence(strategy_id, source_id, timestamp, sequence)
+    })
+}
+
+fn strategy_param_update() -> impl Strategy<Value = StrategyParamUpdateMessage> {
+    (any::<u8>(), any::<u32>(), strategy_param(), any::<f64>(), any::<[u64; 2]>()).prop_map(
+        |(strategy_id, source_id, param, value, [timestamp, sequence])| {
+            StrategyParamUpdateMessage::new_with_sequence(strategy_id, source_id, param, value, timestamp, sequence)
+        },
+    )
+}
+
+fn risk_event() -> impl Strategy<Value = RiskEventMessage> {
+    (any::<u8>(), any::<u32>(), risk_event_kind(), any::<u8>(), any::<[f64; 2]>(), any::<[u64; 2]>()).prop_map(
+        |(strategy_id, source_id, kind, code, [value, limit], [timestamp, sequence])| {
+            RiskEventMessage::new_with_sequence(strategy_id, source_id, kind, code, value, limit, timestamp, sequence)
+        },
+    )
+}
+
+/// Round-trip, bit-flip, truncation and garbage-input properties for a fixed-size message
+macro_rules! fixed_message_properties {
+    ($name:ident, $msg:ty, $strategy:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn roundtrip(msg in $strategy) {
+                    let bytes = msg.to_bytes();
+                    let buffer = Aligned::new(&bytes);
+                    let decoded = <$msg>::from_bytes(&buffer.0[..<$msg>::SIZE]).unwrap();
+                    prop_assert_eq!(decoded.to_bytes(), bytes);
+                }
+
+                #[test]
+                fn single_bit_flips_fail_checksum(msg in $strategy) {
+                    // Everything up to and including the CRC field is protected
+                    let covered = std::mem::offset_of!($msg, crc32) + 4;
+                    let bytes = msg.to_bytes();
+                    for bit in 0..covered * 8 {
+                        let mut buffer = Aligned::new(&bytes);
+                        buffer.0[bit / 8] ^= 1 << (bit % 8);
+                        let flipped = unsafe { <$msg>::from_bytes_unchecked(&buffer.0) };
+                        prop_assert!(flipped.validate_checksum().is_err(), "flip of bit {} not detected", bit);
+                    }
+                }
+
+                #[test]
+                fn truncated_and_oversized(msg in $strategy, cut in 0..<$msg>::SIZE, tail in vec(any::<u8>(), 1..64)) {
+                    let bytes = msg.to_bytes();
+                    let buffer = Aligned::new(&bytes[..cut]);
+                    prop_assert!(<$msg>::from_bytes(&buffer.0[..cut]).is_err());
+
+                    // Trailing bytes, such as a latency trailer, are ignored
+                    let mut long = bytes.to_vec();
+                    long.extend_from_slice(&tail);
+                    let buffer = Aligned::new(&long);
+                    prop_assert_eq!(<$msg>::from_bytes(&buffer.0[..long.len()]).unwrap().to_bytes(), bytes);
+                }
+
+                #[test]
+                fn arbitrary_bytes_do_not_panic(data in vec(any::<u8>(), 0..BUFFER_SIZE)) {
+                    let buffer = Aligned::new(&data);
+                    let _ = <$msg>::from_bytes(&buffer.0[..data.len()]);
+                }
+            }
+        }
+    };
+}
+
+fixed_message_properties!(market_data_message, MarketDataMessage, market_data());
+fixed_message_properties!(pricing_output_message, PricingOutputMessage, pricing_output());
+fixed_message_properties!(heartbeat_message, HeartbeatMessage, heartbeat());
+fixed_message_properties!(collector_state_messages, CollectorStateMessage, collector_state_message());
+fixed_message_properties!(trade_message, TradeMessage, trade());
+fixed_message_properties!(quote_message, QuoteMessage, quote());
+fixed_message_properties!(position_message, PositionMessage, position());
+fixed_message_properties!(order_fill_message, OrderFillMessage, order_fill());
+fixed_message_properties!(new_order_message, NewOrderMessage, new_order());
+fixed_message_properties!(cancel_order_message, CancelOrderMessage, cancel_order());
+fixed_message_properties!(replace_order_message, ReplaceOrderMessage, replace_order());
+fixed_message_properties!(order_ack_message, OrderAckMessage, order_ack());
+fixed_message_properties!(order_reject_message, OrderRejectMessage, order_reject());
+fixed_message_properties!(mark_price_message, MarkPriceMessage, mark_price());
+fixed_message_properties!(funding_rate_message, FundingRateMessage, funding_rate());
+fixed_message_properties!(open_interest_message, OpenInterestMessage, open_interest());
+fixed_message_properties!(liquidation_message, LiquidationMessage, liquidation());
+fixed_message_properties!(kill_switch_message, KillSwitchMessage, kill_switch());
+fixed_message_properties!(resume_message, ResumeMessage, resume());
+fixed_message_properties!(strategy_param_update_message, StrategyParamUpdateMessage, strategy_param_update());
+fixed_message_properties!(risk_event_message, RiskEventMessage, risk_event());
+
+fn levels() -> impl Strategy<Value = Vec<(i64, i64)>> {
+    vec(any::<(i64, i64)>(), 0..20)
+}
+
+fn orderbook_batch() -> impl Strategy<Value = OrderBookBatchMessage> {
+    (exchange(), update_type(), symbol(), any::<[u64; 5]>(), any::<u32>(), levels(), levels(), prop::option::of(1i64..1_000_000)).prop_map(
+        |(exchange, update_type, (symbol, encoding), [timestamp, first, last, prev, sequence], instrument_id, bids, asks, delta_tick)| {
+            let mut msg = OrderBookBatchMessage::new_with_ids(exchange, update_type, symbol, encoding, timestamp, first, last, prev);
+            msg.set_sequence(sequence);
+            msg.set_instrument_id(instrument_id);
+            msg.add_bids(bids);
+            msg.add_asks(asks);
+            if let Some(tick) = delta_tick {
+                msg.set_delta_encoding(tick);
+            }
+            msg
+        },
+    )
+}
+
+fn quote_ladder() -> impl Strategy<Value = QuoteLadderMessage> {
+    (any::<u8>(), symbol(), any::<[u64; 3]>(), any::<[i64; 3]>(), levels(), levels()).prop_map(
+        |(strategy_id, (symbol, encoding), [timestamp, quote_id, sequence], [fair_value, inventory, confidence], bids, asks)| {
+            let mut msg = QuoteLadderMessage::new(strategy_id, symbol, encoding, timestamp, quote_id);
+            msg.set_sequence(sequence);
+            msg.set_valuation(fair_value, inventory, confidence);
+            for (price, size) in bids {
+                msg.add_bid(price, size);
+            }
+            for (price, size) in asks {
+                msg.add_ask(price, size);
+            }
+            msg
+        },
+    )
+}
+
+fn latency_trailer() -> impl Strategy<Value = LatencyTrailer> {
+    (any::<u64>(), vec((0u8..4, any::<[u64; 3]>()), 0..=4)).prop_map(|(exchange_time, hops)| {
+        let mut trailer = LatencyTrailer::new(exchange_time);
+        for (stage, [receive, parse, publish]) in hops {
+            trailer.push_hop(HopTimestamps::new(PipelineStage::from_u8(stage).unwrap(), receive, parse, publish));
+        }
+        trailer
+    })
+}
+
+/// Properties for messages whose length is implied by the frame: any flipped bit,
+/// truncation or extension breaks the trailing CRC
+macro_rules! framed_message_properties {
+    ($name:ident, $msg:ty, $strategy:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn roundtrip(msg in $strategy) {
+                    let bytes = msg.to_bytes();
+                    prop_assert_eq!(<$msg>::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+                }
+
+                #[test]
+                fn single_bit_flips_are_rejected(msg in $strategy, bit in any::<prop::sample::Index>()) {
+                    let mut bytes = msg.to_bytes().to_vec();
+                    let bit = bit.index(bytes.len() * 8);
+                    bytes[bit / 8] ^= 1 << (bit % 8);
+                    prop_assert!(<$msg>::from_bytes(&bytes).is_err(), "flip of bit {} not detected", bit);
+                }
+
+                #[test]
+                fn truncated_and_oversized(msg in $strategy, cut in any::<prop::sample::Index>(), tail in vec(any::<u8>(), 1..64)) {
+                    let bytes = msg.to_bytes();
+                    prop_assert!(<$msg>::from_bytes(&bytes[..cut.index(bytes.len())]).is_err());
+
+                    let mut long = bytes.to_vec();
+                    long.extend_from_slice(&tail);
+                    prop_assert!(<$msg>::from_bytes(&long).is_err());
+                }
+
+                #[test]
+                fn arbitrary_bytes_do_not_panic(data in vec(any::<u8>(), 0..512)) {
+                    let _ = <$msg>::from_bytes(&data);
+                }
+            }
+        }
+    };
+}
+
+framed_message_properties!(orderbook_batch_message, OrderBookBatchMessage, orderbook_batch());
+framed_message_properties!(quote_ladder_message, QuoteLadderMessage, quote_ladder());
+
+proptest! {
+    #[test]
+    fn latency_trailer_roundtrip_and_split(trailer in latency_trailer(), msg in heartbeat(), bit in any::<prop::sample::Index>()) {
+        let bytes = trailer.to_bytes();
+        prop_assert_eq!(LatencyTrailer::from_bytes(&bytes).unwrap(), trailer);
+
+        let mut frame = msg.to_bytes().to_vec();
+        frame.extend_from_slice(&bytes);
+        let (payload, split) = mm_binary::latency::split_trailer(&frame);
+        prop_assert_eq!(payload, &msg.to_bytes()[..]);
+        prop_assert_eq!(split, Some(trailer));
+
+        // A corrupted trailer is not split off, so the frame is left untouched
+        let bit = bit.index(LatencyTrailer::SIZE * 8);
+        let offset = HeartbeatMessage::SIZE + bit / 8;
+        frame[offset] ^= 1 << (bit % 8);
+        prop_assert!(LatencyTrailer::from_bytes(&frame[HeartbeatMessage::SIZE..]).is_err());
+        prop_assert_eq!(mm_binary::latency::split_trailer(&frame).1, None);
+    }
+
+    #[test]
+    fn latency_trailer_truncated(trailer in latency_trailer(), cut in 0..LatencyTrailer::SIZE) {
+        prop_assert!(LatencyTrailer::from_bytes(&trailer.to_bytes()[..cut]).is_err());
+    }
+
+    #[test]
+    fn compressed_string_roundtrip(s in "[A-Z0-9]{0,18}") {
+        if let Ok((symbol, encoding)) = CompressedString::from_str(&s) {
+            prop_assert_eq!(symbol.decode(encoding), s);
+        }
+    }
+
+    #[test]
+    fn compressed_string_decode_arbitrary_bits(low in any::<u64>(), high in any::<u64>(), scheme in 0u8..4) {
+        let encoding = match scheme {
+            0 => EncodingScheme::Hex4Bit,
+            1 => EncodingScheme::Alphabetic5Bit,
+            2 => EncodingScheme::AlphaNumeric6Bit,
+            _ => EncodingScheme::Ascii7Bit,
+        };
+        let decoded = CompressedString { low, high }.decode(encoding);
+        if let Ok((encoded, scheme)) = CompressedString::from_str(&decoded) {
+            prop_assert_eq!(encoded.decode(scheme), decoded);
+        }
+    }
+}
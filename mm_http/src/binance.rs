@@ -2,6 +2,10 @@ use std::sync::Arc;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+use mm_binary::Exchange;
+use mm_binary::InstrumentInfo;
+use mm_binary::InstrumentRegistry;
+use mm_binary::parse_json_decimal_to_fixed_point;
 use mm_ratelimit::MultiLimiter;
 use mm_ratelimit::RateLimiter;
 use serde::Deserialize;
@@ -15,11 +19,14 @@ use crate::errors::Result;
 
 const BINANCE_BASE_URL: &str = "https://api.binance.com";
 const BINANCE_TESTNET_URL: &str = "https://testnet.binance.vision";
+const BINANCE_FUTURES_URL: &str = "https://fapi.binance.com";
+const BINANCE_FUTURES_TESTNET_URL: &str = "https://testnet.binancefuture.com";
 
 /// Binance REST API client for market data and trading
 pub struct BinanceClient {
     client: HttpClient,
     base_url: String,
+    futures_base_url: String,
     rate_limiter: Arc<MultiLimiter>,
     circuit_breaker: Arc<CircuitBreaker>,
     #[allow(dead_code)]
@@ -145,6 +152,27 @@ impl BinanceClient {
             .await
     }
 
+    /// Get USD-M futures open interest, which Binance does not stream
+    pub async fn open_interest(&self, symbol: &str) -> Result<OpenInterest> {
+        self.rate_limiter.try_acquire(1).map_err(|_| HttpError::RateLimitExceeded)?;
+
+        let url = format!("{}/fapi/v1/openInterest", self.futures_base_url);
+
+        self.circuit_breaker
+            .call_async(|| async {
+                let response = self.client.get(&url).query(&[("symbol", symbol)]).send().await?;
+
+                if !response.status().is_success() {
+                    return Err(self.handle_error_response(response).await);
+                }
+
+                let bytes = response.bytes().await?;
+                let open_interest: OpenInterest = serde_json::from_slice(&bytes)?;
+                Ok(open_interest)
+            })
+            .await
+    }
+
     /// Test connectivity to the REST API
     pub async fn ping(&self) -> Result<()> {
         let url = format!("{}/api/v3/ping", self.base_url);
@@ -208,6 +236,7 @@ impl Default for BinanceClient {
 pub struct BinanceClientBuilder {
     http_config: HttpClientConfig,
     base_url: String,
+    futures_base_url: String,
     requests_per_second: usize,
     circuit_breaker_config: CircuitBreakerConfig,
     api_key: Option<String>,
@@ -219,6 +248,7 @@ impl Default for BinanceClientBuilder {
         Self {
             http_config: HttpClientConfig::default(),
             base_url: BINANCE_BASE_URL.to_string(),
+            futures_base_url: BINANCE_FUTURES_URL.to_string(),
             requests_per_second: 20, // Conservative default (Binance limit is higher)
             circuit_breaker_config: CircuitBreakerConfig::default(),
             api_key: None,
@@ -231,6 +261,7 @@ impl BinanceClientBuilder {
     /// Use testnet environment
     pub fn testnet(mut self) -> Self {
         self.base_url = BINANCE_TESTNET_URL.to_string();
+        self.futures_base_url = BINANCE_FUTURES_TESTNET_URL.to_string();
         self
     }
 
@@ -240,6 +271,12 @@ impl BinanceClientBuilder {
         self
     }
 
+    /// Set custom USD-M futures base URL
+    pub fn futures_base_url(mut self, url: String) -> Self {
+        self.futures_base_url = url;
+        self
+    }
+
     /// Configure HTTP client settings
     pub fn http_config(mut self, config: HttpClientConfig) -> Self {
         self.http_config = config;
@@ -285,6 +322,7 @@ impl BinanceClientBuilder {
         Ok(BinanceClient {
             client,
             base_url: self.base_url,
+            futures_base_url: self.futures_base_url,
             rate_limiter: Arc::new(rate_limiter),
             circuit_breaker: Arc::new(circuit_breaker),
             api_key: self.api_key,
@@ -374,6 +412,15 @@ pub struct AveragePrice {
     pub price: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OpenInterest {
+    pub symbol: String,
+    /// Open contracts in base units
+    #[serde(rename = "openInterest", deserialize_with = "mm_binary::serde_helpers::deserialize_fixed_point_string")]
+    pub open_interest: i64,
+    pub time: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ExchangeInfo {
     pub timezone: String,
@@ -382,6 +429,34 @@ pub struct ExchangeInfo {
     pub symbols: Vec<SymbolInfo>,
 }
 
+impl ExchangeInfo {
+    /// Build an instrument registry from the listed symbols
+    ///
+    /// IDs are assigned from 1 in symbol order, so processes that load the same
+    /// listing agree on them. Use a config-backed registry when IDs must stay stable
+    /// across listing changes.
+    pub fn instrument_registry(&self) -> Result<InstrumentRegistry> {
+        let mut symbols: Vec<&SymbolInfo> = self.symbols.iter().collect();
+        symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        let mut registry = InstrumentRegistry::new();
+        for (index, info) in symbols.into_iter().enumerate() {
+            let instrument = InstrumentInfo::new(
+                index as u32 + 1,
+                &info.symbol,
+                Exchange::Binance,
+                &info.base_asset,
+                &info.quote_asset,
+                info.tick_size().unwrap_or(0),
+                info.lot_size().unwrap_or(0),
+            )
+            .with_min_notional(info.min_notional().unwrap_or(0));
+            registry.register(instrument).map_err(|err| HttpError::InvalidResponse(err.to_string()))?;
+        }
+        Ok(registry)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SymbolInfo {
     pub symbol: String,
@@ -390,6 +465,44 @@ pub struct SymbolInfo {
     pub base_asset: String,
     #[serde(rename = "quoteAsset")]
     pub quote_asset: String,
+    #[serde(default)]
+    pub filters: Vec<SymbolFilter>,
+}
+
+impl SymbolInfo {
+    /// Price increment from `PRICE_FILTER`, in fixed-point
+    pub fn tick_size(&self) -> Option<i64> {
+        self.filter_value("PRICE_FILTER", |filter| filter.tick_size.as_deref())
+    }
+
+    /// Quantity increment from `LOT_SIZE`, in fixed-point
+    pub fn lot_size(&self) -> Option<i64> {
+        self.filter_value("LOT_SIZE", |filter| filter.step_size.as_deref())
+    }
+
+    /// Minimum order value from `NOTIONAL` (or the older `MIN_NOTIONAL`), in fixed-point
+    pub fn min_notional(&self) -> Option<i64> {
+        self.filter_value("NOTIONAL", |filter| filter.min_notional.as_deref())
+            .or_else(|| self.filter_value("MIN_NOTIONAL", |filter| filter.min_notional.as_deref()))
+    }
+
+    fn filter_value(&self, filter_type: &str, field: impl Fn(&SymbolFilter) -> Option<&str>) -> Option<i64> {
+        let filter = self.filters.iter().find(|filter| filter.filter_type == filter_type)?;
+        parse_json_decimal_to_fixed_point(field(filter)?.as_bytes()).ok()
+    }
+}
+
+/// Trading rule attached to a symbol; only the fields we use are parsed
+#[derive(Debug, Deserialize)]
+pub struct SymbolFilter {
+    #[serde(rename = "filterType")]
+    pub filter_type: String,
+    #[serde(rename = "tickSize", default)]
+    pub tick_size: Option<String>,
+    #[serde(rename = "stepSize", default)]
+    pub step_size: Option<String>,
+    #[serde(rename = "minNotional", default)]
+    pub min_notional: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -419,6 +532,16 @@ mod tests {
     fn test_builder_testnet() {
         let builder = BinanceClientBuilder::default().testnet();
         assert_eq!(builder.base_url, BINANCE_TESTNET_URL);
+        assert_eq!(builder.futures_base_url, BINANCE_FUTURES_TESTNET_URL);
+    }
+
+    #[test]
+    fn test_open_interest_response() {
+        let json = r#"{"openInterest": "10659.509", "symbol": "BTCUSDT", "time": 1589437530011}"#;
+        let open_interest: OpenInterest = serde_json::from_str(json).unwrap();
+        assert_eq!(open_interest.symbol, "BTCUSDT");
+        assert_eq!(open_interest.open_interest, 1_065_950_900_000);
+        assert_eq!(open_interest.time, 1589437530011);
     }
 
     #[test]
@@ -426,4 +549,33 @@ mod tests {
         let builder = BinanceClientBuilder::default().low_latency();
         assert_eq!(builder.requests_per_second, 50);
     }
+
+    #[test]
+    fn test_exchange_info_instrument_registry() {
+        let json = r#"{
+            "timezone": "UTC",
+            "serverTime": 1700000000000,
+            "symbols": [
+                {"symbol": "ETHUSDT", "status": "TRADING", "baseAsset": "ETH", "quoteAsset": "USDT", "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.00010000", "maxQty": "9000.00000000", "stepSize": "0.00010000"}
+                ]},
+                {"symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "quoteAsset": "USDT", "filters": [
+                    {"filterType": "PRICE_FILTER", "tickSize": "0.01000000"},
+                    {"filterType": "LOT_SIZE", "stepSize": "0.00001000"},
+                    {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true}
+                ]}
+            ]
+        }"#;
+        let info: ExchangeInfo = serde_json::from_str(json).unwrap();
+        let registry = info.instrument_registry().unwrap();
+
+        let btc = registry.by_symbol(Exchange::Binance, "BTCUSDT").unwrap();
+        assert_eq!(btc.id, 1);
+        assert_eq!(btc.tick_size, 1_000_000);
+        assert_eq!(btc.lot_size, 1_000);
+        assert_eq!(btc.min_notional, 500_000_000);
+        let eth = registry.by_symbol(Exchange::Binance, "ETHUSDT").unwrap();
+        assert_eq!((eth.lot_size, eth.min_notional), (10_000, 0));
+    }
 }
@@ -1,11 +1,16 @@
 use std::f64::consts::PI;
 use std::hint::black_box;
 
+use criterion::BatchSize;
 use criterion::Criterion;
 use criterion::criterion_group;
 use criterion::criterion_main;
 use mm_binary::FIXED_POINT_MULTIPLIER;
+use mm_orderbook::BookView;
 use mm_orderbook::OrderBook;
+use mm_orderbook::TickLadderBook;
+use mm_types::Price;
+use mm_types::Qty;
 
 fn bench_orderbook_monotonic(c: &mut Criterion) {
     c.bench_function("orderbook_monotonic", |b| {
@@ -15,10 +20,10 @@ fn bench_orderbook_monotonic(c: &mut Criterion) {
         b.iter(|| {
             for i in 0..1000 {
                 let price = base_price + (i * FIXED_POINT_MULTIPLIER);
-                let qty_1_5 = 150_000_000; // 1.5
-                let qty_1_0 = 100_000_000; // 1.0
-                ob.update_bid(black_box(price - 100 * FIXED_POINT_MULTIPLIER), black_box(qty_1_5));
-                ob.update_ask(black_box(price + 100 * FIXED_POINT_MULTIPLIER), black_box(qty_1_0));
+                let qty_1_5 = Qty(150_000_000); // 1.5
+                let qty_1_0 = Qty(100_000_000); // 1.0
+                ob.update_bid(black_box(Price(price - 100 * FIXED_POINT_MULTIPLIER)), black_box(qty_1_5));
+                ob.update_ask(black_box(Price(price + 100 * FIXED_POINT_MULTIPLIER)), black_box(qty_1_0));
             }
         });
     });
@@ -36,10 +41,10 @@ fn bench_orderbook_sin(c: &mut Criterion) {
                 let angle = (i as f64 / 100.0) * 2.0 * PI;
                 let sin_component = (amplitude as f64 * angle.sin()) as i64;
                 let price = base_price + sin_component;
-                let qty_1_5 = 150_000_000; // 1.5
-                let qty_1_0 = 100_000_000; // 1.0
-                ob.update_bid(price - 100 * FIXED_POINT_MULTIPLIER, black_box(qty_1_5));
-                ob.update_ask(price + 100 * FIXED_POINT_MULTIPLIER, black_box(qty_1_0));
+                let qty_1_5 = Qty(150_000_000); // 1.5
+                let qty_1_0 = Qty(100_000_000); // 1.0
+                ob.update_bid(Price(price - 100 * FIXED_POINT_MULTIPLIER), black_box(qty_1_5));
+                ob.update_ask(Price(price + 100 * FIXED_POINT_MULTIPLIER), black_box(qty_1_0));
             }
 
             black_box(ob)
@@ -50,9 +55,9 @@ fn bench_orderbook_sin(c: &mut Criterion) {
 fn bench_top_bids(c: &mut Criterion) {
     let mut ob = OrderBook::new("BTCUSDT");
     let base_price = 50_000 * FIXED_POINT_MULTIPLIER;
-    let qty_1_0 = 100_000_000; // 1.0
+    let qty_1_0 = Qty(100_000_000); // 1.0
     for i in 0..5000 {
-        ob.update_bid(base_price + (i * FIXED_POINT_MULTIPLIER), qty_1_0);
+        ob.update_bid(Price(base_price + (i * FIXED_POINT_MULTIPLIER)), qty_1_0);
     }
 
     c.bench_function("top_bids_10", |b| {
@@ -62,5 +67,91 @@ fn bench_top_bids(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_orderbook_monotonic, bench_orderbook_sin, bench_top_bids);
+/// One `depthUpdate` event: changed levels as raw `(price, quantity)`, zero quantity removes
+struct DepthDiff {
+    bids: Vec<(i64, i64)>,
+    asks: Vec<(i64, i64)>,
+}
+
+/// BTCUSDT-like flow: 0.01 ticks, a drifting mid, and 20-40 changed levels per side
+/// per event, mostly near the touch with a long tail and about a quarter removals.
+/// Returns a 1000-level snapshot and the events that follow it.
+fn binance_diffs(count: usize) -> (DepthDiff, Vec<DepthDiff>) {
+    let tick = FIXED_POINT_MULTIPLIER / 100;
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut mid_ticks = 5_000_000i64; // 50_000.00
+    let bids = (1..=1000).map(|depth| ((mid_ticks - depth) * tick, FIXED_POINT_MULTIPLIER)).collect();
+    let asks = (1..=1000).map(|depth| ((mid_ticks + depth) * tick, FIXED_POINT_MULTIPLIER)).collect();
+
+    let diffs = (0..count)
+        .map(|_| {
+            mid_ticks += (next() % 7) as i64 - 3;
+            let mut side = |sign: i64| {
+                let levels = 20 + (next() % 21) as usize;
+                (0..levels)
+                    .map(|_| {
+                        // Squaring a uniform draw skews depth toward the touch, up to 800 ticks out
+                        let uniform = (next() % 1_000) as i64;
+                        let depth = 1 + uniform * uniform / 1_250;
+                        let qty = if next() % 4 == 0 { 0 } else { (next() % 500) as i64 * FIXED_POINT_MULTIPLIER / 100 };
+                        ((mid_ticks + sign * depth) * tick, qty)
+                    })
+                    .collect()
+            };
+            DepthDiff { bids: side(-1), asks: side(1) }
+        })
+        .collect();
+
+    (DepthDiff { bids, asks }, diffs)
+}
+
+fn apply_diffs<B: BookView>(book: &mut B, diffs: &[DepthDiff]) {
+    for diff in diffs {
+        for &(price, qty) in &diff.bids {
+            book.update_bid(Price(price), Qty(qty));
+        }
+        for &(price, qty) in &diff.asks {
+            book.update_ask(Price(price), Qty(qty));
+        }
+    }
+}
+
+fn bench_book_diffs(c: &mut Criterion) {
+    let (snapshot, diffs) = binance_diffs(1_000);
+    let mut btree = OrderBook::with_max_levels("BTCUSDT", 5_000);
+    btree.load_snapshot(&snapshot.bids, &snapshot.asks);
+    let mut ladder = TickLadderBook::new("BTCUSDT", Price(FIXED_POINT_MULTIPLIER / 100), 4_096);
+    ladder.load_snapshot(&snapshot.bids, &snapshot.asks);
+
+    let mut group = c.benchmark_group("binance_diffs");
+    group.bench_function("btree", |b| {
+        b.iter_batched_ref(|| btree.clone(), |book| apply_diffs(book, black_box(&diffs)), BatchSize::LargeInput);
+    });
+    group.bench_function("tick_ladder", |b| {
+        b.iter_batched_ref(|| ladder.clone(), |book| apply_diffs(book, black_box(&diffs)), BatchSize::LargeInput);
+    });
+    group.finish();
+
+    apply_diffs(&mut btree, &diffs);
+    apply_diffs(&mut ladder, &diffs);
+
+    let mut group = c.benchmark_group("best_level");
+    group.bench_function("btree", |b| b.iter(|| black_box((BookView::best_bid(&btree), BookView::best_ask(&btree)))));
+    group.bench_function("tick_ladder", |b| b.iter(|| black_box((ladder.best_bid(), ladder.best_ask()))));
+    group.finish();
+
+    let mut group = c.benchmark_group("top_10_levels");
+    group.bench_function("btree", |b| b.iter(|| black_box(btree.bid_levels().take(10).map(|(_, qty)| qty.0).sum::<i64>())));
+    group.bench_function("tick_ladder", |b| b.iter(|| black_box(ladder.bid_levels().take(10).map(|(_, qty)| qty.0).sum::<i64>())));
+    group.finish();
+}
+
+criterion_group!(benches, bench_orderbook_monotonic, bench_orderbook_sin, bench_top_bids, bench_book_diffs);
 criterion_main!(benches);
@@ -0,0 +1,256 @@
+use mm_binary::OrderBookBatchView;
+use mm_types::DepthProfile;
+use mm_types::Notional;
+use mm_types::OrderSide;
+use mm_types::Price;
+use mm_types::Qty;
+use mm_types::RoundingMode;
+use mm_types::Scale;
+
+/// Level access and updates shared by the order book implementations
+///
+/// Prices and quantities are at `Scale::DEFAULT`; a zero quantity removes the level.
+/// The depth analytics take the side of a taker order: a `Bid` buys from the asks
+/// and an `Ask` sells into the bids.
+pub trait BookView {
+    fn symbol(&self) -> &str;
+
+    fn timestamp(&self) -> u64;
+
+    fn set_timestamp(&mut self, timestamp: u64);
+
+    fn best_bid(&self) -> Option<(Price, Qty)>;
+
+    fn best_ask(&self) -> Option<(Price, Qty)>;
+
+    /// Bid levels from best to worst, without allocating
+    fn bid_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_;
+
+    /// Ask levels from best to worst, without allocating
+    fn ask_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_;
+
+    fn update_bid(&mut self, price: Price, quantity: Qty);
+
+    fn update_ask(&mut self, price: Price, quantity: Qty);
+
+    fn mid_price(&self) -> Option<Price> {
+        let (bid_price, _) = self.best_bid()?;
+        let (ask_price, _) = self.best_ask()?;
+        Some(bid_price.midpoint(ask_price))
+    }
+
+    fn spread(&self) -> Option<Price> {
+        let (bid_price, _) = self.best_bid()?;
+        let (ask_price, _) = self.best_ask()?;
+        Some(ask_price - bid_price)
+    }
+
+    /// Levels a taker order on `side` trades against, best first
+    fn levels_against(&self, side: OrderSide) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        let (bids, asks) = match side {
+            OrderSide::Bid => (None, Some(self.ask_levels())),
+            OrderSide::Ask => (Some(self.bid_levels()), None),
+        };
+        bids.into_iter().flatten().chain(asks.into_iter().flatten())
+    }
+
+    /// Average fill price for a market order of `quantity` on `side`
+    ///
+    /// `None` if the visible book cannot fill it.
+    fn sweep_vwap(&self, side: OrderSide, quantity: Qty) -> Option<Price> {
+        let quantity = quantity.abs();
+        if quantity.is_zero() {
+            return None;
+        }
+        let mut remaining = quantity;
+        let mut cost = Notional::ZERO;
+        for (price, level_qty) in self.levels_against(side) {
+            let take = remaining.min(level_qty);
+            cost = cost.saturating_add(price.saturating_mul(take));
+            remaining -= take;
+            if remaining.is_zero() {
+                return cost.per_unit(quantity, RoundingMode::HalfAwayFromZero);
+            }
+        }
+        None
+    }
+
+    /// Adverse move from mid, in basis points, of the average price for trading `notional` on `side`
+    ///
+    /// `None` if either side is empty or the visible book cannot fill it.
+    fn impact_bps(&self, side: OrderSide, notional: Notional) -> Option<f64> {
+        self.capped_impact_bps(side, notional).and_then(|(impact, filled)| filled.then_some(impact))
+    }
+
+    /// [`impact_bps`](Self::impact_bps), measured to the last visible level when the book cannot fill `notional`
+    ///
+    /// The flag is whether the visible book filled all of it. `None` if either side is empty.
+    fn capped_impact_bps(&self, side: OrderSide, notional: Notional) -> Option<(f64, bool)> {
+        let mid = self.mid_price()?;
+        let notional = notional.abs();
+        if notional.is_zero() {
+            return None;
+        }
+        let mut remaining = notional;
+        let mut filled = Qty::ZERO;
+        for (price, level_qty) in self.levels_against(side) {
+            let level_value = price.saturating_mul(level_qty);
+            if level_value < remaining {
+                filled += level_qty;
+                remaining -= level_value;
+                continue;
+            }
+            filled += remaining.units_at(price, RoundingMode::HalfAwayFromZero)?;
+            remaining = Notional::ZERO;
+            break;
+        }
+        let vwap = (notional - remaining).per_unit(filled, RoundingMode::HalfAwayFromZero)?;
+        let moved = match side {
+            OrderSide::Bid => vwap - mid,
+            OrderSide::Ask => mid - vwap,
+        };
+        Some((moved.to_f64() / mid.to_f64() * 10_000.0, remaining.is_zero()))
+    }
+
+    /// Resting quantity on the `side` of the book priced within `bps` of mid
+    fn depth_within_bps(&self, side: OrderSide, bps: f64) -> Qty {
+        let Some(mid) = self.mid_price() else {
+            return Qty::ZERO;
+        };
+        match side {
+            OrderSide::Bid => {
+                let floor = mid.subtract_bps(bps);
+                self.bid_levels().take_while(|&(price, _)| price >= floor).map(|(_, qty)| qty).sum()
+            }
+            OrderSide::Ask => {
+                let ceiling = mid.apply_bps(bps);
+                self.ask_levels().take_while(|&(price, _)| price <= ceiling).map(|(_, qty)| qty).sum()
+            }
+        }
+    }
+
+    /// Micro-price over the top `levels` per side
+    ///
+    /// Each side's volume-weighted price is weighted by the opposite side's
+    /// volume; one level gives the usual top-of-book micro-price.
+    fn weighted_micro_price(&self, levels: usize) -> Option<Price> {
+        let side_totals = |levels: &mut dyn Iterator<Item = (Price, Qty)>| {
+            levels.fold((Notional::ZERO, Qty::ZERO), |(value, volume), (price, qty)| {
+                (value.saturating_add(price.saturating_mul(qty)), volume + qty)
+            })
+        };
+        let (bid_value, bid_volume) = side_totals(&mut self.bid_levels().take(levels));
+        let (ask_value, ask_volume) = side_totals(&mut self.ask_levels().take(levels));
+        if bid_volume.is_zero() || ask_volume.is_zero() {
+            return None;
+        }
+
+        let bid_vwap = bid_value.per_unit(bid_volume, RoundingMode::HalfAwayFromZero)?;
+        let ask_vwap = ask_value.per_unit(ask_volume, RoundingMode::HalfAwayFromZero)?;
+        let numerator = bid_vwap.saturating_mul(ask_volume).saturating_add(ask_vwap.saturating_mul(bid_volume));
+        numerator.per_unit(bid_volume + ask_volume, RoundingMode::HalfAwayFromZero)
+    }
+
+    /// Cost of trading `notional` through each side, for the quote engine
+    ///
+    /// A side too thin to fill it is measured to its last visible level and flagged
+    /// insufficient. `None` if either side is empty.
+    fn depth_profile(&self, notional: Notional) -> Option<DepthProfile> {
+        let (bid_impact_bps, bid_filled) = self.capped_impact_bps(OrderSide::Ask, notional)?;
+        let (ask_impact_bps, ask_filled) = self.capped_impact_bps(OrderSide::Bid, notional)?;
+        Some(DepthProfile { notional, bid_impact_bps, ask_impact_bps, bid_insufficient: !bid_filled, ask_insufficient: !ask_filled })
+    }
+
+    /// Apply a batch read straight from the receive buffer, rescaling its levels to `Scale::DEFAULT`
+    fn apply_batch_view(&mut self, batch: &OrderBookBatchView<'_>) {
+        let (price_scale, qty_scale) = (batch.price_scale(), batch.qty_scale());
+        for bid in batch.bids() {
+            if let Some((price, qty)) = default_scaled_level(bid.price, bid.size, price_scale, qty_scale) {
+                self.update_bid(price, qty);
+            }
+        }
+
+        for ask in batch.asks() {
+            if let Some((price, qty)) = default_scaled_level(ask.price, ask.size, price_scale, qty_scale) {
+                self.update_ask(price, qty);
+            }
+        }
+
+        self.set_timestamp(batch.timestamp());
+    }
+}
+
+/// Convert a wire level at the batch's scales to `Scale::DEFAULT`
+///
+/// Narrowing truncates toward zero and a quantity too large for the default scale
+/// saturates. `None` if the price is not positive once rescaled or does not fit.
+#[inline]
+pub(crate) fn default_scaled_level(price: i64, size: i64, price_scale: Scale, qty_scale: Scale) -> Option<(Price, Qty)> {
+    let price = mm_binary::rescale(price, price_scale, Scale::DEFAULT).filter(|&price| price > 0)?;
+    let size = mm_binary::rescale(size, qty_scale, Scale::DEFAULT).unwrap_or(if size < 0 { i64::MIN } else { i64::MAX });
+    Some((Price(price), Qty(size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderBook;
+    use crate::TickLadderBook;
+
+    fn levels<B: BookView>(mut book: B) -> B {
+        for (price, qty) in [(99.0, 1.0), (98.0, 2.0), (97.0, 4.0)] {
+            book.update_bid(Price::from_f64(price), Qty::from_f64(qty));
+        }
+        for (price, qty) in [(101.0, 1.0), (102.0, 1.0), (104.0, 2.0)] {
+            book.update_ask(Price::from_f64(price), Qty::from_f64(qty));
+        }
+        book
+    }
+
+    #[test]
+    fn test_sweep_and_impact() {
+        let book = levels(OrderBook::new("BTCUSDT"));
+
+        // Buying 2 takes 101 and 102
+        assert_eq!(book.sweep_vwap(OrderSide::Bid, Qty::from_f64(2.0)), Some(Price::from_f64(101.5)));
+        // Selling 3 takes 99 and two at 98
+        assert_eq!(book.sweep_vwap(OrderSide::Ask, Qty::from_f64(3.0)), Some(Price::from_f64(295.0 / 3.0)));
+        assert_eq!(book.sweep_vwap(OrderSide::Bid, Qty::from_f64(5.0)), None);
+
+        // 203 of notional buys 1 at 101 and 1 at 102: 1.5 above a mid of 100
+        assert!((book.impact_bps(OrderSide::Bid, Notional::from_f64(203.0)).unwrap() - 150.0).abs() < 1e-6);
+        // 99 sells exactly the top bid
+        assert!((book.impact_bps(OrderSide::Ask, Notional::from_f64(99.0)).unwrap() - 100.0).abs() < 1e-6);
+        assert_eq!(book.impact_bps(OrderSide::Ask, Notional::from_f64(1_000.0)), None);
+
+        // Buying 99 of notional takes 0.98.. at 101, rounded to a raw unit
+        let profile = book.depth_profile(Notional::from_f64(99.0)).unwrap();
+        assert!((profile.bid_impact_bps - 100.0).abs() < 1e-6);
+        assert!((profile.ask_impact_bps - 100.0).abs() < 1e-3);
+        assert!(!profile.bid_insufficient && !profile.ask_insufficient);
+
+        // The asks hold 411 of notional: buying 500 is measured to 104, all 4 at 102.75
+        let profile = book.depth_profile(Notional::from_f64(500.0)).unwrap();
+        assert!(profile.ask_insufficient && !profile.bid_insufficient);
+        assert!((profile.ask_impact_bps - 275.0).abs() < 1e-6);
+        assert!(profile.bid_impact_bps > 0.0);
+        assert_eq!(OrderBook::new("BTCUSDT").depth_profile(Notional::from_f64(500.0)), None);
+    }
+
+    #[test]
+    fn test_depth_and_micro_price_agree_across_books() {
+        let btree = levels(OrderBook::new("BTCUSDT"));
+        let ladder = levels(TickLadderBook::new("BTCUSDT", Price::from_f64(1.0), 64));
+
+        // 200 bps of 100 reaches 98 and 102
+        assert_eq!(btree.depth_within_bps(OrderSide::Bid, 200.0), Qty::from_f64(3.0));
+        assert_eq!(ladder.depth_within_bps(OrderSide::Ask, 200.0), Qty::from_f64(2.0));
+
+        // One level is the top-of-book micro-price: (99 * 1 + 101 * 1) / 2
+        assert_eq!(btree.weighted_micro_price(1), Some(Price::from_f64(100.0)));
+        // Two levels: bids 3 @ 98.33.., asks 2 @ 101.5, so (98.33.. * 2 + 101.5 * 3) / 5
+        let expected = Price::from_f64((295.0 / 3.0 * 2.0 + 101.5 * 3.0) / 5.0);
+        assert!((btree.weighted_micro_price(2).unwrap() - expected).0.abs() <= 1);
+        assert_eq!(btree.weighted_micro_price(3), ladder.weighted_micro_price(3));
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::fmt::Write;
+
+use mm_binary::FIXED_POINT_MULTIPLIER;
+use mm_binary::checksum::calculate_crc32;
+use mm_types::Price;
+use mm_types::Qty;
+
+use crate::BookView;
+
+/// Decimal places at `Scale::DEFAULT`
+const DEFAULT_DECIMALS: usize = 8;
+
+/// The local book no longer matches the venue's; it must be rebuilt from a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub expected: u32,
+    pub computed: u32,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Order book checksum mismatch: venue {:#010x}, local {:#010x}", self.expected, self.computed)
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Venue CRC-32 over the top of the book
+///
+/// Both venues checksum their own decimal strings, which are rebuilt here from
+/// fixed-point values, so books must be kept at the venue's tick and lot precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookChecksum {
+    /// Top 25 levels interleaved as `bid:size:ask:size:...`, numbers without trailing zeros.
+    /// OKX publishes the CRC as a signed 32-bit integer; compare it as `checksum as u32`.
+    Okx,
+    /// Top 10 asks then top 10 bids, each price then quantity at the pair's precision with
+    /// the decimal point and leading zeros removed
+    Kraken { price_decimals: u8, qty_decimals: u8 },
+}
+
+impl BookChecksum {
+    /// Levels per side the checksum covers
+    pub fn depth(&self) -> usize {
+        match self {
+            BookChecksum::Okx => 25,
+            BookChecksum::Kraken { .. } => 10,
+        }
+    }
+
+    /// The string the venue feeds to CRC-32
+    pub fn payload(&self, book: &impl BookView) -> String {
+        let depth = self.depth();
+        let mut payload = String::with_capacity(depth * 2 * 32);
+        match *self {
+            BookChecksum::Okx => {
+                let mut bids = book.bid_levels().take(depth);
+                let mut asks = book.ask_levels().take(depth);
+                loop {
+                    let (bid, ask) = (bids.next(), asks.next());
+                    if bid.is_none() && ask.is_none() {
+                        break;
+                    }
+                    for (price, qty) in bid.into_iter().chain(ask) {
+                        if !payload.is_empty() {
+                            payload.push(':');
+                        }
+                        push_trimmed(&mut payload, price.0);
+                        payload.push(':');
+                        push_trimmed(&mut payload, qty.0);
+                    }
+                }
+            }
+            BookChecksum::Kraken { price_decimals, qty_decimals } => {
+                let mut push_levels = |levels: &mut dyn Iterator<Item = (Price, Qty)>| {
+                    for (price, qty) in levels.take(depth) {
+                        push_digits(&mut payload, price.0, price_decimals as usize);
+                        push_digits(&mut payload, qty.0, qty_decimals as usize);
+                    }
+                };
+                push_levels(&mut book.ask_levels());
+                push_levels(&mut book.bid_levels());
+            }
+        }
+        payload
+    }
+
+    pub fn compute(&self, book: &impl BookView) -> u32 {
+        calculate_crc32(self.payload(book).as_bytes())
+    }
+
+    /// Compare with the checksum the venue sent alongside the update that produced `book`
+    pub fn verify(&self, book: &impl BookView, expected: u32) -> Result<(), ChecksumMismatch> {
+        let computed = self.compute(book);
+        if computed != expected {
+            return Err(ChecksumMismatch { expected, computed });
+        }
+        Ok(())
+    }
+}
+
+/// `value` as a decimal with no trailing fractional zeros, e.g. `3366.1` or `7`
+fn push_trimmed(out: &mut String, value: i64) {
+    if value < 0 {
+        out.push('-');
+    }
+    let value = value.unsigned_abs();
+    let multiplier = FIXED_POINT_MULTIPLIER as u64;
+    let _ = write!(out, "{}", value / multiplier);
+    let fraction = value % multiplier;
+    if fraction != 0 {
+        let digits = format!("{:0width$}", fraction, width = DEFAULT_DECIMALS);
+        out.push('.');
+        out.push_str(digits.trim_end_matches('0'));
+    }
+}
+
+/// `value` at `decimals` places with the point and leading zeros dropped, e.g. `0.05005` at 5 is `5005`
+fn push_digits(out: &mut String, value: i64, decimals: usize) {
+    let decimals = decimals.min(DEFAULT_DECIMALS);
+    let value = value.unsigned_abs();
+    let multiplier = FIXED_POINT_MULTIPLIER as u64;
+    let start = out.len();
+    let _ = write!(out, "{}", value / multiplier);
+    let fraction = format!("{:0width$}", value % multiplier, width = DEFAULT_DECIMALS);
+    out.push_str(&fraction[..decimals]);
+    let zeros = out[start..].bytes().take_while(|&byte| byte == b'0').count();
+    out.drain(start..start + zeros);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderBook;
+
+    fn level(price: &str, qty: &str) -> (i64, i64) {
+        let parse = |value: &str| mm_binary::parse_json_decimal_to_fixed_point(value.as_bytes()).unwrap();
+        (parse(price), parse(qty))
+    }
+
+    #[test]
+    fn test_okx_checksum() {
+        let mut book = OrderBook::new("BTC-USDT");
+        book.load_snapshot(&[level("3366.1", "7"), level("3366", "6")], &[level("3366.8", "9"), level("3368", "8"), level("3372", "8")]);
+
+        // Interleaved until the shorter side runs out
+        let payload = BookChecksum::Okx.payload(&book);
+        assert_eq!(payload, "3366.1:7:3366.8:9:3366:6:3368:8:3372:8");
+        // zlib.crc32(payload)
+        assert_eq!(BookChecksum::Okx.compute(&book), 0x5132_1FA1);
+
+        assert!(BookChecksum::Okx.verify(&book, 0x5132_1FA1).is_ok());
+        book.update_bid(Price::from_f64(3366.1), Qty::from_int(8));
+        assert_eq!(BookChecksum::Okx.verify(&book, 0x5132_1FA1).unwrap_err().expected, 0x5132_1FA1);
+    }
+
+    #[test]
+    fn test_kraken_checksum() {
+        let mut book = OrderBook::new("XBT/USD");
+        book.load_snapshot(&[level("0.05", "0.1"), level("0.04995", "12.5")], &[level("0.05005", "0.000005")]);
+
+        let kraken = BookChecksum::Kraken { price_decimals: 5, qty_decimals: 8 };
+        assert_eq!(kraken.payload(&book), "500550050001000000049951250000000");
+        // zlib.crc32(payload)
+        assert_eq!(kraken.compute(&book), 0x47A8_F2F0);
+    }
+}
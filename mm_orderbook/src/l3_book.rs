@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use mm_types::OrderSide;
+use mm_types::Price;
+use mm_types::Qty;
+
+use crate::OrderBook;
+
+/// An order-by-order update that does not apply to the current book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L3Error {
+    /// An add reused the ID of a resting order
+    DuplicateOrder { order_id: u64 },
+    /// No resting order has this ID
+    UnknownOrder { order_id: u64 },
+    /// Resting orders need a positive quantity
+    NonPositiveQuantity { order_id: u64, quantity: Qty },
+}
+
+impl fmt::Display for L3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            L3Error::DuplicateOrder { order_id } => write!(f, "Order {} is already resting", order_id),
+            L3Error::UnknownOrder { order_id } => write!(f, "Unknown order {}", order_id),
+            L3Error::NonPositiveQuantity { order_id, quantity } => write!(f, "Order {} has non-positive quantity {}", order_id, quantity),
+        }
+    }
+}
+
+impl std::error::Error for L3Error {}
+
+/// A resting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L3Order {
+    pub order_id: u64,
+    pub side: OrderSide,
+    pub price: Price,
+    pub quantity: Qty,
+}
+
+/// Where an order sits in its price level's FIFO queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePosition {
+    pub side: OrderSide,
+    pub price: Price,
+    /// Orders that fill first; zero at the front of the queue
+    pub orders_ahead: usize,
+    /// Quantity that fills first
+    pub qty_ahead: Qty,
+    /// Total quantity resting at the level, including this order
+    pub level_qty: Qty,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Level {
+    /// Order IDs in time priority
+    queue: VecDeque<u64>,
+    total: Qty,
+}
+
+/// Order-by-order book with FIFO time priority at each price
+///
+/// Modifies follow the common venue rule: reducing the quantity at the same price
+/// keeps the order's place in the queue, while a price change or a size increase
+/// sends it to the back.
+#[derive(Debug, Clone)]
+pub struct L3OrderBook {
+    pub symbol: Arc<str>,
+    pub timestamp: u64,
+    orders: HashMap<u64, L3Order>,
+    bids: BTreeMap<Price, Level>,
+    asks: BTreeMap<Price, Level>,
+}
+
+impl L3OrderBook {
+    pub fn new(symbol: &str) -> Self {
+        Self { symbol: Arc::from(symbol), timestamp: 0, orders: HashMap::new(), bids: BTreeMap::new(), asks: BTreeMap::new() }
+    }
+
+    pub fn clear(&mut self) {
+        self.orders.clear();
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Rest a new order at the back of its price level
+    pub fn add(&mut self, order_id: u64, side: OrderSide, price: Price, quantity: Qty) -> Result<(), L3Error> {
+        if self.orders.contains_key(&order_id) {
+            return Err(L3Error::DuplicateOrder { order_id });
+        }
+        if quantity <= Qty::ZERO {
+            return Err(L3Error::NonPositiveQuantity { order_id, quantity });
+        }
+
+        let level = self.side_mut(side).entry(price).or_default();
+        level.queue.push_back(order_id);
+        level.total += quantity;
+        self.orders.insert(order_id, L3Order { order_id, side, price, quantity });
+        Ok(())
+    }
+
+    /// Change an order's price and quantity; a zero quantity deletes it
+    pub fn modify(&mut self, order_id: u64, price: Price, quantity: Qty) -> Result<(), L3Error> {
+        let order = *self.orders.get(&order_id).ok_or(L3Error::UnknownOrder { order_id })?;
+        if quantity.is_zero() {
+            return self.delete(order_id).map(|_| ());
+        }
+        if quantity < Qty::ZERO {
+            return Err(L3Error::NonPositiveQuantity { order_id, quantity });
+        }
+
+        if price == order.price && quantity <= order.quantity {
+            self.reduce(order_id, order.quantity - quantity);
+            return Ok(());
+        }
+
+        self.delete(order_id)?;
+        self.add(order_id, order.side, price, quantity)
+    }
+
+    /// Remove an order, returning it
+    pub fn delete(&mut self, order_id: u64) -> Result<L3Order, L3Error> {
+        let order = self.orders.remove(&order_id).ok_or(L3Error::UnknownOrder { order_id })?;
+        let levels = self.side_mut(order.side);
+        if let Some(level) = levels.get_mut(&order.price) {
+            if let Some(index) = level.queue.iter().position(|&id| id == order_id) {
+                level.queue.remove(index);
+            }
+            level.total -= order.quantity;
+            if level.queue.is_empty() {
+                levels.remove(&order.price);
+            }
+        }
+        Ok(order)
+    }
+
+    /// Fill part or all of a resting order, keeping its queue place; returns what is left
+    pub fn execute(&mut self, order_id: u64, quantity: Qty) -> Result<Qty, L3Error> {
+        let order = *self.orders.get(&order_id).ok_or(L3Error::UnknownOrder { order_id })?;
+        if quantity >= order.quantity {
+            self.delete(order_id)?;
+            return Ok(Qty::ZERO);
+        }
+        self.reduce(order_id, quantity.max(Qty::ZERO));
+        Ok(order.quantity - quantity.max(Qty::ZERO))
+    }
+
+    pub fn order(&self, order_id: u64) -> Option<&L3Order> {
+        self.orders.get(&order_id)
+    }
+
+    pub fn order_count(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Orders resting at a price, front of the queue first
+    pub fn orders_at(&self, side: OrderSide, price: Price) -> impl Iterator<Item = &L3Order> + '_ {
+        self.side(side).get(&price).into_iter().flat_map(|level| level.queue.iter()).filter_map(|id| self.orders.get(id))
+    }
+
+    /// Where an order sits in its level's queue
+    pub fn queue_position(&self, order_id: u64) -> Option<QueuePosition> {
+        let order = self.orders.get(&order_id)?;
+        let level = self.side(order.side).get(&order.price)?;
+        let orders_ahead = level.queue.iter().position(|&id| id == order_id)?;
+        let qty_ahead = level.queue.iter().take(orders_ahead).filter_map(|id| self.orders.get(id)).map(|ahead| ahead.quantity).sum();
+        Some(QueuePosition { side: order.side, price: order.price, orders_ahead, qty_ahead, level_qty: level.total })
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Qty)> {
+        self.bids.iter().next_back().map(|(price, level)| (*price, level.total))
+    }
+
+    pub fn best_ask(&self) -> Option<(Price, Qty)> {
+        self.asks.iter().next().map(|(price, level)| (*price, level.total))
+    }
+
+    /// Aggregated bid levels from best to worst
+    pub fn bid_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        self.bids.iter().rev().map(|(price, level)| (*price, level.total))
+    }
+
+    /// Aggregated ask levels from best to worst
+    pub fn ask_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        self.asks.iter().map(|(price, level)| (*price, level.total))
+    }
+
+    /// Aggregate into a price-level book holding at most `max_levels` per side
+    pub fn to_l2(&self, max_levels: usize) -> OrderBook {
+        let mut book = OrderBook::with_max_levels(&self.symbol, max_levels);
+        for (price, qty) in self.bid_levels().take(max_levels) {
+            book.update_bid(price, qty);
+        }
+        for (price, qty) in self.ask_levels().take(max_levels) {
+            book.update_ask(price, qty);
+        }
+        book.timestamp = self.timestamp;
+        book
+    }
+
+    /// Shrink a resting order in place
+    fn reduce(&mut self, order_id: u64, by: Qty) {
+        if let Some(order) = self.orders.get_mut(&order_id) {
+            order.quantity -= by;
+            let (side, price) = (order.side, order.price);
+            if let Some(level) = self.side_mut(side).get_mut(&price) {
+                level.total -= by;
+            }
+        }
+    }
+
+    fn side(&self, side: OrderSide) -> &BTreeMap<Price, Level> {
+        match side {
+            OrderSide::Bid => &self.bids,
+            OrderSide::Ask => &self.asks,
+        }
+    }
+
+    fn side_mut(&mut self, side: OrderSide) -> &mut BTreeMap<Price, Level> {
+        match side {
+            OrderSide::Bid => &mut self.bids,
+            OrderSide::Ask => &mut self.asks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BookView;
+
+    fn book() -> L3OrderBook {
+        let mut book = L3OrderBook::new("BTC-USD");
+        book.add(1, OrderSide::Bid, Price::from_int(100), Qty::from_int(1)).unwrap();
+        book.add(2, OrderSide::Bid, Price::from_int(100), Qty::from_int(2)).unwrap();
+        book.add(3, OrderSide::Bid, Price::from_int(100), Qty::from_int(3)).unwrap();
+        book.add(4, OrderSide::Bid, Price::from_int(99), Qty::from_int(5)).unwrap();
+        book.add(5, OrderSide::Ask, Price::from_int(101), Qty::from_int(4)).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_fifo_queue_position() {
+        let mut book = book();
+        let position = book.queue_position(3).unwrap();
+        assert_eq!(position.orders_ahead, 2);
+        assert_eq!(position.qty_ahead, Qty::from_int(3));
+        assert_eq!(position.level_qty, Qty::from_int(6));
+
+        // Partial fills at the front move the queue up without reordering it
+        assert_eq!(book.execute(1, Qty::from_f64(0.5)), Ok(Qty::from_f64(0.5)));
+        assert_eq!(book.queue_position(3).unwrap().qty_ahead, Qty::from_f64(2.5));
+        assert_eq!(book.execute(1, Qty::from_int(1)), Ok(Qty::ZERO));
+        assert_eq!(book.queue_position(3).unwrap().orders_ahead, 1);
+        assert_eq!(book.queue_position(1), None);
+
+        assert_eq!(book.add(2, OrderSide::Ask, Price::from_int(102), Qty::from_int(1)), Err(L3Error::DuplicateOrder { order_id: 2 }));
+        assert_eq!(book.delete(42), Err(L3Error::UnknownOrder { order_id: 42 }));
+    }
+
+    #[test]
+    fn test_modify_priority_rules() {
+        let mut book = book();
+
+        // Reducing in place keeps priority
+        book.modify(1, Price::from_int(100), Qty::from_f64(0.5)).unwrap();
+        assert_eq!(book.queue_position(1).unwrap().orders_ahead, 0);
+
+        // Increasing size goes to the back
+        book.modify(1, Price::from_int(100), Qty::from_int(2)).unwrap();
+        assert_eq!(book.queue_position(1).unwrap().orders_ahead, 2);
+        let ids: Vec<u64> = book.orders_at(OrderSide::Bid, Price::from_int(100)).map(|order| order.order_id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+
+        // Repricing moves the order to the new level
+        book.modify(4, Price::from_int(100), Qty::from_int(5)).unwrap();
+        assert_eq!(book.bid_levels().collect::<Vec<_>>(), vec![(Price::from_int(100), Qty::from_int(12))]);
+
+        // Zero quantity deletes
+        book.modify(5, Price::from_int(101), Qty::ZERO).unwrap();
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.order_count(), 4);
+    }
+
+    #[test]
+    fn test_l2_aggregation() {
+        let book = book();
+        let l2 = book.to_l2(1);
+        assert_eq!(l2.best_bid(), Some((Price::from_int(100), Qty::from_int(6))));
+        assert_eq!(l2.bid_levels().count(), 1);
+        assert_eq!(l2.best_ask(), Some((Price::from_int(101), Qty::from_int(4))));
+        assert_eq!(book.ask_levels().collect::<Vec<_>>(), vec![(Price::from_int(101), Qty::from_int(4))]);
+    }
+}
@@ -1,5 +1,23 @@
+pub mod book_view;
+pub mod checksum;
+pub mod l3_book;
+pub mod manager;
 pub mod orderbook;
+pub mod tick_ladder;
 
+pub use book_view::BookView;
+pub use checksum::BookChecksum;
+pub use checksum::ChecksumMismatch;
+pub use l3_book::L3Error;
+pub use l3_book::L3Order;
+pub use l3_book::L3OrderBook;
+pub use l3_book::QueuePosition;
+pub use manager::BatchOutcome;
+pub use manager::BookSnapshot;
+pub use manager::OrderBookManager;
+pub use manager::SnapshotProvider;
+pub use orderbook::BookIssue;
 pub use orderbook::OrderBook;
 pub use orderbook::json_to_binary;
 pub use orderbook::process_orderbook_update;
+pub use tick_ladder::TickLadderBook;
@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mm_binary::CompressedString;
+use mm_binary::OrderBookBatchMessage;
+use mm_binary::OrderBookBatchView;
+use mm_binary::compressed_string::EncodingScheme;
+use tracing::info;
+use tracing::warn;
+
+use crate::BookIssue;
+use crate::OrderBook;
+
+/// A full book as a venue REST endpoint, a recording or a test fixture returns it
+///
+/// Levels are raw fixed-point `(price, quantity)` pairs; `last_update_id` is the
+/// venue sequence ID the snapshot is current to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookSnapshot {
+    pub last_update_id: u64,
+    pub bids: Vec<(i64, i64)>,
+    pub asks: Vec<(i64, i64)>,
+}
+
+/// Where [`OrderBookManager`] gets full books, when tracking starts and after a sequence gap
+///
+/// Closures taking the symbol implement it, which covers replay and tests.
+pub trait SnapshotProvider {
+    fn fetch_snapshot(&mut self, symbol: &str) -> Result<BookSnapshot, Box<dyn std::error::Error>>;
+}
+
+impl<F> SnapshotProvider for F
+where
+    F: FnMut(&str) -> Result<BookSnapshot, Box<dyn std::error::Error>>,
+{
+    fn fetch_snapshot(&mut self, symbol: &str) -> Result<BookSnapshot, Box<dyn std::error::Error>> {
+        self(symbol)
+    }
+}
+
+/// What [`OrderBookManager`] did with a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutcome {
+    Applied,
+    /// Already covered by the snapshot or an earlier batch
+    Skipped,
+    /// A sequence gap or failed integrity check rebuilt the book from a fresh snapshot
+    Resynced,
+    /// The book needs a snapshot that could not be fetched yet; the batch was dropped
+    OutOfSync,
+    /// No tracked book matches the batch's instrument ID or symbol
+    Untracked,
+}
+
+/// Venue sequence IDs carried by a batch (Binance `U`, `u` and `pu`)
+#[derive(Debug, Clone, Copy)]
+struct UpdateIds {
+    first: u64,
+    last: u64,
+    prev: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncState {
+    /// Holding a snapshot and waiting for the batch that bridges it
+    AwaitingSync { snapshot_id: u64 },
+    /// Every batch up to `last_update_id` has been applied
+    Synced { last_update_id: u64 },
+    /// Nothing applies until a fresh snapshot is loaded
+    NeedsSnapshot,
+}
+
+/// Whether a batch fits the book's sequence
+enum Sequence {
+    Apply,
+    Skip,
+    Gap,
+}
+
+#[derive(Debug)]
+struct ManagedBook {
+    book: OrderBook,
+    /// The symbol as batches carry it, if it fits a `CompressedString`
+    code: Option<(CompressedString, EncodingScheme)>,
+    state: SyncState,
+    batches_since_fetch: usize,
+}
+
+impl ManagedBook {
+    fn load(&mut self, snapshot: &BookSnapshot, trim: bool) {
+        self.book.load_snapshot(&snapshot.bids, &snapshot.asks);
+        if trim {
+            self.book.trim_book();
+        }
+        self.state = SyncState::AwaitingSync { snapshot_id: snapshot.last_update_id };
+        self.batches_since_fetch = 0;
+    }
+
+    /// Binance rules: the first batch must straddle the snapshot (`U <= lastUpdateId + 1 <= u + 1`),
+    /// and each later one must continue the last, by `pu` where the venue sends it or by `U` otherwise.
+    /// Batches without IDs always apply.
+    fn sequence(&mut self, ids: UpdateIds) -> Sequence {
+        if self.state != SyncState::NeedsSnapshot && ids.last == 0 {
+            self.state = SyncState::Synced { last_update_id: 0 };
+            return Sequence::Apply;
+        }
+
+        match self.state {
+            SyncState::NeedsSnapshot => Sequence::Gap,
+            SyncState::AwaitingSync { snapshot_id } => {
+                if ids.last < snapshot_id {
+                    return Sequence::Skip;
+                }
+                if ids.first > snapshot_id + 1 {
+                    warn!("{}: snapshot {} is older than the stream (U={})", self.book.symbol, snapshot_id, ids.first);
+                    return Sequence::Gap;
+                }
+                info!("{}: synchronized at U={}, u={}, snapshot {}", self.book.symbol, ids.first, ids.last, snapshot_id);
+                self.state = SyncState::Synced { last_update_id: ids.last };
+                Sequence::Apply
+            }
+            SyncState::Synced { last_update_id } => {
+                if ids.last <= last_update_id {
+                    return Sequence::Skip;
+                }
+                let continues = if ids.prev != 0 { ids.prev == last_update_id } else { ids.first == last_update_id + 1 };
+                if !continues {
+                    warn!("{}: sequence gap after u={} (U={}, pu={})", self.book.symbol, last_update_id, ids.first, ids.prev);
+                    return Sequence::Gap;
+                }
+                self.state = SyncState::Synced { last_update_id: ids.last };
+                Sequence::Apply
+            }
+        }
+    }
+}
+
+/// Order books for several instruments, kept in sequence with their venue streams
+///
+/// Batches are routed by instrument ID where both the batch and the tracked book
+/// have one, and by symbol otherwise. A sequence gap, or an update that leaves the
+/// book crossed or holding an invalid level, refetches the snapshot through the
+/// [`SnapshotProvider`] straight away.
+pub struct OrderBookManager<P> {
+    provider: P,
+    books: Vec<ManagedBook>,
+    by_symbol: HashMap<Arc<str>, usize>,
+    by_instrument: HashMap<u32, usize>,
+    max_levels: Option<usize>,
+    refetch_interval: usize,
+}
+
+impl<P: SnapshotProvider> OrderBookManager<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            books: Vec::new(),
+            by_symbol: HashMap::new(),
+            by_instrument: HashMap::new(),
+            max_levels: None,
+            refetch_interval: 0,
+        }
+    }
+
+    /// Trim books to `max_levels` per side after every snapshot and batch
+    pub fn with_max_levels(mut self, max_levels: usize) -> Self {
+        self.max_levels = Some(max_levels);
+        self
+    }
+
+    /// Wait for at least `batches` batches on a book between snapshot fetches, so a
+    /// failing or rate-limited provider is not called on every update
+    pub fn with_refetch_interval(mut self, batches: usize) -> Self {
+        self.refetch_interval = batches;
+        self
+    }
+
+    /// Start tracking `symbol` from a fresh snapshot; a non-zero `instrument_id` also routes batches by ID
+    ///
+    /// Tracking a symbol again reloads its snapshot.
+    pub fn track(&mut self, symbol: &str, instrument_id: u32) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = self.provider.fetch_snapshot(symbol)?;
+        let index = match self.by_symbol.get(symbol) {
+            Some(&index) => index,
+            None => {
+                let book = match self.max_levels {
+                    Some(max_levels) => OrderBook::with_max_levels(symbol, max_levels),
+                    None => OrderBook::new(symbol),
+                };
+                self.books.push(ManagedBook {
+                    book,
+                    code: CompressedString::from_str(symbol).ok(),
+                    state: SyncState::NeedsSnapshot,
+                    batches_since_fetch: 0,
+                });
+                self.by_symbol.insert(Arc::from(symbol), self.books.len() - 1);
+                self.books.len() - 1
+            }
+        };
+        if instrument_id != 0 {
+            self.by_instrument.insert(instrument_id, index);
+        }
+
+        self.books[index].load(&snapshot, self.max_levels.is_some());
+        info!("{symbol}: loaded snapshot {} with {} bids, {} asks", snapshot.last_update_id, snapshot.bids.len(), snapshot.asks.len());
+        Ok(())
+    }
+
+    pub fn book(&self, symbol: &str) -> Option<&OrderBook> {
+        self.by_symbol.get(symbol).map(|&index| &self.books[index].book)
+    }
+
+    pub fn book_by_instrument(&self, instrument_id: u32) -> Option<&OrderBook> {
+        self.by_instrument.get(&instrument_id).map(|&index| &self.books[index].book)
+    }
+
+    /// Whether every batch since the snapshot has been applied in sequence
+    pub fn is_synchronized(&self, symbol: &str) -> bool {
+        self.by_symbol.get(symbol).is_some_and(|&index| matches!(self.books[index].state, SyncState::Synced { .. }))
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &str> + '_ {
+        self.books.iter().map(|managed| &*managed.book.symbol)
+    }
+
+    /// Drop the book's state and refetch its snapshot, e.g. after a venue checksum mismatch
+    ///
+    /// Ignores the refetch interval. Returns whether the snapshot was loaded; on
+    /// failure later batches retry.
+    pub fn resync(&mut self, symbol: &str) -> bool {
+        let Some(&index) = self.by_symbol.get(symbol) else {
+            return false;
+        };
+        self.books[index].state = SyncState::NeedsSnapshot;
+        self.books[index].batches_since_fetch = usize::MAX;
+        self.refetch(index)
+    }
+
+    pub fn apply_batch(&mut self, batch: &OrderBookBatchMessage) -> BatchOutcome {
+        let ids = UpdateIds { first: batch.first_update_id(), last: batch.final_update_id(), prev: batch.prev_update_id() };
+        match self.find(batch.instrument_id(), batch.symbol(), batch.encoding()) {
+            Some(index) => self.apply_with(index, ids, |book| book.apply_batch(batch)),
+            None => BatchOutcome::Untracked,
+        }
+    }
+
+    /// Same as [`apply_batch`](Self::apply_batch), reading levels straight from the receive buffer
+    pub fn apply_batch_view(&mut self, batch: &OrderBookBatchView<'_>) -> BatchOutcome {
+        let ids = UpdateIds { first: batch.first_update_id(), last: batch.final_update_id(), prev: batch.prev_update_id() };
+        match self.find(batch.instrument_id(), batch.symbol(), batch.encoding()) {
+            Some(index) => self.apply_with(index, ids, |book| book.apply_batch_view(batch)),
+            None => BatchOutcome::Untracked,
+        }
+    }
+
+    fn find(&self, instrument_id: u32, symbol: CompressedString, encoding: EncodingScheme) -> Option<usize> {
+        if instrument_id != 0 {
+            if let Some(&index) = self.by_instrument.get(&instrument_id) {
+                return Some(index);
+            }
+        }
+        self.books.iter().position(|managed| managed.code == Some((symbol, encoding)))
+    }
+
+    fn apply_with(&mut self, index: usize, ids: UpdateIds, apply: impl Fn(&mut OrderBook)) -> BatchOutcome {
+        let managed = &mut self.books[index];
+        managed.batches_since_fetch = managed.batches_since_fetch.saturating_add(1);
+        match managed.sequence(ids) {
+            Sequence::Apply => {}
+            Sequence::Skip => return BatchOutcome::Skipped,
+            Sequence::Gap => {
+                managed.state = SyncState::NeedsSnapshot;
+                if !self.refetch(index) {
+                    return BatchOutcome::OutOfSync;
+                }
+                // The batch that exposed the gap may be the one that bridges the new snapshot
+                let managed = &mut self.books[index];
+                if let Sequence::Apply = managed.sequence(ids) {
+                    apply(&mut managed.book);
+                    self.finish(index);
+                }
+                return BatchOutcome::Resynced;
+            }
+        }
+
+        apply(&mut managed.book);
+        if self.finish(index) {
+            BatchOutcome::Applied
+        } else if self.refetch(index) {
+            BatchOutcome::Resynced
+        } else {
+            BatchOutcome::OutOfSync
+        }
+    }
+
+    /// Trim and check the book after a batch; false if it can only be fixed by a snapshot
+    fn finish(&mut self, index: usize) -> bool {
+        let managed = &mut self.books[index];
+        if self.max_levels.is_some() {
+            managed.book.trim_book();
+        }
+        // Staleness is the caller's concern: a zero max age skips it
+        match managed.book.validate(0, 0).into_iter().find(BookIssue::requires_resync) {
+            Some(issue) => {
+                warn!("{}: {issue}, refetching snapshot", managed.book.symbol);
+                managed.state = SyncState::NeedsSnapshot;
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Load a fresh snapshot unless the refetch interval has not passed; returns whether it did
+    fn refetch(&mut self, index: usize) -> bool {
+        let managed = &mut self.books[index];
+        if managed.batches_since_fetch < self.refetch_interval {
+            return false;
+        }
+
+        let symbol = Arc::clone(&managed.book.symbol);
+        match self.provider.fetch_snapshot(&symbol) {
+            Ok(snapshot) => {
+                managed.load(&snapshot, self.max_levels.is_some());
+                info!("{symbol}: resynced from snapshot {}", snapshot.last_update_id);
+                true
+            }
+            Err(err) => {
+                warn!("{symbol}: failed to fetch snapshot: {err}");
+                managed.batches_since_fetch = 0;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use mm_binary::Exchange;
+    use mm_binary::messages::UpdateType;
+    use mm_types::Price;
+    use mm_types::Qty;
+
+    use super::*;
+    use crate::BookView;
+
+    fn snapshot(last_update_id: u64, bid: i64, ask: i64) -> BookSnapshot {
+        BookSnapshot {
+            last_update_id,
+            bids: vec![(Price::from_int(bid).0, Qty::from_int(1).0)],
+            asks: vec![(Price::from_int(ask).0, Qty::from_int(1).0)],
+        }
+    }
+
+    /// Hands out `snapshots` in order, then fails
+    fn provider(snapshots: Vec<BookSnapshot>) -> impl FnMut(&str) -> Result<BookSnapshot, Box<dyn Error>> {
+        let mut snapshots = snapshots.into_iter();
+        move |symbol: &str| snapshots.next().ok_or_else(|| format!("no snapshot for {symbol}").into())
+    }
+
+    fn batch(symbol: &str, first: u64, last: u64, prev: u64, bid: i64) -> OrderBookBatchMessage {
+        let (code, encoding) = CompressedString::from_str(symbol).unwrap();
+        let mut batch = OrderBookBatchMessage::new_with_ids(Exchange::Binance, UpdateType::Update, code, encoding, 1, first, last, prev);
+        batch.add_bid(Price::from_int(bid).0, Qty::from_int(2).0);
+        batch
+    }
+
+    #[test]
+    fn test_syncs_on_bridging_batch() {
+        let mut books = OrderBookManager::new(provider(vec![snapshot(100, 99, 101)]));
+        books.track("BTCUSDT", 0).unwrap();
+
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 90, 95, 0, 98)), BatchOutcome::Skipped);
+        assert!(!books.is_synchronized("BTCUSDT"));
+
+        // Spot-style IDs: the first batch straddles the snapshot, then each starts where the last ended
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 96, 101, 0, 98)), BatchOutcome::Applied);
+        assert!(books.is_synchronized("BTCUSDT"));
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 102, 103, 0, 97)), BatchOutcome::Applied);
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 100, 101, 0, 96)), BatchOutcome::Skipped);
+
+        let book = books.book("BTCUSDT").unwrap();
+        assert_eq!(book.best_bid(), Some((Price::from_int(99), Qty::from_int(1))));
+        assert_eq!(book.bid_levels().count(), 3);
+        assert_eq!(books.apply_batch(&batch("ETHUSDT", 1, 2, 0, 98)), BatchOutcome::Untracked);
+    }
+
+    #[test]
+    fn test_gap_refetches_snapshot() {
+        let mut books = OrderBookManager::new(provider(vec![snapshot(100, 99, 101), snapshot(200, 95, 96)]));
+        books.track("BTCUSDT", 0).unwrap();
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 100, 105, 99, 98)), BatchOutcome::Applied);
+
+        // `pu` does not match the last `u`: the batch is older than the new snapshot, so nothing applies yet
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 110, 120, 108, 98)), BatchOutcome::Resynced);
+        assert!(!books.is_synchronized("BTCUSDT"));
+        assert_eq!(books.book("BTCUSDT").unwrap().best_bid(), Some((Price::from_int(95), Qty::from_int(1))));
+
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 195, 201, 194, 94)), BatchOutcome::Applied);
+        assert!(books.is_synchronized("BTCUSDT"));
+
+        // A crossing update cannot be trusted, and the provider has no more snapshots
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 202, 203, 201, 97)), BatchOutcome::OutOfSync);
+        assert!(!books.is_synchronized("BTCUSDT"));
+        assert_eq!(books.apply_batch(&batch("BTCUSDT", 204, 205, 203, 94)), BatchOutcome::OutOfSync);
+    }
+
+    #[test]
+    fn test_refetch_interval_and_instrument_routing() {
+        let snapshots = vec![snapshot(100, 99, 101), snapshot(10, 2, 3), snapshot(300, 99, 101)];
+        let mut books = OrderBookManager::new(provider(snapshots)).with_refetch_interval(2);
+        books.track("BTCUSDT", 7).unwrap();
+        books.track("ETHUSDT", 0).unwrap();
+
+        // The instrument ID wins over the symbol the batch carries
+        let mut routed = batch("ETHUSDT", 250, 260, 0, 98);
+        routed.set_instrument_id(7);
+        assert_eq!(books.apply_batch(&routed), BatchOutcome::OutOfSync);
+        assert_eq!(books.apply_batch(&routed), BatchOutcome::Resynced);
+        assert_eq!(books.book_by_instrument(7).unwrap().symbol(), "BTCUSDT");
+        assert!(!books.is_synchronized("ETHUSDT"));
+
+        // Unknown IDs fall back to the symbol
+        let mut unknown = batch("ETHUSDT", 5, 12, 0, 2);
+        unknown.set_instrument_id(9);
+        assert_eq!(books.apply_batch(&unknown), BatchOutcome::Applied);
+        assert!(books.is_synchronized("ETHUSDT"));
+        assert_eq!(books.symbols().collect::<Vec<_>>(), vec!["BTCUSDT", "ETHUSDT"]);
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::Arc;
 
 use mm_binary::CompressedString;
@@ -6,16 +7,78 @@ use mm_binary::Exchange;
 use mm_binary::MarketDataMessage;
 use mm_binary::messages::UpdateType;
 use mm_binary::parse_json_decimal_to_fixed_point;
+use mm_types::OrderSide;
+use mm_types::Price;
+use mm_types::Qty;
 use simd_json::prelude::ValueAsArray;
 use simd_json::prelude::ValueAsScalar;
 use simd_json::prelude::ValueObjectAccess;
 
+use crate::BookView;
+use crate::book_view::default_scaled_level;
+
+/// Something wrong with an order book's contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookIssue {
+    /// Best bid above best ask
+    Crossed {
+        bid: Price,
+        ask: Price,
+    },
+    /// Best bid equal to best ask
+    Locked {
+        price: Price,
+    },
+    EmptyBids,
+    EmptyAsks,
+    /// More levels than `max_levels` (the book was not trimmed)
+    TooManyLevels {
+        side: OrderSide,
+        levels: usize,
+        max_levels: usize,
+    },
+    /// A level with a non-positive price or quantity
+    InvalidLevel {
+        side: OrderSide,
+        price: Price,
+        quantity: Qty,
+    },
+    /// No update for longer than the allowed age, in milliseconds
+    Stale {
+        age_ms: u64,
+        max_age_ms: u64,
+    },
+}
+
+impl BookIssue {
+    /// Whether the book can no longer be trusted and must be rebuilt from a snapshot
+    ///
+    /// Locked, empty, oversized and stale books can recover through later updates.
+    pub fn requires_resync(&self) -> bool {
+        matches!(self, BookIssue::Crossed { .. } | BookIssue::InvalidLevel { .. })
+    }
+}
+
+impl fmt::Display for BookIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookIssue::Crossed { bid, ask } => write!(f, "Crossed book: bid {} > ask {}", bid, ask),
+            BookIssue::Locked { price } => write!(f, "Locked book at {}", price),
+            BookIssue::EmptyBids => write!(f, "No bids"),
+            BookIssue::EmptyAsks => write!(f, "No asks"),
+            BookIssue::TooManyLevels { side, levels, max_levels } => write!(f, "{:?} side has {} levels, max {}", side, levels, max_levels),
+            BookIssue::InvalidLevel { side, price, quantity } => write!(f, "Invalid {:?} level {} @ {}", side, quantity, price),
+            BookIssue::Stale { age_ms, max_age_ms } => write!(f, "Book is {}ms old, max {}ms", age_ms, max_age_ms),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OrderBook {
     pub symbol: Arc<str>,
     pub timestamp: u64,
-    pub bids: BTreeMap<i64, i64>, // price -> quantity (fixed-point i64)
-    pub asks: BTreeMap<i64, i64>,
+    pub bids: BTreeMap<Price, Qty>,
+    pub asks: BTreeMap<Price, Qty>,
     pub max_levels: usize,
 }
 
@@ -28,16 +91,16 @@ impl OrderBook {
         Self { symbol: Arc::from(symbol), timestamp: 0, bids: BTreeMap::new(), asks: BTreeMap::new(), max_levels }
     }
 
-    pub fn update_bid(&mut self, price: i64, quantity: i64) {
-        if quantity == 0 {
+    pub fn update_bid(&mut self, price: Price, quantity: Qty) {
+        if quantity.is_zero() {
             self.bids.remove(&price);
         } else {
             self.bids.insert(price, quantity);
         }
     }
 
-    pub fn update_ask(&mut self, price: i64, quantity: i64) {
-        if quantity == 0 {
+    pub fn update_ask(&mut self, price: Price, quantity: Qty) {
+        if quantity.is_zero() {
             self.asks.remove(&price);
         } else {
             self.asks.insert(price, quantity);
@@ -46,20 +109,36 @@ impl OrderBook {
 
     /// Apply a batch of orderbook updates from OrderBookBatchMessage
     ///
-    /// This is a convenience method to avoid duplicate loops in binaries
+    /// This is a convenience method to avoid duplicate loops in binaries. Levels of
+    /// batches encoded at an instrument's own scale are rescaled to `Scale::DEFAULT`.
     pub fn apply_batch(&mut self, batch: &mm_binary::OrderBookBatchMessage) {
+        let (price_scale, qty_scale) = (batch.price_scale(), batch.qty_scale());
+        for bid in batch.bids() {
+            if let Some((price, qty)) = default_scaled_level(bid.price, bid.size, price_scale, qty_scale) {
+                self.update_bid(price, qty);
+            }
+        }
+
+        for ask in batch.asks() {
+            if let Some((price, qty)) = default_scaled_level(ask.price, ask.size, price_scale, qty_scale) {
+                self.update_ask(price, qty);
+            }
+        }
+
+        self.timestamp = batch.timestamp();
+    }
+
+    /// Same as [`apply_batch`](Self::apply_batch), reading levels straight from the receive buffer
+    pub fn apply_batch_view(&mut self, batch: &mm_binary::OrderBookBatchView<'_>) {
+        let (price_scale, qty_scale) = (batch.price_scale(), batch.qty_scale());
         for bid in batch.bids() {
-            let price = bid.price;
-            let qty = bid.size;
-            if price > 0 {
+            if let Some((price, qty)) = default_scaled_level(bid.price, bid.size, price_scale, qty_scale) {
                 self.update_bid(price, qty);
             }
         }
 
         for ask in batch.asks() {
-            let price = ask.price;
-            let qty = ask.size;
-            if price > 0 {
+            if let Some((price, qty)) = default_scaled_level(ask.price, ask.size, price_scale, qty_scale) {
                 self.update_ask(price, qty);
             }
         }
@@ -67,6 +146,18 @@ impl OrderBook {
         self.timestamp = batch.timestamp();
     }
 
+    /// Replace both sides with raw fixed-point `(price, quantity)` levels, as a REST depth snapshot returns them
+    pub fn load_snapshot(&mut self, bids: &[(i64, i64)], asks: &[(i64, i64)]) {
+        self.bids.clear();
+        self.asks.clear();
+        for &(price, qty) in bids {
+            self.update_bid(Price(price), Qty(qty));
+        }
+        for &(price, qty) in asks {
+            self.update_ask(Price(price), Qty(qty));
+        }
+    }
+
     pub fn trim_book(&mut self) {
         // Trim bids (remove lowest prices)
         while self.bids.len() > self.max_levels {
@@ -83,21 +174,59 @@ impl OrderBook {
         }
     }
 
-    pub fn best_bid(&self) -> Option<(i64, i64)> {
+    /// Check the book's integrity; an empty list means it looks sound
+    ///
+    /// `now_ms` is compared with `timestamp` (exchange milliseconds); a zero
+    /// `max_age_ms` skips the staleness check. Level checks visit every level, so
+    /// run this per batch rather than per level update.
+    pub fn validate(&self, now_ms: u64, max_age_ms: u64) -> Vec<BookIssue> {
+        let mut issues = Vec::new();
+
+        match (self.best_bid(), self.best_ask()) {
+            (Some((bid, _)), Some((ask, _))) if bid > ask => issues.push(BookIssue::Crossed { bid, ask }),
+            (Some((bid, _)), Some((ask, _))) if bid == ask => issues.push(BookIssue::Locked { price: bid }),
+            (None, ask) => {
+                issues.push(BookIssue::EmptyBids);
+                if ask.is_none() {
+                    issues.push(BookIssue::EmptyAsks);
+                }
+            }
+            (_, None) => issues.push(BookIssue::EmptyAsks),
+            _ => {}
+        }
+
+        for (side, levels) in [(OrderSide::Bid, &self.bids), (OrderSide::Ask, &self.asks)] {
+            if levels.len() > self.max_levels {
+                issues.push(BookIssue::TooManyLevels { side, levels: levels.len(), max_levels: self.max_levels });
+            }
+            if let Some((&price, &quantity)) = levels.iter().find(|(price, quantity)| price.0 <= 0 || quantity.0 <= 0) {
+                issues.push(BookIssue::InvalidLevel { side, price, quantity });
+            }
+        }
+
+        let age_ms = now_ms.saturating_sub(self.timestamp);
+        if max_age_ms > 0 && age_ms > max_age_ms {
+            issues.push(BookIssue::Stale { age_ms, max_age_ms });
+        }
+
+        issues
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Qty)> {
         self.bids.iter().next_back().map(|(p, q)| (*p, *q))
     }
 
-    pub fn best_ask(&self) -> Option<(i64, i64)> {
+    pub fn best_ask(&self) -> Option<(Price, Qty)> {
         self.asks.iter().next().map(|(p, q)| (*p, *q))
     }
 
-    pub fn mid_price(&self) -> Option<i64> {
+    pub fn mid_price(&self) -> Option<Price> {
         let (bid_price, _) = self.best_bid()?;
         let (ask_price, _) = self.best_ask()?;
-        Some((bid_price + ask_price) / 2)
+        Some(bid_price.midpoint(ask_price))
     }
 
-    pub fn spread(&self) -> Option<i64> {
+    pub fn spread(&self) -> Option<Price> {
         match (self.best_bid(), self.best_ask()) {
             (Some((bid_price, _)), Some((ask_price, _))) => Some(ask_price - bid_price),
             _ => None,
@@ -105,16 +234,54 @@ impl OrderBook {
     }
 
     /// Get top N bid levels
-    pub fn top_bids(&self, n: usize) -> Vec<(i64, i64)> {
+    pub fn top_bids(&self, n: usize) -> Vec<(Price, Qty)> {
         self.bids.iter().rev().take(n).map(|(p, q)| (*p, *q)).collect()
     }
 
     /// Get top N ask levels
-    pub fn top_asks(&self, n: usize) -> Vec<(i64, i64)> {
+    pub fn top_asks(&self, n: usize) -> Vec<(Price, Qty)> {
         self.asks.iter().take(n).map(|(p, q)| (*p, *q)).collect()
     }
 }
 
+impl BookView for OrderBook {
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    fn best_bid(&self) -> Option<(Price, Qty)> {
+        OrderBook::best_bid(self)
+    }
+
+    fn best_ask(&self) -> Option<(Price, Qty)> {
+        OrderBook::best_ask(self)
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        self.bids.iter().rev().map(|(p, q)| (*p, *q))
+    }
+
+    fn ask_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        self.asks.iter().map(|(p, q)| (*p, *q))
+    }
+
+    fn update_bid(&mut self, price: Price, quantity: Qty) {
+        OrderBook::update_bid(self, price, quantity);
+    }
+
+    fn update_ask(&mut self, price: Price, quantity: Qty) {
+        OrderBook::update_ask(self, price, quantity);
+    }
+}
+
 /// Converts Binance JSON orderbook update to binary message
 pub fn json_to_binary(json_data: &str) -> Result<MarketDataMessage, Box<dyn std::error::Error>> {
     let mut bytes = json_data.as_bytes().to_vec();
@@ -178,7 +345,7 @@ pub fn process_orderbook_update(orderbook: &mut OrderBook, json_data: &str) -> R
                     let quantity_str = bid_array[1].as_str().ok_or("Invalid bid quantity")?;
                     let price = parse_json_decimal_to_fixed_point(price_str.as_bytes())?;
                     let quantity = parse_json_decimal_to_fixed_point(quantity_str.as_bytes())?;
-                    orderbook.update_bid(price, quantity);
+                    orderbook.update_bid(Price(price), Qty(quantity));
                 }
             }
         }
@@ -193,7 +360,7 @@ pub fn process_orderbook_update(orderbook: &mut OrderBook, json_data: &str) -> R
                     let quantity_str = ask_array[1].as_str().ok_or("Invalid ask quantity")?;
                     let price = parse_json_decimal_to_fixed_point(price_str.as_bytes())?;
                     let quantity = parse_json_decimal_to_fixed_point(quantity_str.as_bytes())?;
-                    orderbook.update_ask(price, quantity);
+                    orderbook.update_ask(Price(price), Qty(quantity));
                 }
             }
         }
@@ -213,14 +380,14 @@ mod tests {
         let mut ob = OrderBook::new("BTCUSDT");
 
         // Use fixed-point i64 values (8 decimal places)
-        let price_50000 = 50_000 * FIXED_POINT_MULTIPLIER;
-        let price_49999 = 49_999 * FIXED_POINT_MULTIPLIER;
-        let price_50001 = 50_001 * FIXED_POINT_MULTIPLIER;
-        let price_50002 = 50_002 * FIXED_POINT_MULTIPLIER;
-        let qty_1_5 = 150_000_000; // 1.5 * 100_000_000
-        let qty_2_0 = 200_000_000; // 2.0 * 100_000_000
-        let qty_1_0 = 100_000_000; // 1.0 * 100_000_000
-        let qty_0_5 = 50_000_000; // 0.5 * 100_000_000
+        let price_50000 = Price(50_000 * FIXED_POINT_MULTIPLIER);
+        let price_49999 = Price(49_999 * FIXED_POINT_MULTIPLIER);
+        let price_50001 = Price(50_001 * FIXED_POINT_MULTIPLIER);
+        let price_50002 = Price(50_002 * FIXED_POINT_MULTIPLIER);
+        let qty_1_5 = Qty(150_000_000); // 1.5 * 100_000_000
+        let qty_2_0 = Qty(200_000_000); // 2.0 * 100_000_000
+        let qty_1_0 = Qty(100_000_000); // 1.0 * 100_000_000
+        let qty_0_5 = Qty(50_000_000); // 0.5 * 100_000_000
 
         ob.update_bid(price_50000, qty_1_5);
         ob.update_bid(price_49999, qty_2_0);
@@ -230,17 +397,80 @@ mod tests {
         assert_eq!(ob.best_bid(), Some((price_50000, qty_1_5)));
         assert_eq!(ob.best_ask(), Some((price_50001, qty_1_0)));
         // Mid price: (50000 + 50001) / 2 = 50000.5
-        assert_eq!(ob.mid_price(), Some((price_50000 + price_50001) / 2));
+        assert_eq!(ob.mid_price(), Some(Price::from_f64(50_000.5)));
         assert_eq!(ob.spread(), Some(price_50001 - price_50000));
     }
 
+    #[test]
+    fn test_apply_batch_rescales_to_default() {
+        use mm_binary::OrderBookBatchMessage;
+        use mm_binary::OrderBookBatchView;
+        use mm_binary::Scale;
+        use mm_binary::ValidationLevel;
+
+        // SHIB-like prices at 12 places, whole-token sizes
+        let (code, encoding) = CompressedString::from_str("SHIBUSDT").unwrap();
+        let mut batch = OrderBookBatchMessage::new(Exchange::Binance, UpdateType::Snapshot, code, encoding, 7);
+        batch.set_scales(Scale::new(12).unwrap(), Scale::new(0).unwrap());
+        batch.add_bid(12_340_000, 50_000_000);
+        batch.add_bid(1, 10); // below the default scale's resolution
+        batch.add_ask(12_350_000, i64::MAX);
+
+        let mut ob = OrderBook::new("SHIBUSDT");
+        ob.apply_batch(&batch);
+        assert_eq!(ob.best_bid(), Some((Price::from_f64(0.00001234), Qty::from_int(50_000_000))));
+        assert_eq!(ob.bids.len(), 1);
+        assert_eq!(ob.best_ask(), Some((Price::from_f64(0.00001235), Qty(i64::MAX))));
+
+        let bytes = batch.to_bytes();
+        let mut viewed = OrderBook::new("SHIBUSDT");
+        viewed.apply_batch_view(&OrderBookBatchView::new(&bytes, ValidationLevel::Standard).unwrap());
+        assert_eq!(viewed.bids, ob.bids);
+        assert_eq!(viewed.asks, ob.asks);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut ob = OrderBook::with_max_levels("BTCUSDT", 2);
+        assert_eq!(ob.validate(0, 0), vec![BookIssue::EmptyBids, BookIssue::EmptyAsks]);
+
+        ob.update_bid(Price::from_int(100), Qty::from_int(1));
+        ob.update_ask(Price::from_int(101), Qty::from_int(1));
+        ob.timestamp = 1_000;
+        assert!(ob.validate(1_500, 1_000).is_empty());
+        assert_eq!(ob.validate(5_000, 1_000), vec![BookIssue::Stale { age_ms: 4_000, max_age_ms: 1_000 }]);
+
+        ob.update_ask(Price::from_int(100), Qty::from_int(1));
+        ob.update_ask(Price::from_int(102), Qty::from_int(1));
+        let issues = ob.validate(1_000, 0);
+        assert_eq!(
+            issues,
+            vec![
+                BookIssue::Locked { price: Price::from_int(100) },
+                BookIssue::TooManyLevels { side: OrderSide::Ask, levels: 3, max_levels: 2 }
+            ]
+        );
+        assert!(!issues.iter().any(BookIssue::requires_resync));
+
+        ob.update_bid(Price::from_int(103), Qty::from_int(1));
+        ob.update_bid(Price::from_int(99), Qty::from_int(-1));
+        let issues = ob.validate(1_000, 0);
+        assert_eq!(issues[0], BookIssue::Crossed { bid: Price::from_int(103), ask: Price::from_int(100) });
+        assert!(issues.contains(&BookIssue::InvalidLevel {
+            side: OrderSide::Bid,
+            price: Price::from_int(99),
+            quantity: Qty::from_int(-1)
+        }));
+        assert!(issues[0].requires_resync());
+    }
+
     #[test]
     fn test_orderbook_updates() {
         let mut ob = OrderBook::new("BTCUSDT");
 
-        let price_50000 = 50_000 * FIXED_POINT_MULTIPLIER;
-        let qty_1_5 = 150_000_000;
-        let qty_2_0 = 200_000_000;
+        let price_50000 = Price(50_000 * FIXED_POINT_MULTIPLIER);
+        let qty_1_5 = Qty(150_000_000);
+        let qty_2_0 = Qty(200_000_000);
 
         ob.update_bid(price_50000, qty_1_5);
         assert_eq!(ob.best_bid(), Some((price_50000, qty_1_5)));
@@ -250,7 +480,7 @@ mod tests {
         assert_eq!(ob.best_bid(), Some((price_50000, qty_2_0)));
 
         // Remove level
-        ob.update_bid(price_50000, 0);
+        ob.update_bid(price_50000, Qty::ZERO);
         assert_eq!(ob.best_bid(), None);
     }
 }
@@ -0,0 +1,348 @@
+use std::sync::Arc;
+
+use mm_types::Price;
+use mm_types::Qty;
+
+use crate::BookView;
+
+/// Where a price falls relative to the ladder window
+enum Slot {
+    In(usize),
+    Below,
+    Above,
+}
+
+/// Order book stored as two price-indexed arrays over a window of ticks
+///
+/// Slot `i` holds the level at `base + i * tick_size`. The best bid and ask
+/// slots are tracked, so best-level reads are O(1) and updates never allocate.
+/// When a level that would become the best on its side lands outside the
+/// window, the window re-centres on it and levels that fall off the far end are
+/// discarded. Other out-of-window levels and prices off the tick grid are
+/// dropped and counted.
+#[derive(Debug, Clone)]
+pub struct TickLadderBook {
+    pub symbol: Arc<str>,
+    pub timestamp: u64,
+    tick_size: i64,
+    /// Price of slot 0, on the tick grid
+    base: i64,
+    bids: Vec<Qty>,
+    asks: Vec<Qty>,
+    best_bid: Option<usize>,
+    best_ask: Option<usize>,
+    dropped: u64,
+}
+
+impl TickLadderBook {
+    /// A window of `ticks` price levels (at least two); non-positive tick sizes become one raw unit
+    pub fn new(symbol: &str, tick_size: Price, ticks: usize) -> Self {
+        let ticks = ticks.max(2);
+        Self {
+            symbol: Arc::from(symbol),
+            timestamp: 0,
+            tick_size: tick_size.0.max(1),
+            base: 0,
+            bids: vec![Qty::ZERO; ticks],
+            asks: vec![Qty::ZERO; ticks],
+            best_bid: None,
+            best_ask: None,
+            dropped: 0,
+        }
+    }
+
+    pub fn tick_size(&self) -> Price {
+        Price(self.tick_size)
+    }
+
+    /// Number of price levels the window holds
+    pub fn ticks(&self) -> usize {
+        self.bids.len()
+    }
+
+    /// Lowest and highest price the window currently covers
+    pub fn window(&self) -> (Price, Price) {
+        (self.price_at(0), self.price_at(self.ticks() - 1))
+    }
+
+    /// Updates discarded for being off the tick grid or too deep to fit the window
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.bids.fill(Qty::ZERO);
+        self.asks.fill(Qty::ZERO);
+        self.best_bid = None;
+        self.best_ask = None;
+    }
+
+    /// Replace both sides with raw fixed-point `(price, quantity)` levels, centred on the snapshot touch
+    pub fn load_snapshot(&mut self, bids: &[(i64, i64)], asks: &[(i64, i64)]) {
+        self.clear();
+        let best_bid = bids.iter().filter(|&&(_, qty)| qty != 0).map(|&(price, _)| price).max();
+        let best_ask = asks.iter().filter(|&&(_, qty)| qty != 0).map(|&(price, _)| price).min();
+        let center = match (best_bid, best_ask) {
+            (Some(bid), Some(ask)) => Some(Price(bid).midpoint(Price(ask))),
+            (bid, ask) => bid.or(ask).map(Price),
+        };
+        if let Some(center) = center {
+            self.recenter(center);
+        }
+        for &(price, qty) in bids {
+            self.update_bid(Price(price), Qty(qty));
+        }
+        for &(price, qty) in asks {
+            self.update_ask(Price(price), Qty(qty));
+        }
+    }
+
+    /// Move the window so `center` sits in its middle, discarding levels that no longer fit
+    pub fn recenter(&mut self, center: Price) {
+        let half = (self.ticks() / 2) as i64;
+        let aligned = center.0.div_euclid(self.tick_size) * self.tick_size;
+        let new_base = aligned.saturating_sub(half.saturating_mul(self.tick_size));
+        let shift = (new_base as i128 - self.base as i128) / self.tick_size as i128;
+        let len = self.ticks();
+
+        if shift.unsigned_abs() >= len as u128 {
+            self.bids.fill(Qty::ZERO);
+            self.asks.fill(Qty::ZERO);
+        } else if shift > 0 {
+            let shift = shift as usize;
+            for side in [&mut self.bids, &mut self.asks] {
+                side.copy_within(shift.., 0);
+                side[len - shift..].fill(Qty::ZERO);
+            }
+        } else if shift < 0 {
+            let shift = shift.unsigned_abs() as usize;
+            for side in [&mut self.bids, &mut self.asks] {
+                side.copy_within(..len - shift, shift);
+                side[..shift].fill(Qty::ZERO);
+            }
+        }
+
+        self.base = new_base;
+        self.best_bid = self.bids.iter().rposition(|qty| !qty.is_zero());
+        self.best_ask = self.asks.iter().position(|qty| !qty.is_zero());
+    }
+
+    #[inline]
+    fn price_at(&self, slot: usize) -> Price {
+        Price(self.base + slot as i64 * self.tick_size)
+    }
+
+    #[inline]
+    fn slot(&self, price: i64) -> Slot {
+        let offset = (price as i128 - self.base as i128) / self.tick_size as i128;
+        if price < self.base {
+            Slot::Below
+        } else if offset >= self.ticks() as i128 {
+            Slot::Above
+        } else {
+            Slot::In(offset as usize)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.best_bid.is_none() && self.best_ask.is_none()
+    }
+
+    fn update(&mut self, is_bid: bool, price: Price, quantity: Qty) {
+        if price.0.rem_euclid(self.tick_size) != 0 {
+            self.dropped += 1;
+            return;
+        }
+        if quantity.is_zero() {
+            // Removing a level the window does not hold is a no-op
+            if let Slot::In(slot) = self.slot(price.0) {
+                self.set(is_bid, slot, quantity);
+            }
+            return;
+        }
+
+        if self.is_empty() {
+            self.recenter(price);
+        }
+        match self.slot(price.0) {
+            Slot::In(slot) => self.set(is_bid, slot, quantity),
+            // Outside the window, only a new best level moves it
+            Slot::Above if is_bid || self.best_ask.is_none() => self.recenter_on(is_bid, price, quantity),
+            Slot::Below if !is_bid || self.best_bid.is_none() => self.recenter_on(is_bid, price, quantity),
+            _ => self.dropped += 1,
+        }
+    }
+
+    /// Re-centre between the new level and the opposite touch if both fit, otherwise on the level
+    fn recenter_on(&mut self, is_bid: bool, price: Price, quantity: Qty) {
+        let opposite = if is_bid { self.best_ask() } else { self.best_bid() };
+        let center = opposite.map_or(price, |(other, _)| price.midpoint(other));
+        self.recenter(center);
+        let slot = match self.slot(price.0) {
+            Slot::In(slot) => slot,
+            _ => {
+                self.recenter(price);
+                match self.slot(price.0) {
+                    Slot::In(slot) => slot,
+                    _ => {
+                        self.dropped += 1;
+                        return;
+                    }
+                }
+            }
+        };
+        self.set(is_bid, slot, quantity);
+    }
+
+    #[inline]
+    fn set(&mut self, is_bid: bool, slot: usize, quantity: Qty) {
+        if is_bid {
+            self.bids[slot] = quantity;
+            if !quantity.is_zero() {
+                self.best_bid = Some(self.best_bid.map_or(slot, |best| best.max(slot)));
+            } else if self.best_bid == Some(slot) {
+                self.best_bid = self.bids[..slot].iter().rposition(|qty| !qty.is_zero());
+            }
+        } else {
+            self.asks[slot] = quantity;
+            if !quantity.is_zero() {
+                self.best_ask = Some(self.best_ask.map_or(slot, |best| best.min(slot)));
+            } else if self.best_ask == Some(slot) {
+                self.best_ask = self.asks[slot + 1..].iter().position(|qty| !qty.is_zero()).map(|offset| slot + 1 + offset);
+            }
+        }
+    }
+}
+
+impl BookView for TickLadderBook {
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    #[inline]
+    fn best_bid(&self) -> Option<(Price, Qty)> {
+        self.best_bid.map(|slot| (self.price_at(slot), self.bids[slot]))
+    }
+
+    #[inline]
+    fn best_ask(&self) -> Option<(Price, Qty)> {
+        self.best_ask.map(|slot| (self.price_at(slot), self.asks[slot]))
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        let end = self.best_bid.map_or(0, |slot| slot + 1);
+        self.bids[..end].iter().enumerate().rev().filter(|(_, qty)| !qty.is_zero()).map(|(slot, qty)| (self.price_at(slot), *qty))
+    }
+
+    fn ask_levels(&self) -> impl Iterator<Item = (Price, Qty)> + '_ {
+        let start = self.best_ask.unwrap_or(self.asks.len());
+        self.asks[start..]
+            .iter()
+            .enumerate()
+            .filter(|(_, qty)| !qty.is_zero())
+            .map(move |(offset, qty)| (self.price_at(start + offset), *qty))
+    }
+
+    #[inline]
+    fn update_bid(&mut self, price: Price, quantity: Qty) {
+        self.update(true, price, quantity);
+    }
+
+    #[inline]
+    fn update_ask(&mut self, price: Price, quantity: Qty) {
+        self.update(false, price, quantity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::OrderBook;
+
+    fn ladder() -> TickLadderBook {
+        TickLadderBook::new("BTCUSDT", Price::from_f64(0.1), 100)
+    }
+
+    #[test]
+    fn test_best_levels_track_updates() {
+        let mut book = ladder();
+        book.update_bid(Price::from_f64(100.0), Qty::from_int(1));
+        book.update_bid(Price::from_f64(99.9), Qty::from_int(2));
+        book.update_ask(Price::from_f64(100.2), Qty::from_int(3));
+        book.update_ask(Price::from_f64(100.5), Qty::from_int(4));
+
+        assert_eq!(book.best_bid(), Some((Price::from_f64(100.0), Qty::from_int(1))));
+        assert_eq!(book.best_ask(), Some((Price::from_f64(100.2), Qty::from_int(3))));
+        assert_eq!(book.mid_price(), Some(Price::from_f64(100.1)));
+
+        // Removing the touch falls back to the next level
+        book.update_bid(Price::from_f64(100.0), Qty::ZERO);
+        book.update_ask(Price::from_f64(100.2), Qty::ZERO);
+        assert_eq!(book.best_bid(), Some((Price::from_f64(99.9), Qty::from_int(2))));
+        assert_eq!(book.best_ask(), Some((Price::from_f64(100.5), Qty::from_int(4))));
+
+        // Off-grid prices are dropped rather than rounded
+        book.update_bid(Price::from_f64(99.95), Qty::from_int(1));
+        assert_eq!(book.dropped(), 1);
+        assert_eq!(book.bid_levels().count(), 1);
+    }
+
+    #[test]
+    fn test_window_recentres_on_new_touch() {
+        let mut book = ladder();
+        book.update_bid(Price::from_f64(100.0), Qty::from_int(1));
+        book.update_ask(Price::from_f64(100.1), Qty::from_int(1));
+        assert_eq!(book.window(), (Price::from_f64(95.0), Price::from_f64(104.9)));
+
+        // Too deep to fit: dropped
+        book.update_bid(Price::from_f64(90.0), Qty::from_int(1));
+        assert_eq!(book.dropped(), 1);
+
+        // The market moves up past the window and the old levels fall off
+        book.update_bid(Price::from_f64(110.0), Qty::from_int(2));
+        assert_eq!(book.window(), (Price::from_f64(105.0), Price::from_f64(114.9)));
+        assert_eq!(book.best_ask(), None);
+
+        book.update_ask(Price::from_f64(110.1), Qty::from_int(2));
+        assert_eq!(book.spread(), Some(Price::from_f64(0.1)));
+        assert_eq!(book.bid_levels().collect::<Vec<_>>(), vec![(Price::from_f64(110.0), Qty::from_int(2))]);
+        assert_eq!(book.dropped(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_btree_book_within_window(updates in prop::collection::vec((any::<bool>(), 0i64..200, 0i64..4), 1..200)) {
+            let tick = Price::from_f64(0.01);
+            let mut ladder = TickLadderBook::new("BTCUSDT", tick, 512);
+            let mut btree = OrderBook::new("BTCUSDT");
+
+            for (is_bid, ticks, qty) in updates {
+                let price = Price(Price::from_int(50_000).0 + ticks * tick.0);
+                let qty = Qty::from_int(qty);
+                if is_bid {
+                    ladder.update_bid(price, qty);
+                    btree.update_bid(price, qty);
+                } else {
+                    ladder.update_ask(price, qty);
+                    btree.update_ask(price, qty);
+                }
+            }
+
+            prop_assert_eq!(ladder.best_bid(), btree.best_bid());
+            prop_assert_eq!(ladder.best_ask(), btree.best_ask());
+            prop_assert!(ladder.bid_levels().eq(btree.bid_levels()));
+            prop_assert!(ladder.ask_levels().eq(btree.ask_levels()));
+            prop_assert_eq!(ladder.dropped(), 0);
+        }
+    }
+}
@@ -1,20 +1,26 @@
 use std::collections::HashMap;
 
 use mm_orderbook::OrderBook;
-use mm_strategy::FixedPoint;
+use mm_strategy::FeeSchedule;
+use mm_strategy::InstrumentSpec;
+use mm_strategy::Notional;
 use mm_strategy::OrderSide;
 use mm_strategy::Position;
+use mm_strategy::Price;
+use mm_strategy::Qty;
+use mm_strategy::SpecViolation;
 use mm_strategy::StrategyQuote;
 use tracing::info;
+use tracing::warn;
 
 /// Simulated order in the order book
 #[derive(Debug, Clone)]
 pub struct SimulatedOrder {
     pub order_id: u64,
     pub side: OrderSide,
-    pub price: FixedPoint,
-    pub remaining_quantity: FixedPoint,
-    pub original_quantity: FixedPoint,
+    pub price: Price,
+    pub remaining_quantity: Qty,
+    pub original_quantity: Qty,
     pub timestamp: u64,
 }
 
@@ -23,9 +29,11 @@ pub struct SimulatedOrder {
 pub struct SimulatedFill {
     pub order_id: u64,
     pub side: OrderSide,
-    pub price: FixedPoint,
-    pub quantity: FixedPoint,
+    pub price: Price,
+    pub quantity: Qty,
     pub is_maker: bool,
+    /// Fee charged under the simulator's schedule (negative for a rebate)
+    pub fee: Notional,
     pub timestamp: u64,
 }
 
@@ -65,11 +73,42 @@ pub struct OrderBookSimulator {
     active_orders: HashMap<u64, SimulatedOrder>,
     position: Position,
     fills: Vec<SimulatedFill>,
+    instrument: InstrumentSpec,
+    fees: FeeSchedule,
+    rejected_orders: u64,
 }
 
 impl OrderBookSimulator {
     pub fn new(config: SimulatorConfig) -> Self {
-        Self { config, next_order_id: 1, active_orders: HashMap::new(), position: Position::new(), fills: Vec::new() }
+        Self {
+            config,
+            next_order_id: 1,
+            active_orders: HashMap::new(),
+            position: Position::new(),
+            fills: Vec::new(),
+            instrument: InstrumentSpec::UNCONSTRAINED,
+            fees: FeeSchedule::zero(),
+            rejected_orders: 0,
+        }
+    }
+
+    /// Reject orders that break the venue's tick, lot or minimum notional rules and
+    /// track the position in the instrument's contract
+    pub fn with_instrument(mut self, instrument: InstrumentSpec) -> Self {
+        self.position = Position::new().with_contract(instrument.contract);
+        self.instrument = instrument;
+        self
+    }
+
+    /// Charge fills under the venue's maker/taker schedule
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Orders rejected for breaking the instrument rules
+    pub fn rejected_orders(&self) -> u64 {
+        self.rejected_orders
     }
 
     /// Get current position
@@ -87,22 +126,41 @@ impl OrderBookSimulator {
         let mut order_ids = Vec::new();
 
         // Place bid order
-        if quote.bid_size > FixedPoint::ZERO {
-            let order_id = self.place_order(OrderSide::Bid, quote.bid_price, quote.bid_size, timestamp);
-            order_ids.push(order_id);
+        if quote.bid_size > Qty::ZERO {
+            order_ids.extend(self.place_order(OrderSide::Bid, quote.bid_price, quote.bid_size, timestamp).ok());
         }
 
         // Place ask order
-        if quote.ask_size > FixedPoint::ZERO {
-            let order_id = self.place_order(OrderSide::Ask, quote.ask_price, quote.ask_size, timestamp);
-            order_ids.push(order_id);
+        if quote.ask_size > Qty::ZERO {
+            order_ids.extend(self.place_order(OrderSide::Ask, quote.ask_price, quote.ask_size, timestamp).ok());
         }
 
         order_ids
     }
 
-    /// Place a single order
-    pub fn place_order(&mut self, side: OrderSide, price: FixedPoint, quantity: FixedPoint, timestamp: u64) -> u64 {
+    /// Replace every resting order with a new ladder of (price, quantity) levels
+    ///
+    /// The old ladder is removed and the new one placed in a single call, so market
+    /// data is never matched against a mix of the two. Levels the venue would reject
+    /// are skipped.
+    pub fn replace_ladder(&mut self, bids: &[(Price, Qty)], asks: &[(Price, Qty)], timestamp: u64) -> Vec<u64> {
+        self.cancel_all_orders();
+
+        let levels = bids.iter().map(|level| (OrderSide::Bid, *level)).chain(asks.iter().map(|level| (OrderSide::Ask, *level)));
+        levels
+            .filter(|(_, (_, quantity))| *quantity > Qty::ZERO)
+            .filter_map(|(side, (price, quantity))| self.place_order(side, price, quantity, timestamp).ok())
+            .collect()
+    }
+
+    /// Place a single order, or reject it as the venue would if it breaks the instrument rules
+    pub fn place_order(&mut self, side: OrderSide, price: Price, quantity: Qty, timestamp: u64) -> Result<u64, SpecViolation> {
+        if let Err(violation) = self.instrument.validate_order(price, quantity) {
+            self.rejected_orders += 1;
+            warn!("Rejected order: side={:?}, price=${:.2}, qty={:.4}: {}", side, price.to_f64(), quantity.to_f64(), violation);
+            return Err(violation);
+        }
+
         let order_id = self.next_order_id;
         self.next_order_id += 1;
 
@@ -122,7 +180,7 @@ impl OrderBookSimulator {
             active_at_ns
         );
 
-        order_id
+        Ok(order_id)
     }
 
     /// Cancel an order
@@ -145,9 +203,9 @@ impl OrderBookSimulator {
 
     /// Update simulation with new market data
     /// Checks if any orders would be filled based on market movement
-    pub fn update_market_data(&mut self, orderbook: &OrderBook, timestamp: u64, last_trade_price: Option<FixedPoint>) {
-        let best_bid = orderbook.best_bid().map(|(price, _qty)| FixedPoint(price));
-        let best_ask = orderbook.best_ask().map(|(price, _qty)| FixedPoint(price));
+    pub fn update_market_data(&mut self, orderbook: &OrderBook, timestamp: u64, last_trade_price: Option<Price>) {
+        let best_bid = orderbook.best_bid().map(|(price, _qty)| price);
+        let best_ask = orderbook.best_ask().map(|(price, _qty)| price);
 
         // Log market data update
         let spread = if let (Some(bid), Some(ask)) = (best_bid, best_ask) { ask.to_f64() - bid.to_f64() } else { 0.0 };
@@ -157,14 +215,8 @@ impl OrderBookSimulator {
         if !LOGGED.swap(true, std::sync::atomic::Ordering::Relaxed) {
             let top_bids = orderbook.top_bids(3);
             let top_asks = orderbook.top_asks(3);
-            info!(
-                "DEBUG - Top 3 bids from orderbook: {:?}",
-                top_bids.iter().map(|(p, q)| (FixedPoint(*p).to_f64(), FixedPoint(*q).to_f64())).collect::<Vec<_>>()
-            );
-            info!(
-                "DEBUG - Top 3 asks from orderbook: {:?}",
-                top_asks.iter().map(|(p, q)| (FixedPoint(*p).to_f64(), FixedPoint(*q).to_f64())).collect::<Vec<_>>()
-            );
+            info!("DEBUG - Top 3 bids from orderbook: {:?}", top_bids.iter().map(|(p, q)| (p.to_f64(), q.to_f64())).collect::<Vec<_>>());
+            info!("DEBUG - Top 3 asks from orderbook: {:?}", top_asks.iter().map(|(p, q)| (p.to_f64(), q.to_f64())).collect::<Vec<_>>());
         }
 
         info!(
@@ -220,33 +272,36 @@ impl OrderBookSimulator {
                 // Determine fill quantity (could be partial)
                 let fill_quantity = Self::calculate_fill_quantity(self.config.fill_probability_factor, order, orderbook);
 
-                if fill_quantity > FixedPoint::ZERO {
-                    // Create fill event
-                    let fill = SimulatedFill {
+                if fill_quantity > Qty::ZERO {
+                    // All our orders are maker orders in this simulation
+                    let is_maker = true;
+
+                    // Update position
+                    let fee = self.position.apply_fill_with_fees(order.side, order.price, fill_quantity, is_maker, &self.fees);
+
+                    self.fills.push(SimulatedFill {
                         order_id: *order_id,
                         side: order.side,
                         price: order.price,
                         quantity: fill_quantity,
-                        is_maker: true, // All our orders are maker orders in this simulation
+                        is_maker,
+                        fee,
                         timestamp,
-                    };
-
-                    self.fills.push(fill);
-
-                    // Update position
-                    self.position.apply_fill(order.side, order.price, fill_quantity);
+                    });
 
                     info!(
-                        "Position updated: qty={:.4}, avg_entry=${:.2}, realized_pnl=${:.2}",
+                        "Position updated: qty={:.4}, avg_entry=${:.2}, realized_pnl=${:.2}, fees=${:.4}, rebates=${:.4}",
                         self.position.quantity.to_f64(),
                         self.position.avg_entry_price.to_f64(),
-                        self.position.realized_pnl.to_f64()
+                        self.position.realized_pnl.to_f64(),
+                        self.position.fees_paid.to_f64(),
+                        self.position.rebates_earned.to_f64()
                     );
 
                     // Update remaining quantity
-                    order.remaining_quantity = FixedPoint(order.remaining_quantity.0 - fill_quantity.0);
+                    order.remaining_quantity -= fill_quantity;
 
-                    let is_full_fill = order.remaining_quantity.0 <= 0;
+                    let is_full_fill = order.remaining_quantity <= Qty::ZERO;
                     let fill_type = if is_full_fill { "Full" } else { "Partial" };
 
                     info!(
@@ -275,11 +330,11 @@ impl OrderBookSimulator {
 
     /// Calculate fill quantity for an order
     /// In a real simulation, this would consider queue position, order book depth, etc.
-    fn calculate_fill_quantity(fill_probability_factor: f64, order: &SimulatedOrder, _orderbook: &OrderBook) -> FixedPoint {
+    fn calculate_fill_quantity(fill_probability_factor: f64, order: &SimulatedOrder, _orderbook: &OrderBook) -> Qty {
         // Simplified: fill a fraction of remaining quantity based on probability
         let fill_qty = order.remaining_quantity.to_f64() * fill_probability_factor;
 
-        FixedPoint::from_f64(fill_qty)
+        Qty::from_f64(fill_qty)
     }
 
     /// Get active order count
@@ -319,6 +374,7 @@ impl LatencySimulator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mm_strategy::ContractKind;
 
     #[test]
     fn test_simulator_creation() {
@@ -329,12 +385,21 @@ mod tests {
         assert_eq!(simulator.position().quantity.0, 0);
     }
 
+    #[test]
+    fn test_position_tracks_instrument_contract() {
+        let contract = ContractKind::LinearPerp { multiplier: Qty::from_f64(0.01) };
+        let spec = InstrumentSpec::new(Price::from_f64(0.1), Qty::from_f64(1.0)).with_contract(contract);
+        let simulator = OrderBookSimulator::new(SimulatorConfig::default()).with_instrument(spec);
+
+        assert_eq!(simulator.position().contract, contract);
+    }
+
     #[test]
     fn test_place_order() {
         let config = SimulatorConfig::default();
         let mut simulator = OrderBookSimulator::new(config);
 
-        let order_id = simulator.place_order(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0), 0);
+        let order_id = simulator.place_order(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0), 0).unwrap();
 
         assert_eq!(order_id, 1);
         assert_eq!(simulator.active_order_count(), 1);
@@ -345,12 +410,63 @@ mod tests {
         let config = SimulatorConfig::default();
         let mut simulator = OrderBookSimulator::new(config);
 
-        let order_id = simulator.place_order(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0), 0);
+        let order_id = simulator.place_order(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0), 0).unwrap();
 
         assert!(simulator.cancel_order(order_id));
         assert_eq!(simulator.active_order_count(), 0);
     }
 
+    #[test]
+    fn test_replace_ladder() {
+        let mut simulator = OrderBookSimulator::new(SimulatorConfig::default());
+        let old_id = simulator.place_order(OrderSide::Bid, Price::from_f64(90.0), Qty::from_f64(1.0), 0).unwrap();
+
+        let bids = [(Price::from_f64(99.0), Qty::from_f64(1.0)), (Price::from_f64(98.0), Qty::from_f64(2.0))];
+        let asks = [(Price::from_f64(101.0), Qty::from_f64(1.0)), (Price::from_f64(102.0), Qty::ZERO)];
+        let order_ids = simulator.replace_ladder(&bids, &asks, 0);
+
+        // Zero-size levels are skipped and the previous ladder is gone
+        assert_eq!(order_ids.len(), 3);
+        assert_eq!(simulator.active_order_count(), 3);
+        assert!(!simulator.active_orders().contains_key(&old_id));
+    }
+
+    #[test]
+    fn test_orders_breaking_instrument_rules_are_rejected() {
+        let spec = InstrumentSpec::new(Price::from_f64(0.01), Qty::from_f64(0.001)).with_min_notional(Notional::from_f64(5.0));
+        let mut simulator = OrderBookSimulator::new(SimulatorConfig::default()).with_instrument(spec);
+
+        let price = Price::from_f64(100.0);
+        assert!(simulator.place_order(OrderSide::Bid, price, Qty::from_f64(0.1), 0).is_ok());
+        assert!(matches!(
+            simulator.place_order(OrderSide::Bid, Price::from_f64(100.005), Qty::from_f64(0.1), 0),
+            Err(SpecViolation::OffTick { .. })
+        ));
+        assert!(matches!(simulator.place_order(OrderSide::Ask, price, Qty::from_f64(0.0005), 0), Err(SpecViolation::OffLot { .. })));
+        assert!(matches!(
+            simulator.place_order(OrderSide::Ask, price, Qty::from_f64(0.01), 0),
+            Err(SpecViolation::BelowMinNotional { .. })
+        ));
+
+        assert_eq!(simulator.active_order_count(), 1);
+        assert_eq!(simulator.rejected_orders(), 3);
+    }
+
+    #[test]
+    fn test_maker_fills_earn_rebates() {
+        let mut simulator = OrderBookSimulator::new(SimulatorConfig::default()).with_fees(FeeSchedule::flat(-1.0, 5.0));
+        simulator.place_order(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0), 0).unwrap();
+
+        // A trade through our bid fills 80% of it: 80 notional at a 1 bp rebate
+        simulator.update_market_data(&OrderBook::new("BTCUSDT"), 1_000_000_000, Some(Price::from_f64(99.0)));
+        let fills = simulator.drain_fills();
+        assert_eq!(fills.len(), 1);
+        assert!(fills[0].is_maker);
+        assert_eq!(fills[0].fee, Notional::from_f64(-0.008));
+        assert_eq!(simulator.position().rebates_earned, Notional::from_f64(0.008));
+        assert_eq!(simulator.position().fees_paid, Notional::ZERO);
+    }
+
     #[test]
     fn test_latency_simulator() {
         let latency_sim = LatencySimulator::new(10_000, 5_000);
@@ -1,30 +1,44 @@
 use std::collections::VecDeque;
 
+use mm_binary::messages::TradeMessage;
 use mm_binary::messages::TradeSide;
 use tracing::debug;
 use tracing::info;
 
 use crate::EMA;
-use crate::FixedPoint;
 use crate::MarketState;
+use crate::Price;
+use crate::Qty;
 use crate::StrategyConfig;
 
 /// Trade for flow analysis
 #[derive(Debug, Clone, Copy)]
 pub struct Trade {
     pub timestamp: u64,
-    pub price: FixedPoint,
-    pub quantity: FixedPoint,
+    pub price: Price,
+    pub quantity: Qty,
     pub side: TradeSide,
     pub is_aggressor: bool,
 }
 
+impl From<&TradeMessage> for Trade {
+    fn from(msg: &TradeMessage) -> Self {
+        Self {
+            timestamp: msg.timestamp,
+            price: Price(msg.price),
+            quantity: Qty(msg.quantity),
+            side: msg.trade_side(),
+            is_aggressor: msg.is_aggressor != 0,
+        }
+    }
+}
+
 /// Order Flow Imbalance (OFI) calculator
 /// Tracks changes in bid/ask volume to predict short-term price movement
 #[derive(Debug, Clone)]
 pub struct OrderFlowImbalance {
-    prev_bid_volume: FixedPoint,
-    prev_ask_volume: FixedPoint,
+    prev_bid_volume: Qty,
+    prev_ask_volume: Qty,
     ofi_ema: EMA,
     initialized: bool,
 }
@@ -32,8 +46,8 @@ pub struct OrderFlowImbalance {
 impl OrderFlowImbalance {
     pub fn new(config: &StrategyConfig) -> Self {
         Self {
-            prev_bid_volume: FixedPoint::ZERO,
-            prev_ask_volume: FixedPoint::ZERO,
+            prev_bid_volume: Qty::ZERO,
+            prev_ask_volume: Qty::ZERO,
             ofi_ema: EMA::new(config.drift_halflife_secs, 0.1), // Assume 100ms updates
             initialized: false,
         }
@@ -186,7 +200,7 @@ impl TradeFlowAnalyzer {
     }
 
     /// Calculate volume-weighted average price (VWAP) of recent trades
-    pub fn vwap(&self) -> Option<FixedPoint> {
+    pub fn vwap(&self) -> Option<Price> {
         if self.trades.is_empty() {
             return None;
         }
@@ -203,7 +217,7 @@ impl TradeFlowAnalyzer {
             return None;
         }
 
-        Some(FixedPoint::from_f64(total_value / total_volume))
+        Some(Price::from_f64(total_value / total_volume))
     }
 }
 
@@ -214,7 +228,7 @@ pub struct DriftEstimator {
     trade_flow: TradeFlowAnalyzer,
     price_change_ema: EMA,
     volatility_ema: EMA,
-    last_price: Option<FixedPoint>,
+    last_price: Option<Price>,
 }
 
 impl DriftEstimator {
@@ -295,12 +309,12 @@ impl DriftEstimator {
     }
 
     /// Estimate drift as an absolute price adjustment
-    pub fn estimate_drift_price(&self, state: &MarketState) -> FixedPoint {
+    pub fn estimate_drift_price(&self, state: &MarketState) -> Price {
         let drift_bps = self.estimate_drift_bps(state);
         let mid = state.mid_price();
         let drift_fraction = drift_bps / 10000.0;
 
-        FixedPoint::from_f64(mid.to_f64() * drift_fraction)
+        Price::from_f64(mid.to_f64() * drift_fraction)
     }
 
     /// Get confidence in drift estimate (0 to 1)
@@ -358,16 +372,16 @@ mod tests {
         // Add buy trades
         analyzer.add_trade(Trade {
             timestamp: 1_000_000_000,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Buy,
             is_aggressor: true,
         });
 
         analyzer.add_trade(Trade {
             timestamp: 1_500_000_000,
-            price: FixedPoint::from_f64(100.5),
-            quantity: FixedPoint::from_f64(0.5),
+            price: Price::from_f64(100.5),
+            quantity: Qty::from_f64(0.5),
             side: TradeSide::Sell,
             is_aggressor: true,
         });
@@ -384,12 +398,12 @@ mod tests {
         // First state to initialize OFI
         let state1 = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
         estimator.update_market_state(&state1);
 
@@ -397,12 +411,12 @@ mod tests {
         // Increasing ask volume = more sellers = bearish
         let state2 = MarketState {
             timestamp: 1_000_100_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0), // Bid volume unchanged
-            ask_volume: FixedPoint::from_f64(20.0), // Ask volume increased = bearish OFI
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0), // Bid volume unchanged
+            ask_volume: Qty::from_f64(20.0), // Ask volume increased = bearish OFI
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
         estimator.update_market_state(&state2);
 
@@ -419,24 +433,24 @@ mod tests {
 
         let state1 = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
         estimator.update_market_state(&state1);
 
         // Bullish signal: bid volume increases
         let state2 = MarketState {
             timestamp: 1_000_100_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(20.0), // Bid volume increased = bullish
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(20.0), // Bid volume increased = bullish
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
         estimator.update_market_state(&state2);
 
@@ -451,10 +465,10 @@ mod tests {
 
         let state = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -472,8 +486,8 @@ mod tests {
         // Add aggressive buy
         analyzer.add_trade(Trade {
             timestamp: 1_000_000_000,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Buy,
             is_aggressor: true,
         });
@@ -481,8 +495,8 @@ mod tests {
         // Add passive sell
         analyzer.add_trade(Trade {
             timestamp: 1_000_100_000,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Sell,
             is_aggressor: false,
         });
@@ -510,16 +524,16 @@ mod tests {
         // Add trades with price changes
         estimator.add_trade(Trade {
             timestamp: 1_000_000_000,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Buy,
             is_aggressor: true,
         });
 
         estimator.add_trade(Trade {
             timestamp: 1_100_000_000,
-            price: FixedPoint::from_f64(101.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(101.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Buy,
             is_aggressor: true,
         });
@@ -536,8 +550,8 @@ mod tests {
         // Add old trade (outside window)
         analyzer.add_trade(Trade {
             timestamp: 1_000_000_000,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(10.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(10.0),
             side: TradeSide::Buy,
             is_aggressor: true,
         });
@@ -545,8 +559,8 @@ mod tests {
         // Add recent trade (inside window)
         analyzer.add_trade(Trade {
             timestamp: 1_000_000_000 + (config.trade_flow_window_secs * 1_000_000_000.0) as u64 + 1,
-            price: FixedPoint::from_f64(100.0),
-            quantity: FixedPoint::from_f64(1.0),
+            price: Price::from_f64(100.0),
+            quantity: Qty::from_f64(1.0),
             side: TradeSide::Sell,
             is_aggressor: true,
         });
@@ -1,8 +1,11 @@
 use tracing::debug;
 use tracing::info;
 
-use crate::FixedPoint;
+use crate::ContractKind;
+use crate::Notional;
 use crate::Position;
+use crate::Price;
+use crate::Qty;
 use crate::StrategyConfig;
 
 /// Inventory manager for opportunistic market making
@@ -18,13 +21,24 @@ impl InventoryManager {
         Self { position: Position::new(), config }
     }
 
+    /// Track the position in `contract`; call before the first fill
+    pub fn with_contract(mut self, contract: ContractKind) -> Self {
+        self.position = Position::new().with_contract(contract);
+        self
+    }
+
+    /// Replace the config (runtime parameter updates)
+    pub fn set_config(&mut self, config: StrategyConfig) {
+        self.config = config;
+    }
+
     /// Get current position
     pub fn position(&self) -> &Position {
         &self.position
     }
 
     /// Get current inventory
-    pub fn inventory(&self) -> FixedPoint {
+    pub fn inventory(&self) -> Qty {
         self.position.quantity
     }
 
@@ -162,7 +176,7 @@ impl InventoryManager {
     }
 
     /// Check if we can increase position (respect limits)
-    pub fn can_increase_position(&self, side: crate::OrderSide, size: FixedPoint) -> bool {
+    pub fn can_increase_position(&self, side: crate::OrderSide, size: Qty) -> bool {
         let current = self.position.quantity.to_f64();
         let delta = match side {
             crate::OrderSide::Bid => size.to_f64(),  // Buying increases
@@ -257,7 +271,7 @@ impl InventoryManager {
     }
 
     /// Calculate PnL metrics for monitoring
-    pub fn pnl_metrics(&self, mark_price: FixedPoint) -> PnLMetrics {
+    pub fn pnl_metrics(&self, mark_price: Price) -> PnLMetrics {
         PnLMetrics {
             realized_pnl: self.position.realized_pnl,
             unrealized_pnl: self.position.unrealized_pnl(mark_price),
@@ -288,11 +302,11 @@ pub enum InventoryAction {
 
 #[derive(Debug, Clone, Copy)]
 pub struct PnLMetrics {
-    pub realized_pnl: FixedPoint,
-    pub unrealized_pnl: FixedPoint,
-    pub total_pnl: FixedPoint,
-    pub inventory: FixedPoint,
-    pub avg_entry_price: FixedPoint,
+    pub realized_pnl: Notional,
+    pub unrealized_pnl: Notional,
+    pub total_pnl: Notional,
+    pub inventory: Qty,
+    pub avg_entry_price: Price,
 }
 
 #[cfg(test)]
@@ -307,8 +321,8 @@ mod tests {
 
         // Simulate long position
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(5.0);
-        pos.avg_entry_price = FixedPoint::from_f64(100.0);
+        pos.quantity = Qty::from_f64(5.0);
+        pos.avg_entry_price = Price::from_f64(100.0);
         manager.update_position(pos);
 
         // With positive inventory and no drift, should get positive skew (widen ask)
@@ -332,17 +346,17 @@ mod tests {
 
         // Small position: no urgency
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(2.0);
+        pos.quantity = Qty::from_f64(2.0);
         manager.update_position(pos);
         assert_eq!(manager.urgency(), 0.0);
 
         // Medium position: some urgency
-        pos.quantity = FixedPoint::from_f64(6.0);
+        pos.quantity = Qty::from_f64(6.0);
         manager.update_position(pos);
         assert!(manager.urgency() > 0.0 && manager.urgency() < 1.0);
 
         // Large position: high urgency
-        pos.quantity = FixedPoint::from_f64(9.0);
+        pos.quantity = Qty::from_f64(9.0);
         manager.update_position(pos);
         assert!(manager.urgency() >= 1.0);
     }
@@ -355,7 +369,7 @@ mod tests {
 
         // Long position + falling market = aggressive unwind
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(5.0);
+        pos.quantity = Qty::from_f64(5.0);
         manager.update_position(pos);
         let action = manager.recommended_action(-10.0);
         assert_eq!(action, InventoryAction::AggressiveUnwind);
@@ -372,18 +386,18 @@ mod tests {
 
         // Small position: full size
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(2.0);
+        pos.quantity = Qty::from_f64(2.0);
         manager.update_position(pos);
         assert_eq!(manager.size_factor(), 1.0);
 
         // Medium position: reduced size
-        pos.quantity = FixedPoint::from_f64(6.0);
+        pos.quantity = Qty::from_f64(6.0);
         manager.update_position(pos);
         let factor = manager.size_factor();
         assert!(factor > 0.5 && factor < 1.0);
 
         // Large position: minimal size
-        pos.quantity = FixedPoint::from_f64(9.0);
+        pos.quantity = Qty::from_f64(9.0);
         manager.update_position(pos);
         assert!(manager.size_factor() < 0.5);
     }
@@ -395,13 +409,13 @@ mod tests {
 
         // Long position: should reduce bid size, keep ask size
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(6.0);
+        pos.quantity = Qty::from_f64(6.0);
         manager.update_position(pos);
         let (bid_factor, ask_factor) = manager.asymmetric_sizes();
         assert!(bid_factor < ask_factor);
 
         // Short position: should reduce ask size, keep bid size
-        pos.quantity = FixedPoint::from_f64(-6.0);
+        pos.quantity = Qty::from_f64(-6.0);
         manager.update_position(pos);
         let (bid_factor, ask_factor) = manager.asymmetric_sizes();
         assert!(bid_factor > ask_factor);
@@ -414,16 +428,16 @@ mod tests {
 
         // Room to grow
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(5.0);
+        pos.quantity = Qty::from_f64(5.0);
         manager.update_position(pos);
-        assert!(manager.can_increase_position(crate::OrderSide::Bid, FixedPoint::from_f64(1.0))); // Can buy more
-        assert!(manager.can_increase_position(crate::OrderSide::Ask, FixedPoint::from_f64(1.0))); // Can sell
+        assert!(manager.can_increase_position(crate::OrderSide::Bid, Qty::from_f64(1.0))); // Can buy more
+        assert!(manager.can_increase_position(crate::OrderSide::Ask, Qty::from_f64(1.0))); // Can sell
 
         // At limit
-        pos.quantity = FixedPoint::from_f64(10.0);
+        pos.quantity = Qty::from_f64(10.0);
         manager.update_position(pos);
-        assert!(!manager.can_increase_position(crate::OrderSide::Bid, FixedPoint::from_f64(1.0))); // Cannot buy more
-        assert!(manager.can_increase_position(crate::OrderSide::Ask, FixedPoint::from_f64(1.0))); // Can still sell
+        assert!(!manager.can_increase_position(crate::OrderSide::Bid, Qty::from_f64(1.0))); // Cannot buy more
+        assert!(manager.can_increase_position(crate::OrderSide::Ask, Qty::from_f64(1.0))); // Can still sell
     }
 
     #[test]
@@ -433,7 +447,7 @@ mod tests {
 
         // Position > limit = emergency unwind
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(11.0); // Over limit
+        pos.quantity = Qty::from_f64(11.0); // Over limit
         manager.update_position(pos);
         let action = manager.recommended_action(0.0);
         assert_eq!(action, InventoryAction::EmergencyUnwind);
@@ -446,7 +460,7 @@ mod tests {
 
         // Short position should have negative skew (widen bid)
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(-5.0);
+        pos.quantity = Qty::from_f64(-5.0);
         manager.update_position(pos);
         let skew = manager.inventory_skew_bps(0.0);
         assert!(skew < 0.0);
@@ -463,13 +477,13 @@ mod tests {
 
         // At target: minimal skew
         let mut pos = Position::new();
-        pos.quantity = FixedPoint::from_f64(2.0);
+        pos.quantity = Qty::from_f64(2.0);
         manager.update_position(pos);
         let skew = manager.inventory_skew_bps(0.0);
         assert!(skew.abs() < 0.1); // Near zero
 
         // Above target: positive skew
-        pos.quantity = FixedPoint::from_f64(5.0);
+        pos.quantity = Qty::from_f64(5.0);
         manager.update_position(pos);
         let skew = manager.inventory_skew_bps(0.0);
         assert!(skew > 0.0);
@@ -3,11 +3,32 @@ pub mod inventory_manager;
 pub mod quote_engine;
 pub mod risk_manager;
 
+use mm_binary::CompressedString;
+use mm_binary::compressed_string::EncodingScheme;
+use mm_binary::from_fixed_point;
+use mm_binary::messages::QuoteMessage;
+use mm_binary::to_fixed_point;
 // Re-export commonly used types from mm_types
+pub use mm_types::ContractKind;
+pub use mm_types::DepthProfile;
+pub use mm_types::FeeSchedule;
+pub use mm_types::FeeTier;
 pub use mm_types::FixedPoint;
+pub use mm_types::FundingClock;
+pub use mm_types::FundingFeed;
+pub use mm_types::FundingSettlement;
+pub use mm_types::InstrumentSpec;
+pub use mm_types::Ledger;
+pub use mm_types::LedgerError;
 pub use mm_types::MarketState;
+pub use mm_types::Notional;
 pub use mm_types::OrderSide;
+pub use mm_types::PnlBreakdown;
+pub use mm_types::Portfolio;
 pub use mm_types::Position;
+pub use mm_types::Price;
+pub use mm_types::Qty;
+pub use mm_types::SpecViolation;
 
 /// Core strategy parameters
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -47,6 +68,20 @@ pub struct StrategyConfig {
 
     /// Minimum confidence score to publish quotes
     pub min_confidence: f64,
+
+    /// Multiplier on the book impact of trading one base quote size through each side;
+    /// the spread is kept at least this wide (0 ignores depth)
+    #[serde(default)]
+    pub depth_impact_factor: f64,
+
+    /// Impact in basis points assumed for a side the visible book cannot fill;
+    /// a thinner measured impact is widened to this before `depth_impact_factor`
+    #[serde(default = "default_max_depth_impact_bps")]
+    pub max_depth_impact_bps: f64,
+}
+
+fn default_max_depth_impact_bps() -> f64 {
+    StrategyConfig::default().max_depth_impact_bps
 }
 
 impl Default for StrategyConfig {
@@ -64,23 +99,83 @@ impl Default for StrategyConfig {
             volatility_halflife_secs: 300.0, // 5 minute half-life for volatility
             trade_flow_window_secs: 10.0,    // 10 second window for OFI
             min_confidence: 0.5,             // 50% minimum confidence
+            depth_impact_factor: 0.0,        // Top of book only
+            max_depth_impact_bps: 50.0,      // 50 bps for a side thinner than one quote
         }
     }
 }
 
+/// One venue fee tier as written in config files (`[[fee_tiers]]`)
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct FeeTierConfig {
+    /// Traded notional in the quote asset at which the tier starts
+    #[serde(default)]
+    pub min_volume: f64,
+
+    /// Maker rate in basis points; negative is a rebate
+    pub maker_bps: f64,
+
+    /// Taker rate in basis points; negative is a rebate
+    pub taker_bps: f64,
+}
+
+impl FeeTierConfig {
+    /// Fee schedule from config tiers; no tiers means no fees
+    pub fn schedule(tiers: &[FeeTierConfig]) -> FeeSchedule {
+        FeeSchedule::tiered(
+            tiers.iter().map(|tier| FeeTier::new(Notional::from_f64(tier.min_volume), tier.maker_bps, tier.taker_bps)).collect(),
+        )
+    }
+}
+
 /// Quote output from the strategy
 #[derive(Debug, Clone, Copy)]
 pub struct StrategyQuote {
     pub timestamp: u64,
-    pub bid_price: FixedPoint,
-    pub bid_size: FixedPoint,
-    pub ask_price: FixedPoint,
-    pub ask_size: FixedPoint,
-    pub fair_value: FixedPoint,
-    pub inventory: FixedPoint,
+    pub bid_price: Price,
+    pub bid_size: Qty,
+    pub ask_price: Price,
+    pub ask_size: Qty,
+    pub fair_value: Price,
+    pub inventory: Qty,
     pub confidence: f64,
 }
 
+impl StrategyQuote {
+    /// Wire form for the strategy quotes stream
+    pub fn to_message(&self, strategy_id: u8, symbol: CompressedString, encoding: EncodingScheme, sequence: u64) -> QuoteMessage {
+        QuoteMessage::new_with_sequence(
+            strategy_id,
+            symbol,
+            encoding,
+            self.timestamp,
+            self.bid_price.to_i64(),
+            self.bid_size.to_i64(),
+            self.ask_price.to_i64(),
+            self.ask_size.to_i64(),
+            self.fair_value.to_i64(),
+            self.inventory.to_i64(),
+            to_fixed_point(self.confidence),
+            sequence,
+        )
+    }
+}
+
+impl From<&QuoteMessage> for StrategyQuote {
+    fn from(msg: &QuoteMessage) -> Self {
+        Self {
+            timestamp: msg.timestamp,
+            bid_price: Price(msg.bid_price),
+            bid_size: Qty(msg.bid_size),
+            ask_price: Price(msg.ask_price),
+            ask_size: Qty(msg.ask_size),
+            fair_value: Price(msg.fair_value),
+            inventory: Qty(msg.inventory),
+            confidence: from_fixed_point(msg.confidence),
+        }
+    }
+}
+
 /// Helper function to calculate EMA alpha from half-life
 pub fn ema_alpha_from_halflife(halflife_secs: f64, dt_secs: f64) -> f64 {
     // alpha = 1 - exp(-ln(2) * dt / halflife)
@@ -127,10 +222,10 @@ mod tests {
     fn test_market_state_mid_price() {
         let state = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -143,10 +238,10 @@ mod tests {
     fn test_market_state_micro_price() {
         let state = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(20.0),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(20.0),
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -159,7 +254,7 @@ mod tests {
     #[test]
     fn test_position_buy_fill() {
         let mut pos = Position::new();
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
 
         assert_eq!(pos.quantity.to_f64(), 1.0);
         assert_eq!(pos.avg_entry_price.to_f64(), 100.0);
@@ -168,7 +263,7 @@ mod tests {
     #[test]
     fn test_position_sell_fill() {
         let mut pos = Position::new();
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(100.0), Qty::from_f64(1.0));
 
         assert_eq!(pos.quantity.to_f64(), -1.0);
         assert_eq!(pos.avg_entry_price.to_f64(), 100.0);
@@ -178,9 +273,9 @@ mod tests {
     fn test_position_realized_pnl() {
         let mut pos = Position::new();
         // Buy 1 @ 100
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
         // Sell 1 @ 110
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(110.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(110.0), Qty::from_f64(1.0));
 
         assert_eq!(pos.quantity.to_f64(), 0.0);
         assert_eq!(pos.realized_pnl.to_f64(), 10.0);
@@ -247,16 +342,39 @@ mod tests {
         assert!(a >= c);
     }
 
+    #[test]
+    fn test_fixedpoint_scaled_arithmetic() {
+        use mm_types::Scale;
+
+        // SHIB-like price needs more places than the default scale keeps
+        let price_scale = Scale::new(12).unwrap();
+        let qty_scale = Scale::new(0).unwrap();
+        let price = FixedPoint::from_f64_scaled(0.00001234, price_scale);
+        let qty = FixedPoint::from_int_scaled(50_000_000, qty_scale);
+
+        let notional = price.mul_mixed(price_scale, qty, qty_scale, Scale::DEFAULT).unwrap();
+        assert_eq!(notional.to_f64(), 617.0);
+
+        let two = FixedPoint::from_int_scaled(2, price_scale);
+        assert_eq!(price.mul_scaled(two, price_scale).unwrap().to_f64_scaled(price_scale), 0.00002468);
+        assert_eq!(price.div_scaled(two, price_scale).unwrap().to_f64_scaled(price_scale), 0.00000617);
+        assert_eq!(price.div_scaled(FixedPoint::ZERO, price_scale), None);
+        assert_eq!(FixedPoint(i64::MAX).mul_scaled(two, price_scale), None);
+        assert_eq!(FixedPoint(i64::MAX).mul_mixed(price_scale, qty, qty_scale, Scale::DEFAULT), None);
+
+        assert_eq!(price.rescale(price_scale, Scale::DEFAULT), Some(FixedPoint::from_f64(0.00001234)));
+    }
+
     // ===== MarketState Edge Case Tests =====
 
     #[test]
     fn test_market_state_zero_volume() {
         let state = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::ZERO,
-            ask_volume: FixedPoint::ZERO,
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::ZERO,
+            ask_volume: Qty::ZERO,
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -274,10 +392,10 @@ mod tests {
     fn test_market_state_spread() {
         let state = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(102.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(102.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -292,10 +410,10 @@ mod tests {
         // All ask volume
         let state = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::ZERO,
-            ask_volume: FixedPoint::from_f64(10.0),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::ZERO,
+            ask_volume: Qty::from_f64(10.0),
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -304,10 +422,10 @@ mod tests {
         // All bid volume
         let state2 = MarketState {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::ZERO,
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::ZERO,
             last_trade_price: None,
             last_trade_size: None,
         };
@@ -321,11 +439,11 @@ mod tests {
         let mut pos = Position::new();
 
         // Buy 1 @ 100
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
         assert_eq!(pos.avg_entry_price.to_f64(), 100.0);
 
         // Buy 1 @ 110
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(110.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(110.0), Qty::from_f64(1.0));
         assert_eq!(pos.quantity.to_f64(), 2.0);
         assert_eq!(pos.avg_entry_price.to_f64(), 105.0); // (100+110)/2
     }
@@ -335,10 +453,10 @@ mod tests {
         let mut pos = Position::new();
 
         // Buy 2 @ 100
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(2.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(2.0));
 
         // Sell 1 @ 110
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(110.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(110.0), Qty::from_f64(1.0));
 
         assert_eq!(pos.quantity.to_f64(), 1.0);
         assert_eq!(pos.avg_entry_price.to_f64(), 100.0);
@@ -350,10 +468,10 @@ mod tests {
         let mut pos = Position::new();
 
         // Buy 1 @ 100
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
 
         // Sell 2 @ 110 (close long and go short 1)
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(110.0), FixedPoint::from_f64(2.0));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(110.0), Qty::from_f64(2.0));
 
         assert_eq!(pos.quantity.to_f64(), -1.0);
         assert_eq!(pos.avg_entry_price.to_f64(), 110.0);
@@ -365,15 +483,15 @@ mod tests {
         let mut pos = Position::new();
 
         // Buy 1 @ 100
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
 
-        let mark_price = FixedPoint::from_f64(105.0);
+        let mark_price = Price::from_f64(105.0);
         let unrealized = pos.unrealized_pnl(mark_price);
         assert_eq!(unrealized.to_f64(), 5.0); // (105-100) * 1
 
         // Short position
         let mut short_pos = Position::new();
-        short_pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
+        short_pos.apply_fill(OrderSide::Ask, Price::from_f64(100.0), Qty::from_f64(1.0));
 
         let unrealized_short = short_pos.unrealized_pnl(mark_price);
         assert_eq!(unrealized_short.to_f64(), -5.0); // (105-100) * -1
@@ -384,10 +502,10 @@ mod tests {
         let mut pos = Position::new();
 
         // Simulate active trading
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(100.0), FixedPoint::from_f64(1.0));
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(101.0), FixedPoint::from_f64(0.5));
-        pos.apply_fill(OrderSide::Bid, FixedPoint::from_f64(99.0), FixedPoint::from_f64(0.5));
-        pos.apply_fill(OrderSide::Ask, FixedPoint::from_f64(102.0), FixedPoint::from_f64(1.0));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(1.0));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(101.0), Qty::from_f64(0.5));
+        pos.apply_fill(OrderSide::Bid, Price::from_f64(99.0), Qty::from_f64(0.5));
+        pos.apply_fill(OrderSide::Ask, Price::from_f64(102.0), Qty::from_f64(1.0));
 
         // After these trades:
         // Buy 1 @ 100 -> qty=1, avg=100
@@ -398,4 +516,27 @@ mod tests {
         assert_eq!(pos.quantity.to_f64(), 0.0);
         assert_eq!(pos.realized_pnl.to_f64(), 3.0);
     }
+
+    #[test]
+    fn test_strategy_quote_message_roundtrip() {
+        let quote = StrategyQuote {
+            timestamp: 42,
+            bid_price: Price::from_f64(99.5),
+            bid_size: Qty::from_f64(0.25),
+            ask_price: Price::from_f64(100.5),
+            ask_size: Qty::from_f64(0.5),
+            fair_value: Price::from_f64(100.0),
+            inventory: Qty::from_f64(-1.0),
+            confidence: 0.75,
+        };
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let msg = quote.to_message(1, symbol, encoding, 9);
+        assert_eq!(msg.sequence, 9);
+
+        let decoded = StrategyQuote::from(&msg);
+        assert_eq!(decoded.bid_price, quote.bid_price);
+        assert_eq!(decoded.ask_size, quote.ask_size);
+        assert_eq!(decoded.inventory, quote.inventory);
+        assert_eq!(decoded.confidence, quote.confidence);
+    }
 }
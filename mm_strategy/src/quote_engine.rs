@@ -1,10 +1,14 @@
+use mm_binary::control_messages::StrategyParam;
 use tracing::debug;
 use tracing::info;
 use tracing::warn;
 
+use crate::DepthProfile;
 use crate::EMA;
-use crate::FixedPoint;
+use crate::InstrumentSpec;
 use crate::MarketState;
+use crate::Price;
+use crate::Qty;
 use crate::StrategyConfig;
 use crate::StrategyQuote;
 use crate::drift_estimator::DriftEstimator;
@@ -22,7 +26,9 @@ pub struct QuoteEngine {
     inventory_manager: InventoryManager,
     risk_manager: RiskManager,
     volatility_ema: EMA,
-    last_mid_price: Option<FixedPoint>,
+    last_mid_price: Option<Price>,
+    instrument: InstrumentSpec,
+    depth: Option<DepthProfile>,
 }
 
 impl QuoteEngine {
@@ -32,7 +38,46 @@ impl QuoteEngine {
         let risk_manager = RiskManager::new(config.clone());
         let volatility_ema = EMA::new(config.volatility_halflife_secs, 1.0);
 
-        Self { config, drift_estimator, inventory_manager, risk_manager, volatility_ema, last_mid_price: None }
+        Self {
+            config,
+            drift_estimator,
+            inventory_manager,
+            risk_manager,
+            volatility_ema,
+            last_mid_price: None,
+            instrument: InstrumentSpec::UNCONSTRAINED,
+            depth: None,
+        }
+    }
+
+    /// Round quotes to the venue's tick and lot sizes, drop sides below its minimum notional
+    /// and track the position in the instrument's contract
+    pub fn with_instrument(mut self, instrument: InstrumentSpec) -> Self {
+        self.inventory_manager = self.inventory_manager.with_contract(instrument.contract);
+        self.instrument = instrument;
+        self
+    }
+
+    pub fn instrument(&self) -> &InstrumentSpec {
+        &self.instrument
+    }
+
+    /// Snap one side to the venue's grid; a side too small to trade gets zero size
+    fn conform_to_instrument(&self, price: Price, size: Qty, is_bid: bool) -> (Price, Qty) {
+        let price = if is_bid { self.instrument.round_bid_price(price) } else { self.instrument.round_ask_price(price) };
+        let size = self.instrument.round_qty(size);
+        match self.instrument.validate_min_notional(price, size) {
+            Ok(()) => (price, size),
+            Err(violation) => {
+                debug!(is_bid = %is_bid, reason = %violation, "Side below venue minimum, not quoting it");
+                (price, Qty::ZERO)
+            }
+        }
+    }
+
+    /// Book liquidity for the next quotes; `None` prices from the top of book alone
+    pub fn set_depth(&mut self, depth: Option<DepthProfile>) {
+        self.depth = depth;
     }
 
     /// Get mutable references to components (for external updates)
@@ -76,14 +121,24 @@ impl QuoteEngine {
         let market_spread_bps = state.spread_bps();
         let tick_spread_bps = market_spread_bps.max(min_spread);
 
+        // Thin books move further against a fill of our size, so quote at least that wide;
+        // a side that cannot fill it counts as at least the configured maximum impact
+        let depth_spread = self.depth.map_or(0.0, |depth| {
+            let max_impact = self.config.max_depth_impact_bps;
+            let bid_impact = if depth.bid_insufficient { depth.bid_impact_bps.max(max_impact) } else { depth.bid_impact_bps };
+            let ask_impact = if depth.ask_insufficient { depth.ask_impact_bps.max(max_impact) } else { depth.ask_impact_bps };
+            (bid_impact + ask_impact) * self.config.depth_impact_factor
+        });
+
         // Take the maximum of all components
-        let base_spread = min_spread.max(vol_spread).max(tick_spread_bps);
+        let base_spread = min_spread.max(vol_spread).max(tick_spread_bps).max(depth_spread);
 
         debug!(
             base_spread_bps = %base_spread,
             min_spread_bps = %min_spread,
             vol_spread_bps = %vol_spread,
             market_spread_bps = %market_spread_bps,
+            depth_spread_bps = %depth_spread,
             volatility = %volatility,
             "Calculated base spread"
         );
@@ -117,7 +172,10 @@ impl QuoteEngine {
         // Estimate fair value with drift
         let mid = state.mid_price();
         let drift_bps = self.drift_estimator.estimate_drift_bps(state);
-        let fair_value = mid.apply_bps(drift_bps);
+        let Some(fair_value) = mid.checked_apply_bps(drift_bps) else {
+            warn!(mid = %mid.to_f64(), drift_bps = %drift_bps, "Fair value out of range, not quoting");
+            return None;
+        };
 
         debug!(
             mid = %mid.to_f64(),
@@ -166,8 +224,12 @@ impl QuoteEngine {
         let ask_spread_bps = half_spread_bps + inventory_skew_bps / 2.0;
 
         // Calculate prices
-        let bid_price = fair_value.subtract_bps(bid_spread_bps);
-        let ask_price = fair_value.apply_bps(ask_spread_bps);
+        let (Some(bid_price), Some(ask_price)) =
+            (fair_value.checked_subtract_bps(bid_spread_bps), fair_value.checked_apply_bps(ask_spread_bps))
+        else {
+            warn!(bid_spread_bps = %bid_spread_bps, ask_spread_bps = %ask_spread_bps, "Quote prices out of range, not quoting");
+            return None;
+        };
 
         info!(
             bid_price = %bid_price.to_f64(),
@@ -190,25 +252,36 @@ impl QuoteEngine {
             InventoryAction::Accumulate => 0.8, // Reduce size when accumulating
         };
 
-        let base_size_fp = FixedPoint::from_f64(base_size);
+        let base_size_qty = Qty::from_f64(base_size);
 
-        let bid_size = if inventory > FixedPoint::ZERO {
+        let bid_size = if inventory > Qty::ZERO {
             // Long: reduce bid size
-            base_size_fp.mul_scalar(bid_size_factor)
+            base_size_qty.checked_mul_scalar(bid_size_factor)
         } else {
             // Short or neutral: normal or increased bid size
-            base_size_fp.mul_scalar(bid_size_factor * size_urgency)
+            base_size_qty.checked_mul_scalar(bid_size_factor * size_urgency)
         };
 
-        let ask_size = if inventory < FixedPoint::ZERO {
+        let ask_size = if inventory < Qty::ZERO {
             // Short: reduce ask size
-            base_size_fp.mul_scalar(ask_size_factor)
+            base_size_qty.checked_mul_scalar(ask_size_factor)
         } else {
             // Long or neutral: normal or increased ask size
-            base_size_fp.mul_scalar(ask_size_factor * size_urgency)
+            base_size_qty.checked_mul_scalar(ask_size_factor * size_urgency)
         };
 
+        let (Some(bid_size), Some(ask_size)) = (bid_size, ask_size) else {
+            warn!(bid_size_factor = %bid_size_factor, ask_size_factor = %ask_size_factor, "Quote sizes out of range, not quoting");
+            return None;
+        };
+
+        // Bids round down and asks up, so rounding only ever widens the spread
+        let (bid_price, bid_size) = self.conform_to_instrument(bid_price, bid_size, true);
+        let (ask_price, ask_size) = self.conform_to_instrument(ask_price, ask_size, false);
+
         debug!(
+            bid_price = %bid_price.to_f64(),
+            ask_price = %ask_price.to_f64(),
             bid_size = %bid_size.to_f64(),
             ask_size = %ask_size.to_f64(),
             bid_size_factor = %bid_size_factor,
@@ -274,12 +347,20 @@ impl QuoteEngine {
                 let level_offset = level_spacing_bps * level as f64;
                 let level_size_factor = 1.0 / (level as f64 + 1.0); // Reduce size at further levels
 
-                let bid_price = base_quote.bid_price.subtract_bps(level_offset);
-                let ask_price = base_quote.ask_price.apply_bps(level_offset);
+                let (Some(bid_price), Some(ask_price)) =
+                    (base_quote.bid_price.checked_subtract_bps(level_offset), base_quote.ask_price.checked_apply_bps(level_offset))
+                else {
+                    warn!(level = %level, level_offset_bps = %level_offset, "Ladder level out of range, stopping");
+                    break;
+                };
 
+                // A factor in (0, 1] cannot overflow the base size
                 let bid_size = base_quote.bid_size.mul_scalar(level_size_factor);
                 let ask_size = base_quote.ask_size.mul_scalar(level_size_factor);
 
+                let (bid_price, bid_size) = self.conform_to_instrument(bid_price, bid_size, true);
+                let (ask_price, ask_size) = self.conform_to_instrument(ask_price, ask_size, false);
+
                 let level_quote = StrategyQuote {
                     timestamp: state.timestamp,
                     bid_price,
@@ -325,11 +406,52 @@ impl QuoteEngine {
     pub fn config(&self) -> &StrategyConfig {
         &self.config
     }
+
+    /// Apply a runtime parameter update and return the previous value
+    ///
+    /// Values must be finite and, except for the inventory target, non-negative.
+    pub fn apply_param(&mut self, param: StrategyParam, value: f64) -> Result<f64, String> {
+        if !value.is_finite() || (value < 0.0 && param != StrategyParam::TargetInventory) {
+            return Err(format!("Invalid value {value} for {param:?}"));
+        }
+
+        // The daily loss limit lives on the risk manager, not in the config
+        if param == StrategyParam::MaxDailyLoss {
+            let previous = self.risk_manager.max_daily_loss().map_or(f64::INFINITY, |limit| -limit.to_f64());
+            self.risk_manager.set_max_daily_loss(Some(-value));
+            info!(param = ?param, previous = %previous, value = %value, "Strategy parameter updated");
+            return Ok(previous);
+        }
+
+        let field = match param {
+            StrategyParam::MinSpreadBps => &mut self.config.min_spread_bps,
+            StrategyParam::VolatilityFactor => &mut self.config.volatility_factor,
+            StrategyParam::InventorySkewFactor => &mut self.config.inventory_skew_factor,
+            StrategyParam::MaxPositionSize => &mut self.config.max_position_size,
+            StrategyParam::MaxOrderSize => &mut self.config.max_order_size,
+            StrategyParam::TargetInventory => &mut self.config.target_inventory,
+            StrategyParam::BaseQuoteSize => &mut self.config.base_quote_size,
+            StrategyParam::RiskAversion => &mut self.config.risk_aversion,
+            StrategyParam::MinConfidence => &mut self.config.min_confidence,
+            StrategyParam::MaxDailyLoss => unreachable!(),
+        };
+        let previous = std::mem::replace(field, value);
+
+        // Components hold their own copy of the config
+        self.inventory_manager.set_config(self.config.clone());
+        self.risk_manager.set_config(self.config.clone());
+
+        info!(param = ?param, previous = %previous, value = %value, "Strategy parameter updated");
+        Ok(previous)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ContractKind;
+    use crate::Notional;
+    use crate::OrderSide;
 
     #[test]
     fn test_quote_generation() {
@@ -344,12 +466,12 @@ mod tests {
 
         let state = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
 
         let quote = engine.generate_quotes(&state);
@@ -357,8 +479,108 @@ mod tests {
 
         let quote = quote.unwrap();
         assert!(quote.bid_price < quote.ask_price);
-        assert!(quote.bid_size > FixedPoint::ZERO);
-        assert!(quote.ask_size > FixedPoint::ZERO);
+        assert!(quote.bid_size > Qty::ZERO);
+        assert!(quote.ask_size > Qty::ZERO);
+    }
+
+    #[test]
+    fn test_quotes_conform_to_instrument() {
+        let config = StrategyConfig { min_spread_bps: 5.0, base_quote_size: 0.123456, min_confidence: 0.1, ..Default::default() };
+        let tick = Price::from_f64(0.05);
+        let lot = Qty::from_f64(0.001);
+        let spec = InstrumentSpec::new(tick, lot).with_min_notional(Notional::from_f64(5.0));
+        let mut engine = QuoteEngine::new(config).with_instrument(spec);
+
+        let state = MarketState {
+            timestamp: 1_000_000_000,
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
+        };
+
+        let quote = engine.generate_quotes(&state).unwrap();
+        assert_eq!(quote.bid_price.0 % tick.0, 0);
+        assert_eq!(quote.ask_price.0 % tick.0, 0);
+        assert_eq!(quote.bid_size.0 % lot.0, 0);
+        assert!(quote.bid_size > Qty::ZERO && quote.bid_size <= Qty::from_f64(0.123456));
+
+        // At ~12 USDT per side a 20 USDT minimum leaves nothing to quote
+        let spec = spec.with_min_notional(Notional::from_f64(20.0));
+        let mut engine = QuoteEngine::new(engine.config().clone()).with_instrument(spec);
+        assert!(engine.generate_quotes(&state).is_none());
+    }
+
+    #[test]
+    fn test_instrument_contract_reaches_position() {
+        let contract = ContractKind::InversePerp { multiplier: Notional::from_f64(100.0) };
+        let spec = InstrumentSpec::new(Price::from_f64(0.1), Qty::from_f64(1.0)).with_contract(contract);
+        let mut engine = QuoteEngine::new(StrategyConfig::default()).with_instrument(spec);
+        assert_eq!(engine.inventory_manager_mut().position().contract, contract);
+
+        // 100 contracts of 100 USD are 0.2 BTC at 50_000, not 100 BTC
+        let mut position = *engine.inventory_manager_mut().position();
+        position.apply_fill(OrderSide::Bid, Price::from_f64(50_000.0), Qty::from_f64(100.0));
+        engine.inventory_manager_mut().update_position(position);
+        let position = engine.inventory_manager_mut().position();
+        assert_eq!(position.contract, contract);
+        assert_eq!(position.base_exposure(Price::from_f64(50_000.0)), Qty::from_f64(0.2));
+    }
+
+    #[test]
+    fn test_depth_widens_spread() {
+        let config = StrategyConfig { min_spread_bps: 5.0, min_confidence: 0.1, depth_impact_factor: 2.0, ..Default::default() };
+        let mut engine = QuoteEngine::new(config);
+        let state = MarketState {
+            timestamp: 1_000_000_000,
+            bid_price: Price::from_f64(49_999.0),
+            ask_price: Price::from_f64(50_001.0),
+            bid_volume: Qty::from_f64(1.0),
+            ask_volume: Qty::from_f64(1.0),
+            last_trade_price: None,
+            last_trade_size: None,
+        };
+        let spread_bps = |quote: StrategyQuote| (quote.ask_price - quote.bid_price).to_f64() / 50_000.0 * 10_000.0;
+
+        let top_of_book = spread_bps(engine.generate_quotes(&state).unwrap());
+
+        // Sweeping one quote size costs 4 bps a side: at least 2 * (4 + 4) = 16 bps wide
+        let depth = DepthProfile { notional: Notional::from_f64(5_000.0), bid_impact_bps: 4.0, ask_impact_bps: 4.0, ..Default::default() };
+        engine.set_depth(Some(depth));
+        let with_depth = spread_bps(engine.generate_quotes(&state).unwrap());
+        assert!(top_of_book < 16.0);
+        assert!(with_depth >= 16.0 - 1e-6, "spread {with_depth} bps");
+
+        // Asks too thin to fill one quote count as the 50 bps maximum: at least 2 * (4 + 50) = 108 bps wide
+        engine.set_depth(Some(DepthProfile { ask_insufficient: true, ..depth }));
+        let thin_asks = spread_bps(engine.generate_quotes(&state).unwrap());
+        assert!(thin_asks >= 108.0 - 1e-6, "spread {thin_asks} bps");
+
+        engine.set_depth(None);
+        assert!((spread_bps(engine.generate_quotes(&state).unwrap()) - top_of_book).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_apply_param() {
+        let mut engine = QuoteEngine::new(StrategyConfig::default());
+
+        let previous = engine.apply_param(StrategyParam::MinSpreadBps, 25.0).unwrap();
+        assert_eq!(previous, StrategyConfig::default().min_spread_bps);
+        assert_eq!(engine.config().min_spread_bps, 25.0);
+
+        // Pushed down to the risk manager's copy of the config
+        engine.apply_param(StrategyParam::MaxOrderSize, 0.5).unwrap();
+        assert!(!engine.risk_manager_mut().check_order_size(Qty::from_f64(0.6)).is_accept());
+
+        assert_eq!(engine.apply_param(StrategyParam::MaxDailyLoss, 250.0).unwrap(), 1000.0);
+        assert_eq!(engine.risk_manager_mut().max_daily_loss(), Some(Notional::from_f64(-250.0)));
+
+        assert!(engine.apply_param(StrategyParam::TargetInventory, -1.0).is_ok());
+        assert!(engine.apply_param(StrategyParam::MaxPositionSize, -1.0).is_err());
+        assert!(engine.apply_param(StrategyParam::MinSpreadBps, f64::NAN).is_err());
+        assert_eq!(engine.config().min_spread_bps, 25.0);
     }
 
     #[test]
@@ -369,12 +591,12 @@ mod tests {
 
         let state = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
 
         let quotes = engine.generate_ladder_quotes(&state, 3, 5.0);
@@ -402,12 +624,12 @@ mod tests {
 
         let state = MarketState {
             timestamp: 1_000_000_000,
-            bid_price: FixedPoint::from_f64(100.0),
-            ask_price: FixedPoint::from_f64(101.0),
-            bid_volume: FixedPoint::from_f64(10.0),
-            ask_volume: FixedPoint::from_f64(10.0),
-            last_trade_price: Some(FixedPoint::from_f64(100.5)),
-            last_trade_size: Some(FixedPoint::from_f64(1.0)),
+            bid_price: Price::from_f64(100.0),
+            ask_price: Price::from_f64(101.0),
+            bid_volume: Qty::from_f64(10.0),
+            ask_volume: Qty::from_f64(10.0),
+            last_trade_price: Some(Price::from_f64(100.5)),
+            last_trade_size: Some(Qty::from_f64(1.0)),
         };
 
         // Generate quote with no inventory
@@ -416,7 +638,7 @@ mod tests {
 
         // Simulate long position
         let mut pos = crate::Position::new();
-        pos.quantity = FixedPoint::from_f64(5.0);
+        pos.quantity = Qty::from_f64(5.0);
         engine.inventory_manager_mut().update_position(pos);
 
         // Generate quote with long inventory
@@ -3,8 +3,12 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-use crate::FixedPoint;
+use crate::Notional;
+use crate::OrderSide;
+use crate::Portfolio;
 use crate::Position;
+use crate::Price;
+use crate::Qty;
 use crate::StrategyConfig;
 use crate::StrategyQuote;
 
@@ -12,8 +16,8 @@ use crate::StrategyQuote;
 #[derive(Debug, Clone)]
 pub struct RiskManager {
     config: StrategyConfig,
-    max_daily_loss: Option<FixedPoint>,
-    daily_realized_pnl: FixedPoint,
+    max_daily_loss: Option<Notional>,
+    daily_realized_pnl: Notional,
     is_killed: bool,
     kill_reason: Option<String>,
 }
@@ -22,20 +26,30 @@ impl RiskManager {
     pub fn new(config: StrategyConfig) -> Self {
         Self {
             config,
-            max_daily_loss: Some(FixedPoint::from_f64(-1000.0)), // Default $1000 daily loss limit
-            daily_realized_pnl: FixedPoint::ZERO,
+            max_daily_loss: Some(Notional::from_f64(-1000.0)), // Default $1000 daily loss limit
+            daily_realized_pnl: Notional::ZERO,
             is_killed: false,
             kill_reason: None,
         }
     }
 
+    /// Replace the config (runtime parameter updates)
+    pub fn set_config(&mut self, config: StrategyConfig) {
+        self.config = config;
+    }
+
+    /// Maximum daily loss as a negative PnL threshold (None = unlimited)
+    pub fn max_daily_loss(&self) -> Option<Notional> {
+        self.max_daily_loss
+    }
+
     /// Set maximum daily loss (None = unlimited)
     pub fn set_max_daily_loss(&mut self, max_loss: Option<f64>) {
-        self.max_daily_loss = max_loss.map(FixedPoint::from_f64);
+        self.max_daily_loss = max_loss.map(Notional::from_f64);
     }
 
     /// Update daily PnL (call this on each fill)
-    pub fn update_daily_pnl(&mut self, pnl_change: FixedPoint) {
+    pub fn update_daily_pnl(&mut self, pnl_change: Notional) {
         self.daily_realized_pnl += pnl_change;
         info!(
             pnl_change = %pnl_change.to_f64(),
@@ -47,12 +61,14 @@ impl RiskManager {
     /// Reset daily PnL (call at start of trading day)
     pub fn reset_daily_pnl(&mut self) {
         info!("Resetting daily PnL");
-        self.daily_realized_pnl = FixedPoint::ZERO;
+        self.daily_realized_pnl = Notional::ZERO;
     }
 
     /// Check if position limit is exceeded
-    pub fn check_position_limit(&self, position: &Position) -> RiskCheckResult {
-        let abs_position = position.quantity.to_f64().abs();
+    ///
+    /// The limit is in base units, so contract positions are converted at `mark_price`.
+    pub fn check_position_limit(&self, position: &Position, mark_price: Price) -> RiskCheckResult {
+        let abs_position = position.base_exposure(mark_price).to_f64().abs();
 
         if abs_position > self.config.max_position_size {
             error!(
@@ -73,8 +89,31 @@ impl RiskManager {
         RiskCheckResult::Accept
     }
 
+    /// Check that unreserved balances cover both sides of a quote
+    ///
+    /// Each side is checked on its own; a spot bid needs the quote asset and a
+    /// spot ask the base asset.
+    pub fn check_available_balance(&self, quote: &StrategyQuote, portfolio: &Portfolio, instrument_id: u32) -> RiskCheckResult {
+        let sides = [(OrderSide::Bid, quote.bid_price, quote.bid_size), (OrderSide::Ask, quote.ask_price, quote.ask_size)];
+        for (side, price, size) in sides {
+            if size == Qty::ZERO {
+                continue;
+            }
+            if let Err(e) = portfolio.check_available(instrument_id, side, price, size) {
+                warn!(
+                    side = ?side,
+                    error = %e,
+                    "Insufficient balance for quote"
+                );
+                return RiskCheckResult::Reject { reason: format!("{:?}: {}", side, e) };
+            }
+        }
+
+        RiskCheckResult::Accept
+    }
+
     /// Check if order size is within limits
-    pub fn check_order_size(&self, order_size: FixedPoint) -> RiskCheckResult {
+    pub fn check_order_size(&self, order_size: Qty) -> RiskCheckResult {
         let abs_size = order_size.to_f64().abs();
 
         if abs_size > self.config.max_order_size {
@@ -117,7 +156,7 @@ impl RiskManager {
     }
 
     /// Check if unrealized PnL is within acceptable range
-    pub fn check_unrealized_pnl(&self, position: &Position, mark_price: FixedPoint, max_unrealized_loss: f64) -> RiskCheckResult {
+    pub fn check_unrealized_pnl(&self, position: &Position, mark_price: Price, max_unrealized_loss: f64) -> RiskCheckResult {
         let unrealized = position.unrealized_pnl(mark_price).to_f64();
 
         if unrealized < -max_unrealized_loss {
@@ -128,7 +167,7 @@ impl RiskManager {
     }
 
     /// Comprehensive risk check before publishing quotes
-    pub fn check_quote(&self, quote: &StrategyQuote, position: &Position, mark_price: FixedPoint) -> RiskCheckResult {
+    pub fn check_quote(&self, quote: &StrategyQuote, position: &Position, mark_price: Price) -> RiskCheckResult {
         debug!(
             bid = %quote.bid_price.to_f64(),
             ask = %quote.ask_price.to_f64(),
@@ -152,18 +191,27 @@ impl RiskManager {
         }
 
         // Check position limit
-        if let RiskCheckResult::Reject { reason } = self.check_position_limit(position) {
+        if let RiskCheckResult::Reject { reason } = self.check_position_limit(position, mark_price) {
             return RiskCheckResult::Reject { reason };
         }
 
+        // A zero size means that side is not quoted (e.g. below the venue minimum)
+        if quote.bid_size == Qty::ZERO && quote.ask_size == Qty::ZERO {
+            return RiskCheckResult::Reject { reason: "Quote has no side to trade".to_string() };
+        }
+
         // Check bid size
-        if let RiskCheckResult::Reject { reason } = self.check_order_size(quote.bid_size) {
-            return RiskCheckResult::Reject { reason: format!("Bid: {}", reason) };
+        if quote.bid_size != Qty::ZERO {
+            if let RiskCheckResult::Reject { reason } = self.check_order_size(quote.bid_size) {
+                return RiskCheckResult::Reject { reason: format!("Bid: {}", reason) };
+            }
         }
 
         // Check ask size
-        if let RiskCheckResult::Reject { reason } = self.check_order_size(quote.ask_size) {
-            return RiskCheckResult::Reject { reason: format!("Ask: {}", reason) };
+        if quote.ask_size != Qty::ZERO {
+            if let RiskCheckResult::Reject { reason } = self.check_order_size(quote.ask_size) {
+                return RiskCheckResult::Reject { reason: format!("Ask: {}", reason) };
+            }
         }
 
         // Check spread sanity (prevent crossed quotes)
@@ -247,7 +295,7 @@ impl RiskManager {
     }
 
     /// Get current daily PnL
-    pub fn daily_pnl(&self) -> FixedPoint {
+    pub fn daily_pnl(&self) -> Notional {
         self.daily_realized_pnl
     }
 
@@ -296,6 +344,7 @@ impl RiskCheckResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ContractKind;
 
     #[test]
     fn test_position_limit() {
@@ -303,12 +352,54 @@ mod tests {
 
         let manager = RiskManager::new(config);
 
+        let mark = Price::from_f64(50_000.0);
         let mut position = Position::new();
-        position.quantity = FixedPoint::from_f64(5.0);
-        assert!(manager.check_position_limit(&position).is_accept());
+        position.quantity = Qty::from_f64(5.0);
+        assert!(manager.check_position_limit(&position, mark).is_accept());
+
+        position.quantity = Qty::from_f64(15.0);
+        assert!(manager.check_position_limit(&position, mark).is_reject());
+
+        // 1_000 contracts of 0.01 BTC is 10 BTC; 4_000 contracts of 100 USD is 8 BTC at 50_000 but 16 at 25_000
+        let mut linear = Position::new().with_contract(ContractKind::LinearPerp { multiplier: Qty::from_f64(0.01) });
+        linear.quantity = Qty::from_f64(1_001.0);
+        assert!(manager.check_position_limit(&linear, mark).is_reject());
+
+        let mut inverse = Position::new().with_contract(ContractKind::InversePerp { multiplier: Notional::from_f64(100.0) });
+        inverse.quantity = Qty::from_f64(-4_000.0);
+        assert!(manager.check_position_limit(&inverse, mark).is_accept());
+        assert!(manager.check_position_limit(&inverse, Price::from_f64(25_000.0)).is_reject());
+    }
+
+    #[test]
+    fn test_available_balance() {
+        let manager = RiskManager::new(StrategyConfig::default());
+
+        let mut portfolio = Portfolio::new();
+        portfolio.add_instrument(1, "BTC", "USDT", ContractKind::Spot);
+        portfolio.ledger_mut().deposit("USDT", Notional::from_f64(60_000.0)).unwrap();
+
+        let mut quote = StrategyQuote {
+            timestamp: 0,
+            bid_price: Price::from_f64(49_990.0),
+            bid_size: Qty::from_f64(1.0),
+            ask_price: Price::from_f64(50_010.0),
+            ask_size: Qty::ZERO,
+            fair_value: Price::from_f64(50_000.0),
+            inventory: Qty::ZERO,
+            confidence: 0.8,
+        };
+        assert!(manager.check_available_balance(&quote, &portfolio, 1).is_accept());
+
+        // Asking needs BTC, which there is none of
+        quote.ask_size = Qty::from_f64(0.1);
+        assert!(manager.check_available_balance(&quote, &portfolio, 1).is_reject());
 
-        position.quantity = FixedPoint::from_f64(15.0);
-        assert!(manager.check_position_limit(&position).is_reject());
+        // Quote balance already locked by an open order
+        quote.ask_size = Qty::ZERO;
+        portfolio.reserve(7, 1, OrderSide::Bid, Price::from_f64(49_000.0), Qty::from_f64(0.5)).unwrap();
+        assert!(manager.check_available_balance(&quote, &portfolio, 1).is_reject());
+        assert!(manager.check_available_balance(&quote, &portfolio, 2).is_reject());
     }
 
     #[test]
@@ -317,9 +408,9 @@ mod tests {
 
         let manager = RiskManager::new(config);
 
-        assert!(manager.check_order_size(FixedPoint::from_f64(0.5)).is_accept());
-        assert!(manager.check_order_size(FixedPoint::from_f64(2.0)).is_reject());
-        assert!(manager.check_order_size(FixedPoint::from_f64(0.0)).is_reject());
+        assert!(manager.check_order_size(Qty::from_f64(0.5)).is_accept());
+        assert!(manager.check_order_size(Qty::from_f64(2.0)).is_reject());
+        assert!(manager.check_order_size(Qty::from_f64(0.0)).is_reject());
     }
 
     #[test]
@@ -330,10 +421,10 @@ mod tests {
 
         assert!(manager.check_daily_loss().is_accept());
 
-        manager.update_daily_pnl(FixedPoint::from_f64(-50.0));
+        manager.update_daily_pnl(Notional::from_f64(-50.0));
         assert!(manager.check_daily_loss().is_accept());
 
-        manager.update_daily_pnl(FixedPoint::from_f64(-60.0)); // Total: -110
+        manager.update_daily_pnl(Notional::from_f64(-60.0)); // Total: -110
         assert!(manager.check_daily_loss().is_reject());
     }
 
@@ -359,17 +450,17 @@ mod tests {
 
         let quote = StrategyQuote {
             timestamp: 0,
-            bid_price: FixedPoint::from_f64(101.0),
-            bid_size: FixedPoint::from_f64(1.0),
-            ask_price: FixedPoint::from_f64(100.0),
-            ask_size: FixedPoint::from_f64(1.0),
-            fair_value: FixedPoint::from_f64(100.5),
-            inventory: FixedPoint::ZERO,
+            bid_price: Price::from_f64(101.0),
+            bid_size: Qty::from_f64(1.0),
+            ask_price: Price::from_f64(100.0),
+            ask_size: Qty::from_f64(1.0),
+            fair_value: Price::from_f64(100.5),
+            inventory: Qty::ZERO,
             confidence: 0.8,
         };
 
         let position = Position::new();
-        let mark = FixedPoint::from_f64(100.5);
+        let mark = Price::from_f64(100.5);
 
         let result = manager.check_quote(&quote, &position, mark);
         assert!(result.is_reject());
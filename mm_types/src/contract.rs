@@ -0,0 +1,150 @@
+use crate::FixedPoint;
+use crate::Notional;
+use crate::Price;
+use crate::Qty;
+use crate::RoundingMode;
+
+/// How an instrument's quantity, value and PnL relate to its price
+///
+/// Quantities are in contracts (base units for `Spot`). PnL, fees and funding
+/// are in the settlement asset: the quote asset for `Spot` and `LinearPerp`, the
+/// base asset for `InversePerp`; they are still carried as `Notional` at
+/// `Scale::DEFAULT`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ContractKind {
+    /// One unit is one unit of the base asset, settled in the quote asset
+    #[default]
+    Spot,
+    /// Quote-settled perpetual; one contract is `multiplier` units of the base asset
+    LinearPerp { multiplier: Qty },
+    /// Base-settled perpetual; one contract is worth `multiplier` of the quote asset
+    /// (e.g. 100 USD on BTCUSD)
+    InversePerp { multiplier: Notional },
+}
+
+impl ContractKind {
+    /// Whether PnL settles in the base asset rather than the quote asset
+    #[inline]
+    pub fn is_inverse(self) -> bool {
+        matches!(self, ContractKind::InversePerp { .. })
+    }
+
+    /// Whether the contract pays or receives funding
+    #[inline]
+    pub fn is_perpetual(self) -> bool {
+        matches!(self, ContractKind::LinearPerp { .. } | ContractKind::InversePerp { .. })
+    }
+
+    /// Signed base-asset exposure of `contracts` at `price`
+    ///
+    /// Only inverse contracts depend on the price; a zero price gives zero exposure.
+    #[inline]
+    pub fn base_exposure(self, contracts: Qty, price: Price) -> Qty {
+        match self {
+            ContractKind::Spot => contracts,
+            ContractKind::LinearPerp { multiplier } => Qty(FixedPoint(contracts.0).saturating_mul(FixedPoint(multiplier.0)).0),
+            ContractKind::InversePerp { multiplier } => {
+                let value = Notional(FixedPoint(contracts.0).saturating_mul(FixedPoint(multiplier.0)).0);
+                value.units_at(price, RoundingMode::TowardZero).unwrap_or(Qty::ZERO)
+            }
+        }
+    }
+
+    /// Signed quote-asset value of `contracts` at `price`
+    #[inline]
+    pub fn notional(self, contracts: Qty, price: Price) -> Notional {
+        match self {
+            ContractKind::InversePerp { multiplier } => Notional(FixedPoint(contracts.0).saturating_mul(FixedPoint(multiplier.0)).0),
+            _ => price.saturating_mul(self.base_exposure(contracts, price)),
+        }
+    }
+
+    /// Signed value of `contracts` at `price` in the settlement asset
+    ///
+    /// Fees and funding are charged on this amount.
+    #[inline]
+    pub fn settlement_value(self, contracts: Qty, price: Price) -> Notional {
+        match self {
+            ContractKind::InversePerp { .. } => Notional(self.base_exposure(contracts, price).0),
+            _ => self.notional(contracts, price),
+        }
+    }
+
+    /// PnL in the settlement asset of holding `contracts` from `entry` to `exit`
+    #[inline]
+    pub fn pnl(self, contracts: Qty, entry: Price, exit: Price) -> Notional {
+        match self {
+            // Base value shrinks as the price rises: value/entry - value/exit
+            ContractKind::InversePerp { .. } => Notional((self.base_exposure(contracts, entry) - self.base_exposure(contracts, exit)).0),
+            _ => (exit - entry) * self.base_exposure(contracts, exit),
+        }
+    }
+
+    /// Entry price after adding `added` contracts at `price` to `held` contracts at `entry`
+    ///
+    /// Linear contracts average prices by quantity; inverse contracts average by
+    /// base value, which is the harmonic mean of the prices.
+    #[inline]
+    pub fn average_entry(self, held: Qty, entry: Price, added: Qty, price: Price) -> Price {
+        let total = held + added;
+        match self {
+            ContractKind::InversePerp { .. } => {
+                // total / (held / entry + added / price); the multiplier cancels
+                let cost = held.0 as f64 / entry.0 as f64 + added.0 as f64 / price.0 as f64;
+                Price((total.0 as f64 / cost).round() as i64)
+            }
+            _ => (entry * held + price * added) / total,
+        }
+    }
+
+    /// Convert a settlement-asset amount to the quote asset at `price`
+    #[inline]
+    pub fn to_quote(self, amount: Notional, price: Price) -> Notional {
+        match self {
+            ContractKind::InversePerp { .. } => price.saturating_mul(Qty(amount.0)),
+            _ => amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_multiplier_scales_value_and_pnl() {
+        let contract = ContractKind::LinearPerp { multiplier: Qty::from_f64(0.001) };
+        let contracts = Qty::from_int(100);
+        assert_eq!(contract.base_exposure(contracts, Price::from_int(50_000)), Qty::from_f64(0.1));
+        assert_eq!(contract.notional(contracts, Price::from_int(50_000)), Notional::from_int(5_000));
+        assert_eq!(contract.pnl(contracts, Price::from_int(50_000), Price::from_int(51_000)), Notional::from_int(100));
+        assert_eq!(ContractKind::Spot.pnl(contracts, Price::from_int(100), Price::from_int(101)), Notional::from_int(100));
+    }
+
+    #[test]
+    fn test_inverse_value_and_pnl_in_base() {
+        let contract = ContractKind::InversePerp { multiplier: Notional::from_int(100) };
+        let contracts = Qty::from_int(100);
+
+        // 10_000 USD of contracts is 0.2 BTC at 50_000, whatever the price the quote value is fixed
+        assert_eq!(contract.notional(contracts, Price::from_int(40_000)), Notional::from_int(10_000));
+        assert_eq!(contract.base_exposure(contracts, Price::from_int(50_000)), Qty::from_f64(0.2));
+        assert_eq!(contract.settlement_value(-contracts, Price::from_int(50_000)), Notional::from_f64(-0.2));
+
+        // Long from 40_000 to 50_000: 0.25 - 0.2 = 0.05 BTC, worth 2_500 USD at 50_000
+        let pnl = contract.pnl(contracts, Price::from_int(40_000), Price::from_int(50_000));
+        assert_eq!(pnl, Notional::from_f64(0.05));
+        assert_eq!(contract.to_quote(pnl, Price::from_int(50_000)), Notional::from_int(2_500));
+        assert_eq!(contract.pnl(-contracts, Price::from_int(40_000), Price::from_int(50_000)), -pnl);
+    }
+
+    #[test]
+    fn test_inverse_entry_is_harmonic_mean() {
+        let contract = ContractKind::InversePerp { multiplier: Notional::from_int(1) };
+        let held = Qty::from_int(100);
+        let entry = contract.average_entry(held, Price::from_int(40_000), held, Price::from_int(60_000));
+        assert_eq!(entry, Price::from_int(48_000));
+        let linear = ContractKind::LinearPerp { multiplier: Qty::from_int(1) };
+        assert_eq!(linear.average_entry(held, Price::from_int(40_000), held, Price::from_int(60_000)), Price::from_int(50_000));
+    }
+}
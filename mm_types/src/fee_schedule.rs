@@ -0,0 +1,123 @@
+use crate::InstrumentSpec;
+use crate::Notional;
+use crate::Price;
+use crate::Qty;
+
+/// Maker and taker rates that apply once traded volume reaches `min_volume`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeTier {
+    /// Traded notional at which this tier starts
+    pub min_volume: Notional,
+    /// Rate on passive fills in basis points; negative is a rebate
+    pub maker_bps: f64,
+    /// Rate on aggressive fills in basis points; negative is a rebate
+    pub taker_bps: f64,
+}
+
+impl FeeTier {
+    pub fn new(min_volume: Notional, maker_bps: f64, taker_bps: f64) -> Self {
+        Self { min_volume, maker_bps, taker_bps }
+    }
+}
+
+/// Venue maker/taker fees, tiered by traded volume
+///
+/// Fees are signed: positive is paid to the venue, negative is a rebate earned.
+/// An empty schedule charges nothing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeeSchedule {
+    /// Sorted by `min_volume`, lowest first
+    tiers: Vec<FeeTier>,
+}
+
+impl FeeSchedule {
+    /// No fees and no rebates
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// The same rates at every volume
+    pub fn flat(maker_bps: f64, taker_bps: f64) -> Self {
+        Self { tiers: vec![FeeTier::new(Notional::ZERO, maker_bps, taker_bps)] }
+    }
+
+    /// Tiers in any order; volume below the lowest tier pays the lowest tier's rates
+    pub fn tiered(mut tiers: Vec<FeeTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.min_volume);
+        Self { tiers }
+    }
+
+    pub fn tiers(&self) -> &[FeeTier] {
+        &self.tiers
+    }
+
+    /// Tier reached at `volume`, `None` for an empty schedule
+    pub fn tier(&self, volume: Notional) -> Option<&FeeTier> {
+        self.tiers.iter().rev().find(|tier| volume >= tier.min_volume).or(self.tiers.first())
+    }
+
+    /// Rate in basis points for a maker or taker fill at `volume`
+    pub fn rate_bps(&self, is_maker: bool, volume: Notional) -> f64 {
+        match self.tier(volume) {
+            Some(tier) if is_maker => tier.maker_bps,
+            Some(tier) => tier.taker_bps,
+            None => 0.0,
+        }
+    }
+
+    /// Fee on a spot fill of `qty` at `price`, charged at the tier reached at `volume`
+    ///
+    /// Positive is paid, negative is a rebate; rounded to the nearest raw unit.
+    pub fn fee(&self, price: Price, qty: Qty, is_maker: bool, volume: Notional) -> Notional {
+        self.fee_on(InstrumentSpec::notional(price, qty), is_maker, volume)
+    }
+
+    /// Fee on a fill worth `value`, in whatever asset `value` is in
+    pub fn fee_on(&self, value: Notional, is_maker: bool, volume: Notional) -> Notional {
+        let rate_bps = self.rate_bps(is_maker, volume);
+        if rate_bps == 0.0 {
+            return Notional::ZERO;
+        }
+        Notional((value.abs().0 as f64 * rate_bps / 10_000.0).round() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> FeeSchedule {
+        FeeSchedule::tiered(vec![
+            FeeTier::new(Notional::from_int(1_000_000), -0.5, 4.0),
+            FeeTier::new(Notional::ZERO, 1.0, 5.0),
+            FeeTier::new(Notional::from_int(10_000_000), -1.0, 3.0),
+        ])
+    }
+
+    #[test]
+    fn test_tier_selection() {
+        let fees = schedule();
+        assert_eq!(fees.tiers()[0].min_volume, Notional::ZERO);
+        assert_eq!(fees.rate_bps(true, Notional::ZERO), 1.0);
+        assert_eq!(fees.rate_bps(false, Notional::from_int(999_999)), 5.0);
+        assert_eq!(fees.rate_bps(true, Notional::from_int(1_000_000)), -0.5);
+        assert_eq!(fees.rate_bps(false, Notional::from_int(50_000_000)), 3.0);
+
+        assert_eq!(FeeSchedule::zero().tier(Notional::ZERO), None);
+        assert_eq!(FeeSchedule::zero().fee(Price::from_int(100), Qty::from_int(1), false, Notional::ZERO), Notional::ZERO);
+    }
+
+    #[test]
+    fn test_fee_sign_and_size() {
+        let fees = schedule();
+        let price = Price::from_int(50_000);
+        let qty = Qty::from_f64(0.2);
+
+        // 10_000 notional: 5 bps taker is 5, a -0.5 bps maker rebate is -0.5
+        assert_eq!(fees.fee(price, qty, false, Notional::ZERO), Notional::from_int(5));
+        assert_eq!(fees.fee(price, qty, true, Notional::from_int(2_000_000)), Notional::from_f64(-0.5));
+
+        // Sells are charged on the absolute notional
+        assert_eq!(fees.fee(price, -qty, false, Notional::ZERO), Notional::from_int(5));
+    }
+}
@@ -0,0 +1,144 @@
+use mm_binary::AnyMessage;
+use mm_binary::CompressedString;
+use mm_binary::FundingRateMessage;
+use mm_binary::ProtocolError;
+use mm_binary::decode_any;
+use mm_binary::from_fixed_point;
+
+use crate::ContractKind;
+use crate::Price;
+
+/// Turns a stream of funding rate updates into funding settlements
+///
+/// Venues publish the predicted rate for the upcoming funding time. Once an update
+/// names a later funding time, the earlier one has settled at the last rate seen
+/// before it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FundingClock {
+    rate: f64,
+    /// Zero until the first update
+    next_funding_time: u64,
+}
+
+impl FundingClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a predicted `rate` for `next_funding_time`, returning the rate that
+    /// settled if a funding time has passed since the previous update
+    pub fn observe(&mut self, rate: f64, next_funding_time: u64) -> Option<f64> {
+        let settled = (self.next_funding_time != 0 && next_funding_time > self.next_funding_time).then_some(self.rate);
+        self.rate = rate;
+        self.next_funding_time = next_funding_time;
+        settled
+    }
+
+    /// [`observe`](Self::observe) a funding rate message
+    pub fn observe_message(&mut self, msg: &FundingRateMessage) -> Option<f64> {
+        self.observe(from_fixed_point(msg.funding_rate), msg.next_funding_time)
+    }
+
+    /// Latest predicted rate
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn next_funding_time(&self) -> u64 {
+        self.next_funding_time
+    }
+}
+
+/// One funding interval settled for the tracked perpetual
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingSettlement {
+    pub rate: f64,
+    /// Latest mark price seen for the symbol, if any
+    pub mark_price: Option<Price>,
+}
+
+/// Funding settlements for one instrument, read from the derivatives stream
+///
+/// Frames for other symbols are ignored and non-perpetual contracts never settle.
+/// Mark price updates for the symbol are kept to mark the settlements at.
+#[derive(Debug, Clone, Copy)]
+pub struct FundingFeed {
+    symbol: CompressedString,
+    contract: ContractKind,
+    clock: FundingClock,
+    mark_price: Option<Price>,
+}
+
+impl FundingFeed {
+    pub fn new(symbol: CompressedString, contract: ContractKind) -> Self {
+        Self { symbol, contract, clock: FundingClock::new(), mark_price: None }
+    }
+
+    /// Read one enveloped derivatives frame, returning the settlement it completes
+    pub fn observe_frame(&mut self, frame: &[u8]) -> Result<Option<FundingSettlement>, ProtocolError> {
+        match decode_any(frame)? {
+            AnyMessage::MarkPrice(msg) if msg.symbol() == self.symbol => {
+                self.mark_price = Some(Price(msg.mark_price));
+                Ok(None)
+            }
+            AnyMessage::FundingRate(msg) if msg.symbol() == self.symbol && self.contract.is_perpetual() => {
+                Ok(self.clock.observe_message(&msg).map(|rate| FundingSettlement { rate, mark_price: self.mark_price }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Latest mark price seen for the symbol
+    pub fn mark_price(&self) -> Option<Price> {
+        self.mark_price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Qty;
+    use mm_binary::Exchange;
+    use mm_binary::MarkPriceMessage;
+
+    #[test]
+    fn test_settles_when_funding_time_rolls() {
+        let mut clock = FundingClock::new();
+        assert_eq!(clock.observe(0.0001, 8_000), None);
+        assert_eq!(clock.observe(0.0002, 8_000), None);
+        // The 8_000 funding settled at the last prediction before it
+        assert_eq!(clock.observe(-0.0001, 16_000), Some(0.0002));
+        assert_eq!(clock.observe(-0.0001, 16_000), None);
+        assert_eq!(clock.rate(), -0.0001);
+        assert_eq!(clock.next_funding_time(), 16_000);
+    }
+
+    #[test]
+    fn test_feed_settles_only_the_tracked_perpetual() {
+        let (btc, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let (eth, _) = CompressedString::from_str("ETHUSDT").unwrap();
+        let funding = |symbol, rate: f64, next| {
+            AnyMessage::from(FundingRateMessage::new(Exchange::Binance, symbol, encoding, 1, mm_binary::to_fixed_point(rate), next))
+                .to_bytes()
+        };
+        let mark = |symbol, price| AnyMessage::from(MarkPriceMessage::new(Exchange::Binance, symbol, encoding, 1, price, price)).to_bytes();
+
+        let perp = ContractKind::LinearPerp { multiplier: Qty::from_int(1) };
+        let mut feed = FundingFeed::new(btc, perp);
+        assert_eq!(feed.observe_frame(&funding(btc, 0.0001, 8_000)).unwrap(), None);
+        assert_eq!(feed.observe_frame(&mark(btc, Price::from_int(50_000).0)).unwrap(), None);
+        // Another symbol's mark and funding leave the feed alone
+        assert_eq!(feed.observe_frame(&mark(eth, Price::from_int(3_000).0)).unwrap(), None);
+        assert_eq!(feed.observe_frame(&funding(eth, 0.0005, 16_000)).unwrap(), None);
+        assert_eq!(feed.mark_price(), Some(Price::from_int(50_000)));
+
+        let settled = feed.observe_frame(&funding(btc, 0.0002, 16_000)).unwrap().unwrap();
+        assert!((settled.rate - 0.0001).abs() < 1e-12);
+        assert_eq!(settled.mark_price, Some(Price::from_int(50_000)));
+
+        // A spot instrument never settles funding
+        let mut spot = FundingFeed::new(btc, ContractKind::Spot);
+        assert_eq!(spot.observe_frame(&funding(btc, 0.0001, 8_000)).unwrap(), None);
+        assert_eq!(spot.observe_frame(&funding(btc, 0.0001, 16_000)).unwrap(), None);
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use mm_binary::ContractType;
+use mm_binary::InstrumentInfo;
+use mm_binary::Scale;
+
+use crate::ContractKind;
+use crate::FixedPoint;
+use crate::Notional;
+use crate::Price;
+use crate::Qty;
+
+/// An order that a venue would reject under its trading rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecViolation {
+    /// Price is not a whole number of ticks
+    OffTick { price: Price, tick_size: Price },
+    /// Quantity is not a whole number of lots
+    OffLot { qty: Qty, lot_size: Qty },
+    /// Price × quantity is below the venue minimum
+    BelowMinNotional { notional: Notional, min_notional: Notional },
+}
+
+impl fmt::Display for SpecViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecViolation::OffTick { price, tick_size } => write!(f, "Price {} is not a multiple of tick size {}", price, tick_size),
+            SpecViolation::OffLot { qty, lot_size } => write!(f, "Quantity {} is not a multiple of lot size {}", qty, lot_size),
+            SpecViolation::BelowMinNotional { notional, min_notional } => {
+                write!(f, "Notional {} is below minimum {}", notional, min_notional)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecViolation {}
+
+/// Venue trading rules for one instrument, at `Scale::DEFAULT`
+///
+/// Quote prices round away from the other side of the book, so a rounded bid
+/// never moves up toward the ask; sizes round down so they never exceed what the
+/// caller asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstrumentSpec {
+    pub tick_size: Price,
+    pub lot_size: Qty,
+    /// Smallest accepted order value in the quote asset (zero = no minimum)
+    pub min_notional: Notional,
+    pub contract: ContractKind,
+}
+
+impl InstrumentSpec {
+    /// Every raw fixed-point unit is a valid price and size
+    pub const UNCONSTRAINED: Self =
+        Self { tick_size: Price(1), lot_size: Qty(1), min_notional: Notional::ZERO, contract: ContractKind::Spot };
+
+    /// Non-positive increments are treated as one raw unit
+    pub fn new(tick_size: Price, lot_size: Qty) -> Self {
+        Self {
+            tick_size: Price(tick_size.0.max(1)),
+            lot_size: Qty(lot_size.0.max(1)),
+            min_notional: Notional::ZERO,
+            contract: ContractKind::Spot,
+        }
+    }
+
+    pub fn with_min_notional(mut self, min_notional: Notional) -> Self {
+        self.min_notional = min_notional;
+        self
+    }
+
+    /// Quantities become contracts of this kind
+    pub fn with_contract(mut self, contract: ContractKind) -> Self {
+        self.contract = contract;
+        self
+    }
+
+    /// Rules from a registry entry; `None` if its increments do not fit at `Scale::DEFAULT`
+    ///
+    /// Increments finer than `Scale::DEFAULT` round to zero and become one raw unit.
+    pub fn from_info(info: &InstrumentInfo) -> Option<Self> {
+        let tick_size = FixedPoint(info.tick_size).rescale(info.price_scale, Scale::DEFAULT)?;
+        let lot_size = FixedPoint(info.lot_size).rescale(info.qty_scale, Scale::DEFAULT)?;
+        let min_notional = FixedPoint(info.min_notional).rescale(info.price_scale, Scale::DEFAULT)?;
+        let contract = match info.contract {
+            ContractType::Spot => ContractKind::Spot,
+            ContractType::LinearPerp => ContractKind::LinearPerp { multiplier: Qty(info.multiplier) },
+            ContractType::InversePerp => ContractKind::InversePerp { multiplier: Notional(info.multiplier) },
+        };
+        Some(Self::new(tick_size.into(), lot_size.into()).with_min_notional(min_notional.into()).with_contract(contract))
+    }
+
+    /// Round a bid price down to the tick grid
+    #[inline]
+    pub fn round_bid_price(&self, price: Price) -> Price {
+        Price(price.0.div_euclid(self.tick_size.0) * self.tick_size.0)
+    }
+
+    /// Round an ask price up to the tick grid
+    #[inline]
+    pub fn round_ask_price(&self, price: Price) -> Price {
+        let floor = self.round_bid_price(price);
+        if floor == price { price } else { floor.saturating_add(self.tick_size) }
+    }
+
+    /// Round a quantity toward zero to whole lots
+    #[inline]
+    pub fn round_qty(&self, qty: Qty) -> Qty {
+        Qty(qty.0 / self.lot_size.0 * self.lot_size.0)
+    }
+
+    /// Absolute price × quantity, saturating on overflow
+    #[inline]
+    pub fn notional(price: Price, qty: Qty) -> Notional {
+        price.saturating_mul(qty).abs()
+    }
+
+    /// Check the order's quote-asset value, which accounts for the contract size
+    pub fn validate_min_notional(&self, price: Price, qty: Qty) -> Result<(), SpecViolation> {
+        let notional = self.contract.notional(qty, price).abs();
+        if notional < self.min_notional {
+            return Err(SpecViolation::BelowMinNotional { notional, min_notional: self.min_notional });
+        }
+        Ok(())
+    }
+
+    /// Check an order against every rule, as the venue would on entry
+    pub fn validate_order(&self, price: Price, qty: Qty) -> Result<(), SpecViolation> {
+        if price.0 % self.tick_size.0 != 0 {
+            return Err(SpecViolation::OffTick { price, tick_size: self.tick_size });
+        }
+        if qty.0 % self.lot_size.0 != 0 {
+            return Err(SpecViolation::OffLot { qty, lot_size: self.lot_size });
+        }
+        self.validate_min_notional(price, qty)
+    }
+}
+
+impl Default for InstrumentSpec {
+    fn default() -> Self {
+        Self::UNCONSTRAINED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_binary::Exchange;
+
+    fn btc() -> InstrumentSpec {
+        InstrumentSpec::new(Price::from_f64(0.01), Qty::from_f64(0.00001)).with_min_notional(Notional::from_f64(5.0))
+    }
+
+    #[test]
+    fn test_price_rounding_never_crosses() {
+        let spec = btc();
+        let price = Price::from_f64(50_000.123);
+        assert_eq!(spec.round_bid_price(price), Price::from_f64(50_000.12));
+        assert_eq!(spec.round_ask_price(price), Price::from_f64(50_000.13));
+
+        // On-tick prices are unchanged
+        let on_tick = Price::from_f64(50_000.12);
+        assert_eq!(spec.round_bid_price(on_tick), on_tick);
+        assert_eq!(spec.round_ask_price(on_tick), on_tick);
+
+        // Negative prices (spreads, some futures) still round down for bids
+        assert_eq!(spec.round_bid_price(Price::from_f64(-0.005)), Price::from_f64(-0.01));
+        assert_eq!(spec.round_ask_price(Price::from_f64(-0.005)), Price::ZERO);
+    }
+
+    #[test]
+    fn test_qty_rounding_and_validation() {
+        let spec = btc();
+        assert_eq!(spec.round_qty(Qty::from_f64(0.123456789)), Qty::from_f64(0.12345));
+
+        let price = Price::from_f64(50_000.0);
+        assert!(spec.validate_min_notional(price, Qty::from_f64(0.0001)).is_ok());
+        assert!(matches!(spec.validate_min_notional(price, Qty::from_f64(0.00005)), Err(SpecViolation::BelowMinNotional { .. })));
+
+        assert!(spec.validate_order(price, Qty::from_f64(0.001)).is_ok());
+        assert!(matches!(spec.validate_order(Price::from_f64(50_000.005), Qty::from_f64(0.001)), Err(SpecViolation::OffTick { .. })));
+        assert!(matches!(spec.validate_order(price, Qty::from_f64(0.000015)), Err(SpecViolation::OffLot { .. })));
+    }
+
+    #[test]
+    fn test_from_info_rescales() {
+        let info = InstrumentInfo::new(1, "SHIBUSDT", Exchange::Binance, "SHIB", "USDT", 10_000, 1)
+            .with_scales(Scale::new(12).unwrap(), Scale::new(0).unwrap())
+            .with_min_notional(5_000_000_000_000);
+        let spec = InstrumentSpec::from_info(&info).unwrap();
+        assert_eq!(spec.tick_size, Price(1));
+        assert_eq!(spec.lot_size, Qty::from_int(1));
+        assert_eq!(spec.min_notional, Notional::from_int(5));
+        assert_eq!(spec.contract, ContractKind::Spot);
+
+        let perp = InstrumentInfo::new(2, "BTCUSD_PERP", Exchange::Binance, "BTC", "USD", 10_000_000, 100_000_000)
+            .with_contract(ContractType::InversePerp, 10_000_000_000)
+            .with_min_notional(10_000_000_000);
+        let spec = InstrumentSpec::from_info(&perp).unwrap();
+        assert_eq!(spec.contract, ContractKind::InversePerp { multiplier: Notional::from_int(100) });
+
+        // One 100 USD contract passes a 100 USD minimum at any price
+        assert!(spec.validate_min_notional(Price::from_int(50_000), Qty::from_int(1)).is_ok());
+        assert!(spec.validate_min_notional(Price::from_int(50_000), Qty::from_f64(0.5)).is_err());
+    }
+}
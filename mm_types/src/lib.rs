@@ -1,11 +1,97 @@
+mod contract;
+mod fee_schedule;
+mod funding;
+mod instrument_spec;
+mod portfolio;
+mod units;
+
+pub use contract::ContractKind;
+pub use fee_schedule::FeeSchedule;
+pub use fee_schedule::FeeTier;
+pub use funding::FundingClock;
+pub use funding::FundingFeed;
+pub use funding::FundingSettlement;
+pub use instrument_spec::InstrumentSpec;
+pub use instrument_spec::SpecViolation;
+use mm_binary::CompressedString;
 use mm_binary::FIXED_POINT_MULTIPLIER;
+pub use mm_binary::Scale;
+use mm_binary::compressed_string::EncodingScheme;
 use mm_binary::from_fixed_point;
+use mm_binary::from_fixed_point_scaled;
 // Re-export OrderSide from mm_binary for consistency
+use mm_binary::messages::OrderFillMessage;
 pub use mm_binary::messages::OrderSide;
+use mm_binary::messages::PositionMessage;
 use mm_binary::to_fixed_point;
+use mm_binary::to_fixed_point_scaled;
+pub use portfolio::Balance;
+pub use portfolio::Ledger;
+pub use portfolio::LedgerError;
+pub use portfolio::Portfolio;
+pub use units::Notional;
+pub use units::Price;
+pub use units::Qty;
+
+/// How to round a result that falls between two representable values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Truncate, as integer division does (the operator default)
+    TowardZero,
+    AwayFromZero,
+    /// Toward negative infinity
+    Floor,
+    /// Toward positive infinity
+    Ceil,
+    /// Nearest, ties away from zero
+    HalfAwayFromZero,
+    /// Nearest, ties to even (banker's rounding)
+    HalfEven,
+}
+
+/// `num / den` rounded with `mode`; `None` if `den` is zero
+#[inline]
+fn div_round(num: i128, den: i128, mode: RoundingMode) -> Option<i128> {
+    if den == 0 {
+        return None;
+    }
+    let quotient = num / den;
+    let remainder = num % den;
+    if remainder == 0 {
+        return Some(quotient);
+    }
+
+    let positive = (num < 0) == (den < 0);
+    let away = match mode {
+        RoundingMode::TowardZero => false,
+        RoundingMode::AwayFromZero => true,
+        RoundingMode::Floor => !positive,
+        RoundingMode::Ceil => positive,
+        RoundingMode::HalfAwayFromZero | RoundingMode::HalfEven => match (remainder.unsigned_abs() * 2).cmp(&den.unsigned_abs()) {
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => mode == RoundingMode::HalfAwayFromZero || quotient % 2 != 0,
+        },
+    };
+
+    Some(match (away, positive) {
+        (false, _) => quotient,
+        (true, true) => quotient + 1,
+        (true, false) => quotient - 1,
+    })
+}
+
+/// Clamp an exact result to the `i64` range
+#[inline(always)]
+fn saturate(value: i128) -> i64 {
+    value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
 
 /// Fixed-point number wrapper for cleaner API
 /// Internally uses i64 with 8 decimal places (satoshi precision)
+///
+/// The operators assume `Scale::DEFAULT`. Values of instruments registered with
+/// another scale must use the `*_scaled` methods, or be `rescale`d first.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FixedPoint(pub i64);
 
@@ -32,28 +118,182 @@ impl FixedPoint {
         self.0
     }
 
-    /// Apply basis points to this value
+    /// `self * bps / 10_000` with `bps` taken to 4 decimal places, exact in `i128`
+    #[inline(always)]
+    fn bps_adjustment(self, bps: f64) -> i128 {
+        // bps * 10_000 is the multiplier at 8 decimal places
+        (self.0 as i128).saturating_mul((bps * 10000.0) as i128) / 100_000_000
+    }
+
+    /// Apply basis points to this value, clamped to the representable range
     /// Example: 100.0 with 50 bps = 100.5
     #[inline(always)]
     pub fn apply_bps(self, bps: f64) -> Self {
-        // bps / 10000.0 gives the decimal multiplier
-        // self * (bps / 10000.0) gives the adjustment
-        let adjustment = (self.0 as i128 * (bps * 10000.0) as i128) / 100_000_000;
-        FixedPoint(self.0 + adjustment as i64)
+        FixedPoint(saturate(self.0 as i128 + self.bps_adjustment(bps)))
     }
 
-    /// Subtract basis points from this value
+    /// Subtract basis points from this value, clamped to the representable range
     #[inline(always)]
     pub fn subtract_bps(self, bps: f64) -> Self {
-        let adjustment = (self.0 as i128 * (bps * 10000.0) as i128) / 100_000_000;
-        FixedPoint(self.0 - adjustment as i64)
+        FixedPoint(saturate(self.0 as i128 - self.bps_adjustment(bps)))
+    }
+
+    /// [`apply_bps`](Self::apply_bps), or `None` if the result does not fit in an `i64`
+    #[inline(always)]
+    pub fn checked_apply_bps(self, bps: f64) -> Option<Self> {
+        i64::try_from(self.0 as i128 + self.bps_adjustment(bps)).ok().map(FixedPoint)
     }
 
-    /// Multiply by a scalar (f64) while maintaining precision
+    /// [`subtract_bps`](Self::subtract_bps), or `None` if the result does not fit in an `i64`
+    #[inline(always)]
+    pub fn checked_subtract_bps(self, bps: f64) -> Option<Self> {
+        i64::try_from(self.0 as i128 - self.bps_adjustment(bps)).ok().map(FixedPoint)
+    }
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(FixedPoint)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(FixedPoint)
+    }
+
+    #[inline(always)]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        FixedPoint(self.0.saturating_add(rhs.0))
+    }
+
+    #[inline(always)]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        FixedPoint(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Exact product rounded with `mode`; `None` if it does not fit in an `i64`
+    #[inline]
+    pub fn mul_round(self, rhs: Self, mode: RoundingMode) -> Option<Self> {
+        let product = self.0 as i128 * rhs.0 as i128;
+        let result = div_round(product, FIXED_POINT_MULTIPLIER as i128, mode)?;
+        i64::try_from(result).ok().map(FixedPoint)
+    }
+
+    /// Exact quotient rounded with `mode`; `None` on division by zero or overflow
+    #[inline]
+    pub fn div_round(self, rhs: Self, mode: RoundingMode) -> Option<Self> {
+        let numerator = self.0 as i128 * FIXED_POINT_MULTIPLIER as i128;
+        let result = div_round(numerator, rhs.0 as i128, mode)?;
+        i64::try_from(result).ok().map(FixedPoint)
+    }
+
+    /// Product truncated like `*`, or `None` on overflow
+    #[inline(always)]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.mul_round(rhs, RoundingMode::TowardZero)
+    }
+
+    /// Quotient truncated like `/`, or `None` on division by zero or overflow
+    #[inline(always)]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.div_round(rhs, RoundingMode::TowardZero)
+    }
+
+    /// Product truncated like `*`, clamped to the representable range
+    #[inline]
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or(if (self.0 < 0) == (rhs.0 < 0) { FixedPoint(i64::MAX) } else { FixedPoint(i64::MIN) })
+    }
+
+    /// Quotient truncated like `/`, clamped to the representable range
+    ///
+    /// A zero divisor gives zero for a zero dividend and the bound of the dividend's sign otherwise.
+    #[inline]
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        self.checked_div(rhs).unwrap_or(match self.0.signum() * if rhs.0 < 0 { -1 } else { 1 } {
+            0 => FixedPoint::ZERO,
+            1 => FixedPoint(i64::MAX),
+            _ => FixedPoint(i64::MIN),
+        })
+    }
+
+    /// `self * scalar` exact in `i128` with `scalar` taken to 8 decimal places, truncated
+    ///
+    /// Non-finite scalars saturate the conversion (NaN becomes zero).
+    #[inline(always)]
+    fn scalar_product(self, scalar: f64) -> i128 {
+        let scalar = (scalar * FIXED_POINT_MULTIPLIER as f64).round() as i128;
+        (self.0 as i128).saturating_mul(scalar) / FIXED_POINT_MULTIPLIER as i128
+    }
+
+    /// Multiply by a dimensionless scalar, clamped to the representable range
+    ///
+    /// The scalar is rounded to 8 decimal places and the product is exact, so large
+    /// values keep full precision. A NaN scalar gives zero.
     #[inline(always)]
     pub fn mul_scalar(self, scalar: f64) -> Self {
-        let result = (self.0 as f64 * scalar) as i64;
-        FixedPoint(result)
+        FixedPoint(saturate(self.scalar_product(scalar)))
+    }
+
+    /// [`mul_scalar`](Self::mul_scalar), or `None` if `scalar` is not finite or the
+    /// product does not fit in an `i64`
+    #[inline(always)]
+    pub fn checked_mul_scalar(self, scalar: f64) -> Option<Self> {
+        if !scalar.is_finite() {
+            return None;
+        }
+        i64::try_from(self.scalar_product(scalar)).ok().map(FixedPoint)
+    }
+
+    #[inline(always)]
+    pub fn from_f64_scaled(value: f64, scale: Scale) -> Self {
+        FixedPoint(to_fixed_point_scaled(value, scale))
+    }
+
+    #[inline(always)]
+    pub fn from_int_scaled(value: i64, scale: Scale) -> Self {
+        FixedPoint(value * scale.multiplier())
+    }
+
+    #[inline(always)]
+    pub fn to_f64_scaled(self, scale: Scale) -> f64 {
+        from_fixed_point_scaled(self.0, scale)
+    }
+
+    /// Multiply two values that share `scale`; `None` if the product does not fit in an `i64`
+    #[inline]
+    pub fn mul_scaled(self, rhs: Self, scale: Scale) -> Option<Self> {
+        let result = (self.0 as i128 * rhs.0 as i128) / scale.multiplier() as i128;
+        i64::try_from(result).ok().map(FixedPoint)
+    }
+
+    /// Divide two values that share `scale`; `None` on division by zero or overflow
+    #[inline]
+    pub fn div_scaled(self, rhs: Self, scale: Scale) -> Option<Self> {
+        let result = (self.0 as i128 * scale.multiplier() as i128).checked_div(rhs.0 as i128)?;
+        i64::try_from(result).ok().map(FixedPoint)
+    }
+
+    /// Multiply a value at `lhs_scale` by one at `rhs_scale`, producing `out_scale`
+    ///
+    /// E.g. a price at 12 places times a quantity at 0 places gives a notional at 8.
+    /// `None` if the result does not fit in an `i64`.
+    #[inline]
+    pub fn mul_mixed(self, lhs_scale: Scale, rhs: Self, rhs_scale: Scale, out_scale: Scale) -> Option<Self> {
+        let product = self.0 as i128 * rhs.0 as i128;
+        let product_decimals = lhs_scale.decimals() as u32 + rhs_scale.decimals() as u32;
+        let out_decimals = out_scale.decimals() as u32;
+        let result = if product_decimals >= out_decimals {
+            product / 10i128.pow(product_decimals - out_decimals)
+        } else {
+            product.checked_mul(10i128.pow(out_decimals - product_decimals))?
+        };
+        i64::try_from(result).ok().map(FixedPoint)
+    }
+
+    /// Convert between scales; `None` if widening overflows
+    #[inline]
+    pub fn rescale(self, from: Scale, to: Scale) -> Option<Self> {
+        mm_binary::rescale(self.0, from, to).map(FixedPoint)
     }
 }
 
@@ -76,20 +316,18 @@ impl std::ops::Sub for FixedPoint {
 impl std::ops::Mul for FixedPoint {
     type Output = Self;
 
+    /// Saturates like `saturating_mul`; use `checked_mul` to detect overflow
     fn mul(self, rhs: Self) -> Self::Output {
-        // Fixed-point multiplication: (a * b) / MULTIPLIER
-        let result = (self.0 as i128 * rhs.0 as i128) / FIXED_POINT_MULTIPLIER as i128;
-        FixedPoint(result as i64)
+        self.saturating_mul(rhs)
     }
 }
 
 impl std::ops::Div for FixedPoint {
     type Output = Self;
 
+    /// Saturates like `saturating_div`, including on a zero divisor; use `checked_div` to detect either
     fn div(self, rhs: Self) -> Self::Output {
-        // Fixed-point division: (a * MULTIPLIER) / b
-        let result = (self.0 as i128 * FIXED_POINT_MULTIPLIER as i128) / rhs.0 as i128;
-        FixedPoint(result as i64)
+        self.saturating_div(rhs)
     }
 }
 
@@ -123,18 +361,18 @@ impl std::fmt::Display for FixedPoint {
 #[derive(Debug, Clone, Copy)]
 pub struct MarketState {
     pub timestamp: u64,
-    pub bid_price: FixedPoint,
-    pub ask_price: FixedPoint,
-    pub bid_volume: FixedPoint,
-    pub ask_volume: FixedPoint,
-    pub last_trade_price: Option<FixedPoint>,
-    pub last_trade_size: Option<FixedPoint>,
+    pub bid_price: Price,
+    pub ask_price: Price,
+    pub bid_volume: Qty,
+    pub ask_volume: Qty,
+    pub last_trade_price: Option<Price>,
+    pub last_trade_size: Option<Qty>,
 }
 
 impl MarketState {
     /// Calculate mid-price
-    pub fn mid_price(&self) -> FixedPoint {
-        (self.bid_price + self.ask_price) / FixedPoint::from_int(2)
+    pub fn mid_price(&self) -> Price {
+        self.bid_price.midpoint(self.ask_price)
     }
 
     /// Calculate spread in basis points
@@ -145,9 +383,9 @@ impl MarketState {
     }
 
     /// Calculate micro-price (volume-weighted price)
-    pub fn micro_price(&self) -> FixedPoint {
+    pub fn micro_price(&self) -> Price {
         let total_volume = self.bid_volume + self.ask_volume;
-        if total_volume == FixedPoint::ZERO {
+        if total_volume == Qty::ZERO {
             return self.mid_price();
         }
 
@@ -160,7 +398,7 @@ impl MarketState {
     /// Positive = more ask volume (bullish), Negative = more bid volume (bearish)
     pub fn orderbook_imbalance(&self) -> f64 {
         let total_volume = self.bid_volume + self.ask_volume;
-        if total_volume == FixedPoint::ZERO {
+        if total_volume == Qty::ZERO {
             return 0.0;
         }
 
@@ -168,29 +406,113 @@ impl MarketState {
     }
 }
 
+/// Liquidity beyond the top of book, summarised from a full order book
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DepthProfile {
+    /// Notional the impacts are measured for
+    pub notional: Notional,
+    /// Basis points below mid of the average price for selling `notional` into the bids
+    pub bid_impact_bps: f64,
+    /// Basis points above mid of the average price for buying `notional` from the asks
+    pub ask_impact_bps: f64,
+    /// The visible bids hold less than `notional`; `bid_impact_bps` only reaches the last level
+    pub bid_insufficient: bool,
+    /// The visible asks hold less than `notional`; `ask_impact_bps` only reaches the last level
+    pub ask_insufficient: bool,
+}
+
 /// Position tracking
+///
+/// `realized_pnl` and `unrealized_pnl` are trading PnL only; fees, rebates and
+/// funding are kept apart so the breakdown survives aggregation. Quantities are
+/// in contracts and every PnL amount is in the contract's settlement asset (see
+/// `ContractKind`).
 #[derive(Debug, Clone, Copy)]
 pub struct Position {
-    pub quantity: FixedPoint,
-    pub avg_entry_price: FixedPoint,
-    pub realized_pnl: FixedPoint,
+    pub contract: ContractKind,
+    pub quantity: Qty,
+    pub avg_entry_price: Price,
+    pub realized_pnl: Notional,
+    /// Cumulative fees paid to the venue (non-negative)
+    pub fees_paid: Notional,
+    /// Cumulative maker rebates earned (non-negative)
+    pub rebates_earned: Notional,
+    /// Cumulative funding received; negative when paid
+    pub funding: Notional,
+    /// Cumulative absolute traded notional, used to pick the fee tier
+    pub volume: Notional,
+}
+
+/// PnL split into trading result and costs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PnlBreakdown {
+    /// Realized plus unrealized trading PnL
+    pub gross: Notional,
+    pub fees_paid: Notional,
+    pub rebates_earned: Notional,
+    pub funding: Notional,
+    /// Gross less fees, plus rebates and funding
+    pub net: Notional,
+}
+
+impl PnlBreakdown {
+    /// Fees less rebates; positive is a net cost
+    pub fn net_fees(&self) -> Notional {
+        self.fees_paid - self.rebates_earned
+    }
+
+    /// Every amount converted from `contract`'s settlement asset to the quote asset at `price`
+    pub fn in_quote(&self, contract: ContractKind, price: Price) -> Self {
+        let convert = |amount| contract.to_quote(amount, price);
+        Self {
+            gross: convert(self.gross),
+            fees_paid: convert(self.fees_paid),
+            rebates_earned: convert(self.rebates_earned),
+            funding: convert(self.funding),
+            net: convert(self.net),
+        }
+    }
 }
 
 impl Position {
     pub fn new() -> Self {
-        Self { quantity: FixedPoint::ZERO, avg_entry_price: FixedPoint::ZERO, realized_pnl: FixedPoint::ZERO }
+        Self {
+            contract: ContractKind::Spot,
+            quantity: Qty::ZERO,
+            avg_entry_price: Price::ZERO,
+            realized_pnl: Notional::ZERO,
+            fees_paid: Notional::ZERO,
+            rebates_earned: Notional::ZERO,
+            funding: Notional::ZERO,
+            volume: Notional::ZERO,
+        }
+    }
+    /// Track a position in `contract`; call before the first fill
+    pub fn with_contract(mut self, contract: ContractKind) -> Self {
+        self.contract = contract;
+        self
     }
 
     /// Calculate unrealized PnL given current market price
-    pub fn unrealized_pnl(&self, mark_price: FixedPoint) -> FixedPoint {
-        if self.quantity == FixedPoint::ZERO {
-            return FixedPoint::ZERO;
+    pub fn unrealized_pnl(&self, mark_price: Price) -> Notional {
+        if self.quantity == Qty::ZERO {
+            return Notional::ZERO;
         }
-        (mark_price - self.avg_entry_price) * self.quantity
+        self.contract.pnl(self.quantity, self.avg_entry_price, mark_price)
+    }
+
+    /// Signed base-asset exposure at `mark_price`
+    pub fn base_exposure(&self, mark_price: Price) -> Qty {
+        self.contract.base_exposure(self.quantity, mark_price)
+    }
+
+    /// Signed quote-asset value at `mark_price`
+    pub fn notional(&self, mark_price: Price) -> Notional {
+        self.contract.notional(self.quantity, mark_price)
     }
 
     /// Update position with a fill
-    pub fn apply_fill(&mut self, side: OrderSide, price: FixedPoint, quantity: FixedPoint) {
+    pub fn apply_fill(&mut self, side: OrderSide, price: Price, quantity: Qty) {
         let fill_qty = match side {
             OrderSide::Bid => quantity,  // Buying increases position
             OrderSide::Ask => -quantity, // Selling decreases position
@@ -199,20 +521,18 @@ impl Position {
         let new_quantity = self.quantity + fill_qty;
 
         // Handle position changes
-        if self.quantity == FixedPoint::ZERO {
+        if self.quantity == Qty::ZERO {
             // Opening new position
             self.quantity = new_quantity;
             self.avg_entry_price = price;
-        } else if (self.quantity.to_i64() > 0) == (new_quantity.to_i64() > 0) && new_quantity.to_i64().abs() > self.quantity.to_i64().abs()
-        {
+        } else if (self.quantity > Qty::ZERO) == (new_quantity > Qty::ZERO) && new_quantity.abs() > self.quantity.abs() {
             // Adding to position (same sign, larger absolute value)
-            let total_cost = self.avg_entry_price * self.quantity + price * fill_qty;
-            self.avg_entry_price = total_cost / new_quantity;
+            self.avg_entry_price = self.contract.average_entry(self.quantity, self.avg_entry_price, fill_qty, price);
             self.quantity = new_quantity;
-        } else if (self.quantity.to_i64() > 0) != (new_quantity.to_i64() > 0) {
+        } else if (self.quantity > Qty::ZERO) != (new_quantity > Qty::ZERO) {
             // Flipping position (crossing zero)
             // Close old position
-            let close_pnl = (price - self.avg_entry_price) * self.quantity;
+            let close_pnl = self.contract.pnl(self.quantity, self.avg_entry_price, price);
             self.realized_pnl += close_pnl;
 
             // Open new position with remainder
@@ -222,20 +542,114 @@ impl Position {
             // Reducing position (same sign, smaller absolute value)
             // fill_qty is negative for sells, so negate it to get the closed amount
             let closed_qty = -fill_qty;
-            let close_pnl = (price - self.avg_entry_price) * closed_qty;
+            let close_pnl = self.contract.pnl(closed_qty, self.avg_entry_price, price);
             self.realized_pnl += close_pnl;
             self.quantity = new_quantity;
         }
     }
 
-    /// Total PnL (realized + unrealized)
-    pub fn total_pnl(&self, mark_price: FixedPoint) -> FixedPoint {
+    /// Total trading PnL (realized + unrealized), before fees and funding
+    pub fn total_pnl(&self, mark_price: Price) -> Notional {
         self.realized_pnl + self.unrealized_pnl(mark_price)
     }
 
+    /// Total PnL after fees, rebates and funding
+    pub fn net_pnl(&self, mark_price: Price) -> Notional {
+        self.pnl_breakdown(mark_price).net
+    }
+
+    pub fn pnl_breakdown(&self, mark_price: Price) -> PnlBreakdown {
+        let gross = self.total_pnl(mark_price);
+        PnlBreakdown {
+            gross,
+            fees_paid: self.fees_paid,
+            rebates_earned: self.rebates_earned,
+            funding: self.funding,
+            net: gross - self.fees_paid + self.rebates_earned + self.funding,
+        }
+    }
+
+    /// Book a fee; positive is paid, negative is a rebate earned
+    pub fn record_fee(&mut self, fee: Notional) {
+        if fee > Notional::ZERO {
+            self.fees_paid += fee;
+        } else {
+            self.rebates_earned -= fee;
+        }
+    }
+
+    /// Apply a fill and charge it under `fees` at the tier reached so far
+    ///
+    /// The fee is on the fill's settlement value; tiers count quote-asset volume.
+    /// Returns the fee booked (negative for a rebate).
+    pub fn apply_fill_with_fees(&mut self, side: OrderSide, price: Price, quantity: Qty, is_maker: bool, fees: &FeeSchedule) -> Notional {
+        let fee = fees.fee_on(self.contract.settlement_value(quantity, price), is_maker, self.volume);
+        self.apply_fill(side, price, quantity);
+        self.record_fee(fee);
+        self.volume = self.volume.saturating_add(self.contract.notional(quantity, price).abs());
+        fee
+    }
+
+    /// Settle one funding interval at `rate` (0.0001 = 1 bp) on the position marked at `mark_price`
+    ///
+    /// Longs pay shorts when the rate is positive. Returns the amount received
+    /// (negative when paid); spot positions never pay funding.
+    pub fn apply_funding(&mut self, rate: f64, mark_price: Price) -> Notional {
+        if !self.contract.is_perpetual() {
+            return Notional::ZERO;
+        }
+        let payment = Notional::ZERO.saturating_sub(self.contract.settlement_value(self.quantity, mark_price).mul_scalar(rate));
+        self.funding = self.funding.saturating_add(payment);
+        payment
+    }
+
     /// Check if position is flat
     pub fn is_flat(&self) -> bool {
-        self.quantity == FixedPoint::ZERO
+        self.quantity == Qty::ZERO
+    }
+
+    /// Apply a fill as reported on the order fills stream, charging it under `fees`
+    pub fn apply_fill_message(&mut self, fill: &OrderFillMessage, fees: &FeeSchedule) -> Notional {
+        self.apply_fill_with_fees(fill.order_side(), Price(fill.fill_price), Qty(fill.fill_quantity), fill.is_maker != 0, fees)
+    }
+
+    /// Wire snapshot, with unrealized PnL marked at `mark_price`
+    pub fn to_message(
+        &self,
+        symbol: CompressedString,
+        encoding: EncodingScheme,
+        timestamp: u64,
+        mark_price: Price,
+        sequence: u64,
+    ) -> PositionMessage {
+        PositionMessage::new_with_sequence(
+            symbol,
+            encoding,
+            timestamp,
+            self.quantity.to_i64(),
+            self.avg_entry_price.to_i64(),
+            self.unrealized_pnl(mark_price).to_i64(),
+            self.realized_pnl.to_i64(),
+            sequence,
+        )
+        .with_costs(self.fees_paid.to_i64(), self.rebates_earned.to_i64(), self.funding.to_i64())
+    }
+}
+
+/// The published unrealized PnL is derived from a mark price, and traded volume
+/// is not published, so neither is kept; the contract is taken to be spot
+impl From<&PositionMessage> for Position {
+    fn from(msg: &PositionMessage) -> Self {
+        Self {
+            contract: ContractKind::Spot,
+            quantity: Qty(msg.quantity),
+            avg_entry_price: Price(msg.avg_entry_price),
+            realized_pnl: Notional(msg.realized_pnl),
+            fees_paid: Notional(msg.fees_paid),
+            rebates_earned: Notional(msg.rebates_earned),
+            funding: Notional(msg.funding_pnl),
+            volume: Notional::ZERO,
+        }
     }
 }
 
@@ -244,3 +658,231 @@ impl Default for Position {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const MODES: [RoundingMode; 6] = [
+        RoundingMode::TowardZero,
+        RoundingMode::AwayFromZero,
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+        RoundingMode::HalfAwayFromZero,
+        RoundingMode::HalfEven,
+    ];
+
+    /// Reference rounding built from the floor/ceiling pair of the exact quotient
+    fn reference_div(num: i128, den: i128, mode: RoundingMode) -> i128 {
+        let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+        let floor = num.div_euclid(den);
+        let fraction = num.rem_euclid(den);
+        if fraction == 0 {
+            return floor;
+        }
+        let ceil = floor + 1;
+        let toward_zero = if num >= 0 { floor } else { ceil };
+        let away_from_zero = if num >= 0 { ceil } else { floor };
+        match mode {
+            RoundingMode::TowardZero => toward_zero,
+            RoundingMode::AwayFromZero => away_from_zero,
+            RoundingMode::Floor => floor,
+            RoundingMode::Ceil => ceil,
+            RoundingMode::HalfAwayFromZero | RoundingMode::HalfEven => match (2 * fraction).cmp(&den) {
+                std::cmp::Ordering::Less => floor,
+                std::cmp::Ordering::Greater => ceil,
+                std::cmp::Ordering::Equal if mode == RoundingMode::HalfAwayFromZero => away_from_zero,
+                std::cmp::Ordering::Equal => {
+                    if floor % 2 == 0 {
+                        floor
+                    } else {
+                        ceil
+                    }
+                }
+            },
+        }
+    }
+
+    fn fp(value: f64) -> FixedPoint {
+        FixedPoint::from_f64(value)
+    }
+
+    #[test]
+    fn test_rounding_modes_on_ties() {
+        let half = fp(0.000_000_05);
+        let one_unit = fp(0.1);
+        // 0.5 units of 1e-8 after scaling: 5e-8 * 0.1 = 5e-9
+        assert_eq!(half.mul_round(one_unit, RoundingMode::HalfEven), Some(FixedPoint(0)));
+        assert_eq!(half.mul_round(one_unit, RoundingMode::HalfAwayFromZero), Some(FixedPoint(1)));
+        assert_eq!((-half).mul_round(one_unit, RoundingMode::HalfAwayFromZero), Some(FixedPoint(-1)));
+        assert_eq!((-half).mul_round(one_unit, RoundingMode::Floor), Some(FixedPoint(-1)));
+        assert_eq!((-half).mul_round(one_unit, RoundingMode::Ceil), Some(FixedPoint(0)));
+
+        // 1 / 3 = 0.33333333|3...
+        let third = FixedPoint::from_int(1).div_round(FixedPoint::from_int(3), RoundingMode::AwayFromZero);
+        assert_eq!(third, Some(FixedPoint(33_333_334)));
+        assert_eq!(FixedPoint::from_int(2).div_round(FixedPoint::from_int(3), RoundingMode::HalfEven), Some(FixedPoint(66_666_667)));
+    }
+
+    #[test]
+    fn test_overflow_is_detected() {
+        // 1e7 BTC at a price of 1e7 is a 1e14 notional, far beyond i64 at 8 decimals
+        let size = FixedPoint::from_int(10_000_000);
+        assert_eq!(size.checked_mul(size), None);
+        assert_eq!(size.saturating_mul(size), FixedPoint(i64::MAX));
+        assert_eq!(size.saturating_mul(-size), FixedPoint(i64::MIN));
+
+        assert_eq!(FixedPoint(i64::MAX).checked_div(fp(0.5)), None);
+        assert_eq!(size.checked_div(FixedPoint::ZERO), None);
+        assert_eq!(FixedPoint(i64::MAX).checked_add(FixedPoint(1)), None);
+        assert_eq!(FixedPoint(i64::MAX).saturating_add(FixedPoint(1)), FixedPoint(i64::MAX));
+        assert_eq!(FixedPoint(i64::MIN).saturating_sub(FixedPoint(1)), FixedPoint(i64::MIN));
+
+        // A realistic notional still fits
+        assert_eq!(fp(2.5).checked_mul(fp(60_000.0)), Some(fp(150_000.0)));
+
+        // The operators saturate rather than wrap or panic
+        assert_eq!(size * size, FixedPoint(i64::MAX));
+        assert_eq!(size * -size, FixedPoint(i64::MIN));
+        assert_eq!(FixedPoint(i64::MIN) / fp(0.5), FixedPoint(i64::MIN));
+        assert_eq!(size / FixedPoint::ZERO, FixedPoint(i64::MAX));
+        assert_eq!(-size / FixedPoint::ZERO, FixedPoint(i64::MIN));
+        assert_eq!(FixedPoint::ZERO / FixedPoint::ZERO, FixedPoint::ZERO);
+        assert_eq!(fp(3.0) / fp(2.0), fp(1.5));
+    }
+
+    #[test]
+    fn test_scalar_and_bps_overflow() {
+        let large = FixedPoint(i64::MAX / 2);
+        assert_eq!(large.checked_mul_scalar(3.0), None);
+        assert_eq!(large.mul_scalar(3.0), FixedPoint(i64::MAX));
+        assert_eq!(large.mul_scalar(-3.0), FixedPoint(i64::MIN));
+        assert_eq!(large.checked_mul_scalar(f64::NAN), None);
+        assert_eq!(large.mul_scalar(f64::NAN), FixedPoint::ZERO);
+        assert_eq!(large.mul_scalar(f64::INFINITY), FixedPoint(i64::MAX));
+
+        // Exact beyond f64's 53 bits: 2^62 + 1 raw units halved
+        assert_eq!(FixedPoint((1 << 62) + 2).mul_scalar(0.5), FixedPoint((1 << 61) + 1));
+        assert_eq!(fp(0.1).checked_mul_scalar(0.73456789), Some(FixedPoint(7_345_678)));
+
+        // 20_000 bps triples the value
+        assert_eq!(large.checked_apply_bps(20_000.0), None);
+        assert_eq!(large.apply_bps(20_000.0), FixedPoint(i64::MAX));
+        assert_eq!(large.checked_subtract_bps(-20_000.0), None);
+        assert_eq!((-large).apply_bps(20_000.0), FixedPoint(i64::MIN));
+        assert_eq!(fp(100.0).checked_apply_bps(50.0), Some(fp(100.5)));
+        assert_eq!(fp(100.0).checked_subtract_bps(50.0), Some(fp(99.5)));
+    }
+
+    #[test]
+    fn test_position_message_roundtrip() {
+        let mut position = Position::new();
+        position.apply_fill(OrderSide::Bid, Price::from_f64(100.0), Qty::from_f64(2.0));
+        position.apply_fill(OrderSide::Ask, Price::from_f64(110.0), Qty::from_f64(0.5));
+
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let msg = position.to_message(symbol, encoding, 1, Price::from_f64(120.0), 7);
+        assert_eq!(msg.unrealized_pnl, Notional::from_f64(30.0).to_i64());
+
+        let decoded = Position::from(&msg);
+        assert_eq!(decoded.quantity, Qty::from_f64(1.5));
+        assert_eq!(decoded.avg_entry_price, Price::from_f64(100.0));
+        assert_eq!(decoded.realized_pnl, Notional::from_f64(5.0));
+    }
+
+    #[test]
+    fn test_inverse_position_settles_in_base() {
+        let contract = ContractKind::InversePerp { multiplier: Notional::from_int(100) };
+        let fees = FeeSchedule::flat(0.0, 5.0);
+        let mut position = Position::new().with_contract(contract);
+
+        // 200 contracts = 20_000 USD, bought at 40_000 then 60_000: entry is the harmonic mean
+        position.apply_fill_with_fees(OrderSide::Bid, Price::from_int(40_000), Qty::from_int(100), false, &fees);
+        position.apply_fill(OrderSide::Bid, Price::from_int(60_000), Qty::from_int(100));
+        assert_eq!(position.avg_entry_price, Price::from_int(48_000));
+        assert_eq!(position.notional(Price::from_int(50_000)), Notional::from_int(20_000));
+        assert_eq!(position.base_exposure(Price::from_int(50_000)), Qty::from_f64(0.4));
+
+        // The 10_000 USD taker fill paid 5 bps of 0.25 BTC
+        assert_eq!(position.fees_paid, Notional::from_f64(0.000125));
+        assert_eq!(position.volume, Notional::from_int(10_000));
+
+        // Closing half at 50_000: 10_000/48_000 - 10_000/50_000 BTC
+        position.apply_fill(OrderSide::Ask, Price::from_int(50_000), Qty::from_int(100));
+        assert_eq!(position.realized_pnl, Notional(833_333));
+        assert_eq!(position.unrealized_pnl(Price::from_int(50_000)), Notional(833_333));
+
+        let breakdown = position.pnl_breakdown(Price::from_int(50_000)).in_quote(contract, Price::from_int(50_000));
+        assert_eq!(breakdown.gross, Notional::from_f64(833.333));
+    }
+
+    #[test]
+    fn test_position_fee_and_funding_breakdown() {
+        let fees = FeeSchedule::flat(-1.0, 5.0);
+        let mut position = Position::new().with_contract(ContractKind::LinearPerp { multiplier: Qty::from_int(1) });
+
+        // Taker buy of 10_000 notional pays 5; maker sell of 5_500 earns 0.55
+        let taker_fee = position.apply_fill_with_fees(OrderSide::Bid, Price::from_int(100), Qty::from_int(100), false, &fees);
+        assert_eq!(taker_fee, Notional::from_int(5));
+        position.apply_fill_with_fees(OrderSide::Ask, Price::from_int(110), Qty::from_int(50), true, &fees);
+        assert_eq!(position.volume, Notional::from_int(15_500));
+
+        // Long 50 marked at 100 pays 1 bp funding
+        assert_eq!(position.apply_funding(0.0001, Price::from_int(100)), Notional::from_f64(-0.5));
+        let mut spot = Position { contract: ContractKind::Spot, ..position };
+        assert_eq!(spot.apply_funding(0.0001, Price::from_int(100)), Notional::ZERO);
+        assert_eq!(spot.funding, position.funding);
+
+        let breakdown = position.pnl_breakdown(Price::from_int(100));
+        assert_eq!(breakdown.gross, Notional::from_int(500));
+        assert_eq!(breakdown.fees_paid, Notional::from_int(5));
+        assert_eq!(breakdown.rebates_earned, Notional::from_f64(0.55));
+        assert_eq!(breakdown.net_fees(), Notional::from_f64(4.45));
+        assert_eq!(breakdown.funding, Notional::from_f64(-0.5));
+        assert_eq!(breakdown.net, Notional::from_f64(495.05));
+
+        let (symbol, encoding) = CompressedString::from_str("BTCUSDT").unwrap();
+        let msg = position.to_message(symbol, encoding, 1, Price::from_int(100), 1);
+        assert_eq!(msg.net_pnl(), breakdown.net.to_i64());
+        assert_eq!(Position::from(&msg).pnl_breakdown(Price::from_int(100)), breakdown);
+    }
+
+    proptest! {
+        #[test]
+        fn mul_round_matches_reference(a in any::<i64>(), b in any::<i64>(), mode in 0..MODES.len()) {
+            let mode = MODES[mode];
+            let expected = reference_div(a as i128 * b as i128, FIXED_POINT_MULTIPLIER as i128, mode);
+            prop_assert_eq!(FixedPoint(a).mul_round(FixedPoint(b), mode), i64::try_from(expected).ok().map(FixedPoint));
+        }
+
+        #[test]
+        fn div_round_matches_reference(a in any::<i64>(), b in any::<i64>().prop_filter("non-zero", |b| *b != 0), mode in 0..MODES.len()) {
+            let mode = MODES[mode];
+            let expected = reference_div(a as i128 * FIXED_POINT_MULTIPLIER as i128, b as i128, mode);
+            prop_assert_eq!(FixedPoint(a).div_round(FixedPoint(b), mode), i64::try_from(expected).ok().map(FixedPoint));
+        }
+
+        #[test]
+        fn operators_agree_with_checked_variants(a in -(1i64 << 40)..(1i64 << 40), b in -(1i64 << 40)..(1i64 << 40)) {
+            let (a, b) = (FixedPoint(a), FixedPoint(b));
+            prop_assert_eq!(a.checked_mul(b), Some(a * b));
+            prop_assert_eq!(a.checked_add(b), Some(a + b));
+            if b != FixedPoint::ZERO {
+                prop_assert_eq!(a.checked_div(b), Some(a / b));
+            }
+        }
+
+        #[test]
+        fn rounding_stays_within_one_unit(a in any::<i64>(), b in any::<i64>()) {
+            let (a, b) = (FixedPoint(a), FixedPoint(b));
+            if let (Some(floor), Some(ceil)) = (a.mul_round(b, RoundingMode::Floor), a.mul_round(b, RoundingMode::Ceil)) {
+                prop_assert!(ceil.0 - floor.0 <= 1);
+                for mode in MODES {
+                    let rounded = a.mul_round(b, mode).unwrap();
+                    prop_assert!(floor <= rounded && rounded <= ceil);
+                }
+            }
+        }
+    }
+}
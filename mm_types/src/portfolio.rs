@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ContractKind;
+use crate::Notional;
+use crate::OrderSide;
+use crate::Position;
+use crate::Price;
+use crate::Qty;
+
+/// A portfolio operation that would leave the books inconsistent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerError {
+    /// The instrument was never added to the portfolio
+    UnknownInstrument { instrument_id: u32 },
+    /// The order ID already holds a reservation
+    DuplicateOrder { order_id: u64 },
+    /// Not enough unreserved balance to cover the order
+    InsufficientBalance { asset: String, required: Notional, available: Notional },
+    /// An amount does not fit at `Scale::DEFAULT`
+    Overflow,
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::UnknownInstrument { instrument_id } => write!(f, "Unknown instrument {}", instrument_id),
+            LedgerError::DuplicateOrder { order_id } => write!(f, "Order {} already has a reservation", order_id),
+            LedgerError::InsufficientBalance { asset, required, available } => {
+                write!(f, "Insufficient {} balance: {} required, {} available", asset, required, available)
+            }
+            LedgerError::Overflow => write!(f, "Balance overflow"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+/// Holdings of one asset; amounts are at `Scale::DEFAULT` whatever the asset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Balance {
+    pub total: Notional,
+    /// Held back for open orders
+    pub reserved: Notional,
+}
+
+impl Balance {
+    /// Total less reserved
+    #[inline]
+    pub fn available(&self) -> Notional {
+        self.total - self.reserved
+    }
+}
+
+/// Per-asset balances
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    balances: HashMap<String, Balance>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Balance of `asset`, zero if it was never held
+    pub fn balance(&self, asset: &str) -> Balance {
+        self.balances.get(asset).copied().unwrap_or_default()
+    }
+
+    pub fn available(&self, asset: &str) -> Notional {
+        self.balance(asset).available()
+    }
+
+    pub fn deposit(&mut self, asset: &str, amount: Notional) -> Result<(), LedgerError> {
+        self.adjust(&[(asset, amount)])
+    }
+
+    /// Remove unreserved funds
+    pub fn withdraw(&mut self, asset: &str, amount: Notional) -> Result<(), LedgerError> {
+        self.ensure_available(asset, amount)?;
+        self.adjust(&[(asset, -amount)])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Balance)> {
+        self.balances.iter().map(|(asset, balance)| (asset.as_str(), balance))
+    }
+
+    fn ensure_available(&self, asset: &str, required: Notional) -> Result<(), LedgerError> {
+        let available = self.available(asset);
+        if required > available {
+            return Err(LedgerError::InsufficientBalance { asset: asset.to_string(), required, available });
+        }
+        Ok(())
+    }
+
+    /// Apply every total change or none of them
+    fn adjust(&mut self, changes: &[(&str, Notional)]) -> Result<(), LedgerError> {
+        self.apply(changes, |balance| &mut balance.total)
+    }
+
+    /// Apply every reservation change or none of them
+    fn adjust_reserved(&mut self, changes: &[(&str, Notional)]) -> Result<(), LedgerError> {
+        self.apply(changes, |balance| &mut balance.reserved)
+    }
+
+    fn apply(&mut self, changes: &[(&str, Notional)], field: fn(&mut Balance) -> &mut Notional) -> Result<(), LedgerError> {
+        let mut updated: Vec<(&str, Balance)> = Vec::with_capacity(changes.len());
+        for &(asset, change) in changes {
+            let mut balance = match updated.iter().find(|(name, _)| *name == asset) {
+                Some((_, balance)) => *balance,
+                None => self.balance(asset),
+            };
+            let amount = field(&mut balance);
+            *amount = amount.checked_add(change).ok_or(LedgerError::Overflow)?;
+            match updated.iter_mut().find(|(name, _)| *name == asset) {
+                Some(entry) => entry.1 = balance,
+                None => updated.push((asset, balance)),
+            }
+        }
+
+        for (asset, balance) in updated {
+            self.balances.insert(asset.to_string(), balance);
+        }
+        Ok(())
+    }
+}
+
+/// Assets an instrument trades and settles in
+#[derive(Debug, Clone)]
+struct Holding {
+    base_asset: String,
+    quote_asset: String,
+    position: Position,
+}
+
+impl Holding {
+    /// Asset that PnL, fees and margin are paid in
+    fn settlement_asset(&self) -> &str {
+        if self.position.contract.is_inverse() { &self.base_asset } else { &self.quote_asset }
+    }
+
+    /// Asset and amount an order locks up
+    ///
+    /// Spot buys lock quote and spot sells lock base; perpetuals lock the order's
+    /// settlement value (1x margin).
+    fn requirement(&self, side: OrderSide, price: Price, quantity: Qty) -> (&str, Notional) {
+        let contract = self.position.contract;
+        match (contract, side) {
+            (ContractKind::Spot, OrderSide::Bid) => (&self.quote_asset, price.saturating_mul(quantity).abs()),
+            (ContractKind::Spot, OrderSide::Ask) => (&self.base_asset, Notional(quantity.abs().0)),
+            _ => (self.settlement_asset(), contract.settlement_value(quantity, price).abs()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Reservation {
+    instrument_id: u32,
+    side: OrderSide,
+    price: Price,
+    remaining: Qty,
+}
+
+/// Balances across assets plus positions and open-order reservations across instruments
+///
+/// Spot fills move both the base and quote balance; perpetual fills credit
+/// realized PnL and debit fees in the settlement asset.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    ledger: Ledger,
+    holdings: HashMap<u32, Holding>,
+    reservations: HashMap<u64, Reservation>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from existing balances
+    pub fn with_ledger(ledger: Ledger) -> Self {
+        Self { ledger, ..Self::default() }
+    }
+
+    /// Track an instrument; a second call for the same ID keeps its position
+    pub fn add_instrument(&mut self, instrument_id: u32, base_asset: &str, quote_asset: &str, contract: ContractKind) {
+        self.holdings.entry(instrument_id).or_insert_with(|| Holding {
+            base_asset: base_asset.to_string(),
+            quote_asset: quote_asset.to_string(),
+            position: Position::new().with_contract(contract),
+        });
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub fn ledger_mut(&mut self) -> &mut Ledger {
+        &mut self.ledger
+    }
+
+    pub fn position(&self, instrument_id: u32) -> Option<&Position> {
+        self.holdings.get(&instrument_id).map(|holding| &holding.position)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (u32, &Position)> {
+        self.holdings.iter().map(|(&id, holding)| (id, &holding.position))
+    }
+
+    /// Asset and amount an order would lock up
+    pub fn required(&self, instrument_id: u32, side: OrderSide, price: Price, quantity: Qty) -> Result<(String, Notional), LedgerError> {
+        let (asset, amount) = self.holding(instrument_id)?.requirement(side, price, quantity);
+        Ok((asset.to_string(), amount))
+    }
+
+    /// Whether the unreserved balance covers an order
+    pub fn check_available(&self, instrument_id: u32, side: OrderSide, price: Price, quantity: Qty) -> Result<(), LedgerError> {
+        let (asset, required) = self.holding(instrument_id)?.requirement(side, price, quantity);
+        self.ledger.ensure_available(asset, required)
+    }
+
+    /// Lock the balance an open order needs
+    pub fn reserve(&mut self, order_id: u64, instrument_id: u32, side: OrderSide, price: Price, quantity: Qty) -> Result<(), LedgerError> {
+        if self.reservations.contains_key(&order_id) {
+            return Err(LedgerError::DuplicateOrder { order_id });
+        }
+        let (asset, required) = self.holding(instrument_id)?.requirement(side, price, quantity);
+        self.ledger.ensure_available(asset, required)?;
+        let asset = asset.to_string();
+        self.ledger.adjust_reserved(&[(&asset, required)])?;
+        self.reservations.insert(order_id, Reservation { instrument_id, side, price, remaining: quantity.abs() });
+        Ok(())
+    }
+
+    /// Unlock whatever an order still holds (cancel or expiry); unknown IDs are ignored
+    pub fn release(&mut self, order_id: u64) {
+        if let Some(reservation) = self.reservations.remove(&order_id) {
+            if let Some(holding) = self.holdings.get(&reservation.instrument_id) {
+                let (asset, amount) = holding.requirement(reservation.side, reservation.price, reservation.remaining);
+                let asset = asset.to_string();
+                // Releasing only lowers the reserved amount, so it cannot overflow
+                let _ = self.ledger.adjust_reserved(&[(&asset, -amount)]);
+            }
+        }
+    }
+
+    /// Book a fill against balances, position and (if `order_id` is given) the order's reservation
+    ///
+    /// `fee` is in the settlement asset, positive paid and negative a rebate.
+    /// Either every balance, the position and the reservation change, or, on
+    /// error, nothing does. A fill is a fact, so balances may go negative.
+    pub fn apply_fill(
+        &mut self,
+        order_id: Option<u64>,
+        instrument_id: u32,
+        side: OrderSide,
+        price: Price,
+        quantity: Qty,
+        fee: Notional,
+    ) -> Result<(), LedgerError> {
+        let holding = self.holding(instrument_id)?;
+
+        // Rehearse on a copy so the position only changes if the balances do
+        let mut position = holding.position;
+        let realized_before = position.realized_pnl;
+        position.apply_fill(side, price, quantity);
+        position.record_fee(fee);
+        let settlement = holding.settlement_asset().to_string();
+
+        let mut changes: Vec<(String, Notional)> = Vec::with_capacity(2);
+        match position.contract {
+            ContractKind::Spot => {
+                let value = price.checked_mul(quantity).ok_or(LedgerError::Overflow)?;
+                let (base_change, quote_change) = match side {
+                    OrderSide::Bid => (Notional(quantity.0), -value),
+                    OrderSide::Ask => (-Notional(quantity.0), value),
+                };
+                changes.push((holding.base_asset.clone(), base_change));
+                changes.push((holding.quote_asset.clone(), quote_change - fee));
+            }
+            _ => {
+                let realized = position.realized_pnl.checked_sub(realized_before).ok_or(LedgerError::Overflow)?;
+                changes.push((settlement, realized - fee));
+            }
+        }
+
+        // Filled quantity no longer needs its reservation
+        let mut reservation_update = None;
+        if let Some(order_id) = order_id {
+            if let Some(reservation) = self.reservations.get(&order_id) {
+                let filled = quantity.abs().min(reservation.remaining);
+                let (asset, amount) = holding.requirement(reservation.side, reservation.price, filled);
+                reservation_update = Some((order_id, reservation.remaining - filled, asset.to_string(), amount));
+            }
+        }
+
+        let total_changes: Vec<(&str, Notional)> = changes.iter().map(|(asset, amount)| (asset.as_str(), *amount)).collect();
+        let mut ledger = self.ledger.clone();
+        ledger.adjust(&total_changes)?;
+        if let Some((_, _, asset, amount)) = &reservation_update {
+            ledger.adjust_reserved(&[(asset, -*amount)])?;
+        }
+
+        self.ledger = ledger;
+        if let Some(holding) = self.holdings.get_mut(&instrument_id) {
+            holding.position = position;
+        }
+        if let Some((order_id, remaining, _, _)) = reservation_update {
+            if remaining == Qty::ZERO {
+                self.reservations.remove(&order_id);
+            } else if let Some(reservation) = self.reservations.get_mut(&order_id) {
+                reservation.remaining = remaining;
+            }
+        }
+        Ok(())
+    }
+
+    /// Net base-asset exposure to `asset`: its balance plus every perpetual on it
+    ///
+    /// `mark` supplies the mark price per instrument; perpetuals without one are skipped.
+    pub fn net_exposure(&self, asset: &str, mark: impl Fn(u32) -> Option<Price>) -> Qty {
+        let perpetuals = self
+            .holdings
+            .iter()
+            .filter(|(_, holding)| holding.base_asset == asset && holding.position.contract != ContractKind::Spot)
+            .filter_map(|(&id, holding)| mark(id).map(|price| holding.position.base_exposure(price)))
+            .sum::<Qty>();
+        Qty(self.ledger.balance(asset).total.0).saturating_add(perpetuals)
+    }
+
+    fn holding(&self, instrument_id: u32) -> Result<&Holding, LedgerError> {
+        self.holdings.get(&instrument_id).ok_or(LedgerError::UnknownInstrument { instrument_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BTCUSDT: u32 = 1;
+    const BTCUSD_PERP: u32 = 2;
+
+    fn portfolio() -> Portfolio {
+        let mut ledger = Ledger::new();
+        ledger.deposit("USDT", Notional::from_int(100_000)).unwrap();
+        ledger.deposit("BTC", Notional::from_int(1)).unwrap();
+
+        let mut portfolio = Portfolio::with_ledger(ledger);
+        portfolio.add_instrument(BTCUSDT, "BTC", "USDT", ContractKind::Spot);
+        portfolio.add_instrument(BTCUSD_PERP, "BTC", "USD", ContractKind::InversePerp { multiplier: Notional::from_int(100) });
+        portfolio
+    }
+
+    #[test]
+    fn test_spot_fill_moves_both_legs_and_releases_reservation() {
+        let mut portfolio = portfolio();
+        let price = Price::from_int(50_000);
+
+        portfolio.reserve(7, BTCUSDT, OrderSide::Bid, price, Qty::from_int(1)).unwrap();
+        assert_eq!(portfolio.ledger().balance("USDT").reserved, Notional::from_int(50_000));
+        assert_eq!(portfolio.ledger().available("USDT"), Notional::from_int(50_000));
+
+        // Half fills, paying a 5 USDT fee
+        portfolio.apply_fill(Some(7), BTCUSDT, OrderSide::Bid, price, Qty::from_f64(0.5), Notional::from_int(5)).unwrap();
+        let usdt = portfolio.ledger().balance("USDT");
+        assert_eq!(usdt.total, Notional::from_int(74_995));
+        assert_eq!(usdt.reserved, Notional::from_int(25_000));
+        assert_eq!(portfolio.ledger().balance("BTC").total, Notional::from_f64(1.5));
+        assert_eq!(portfolio.position(BTCUSDT).unwrap().quantity, Qty::from_f64(0.5));
+        assert_eq!(portfolio.position(BTCUSDT).unwrap().fees_paid, Notional::from_int(5));
+
+        portfolio.release(7);
+        assert_eq!(portfolio.ledger().balance("USDT").reserved, Notional::ZERO);
+    }
+
+    #[test]
+    fn test_reservations_respect_available_balance() {
+        let mut portfolio = portfolio();
+        let price = Price::from_int(50_000);
+
+        portfolio.reserve(1, BTCUSDT, OrderSide::Ask, price, Qty::from_f64(0.75)).unwrap();
+        assert!(matches!(
+            portfolio.reserve(2, BTCUSDT, OrderSide::Ask, price, Qty::from_f64(0.5)),
+            Err(LedgerError::InsufficientBalance { .. })
+        ));
+        assert_eq!(
+            portfolio.reserve(1, BTCUSDT, OrderSide::Ask, price, Qty::from_f64(0.1)),
+            Err(LedgerError::DuplicateOrder { order_id: 1 })
+        );
+        assert!(portfolio.check_available(BTCUSDT, OrderSide::Bid, price, Qty::from_int(2)).is_ok());
+        assert!(portfolio.check_available(BTCUSDT, OrderSide::Bid, price, Qty::from_int(3)).is_err());
+        assert_eq!(
+            portfolio.check_available(9, OrderSide::Bid, price, Qty::from_int(1)),
+            Err(LedgerError::UnknownInstrument { instrument_id: 9 })
+        );
+        assert!(portfolio.ledger_mut().withdraw("BTC", Notional::from_f64(0.5)).is_err());
+    }
+
+    #[test]
+    fn test_failed_fill_changes_nothing() {
+        let mut portfolio = portfolio();
+        portfolio.ledger_mut().deposit("USDT", Notional(i64::MAX - Notional::from_int(100_000).0)).unwrap();
+
+        // Selling would push USDT past i64::MAX, so neither leg nor the position moves
+        let result = portfolio.apply_fill(None, BTCUSDT, OrderSide::Ask, Price::from_int(10), Qty::from_int(1), Notional::ZERO);
+        assert_eq!(result, Err(LedgerError::Overflow));
+        assert_eq!(portfolio.ledger().balance("BTC").total, Notional::from_int(1));
+        assert!(portfolio.position(BTCUSDT).unwrap().is_flat());
+    }
+
+    #[test]
+    fn test_inverse_perp_settles_in_base_and_nets_exposure() {
+        let mut portfolio = portfolio();
+
+        // Short 100 contracts (10_000 USD) at 50_000 hedges 0.2 of the 1 BTC balance
+        portfolio.apply_fill(None, BTCUSD_PERP, OrderSide::Ask, Price::from_int(50_000), Qty::from_int(100), Notional::ZERO).unwrap();
+        let mark = |id| (id == BTCUSD_PERP).then_some(Price::from_int(50_000));
+        assert_eq!(portfolio.net_exposure("BTC", mark), Qty::from_f64(0.8));
+        assert_eq!(portfolio.ledger().balance("USD").total, Notional::ZERO);
+
+        // Buying back at 40_000 realizes 0.25 - 0.2 = 0.05 BTC, less a 0.0001 BTC fee
+        portfolio
+            .apply_fill(None, BTCUSD_PERP, OrderSide::Bid, Price::from_int(40_000), Qty::from_int(100), Notional::from_f64(0.0001))
+            .unwrap();
+        assert_eq!(portfolio.ledger().balance("BTC").total, Notional::from_f64(1.0499));
+    }
+}
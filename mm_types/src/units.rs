@@ -0,0 +1,314 @@
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+use crate::FixedPoint;
+use crate::RoundingMode;
+
+/// Shared surface of the unit newtypes: construction, conversion, and
+/// addition within the same unit
+macro_rules! fixed_point_unit {
+    ($name:ident) => {
+        impl $name {
+            pub const ZERO: Self = $name(0);
+
+            #[inline(always)]
+            pub fn from_f64(value: f64) -> Self {
+                $name(FixedPoint::from_f64(value).0)
+            }
+
+            #[inline(always)]
+            pub fn from_int(value: i64) -> Self {
+                $name(FixedPoint::from_int(value).0)
+            }
+
+            #[inline(always)]
+            pub fn to_f64(self) -> f64 {
+                FixedPoint(self.0).to_f64()
+            }
+
+            /// Raw value at `Scale::DEFAULT`, as carried in binary messages
+            #[inline(always)]
+            pub fn to_i64(self) -> i64 {
+                self.0
+            }
+
+            #[inline(always)]
+            pub fn is_zero(self) -> bool {
+                self.0 == 0
+            }
+
+            #[inline(always)]
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map($name)
+            }
+
+            #[inline(always)]
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map($name)
+            }
+
+            #[inline(always)]
+            pub fn saturating_add(self, rhs: Self) -> Self {
+                $name(self.0.saturating_add(rhs.0))
+            }
+
+            #[inline(always)]
+            pub fn saturating_sub(self, rhs: Self) -> Self {
+                $name(self.0.saturating_sub(rhs.0))
+            }
+
+            /// Scale by a dimensionless factor, saturating; see `FixedPoint::mul_scalar`
+            #[inline(always)]
+            pub fn mul_scalar(self, scalar: f64) -> Self {
+                $name(FixedPoint(self.0).mul_scalar(scalar).0)
+            }
+
+            /// Scale by a dimensionless factor; `None` on overflow or a non-finite factor
+            #[inline(always)]
+            pub fn checked_mul_scalar(self, scalar: f64) -> Option<Self> {
+                FixedPoint(self.0).checked_mul_scalar(scalar).map(|value| $name(value.0))
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl From<FixedPoint> for $name {
+            #[inline(always)]
+            fn from(value: FixedPoint) -> Self {
+                $name(value.0)
+            }
+        }
+
+        impl From<$name> for FixedPoint {
+            #[inline(always)]
+            fn from(value: $name) -> Self {
+                FixedPoint(value.0)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.to_f64())
+            }
+        }
+    };
+}
+
+/// Signed units that can be negated and summed
+macro_rules! signed_unit {
+    ($name:ident) => {
+        impl $name {
+            #[inline(always)]
+            pub fn abs(self) -> Self {
+                $name(self.0.saturating_abs())
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $name(-self.0)
+            }
+        }
+
+        impl std::iter::Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold($name::ZERO, |acc, value| acc + value)
+            }
+        }
+    };
+}
+
+/// Quote-currency price of one unit of the base asset, at `Scale::DEFAULT`
+///
+/// Differences of prices (spreads, tick offsets) are also prices. Prices are
+/// never multiplied by each other; times a `Qty` they give a `Notional`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Price(pub i64);
+
+/// Signed base-asset quantity, at `Scale::DEFAULT` (positive = long or buy)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Qty(pub i64);
+
+/// Signed quote-currency amount: order values, PnL, fees, at `Scale::DEFAULT`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Notional(pub i64);
+
+fixed_point_unit!(Price);
+fixed_point_unit!(Qty);
+fixed_point_unit!(Notional);
+signed_unit!(Qty);
+signed_unit!(Notional);
+
+impl Price {
+    /// Move the price up by `bps` basis points, saturating
+    #[inline(always)]
+    pub fn apply_bps(self, bps: f64) -> Self {
+        Price(FixedPoint(self.0).apply_bps(bps).0)
+    }
+
+    /// Move the price down by `bps` basis points, saturating
+    #[inline(always)]
+    pub fn subtract_bps(self, bps: f64) -> Self {
+        Price(FixedPoint(self.0).subtract_bps(bps).0)
+    }
+
+    /// Move the price up by `bps` basis points; `None` on overflow
+    #[inline(always)]
+    pub fn checked_apply_bps(self, bps: f64) -> Option<Self> {
+        FixedPoint(self.0).checked_apply_bps(bps).map(|value| Price(value.0))
+    }
+
+    /// Move the price down by `bps` basis points; `None` on overflow
+    #[inline(always)]
+    pub fn checked_subtract_bps(self, bps: f64) -> Option<Self> {
+        FixedPoint(self.0).checked_subtract_bps(bps).map(|value| Price(value.0))
+    }
+
+    /// Halfway between two prices, rounded toward negative infinity; never overflows
+    #[inline(always)]
+    pub fn midpoint(self, other: Self) -> Self {
+        Price(((self.0 as i128 + other.0 as i128).div_euclid(2)) as i64)
+    }
+
+    /// Value of `qty` at this price, rounded with `mode`; `None` on overflow
+    #[inline]
+    pub fn mul_round(self, qty: Qty, mode: RoundingMode) -> Option<Notional> {
+        FixedPoint(self.0).mul_round(FixedPoint(qty.0), mode).map(|value| Notional(value.0))
+    }
+
+    /// Value of `qty` truncated like `*`, or `None` on overflow
+    #[inline(always)]
+    pub fn checked_mul(self, qty: Qty) -> Option<Notional> {
+        self.mul_round(qty, RoundingMode::TowardZero)
+    }
+
+    /// Value of `qty` truncated like `*`, clamped to the representable range
+    #[inline]
+    pub fn saturating_mul(self, qty: Qty) -> Notional {
+        Notional(FixedPoint(self.0).saturating_mul(FixedPoint(qty.0)).0)
+    }
+}
+
+impl Notional {
+    /// Price that `qty` must trade at to reach this notional, rounded with `mode`
+    ///
+    /// `None` on a zero quantity or overflow.
+    #[inline]
+    pub fn per_unit(self, qty: Qty, mode: RoundingMode) -> Option<Price> {
+        FixedPoint(self.0).div_round(FixedPoint(qty.0), mode).map(|value| Price(value.0))
+    }
+
+    /// Quantity this notional buys at `price`, rounded with `mode`
+    ///
+    /// `None` on a zero price or overflow.
+    #[inline]
+    pub fn units_at(self, price: Price, mode: RoundingMode) -> Option<Qty> {
+        FixedPoint(self.0).div_round(FixedPoint(price.0), mode).map(|value| Qty(value.0))
+    }
+}
+
+impl Mul<Qty> for Price {
+    type Output = Notional;
+
+    fn mul(self, rhs: Qty) -> Self::Output {
+        Notional((FixedPoint(self.0) * FixedPoint(rhs.0)).0)
+    }
+}
+
+impl Mul<Price> for Qty {
+    type Output = Notional;
+
+    fn mul(self, rhs: Price) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<Qty> for Notional {
+    type Output = Price;
+
+    fn div(self, rhs: Qty) -> Self::Output {
+        Price((FixedPoint(self.0) / FixedPoint(rhs.0)).0)
+    }
+}
+
+impl Div<Price> for Notional {
+    type Output = Qty;
+
+    fn div(self, rhs: Price) -> Self::Output {
+        Qty((FixedPoint(self.0) / FixedPoint(rhs.0)).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_arithmetic() {
+        let price = Price::from_f64(50_000.0);
+        let qty = Qty::from_f64(0.25);
+        let notional = price * qty;
+        assert_eq!(notional, Notional::from_f64(12_500.0));
+        assert_eq!(qty * price, notional);
+        assert_eq!(notional / qty, price);
+        assert_eq!(notional / price, qty);
+
+        assert_eq!(Price::from_f64(100.5) - Price::from_f64(100.0), Price::from_f64(0.5));
+        assert_eq!(-qty + Qty::from_f64(1.0), Qty::from_f64(0.75));
+        assert_eq!([notional, -notional, notional].into_iter().sum::<Notional>(), notional);
+        assert_eq!((-notional).abs(), notional);
+
+        assert_eq!(Price(i64::MAX).midpoint(Price(i64::MAX - 2)), Price(i64::MAX - 1));
+        assert_eq!(Price(-3).midpoint(Price(0)), Price(-2));
+    }
+
+    #[test]
+    fn test_checked_unit_arithmetic() {
+        let price = Price::from_int(10_000_000);
+        let qty = Qty::from_int(10_000_000);
+        assert_eq!(price.checked_mul(qty), None);
+        assert_eq!(price.saturating_mul(-qty), Notional(i64::MIN));
+        assert_eq!(Price::from_f64(3.0).mul_round(Qty(1), RoundingMode::Ceil), Some(Notional(3)));
+
+        let cost = Notional::from_int(100);
+        assert_eq!(cost.per_unit(Qty::ZERO, RoundingMode::HalfEven), None);
+        assert_eq!(cost.per_unit(Qty::from_int(3), RoundingMode::Floor), Some(Price(3_333_333_333)));
+        assert_eq!(cost.units_at(Price::from_int(3), RoundingMode::Ceil), Some(Qty(3_333_333_334)));
+
+        assert_eq!(FixedPoint::from(price), FixedPoint::from_int(10_000_000));
+        assert_eq!(Qty::from(FixedPoint::from_f64(1.5)), Qty::from_f64(1.5));
+    }
+}
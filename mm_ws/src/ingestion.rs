@@ -65,6 +65,33 @@ impl BinanceIngestor {
         })
     }
 
+    /// Create a new USD-M futures mark price stream (mark/index price and funding, every second)
+    pub fn new_mark_price_stream(symbol: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new_futures_stream(symbol, "markPrice@1s")
+    }
+
+    /// Create a new USD-M futures liquidation order stream
+    pub fn new_force_order_stream(symbol: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new_futures_stream(symbol, "forceOrder")
+    }
+
+    /// Create an ingestor for the USD-M futures stream `<symbol>@<stream>`
+    fn new_futures_stream(symbol: &str, stream: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let url = format!("wss://fstream.binance.com/ws/{}@{}", symbol.to_lowercase(), stream).into_boxed_str();
+        let (tx, rx) = bounded(10_000);
+
+        Ok(Self {
+            _symbol: Arc::from(symbol),
+            url,
+            websocket: None,
+            message_sender: tx,
+            message_receiver: rx,
+            running: Arc::new(AtomicBool::new(false)),
+            messages_processed: Arc::new(AtomicU64::new(0)),
+            buffer_pool: Arc::new(BufferPool::new(BUFFER_POOL_SIZE, BUFFER_SIZE)),
+        })
+    }
+
     pub fn connect(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Connecting to Binance WebSocket: {}", self.url);
         let (ws, response) = tungstenite::connect(self.url.as_ref())?;